@@ -0,0 +1,397 @@
+use std::{
+  borrow::Borrow,
+  collections::hash_map::DefaultHasher,
+  fmt,
+  hash::{Hash, Hasher},
+  mem::replace,
+  rc::Rc,
+};
+
+use crate::hamt_iterator::HamtIterator;
+
+const BITS: u32 = 5;
+const MASK: u64 = (1 << BITS) - 1;
+
+/// A persistent hash array mapped trie. Cloning is O(1), and modifying a clone only copies the
+/// nodes on the path to the modified entry.
+#[derive(Clone)]
+pub struct Hamt<K, V> {
+  root: Rc<Node<K, V>>,
+  len: usize,
+}
+
+/// Each node uses `BITS` bits of the hash to index up to `1 << BITS` entries. Only the entries
+/// which are present are stored, in the order of their bits in `bitmap`.
+#[derive(Clone)]
+pub(crate) struct Node<K, V> {
+  bitmap: u32,
+  pub(crate) entries: Vec<Entry<K, V>>,
+}
+
+#[derive(Clone)]
+pub(crate) enum Entry<K, V> {
+  Leaf(u64, K, V),
+
+  /// Keys with the same full hash. There are always at least two.
+  Collision(u64, Vec<(K, V)>),
+
+  Node(Rc<Node<K, V>>),
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Hamt<K, V> {
+  pub fn new() -> Self {
+    Hamt {
+      root: Rc::new(Node::empty()),
+      len: 0,
+    }
+  }
+
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  pub fn get<Q>(&self, key: &Q) -> Option<&V>
+  where
+    K: Borrow<Q>,
+    Q: Hash + Eq + ?Sized,
+  {
+    let hash = hash_key(key);
+    let mut node = &*self.root;
+    let mut shift = 0;
+
+    loop {
+      let pos = node.pos(hash, shift)?;
+
+      match &node.entries[pos] {
+        Entry::Leaf(leaf_hash, leaf_key, value) => {
+          return match *leaf_hash == hash && leaf_key.borrow() == key {
+            true => Some(value),
+            false => None,
+          };
+        }
+        Entry::Collision(collision_hash, pairs) => {
+          if *collision_hash != hash {
+            return None;
+          }
+
+          return pairs
+            .iter()
+            .find(|(pair_key, _)| pair_key.borrow() == key)
+            .map(|(_, value)| value);
+        }
+        Entry::Node(child) => {
+          node = child;
+          shift += BITS;
+        }
+      }
+    }
+  }
+
+  pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+  where
+    K: Borrow<Q>,
+    Q: Hash + Eq + ?Sized,
+  {
+    // Avoid copying the path when the key isn't present
+    self.get(key)?;
+
+    let hash = hash_key(key);
+    let mut node = Rc::make_mut(&mut self.root);
+    let mut shift = 0;
+
+    loop {
+      let pos = node.pos(hash, shift)?;
+
+      match &mut node.entries[pos] {
+        Entry::Leaf(_, _, value) => return Some(value),
+        Entry::Collision(_, pairs) => {
+          return pairs
+            .iter_mut()
+            .find(|(pair_key, _)| pair_key.borrow() == key)
+            .map(|(_, value)| value);
+        }
+        Entry::Node(child) => {
+          node = Rc::make_mut(child);
+          shift += BITS;
+        }
+      }
+    }
+  }
+
+  pub fn contains_key<Q>(&self, key: &Q) -> bool
+  where
+    K: Borrow<Q>,
+    Q: Hash + Eq + ?Sized,
+  {
+    self.get(key).is_some()
+  }
+
+  /// Sets the value for `key`, returning the previous value if there was one.
+  pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+    let hash = hash_key(&key);
+    let res = insert_into(&mut self.root, hash, 0, key, value);
+
+    if res.is_none() {
+      self.len += 1;
+    }
+
+    res
+  }
+
+  pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+  where
+    K: Borrow<Q>,
+    Q: Hash + Eq + ?Sized,
+  {
+    // Avoid copying the path when the key isn't present
+    self.get(key)?;
+
+    let res = remove_from(&mut self.root, hash_key(key), 0, key);
+
+    if res.is_some() {
+      self.len -= 1;
+    }
+
+    res
+  }
+
+  pub fn clear(&mut self) {
+    *self = Hamt::new();
+  }
+
+  /// Iterates in an arbitrary (but deterministic) order.
+  pub fn iter(&self) -> HamtIterator<'_, K, V> {
+    HamtIterator::new(&self.root)
+  }
+
+  #[cfg(test)]
+  pub(crate) fn depth(&self) -> usize {
+    fn node_depth<K, V>(node: &Node<K, V>) -> usize {
+      1 + node
+        .entries
+        .iter()
+        .map(|entry| match entry {
+          Entry::Node(child) => node_depth(child),
+          _ => 0,
+        })
+        .max()
+        .unwrap_or(0)
+    }
+
+    node_depth(&self.root)
+  }
+}
+
+impl<K, V> Node<K, V> {
+  fn empty() -> Self {
+    Node {
+      bitmap: 0,
+      entries: vec![],
+    }
+  }
+
+  fn bit(hash: u64, shift: u32) -> u32 {
+    1 << ((hash >> shift) & MASK)
+  }
+
+  /// The position in `entries` of the entry for `hash`, if there is one.
+  fn pos(&self, hash: u64, shift: u32) -> Option<usize> {
+    let bit = Self::bit(hash, shift);
+
+    match self.bitmap & bit {
+      0 => None,
+      _ => Some(self.insert_pos(bit)),
+    }
+  }
+
+  fn insert_pos(&self, bit: u32) -> usize {
+    (self.bitmap & (bit - 1)).count_ones() as usize
+  }
+
+  /// A node containing two entries with different hashes.
+  fn pair(a: Entry<K, V>, a_hash: u64, b: Entry<K, V>, b_hash: u64, shift: u32) -> Self {
+    let a_bit = Self::bit(a_hash, shift);
+    let b_bit = Self::bit(b_hash, shift);
+
+    if a_bit == b_bit {
+      return Node {
+        bitmap: a_bit,
+        entries: vec![Entry::Node(Rc::new(Self::pair(
+          a,
+          a_hash,
+          b,
+          b_hash,
+          shift + BITS,
+        )))],
+      };
+    }
+
+    Node {
+      bitmap: a_bit | b_bit,
+      entries: match a_bit < b_bit {
+        true => vec![a, b],
+        false => vec![b, a],
+      },
+    }
+  }
+}
+
+fn insert_into<K: Eq + Clone, V: Clone>(
+  node: &mut Rc<Node<K, V>>,
+  hash: u64,
+  shift: u32,
+  key: K,
+  value: V,
+) -> Option<V> {
+  let node = Rc::make_mut(node);
+  let bit = Node::<K, V>::bit(hash, shift);
+  let pos = node.insert_pos(bit);
+
+  if node.bitmap & bit == 0 {
+    node.bitmap |= bit;
+    node.entries.insert(pos, Entry::Leaf(hash, key, value));
+
+    return None;
+  }
+
+  match &mut node.entries[pos] {
+    Entry::Leaf(leaf_hash, leaf_key, leaf_value) if *leaf_hash == hash && *leaf_key == key => {
+      return Some(replace(leaf_value, value));
+    }
+    Entry::Collision(collision_hash, pairs) if *collision_hash == hash => {
+      if let Some((_, pair_value)) = pairs.iter_mut().find(|(pair_key, _)| *pair_key == key) {
+        return Some(replace(pair_value, value));
+      }
+
+      pairs.push((key, value));
+
+      return None;
+    }
+    Entry::Node(child) => return insert_into(child, hash, shift + BITS, key, value),
+    Entry::Leaf(..) | Entry::Collision(..) => {}
+  }
+
+  // The slot is taken by a different key, so it needs to become a collision or a node
+  let existing = replace(&mut node.entries[pos], Entry::Node(Rc::new(Node::empty())));
+
+  node.entries[pos] = match existing {
+    Entry::Leaf(leaf_hash, leaf_key, leaf_value) if leaf_hash == hash => {
+      Entry::Collision(hash, vec![(leaf_key, leaf_value), (key, value)])
+    }
+    Entry::Leaf(existing_hash, ..) | Entry::Collision(existing_hash, ..) => {
+      Entry::Node(Rc::new(Node::pair(
+        existing,
+        existing_hash,
+        Entry::Leaf(hash, key, value),
+        hash,
+        shift + BITS,
+      )))
+    }
+    Entry::Node(_) => panic!("Should have recursed into node"),
+  };
+
+  None
+}
+
+fn remove_from<K, V, Q>(node: &mut Rc<Node<K, V>>, hash: u64, shift: u32, key: &Q) -> Option<V>
+where
+  K: Borrow<Q> + Clone,
+  V: Clone,
+  Q: Eq + ?Sized,
+{
+  let node = Rc::make_mut(node);
+  let pos = node.pos(hash, shift)?;
+
+  let res = match &mut node.entries[pos] {
+    Entry::Leaf(..) => None,
+    Entry::Collision(_, pairs) => {
+      let i = pairs
+        .iter()
+        .position(|(pair_key, _)| pair_key.borrow() == key)?;
+
+      let (_, value) = pairs.remove(i);
+
+      if pairs.len() == 1 {
+        let (last_key, last_value) = pairs.pop().unwrap();
+        node.entries[pos] = Entry::Leaf(hash, last_key, last_value);
+      }
+
+      return Some(value);
+    }
+    Entry::Node(child) => {
+      let value = remove_from(child, hash, shift + BITS, key)?;
+
+      // A node left with a single leaf or collision is replaced by it
+      let collapsed = match child.entries.as_slice() {
+        [entry @ (Entry::Leaf(..) | Entry::Collision(..))] => Some(entry.clone()),
+        _ => None,
+      };
+
+      if let Some(entry) = collapsed {
+        node.entries[pos] = entry;
+      }
+
+      Some(value)
+    }
+  };
+
+  if res.is_some() {
+    return res;
+  }
+
+  // The entry is a leaf. The caller has checked that the key is present.
+  node.bitmap &= !Node::<K, V>::bit(hash, shift);
+
+  match node.entries.remove(pos) {
+    Entry::Leaf(_, _, value) => Some(value),
+    _ => panic!("Expected leaf"),
+  }
+}
+
+fn hash_key<Q: Hash + ?Sized>(key: &Q) -> u64 {
+  let mut hasher = DefaultHasher::new();
+  key.hash(&mut hasher);
+  hasher.finish()
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Default for Hamt<K, V> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> FromIterator<(K, V)> for Hamt<K, V> {
+  fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+    let mut hamt = Self::new();
+    hamt.extend(iter);
+
+    hamt
+  }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Extend<(K, V)> for Hamt<K, V> {
+  fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+    for (key, value) in iter {
+      self.insert(key, value);
+    }
+  }
+}
+
+impl<K: Hash + Eq + Clone + fmt::Debug, V: Clone + fmt::Debug> fmt::Debug for Hamt<K, V> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_map().entries(self.iter()).finish()
+  }
+}
+
+impl<'a, K: Hash + Eq + Clone, V: Clone> IntoIterator for &'a Hamt<K, V> {
+  type Item = (&'a K, &'a V);
+  type IntoIter = HamtIterator<'a, K, V>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
@@ -0,0 +1,44 @@
+use std::slice;
+
+use crate::hamt::{Entry, Node};
+
+pub struct HamtIterator<'a, K, V> {
+  stack: Vec<slice::Iter<'a, Entry<K, V>>>,
+  collision: slice::Iter<'a, (K, V)>,
+}
+
+impl<'a, K, V> HamtIterator<'a, K, V> {
+  pub(crate) fn new(root: &'a Node<K, V>) -> Self {
+    HamtIterator {
+      stack: vec![root.entries.iter()],
+      collision: [].iter(),
+    }
+  }
+}
+
+impl<'a, K, V> Iterator for HamtIterator<'a, K, V> {
+  type Item = (&'a K, &'a V);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      if let Some((key, value)) = self.collision.next() {
+        return Some((key, value));
+      }
+
+      let entry = loop {
+        match self.stack.last_mut()?.next() {
+          Some(entry) => break entry,
+          None => {
+            self.stack.pop();
+          }
+        }
+      };
+
+      match entry {
+        Entry::Leaf(_, key, value) => return Some((key, value)),
+        Entry::Collision(_, pairs) => self.collision = pairs.iter(),
+        Entry::Node(child) => self.stack.push(child.entries.iter()),
+      }
+    }
+  }
+}
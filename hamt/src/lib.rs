@@ -0,0 +1,129 @@
+mod hamt;
+mod hamt_iterator;
+
+pub use crate::hamt::Hamt;
+pub use crate::hamt_iterator::HamtIterator;
+
+#[cfg(test)]
+mod tests {
+  use std::hash::{Hash, Hasher};
+
+  use super::*;
+
+  #[test]
+  fn empty() {
+    let hamt = Hamt::<String, usize>::new();
+    assert!(hamt.is_empty());
+    assert_eq!(hamt.len(), 0);
+    assert_eq!(hamt.get("a"), None);
+    assert_eq!(hamt.iter().count(), 0);
+  }
+
+  #[test]
+  fn insert_get_1000() {
+    let mut hamt = Hamt::<String, usize>::new();
+
+    for i in 0..1000 {
+      assert_eq!(hamt.insert(i.to_string(), i), None);
+      assert_eq!(hamt.len(), i + 1);
+    }
+
+    for i in 0..1000 {
+      assert_eq!(hamt.get(&i.to_string()), Some(&i));
+    }
+
+    assert_eq!(hamt.get("1000"), None);
+    assert!(hamt.depth() > 1);
+
+    assert_eq!(hamt.insert("5".to_string(), 500), Some(5));
+    assert_eq!(hamt.len(), 1000);
+    assert_eq!(hamt.get("5"), Some(&500));
+
+    *hamt.get_mut("6").unwrap() = 600;
+    assert_eq!(hamt.get("6"), Some(&600));
+    assert_eq!(hamt.get_mut("1000"), None);
+  }
+
+  #[test]
+  fn remove_1000() {
+    let mut hamt = (0..1000).map(|i| (i, i)).collect::<Hamt<usize, usize>>();
+
+    assert_eq!(hamt.remove(&1000), None);
+
+    for i in 0..1000 {
+      assert_eq!(hamt.remove(&i), Some(i));
+      assert_eq!(hamt.remove(&i), None);
+      assert_eq!(hamt.len(), 999 - i);
+
+      if i % 100 == 0 {
+        assert_eq!(hamt.iter().count(), 999 - i);
+        assert!((i + 1..1000).all(|j| hamt.get(&j) == Some(&j)));
+      }
+    }
+
+    assert!(hamt.is_empty());
+    assert_eq!(hamt.depth(), 1);
+  }
+
+  #[test]
+  fn iters() {
+    let hamt = (0..1000)
+      .map(|i| (i, 2 * i))
+      .collect::<Hamt<usize, usize>>();
+
+    let mut entries = hamt.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>();
+    entries.sort();
+
+    assert_eq!(entries, (0..1000).map(|i| (i, 2 * i)).collect::<Vec<_>>());
+  }
+
+  #[test]
+  fn structural_sharing() {
+    let mut hamt = (0..1000).map(|i| (i, i)).collect::<Hamt<usize, usize>>();
+    let copy = hamt.clone();
+
+    hamt.insert(0, 1000);
+    hamt.remove(&1);
+    hamt.insert(1000, 1000);
+
+    assert_eq!(copy.get(&0), Some(&0));
+    assert_eq!(copy.get(&1), Some(&1));
+    assert_eq!(copy.get(&1000), None);
+    assert_eq!(copy.len(), 1000);
+
+    assert_eq!(hamt.get(&0), Some(&1000));
+    assert_eq!(hamt.get(&1), None);
+    assert_eq!(hamt.len(), 1000);
+  }
+
+  /// All keys have the same hash, so they can only be told apart by equality.
+  #[derive(Clone, Debug, PartialEq, Eq)]
+  struct Colliding(usize);
+
+  impl Hash for Colliding {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+      0.hash(state);
+    }
+  }
+
+  #[test]
+  fn collisions() {
+    let mut hamt = Hamt::<Colliding, usize>::new();
+
+    for i in 0..10 {
+      hamt.insert(Colliding(i), i);
+    }
+
+    hamt.insert(Colliding(3), 30);
+
+    assert_eq!(hamt.len(), 10);
+    assert_eq!(hamt.get(&Colliding(3)), Some(&30));
+    assert_eq!(hamt.iter().count(), 10);
+
+    for i in 0..10 {
+      assert!(hamt.remove(&Colliding(i)).is_some());
+    }
+
+    assert!(hamt.is_empty());
+  }
+}
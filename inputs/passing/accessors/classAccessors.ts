@@ -0,0 +1,45 @@
+//! test_output([30,25,5,[20,10],[25,5],"order"])
+
+class Order {
+  items: number[] = [];
+  discount = 0;
+
+  get total() {
+    let sum = 0;
+
+    for (const item of this.items) {
+      sum += item;
+    }
+
+    return sum - this.discount;
+  }
+
+  set total(value: number) {
+    this.discount = this.total + this.discount - value;
+  }
+
+  static get kind() {
+    return "order";
+  }
+}
+
+export default function () {
+  let order = new Order();
+  order.items.push(10, 20);
+
+  const before = order.total;
+  order.total = 25;
+
+  // The setter updates the copy in the holder, not the original
+  let holder = { order };
+  holder.order.total = 20;
+
+  return [
+    before,
+    order.total,
+    order.discount,
+    [holder.order.total, holder.order.discount],
+    [order.total, order.discount],
+    Order.kind,
+  ];
+}
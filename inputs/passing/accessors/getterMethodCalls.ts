@@ -0,0 +1,34 @@
+//! test_output([42,[5,5],"fn_ is not a function"])
+
+class Counter {
+  n = 0;
+
+  get add() {
+    return function (this: Counter, by: number) {
+      this.n += by;
+      return this.n;
+    };
+  }
+}
+
+export default function () {
+  const answer = {
+    get f() {
+      return () => 42;
+    },
+    set g(_value: unknown) {},
+  };
+
+  let counter = new Counter();
+  const added = counter.add(5);
+
+  let message = "";
+
+  try {
+    (answer as any).g();
+  } catch (e) {
+    message = (e as Error).message;
+  }
+
+  return [answer.f(), [added, counter.n], message];
+}
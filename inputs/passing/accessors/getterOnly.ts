@@ -0,0 +1,35 @@
+//! test_output(["Cannot set property value which only has a getter","Cannot mutate this because it is const"])
+
+class Counter {
+  count = 0;
+
+  get value() {
+    return this.count;
+  }
+
+  get next() {
+    this.count++;
+    return this.count;
+  }
+}
+
+export default function () {
+  let counter = new Counter();
+  let errors: string[] = [];
+
+  try {
+    (counter as any).value = 1;
+  } catch (e) {
+    errors.push((e as Error).message);
+  }
+
+  // Getters can't mutate this
+  try {
+    const next = counter.next;
+    errors.push(`${next}`);
+  } catch (e) {
+    errors.push((e as Error).message);
+  }
+
+  return errors;
+}
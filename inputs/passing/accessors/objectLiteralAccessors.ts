@@ -0,0 +1,28 @@
+//! test_output([6,[10,5],"computed",true,6])
+
+export default function () {
+  const key = "dynamic";
+
+  const point = {
+    x: 3,
+    get double() {
+      return this.x * 2;
+    },
+    set double(value: number) {
+      this.x = value / 2;
+    },
+  };
+
+  let copy = point;
+  copy.double = 10;
+
+  const computed = {
+    get [key]() {
+      return "computed";
+    },
+  };
+
+  const { double } = point;
+
+  return [point.double, [copy.double, copy.x], computed.dynamic, "double" in copy, double];
+}
@@ -0,0 +1,17 @@
+//! test_output([{"a":1,"b":2,"c":undefined},2,{"b":2,"c":undefined},1,{"a":1,"b":5,"c":undefined},{"0":"h","1":"i","x":1}])
+
+// Spreads and rest patterns read accessor properties by calling their getters.
+
+export default function () {
+  const o = {
+    a: 1,
+    get b() {
+      return this.a + 1;
+    },
+    set c(_value: number) {},
+  };
+
+  const { a, ...rest } = o;
+
+  return [{ ...o }, { ...o }.b, rest, a, { ...o, b: 5 }, { ..."hi", x: 1 }];
+}
@@ -0,0 +1,25 @@
+//! test_output(["symbol key","string key",true,false,"string key"])
+
+// Accessors with symbol keys are kept apart from string keys with the same name.
+
+export default function () {
+  const o = {
+    get [Symbol.iterator]() {
+      return "symbol key";
+    },
+    get iterator() {
+      return "string key";
+    },
+  };
+
+  let deleted = o;
+  delete deleted[Symbol.iterator];
+
+  return [
+    o[Symbol.iterator],
+    o.iterator,
+    Symbol.iterator in o,
+    Symbol.iterator in deleted,
+    deleted.iterator,
+  ];
+}
@@ -0,0 +1,12 @@
+//! test_output([3,"done"])
+
+export default async function main() {
+  const x = await add(1, 2);
+  const label = await "done";
+
+  return [x, label];
+}
+
+async function add(a: number, b: number) {
+  return a + b;
+}
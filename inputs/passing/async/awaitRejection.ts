@@ -0,0 +1,16 @@
+//! test_output(["caught","boom"])
+
+export default async function main() {
+  try {
+    await fail();
+  } catch (e) {
+    return ["caught", (e as Error).message];
+  }
+
+  return ["not caught"];
+}
+
+async function fail() {
+  await Promise.resolve(1);
+  throw new Error("boom");
+}
@@ -0,0 +1,14 @@
+//! test_output([Promise { 1 },Promise { <rejected> Error{"message":"oops"} }])
+
+export default function main() {
+  // Nothing awaits these, but the VM still runs them after main returns
+  return [id(1), fail()];
+}
+
+async function id(x: number) {
+  return x;
+}
+
+async function fail() {
+  throw new Error("oops");
+}
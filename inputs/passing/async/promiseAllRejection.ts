@@ -0,0 +1,5 @@
+//! test_output(E: "second")
+
+export default async function main() {
+  return await Promise.all([Promise.resolve(1), Promise.reject("second"), Promise.reject("third")]);
+}
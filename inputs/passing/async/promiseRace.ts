@@ -0,0 +1,12 @@
+//! test_output(["settled","first"])
+
+export default async function main() {
+  const a = await Promise.race([slow("pending"), Promise.resolve("settled")]);
+  const b = await Promise.race([slow("first"), slow("second")]);
+
+  return [a, b];
+}
+
+async function slow(x: string) {
+  return x;
+}
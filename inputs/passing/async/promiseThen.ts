@@ -0,0 +1,16 @@
+//! test_output([4,"recovered: bad",7])
+
+export default async function main() {
+  const a = await Promise.resolve(2).then((x) => x * 2);
+  const b = await Promise.reject("bad").catch((e) => `recovered: ${e}`);
+
+  const c = await Promise.resolve(3)
+    .then((x) => addLater(x, 4))
+    .catch(() => -1);
+
+  return [a, b, c];
+}
+
+async function addLater(a: number, b: number) {
+  return a + b;
+}
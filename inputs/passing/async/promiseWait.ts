@@ -0,0 +1,9 @@
+//! test_output([10,20,30])
+
+export default function main() {
+  return Promise.all([double(5), double(10), 30]).wait();
+}
+
+async function double(x: number) {
+  return 2 * x;
+}
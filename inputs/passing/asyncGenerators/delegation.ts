@@ -0,0 +1,36 @@
+//! test_output([10,2,3,40,"cleanup","E: broken stream"])
+
+async function fetchValue(x: number) {
+  return x * 10;
+}
+
+async function* inner() {
+  yield fetchValue(4);
+}
+
+async function* source() {
+  try {
+    yield await fetchValue(1);
+    yield* [2, 3];
+    yield* inner();
+    throw new Error("broken stream");
+  } finally {
+    yield "cleanup";
+  }
+}
+
+export default async function () {
+  let out: unknown[] = [];
+  let gen = source();
+
+  while (true) {
+    const res = await gen.next().catch((e: Error) => ({ value: `E: ${e.message}`, done: true }));
+    out.push(res.value);
+
+    if (res.done) {
+      break;
+    }
+  }
+
+  return out;
+}
@@ -0,0 +1,31 @@
+//! test_output([6,4,2,"a","b","[object AsyncGenerator]"])
+
+async function* countdown(n: number) {
+  while (n > 0) {
+    yield Promise.resolve(n);
+    n--;
+  }
+}
+
+async function* doubled(source: AsyncIterable<number>) {
+  for await (const x of source) {
+    yield x * 2;
+  }
+}
+
+export default async function () {
+  let out: unknown[] = [];
+
+  for await (const x of doubled(countdown(3))) {
+    out.push(x);
+  }
+
+  // Sync iterables work too, and their values are awaited
+  for await (const x of [Promise.resolve("a"), "b"]) {
+    out.push(x);
+  }
+
+  out.push(`${countdown(1)}`);
+
+  return out;
+}
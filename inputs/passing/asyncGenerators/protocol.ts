@@ -0,0 +1,46 @@
+//! test_output([[1,false],["got x",false],["liftoff",true],[undefined,true],[10,false],["cleanup",false],["stop",true],"rejected: injected"])
+
+async function fetchValue(x: number) {
+  return x * 10;
+}
+
+async function* echo() {
+  const x = yield 1;
+  yield `got ${x}`;
+  return Promise.resolve("liftoff");
+}
+
+async function* source() {
+  try {
+    yield await fetchValue(1);
+    yield 2;
+  } finally {
+    yield "cleanup";
+  }
+}
+
+export default async function () {
+  let out: unknown[] = [];
+
+  let gen = echo();
+
+  for (const arg of [undefined, "x", undefined, undefined]) {
+    const { value, done } = await gen.next(arg);
+    out.push([value, done]);
+  }
+
+  let gen2 = source();
+
+  for (const res of [gen2.next(), gen2.return("stop"), gen2.next()]) {
+    const { value, done } = await res;
+    out.push([value, done]);
+  }
+
+  let gen3 = source();
+  await gen3.next();
+  await gen3.throw(new Error("injected"));
+
+  out.push(await gen3.next().catch((e: Error) => `rejected: ${e.message}`));
+
+  return out;
+}
@@ -0,0 +1,10 @@
+//! test_output(["NotFound","missing",true,true])
+
+class NotFound extends Error {
+  code = "NotFound";
+}
+
+export default function () {
+  const e = new NotFound("missing");
+  return [e.code, e.message, e instanceof NotFound, e instanceof Error];
+}
@@ -0,0 +1,10 @@
+//! test_output(E: TypeError{"message":"Class extends value 1 is not a constructor or null"})
+
+const notAClass = 1;
+
+// @ts-ignore
+class Broken extends notAClass {}
+
+export default function () {
+  return new Broken();
+}
@@ -0,0 +1,23 @@
+//! test_output([["base field","base ctor","derived field","derived ctor"],3])
+
+class Base {
+  log: string[] = ["base field"];
+
+  constructor() {
+    this.log.push("base ctor");
+  }
+}
+
+class Derived extends Base {
+  count = this.log.push("derived field");
+
+  constructor() {
+    super();
+    this.log.push("derived ctor");
+  }
+}
+
+export default function () {
+  const d = new Derived();
+  return [d.log, d.count];
+}
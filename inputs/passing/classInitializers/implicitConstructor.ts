@@ -0,0 +1,26 @@
+//! test_output(["Alice",30,"adult","Hello, Alice",true,true])
+
+class Person {
+  constructor(public name: string, public age: number) {}
+
+  greet() {
+    return `Hello, ${this.name}`;
+  }
+}
+
+class Adult extends Person {
+  kind = "adult";
+}
+
+export default function () {
+  const a = new Adult("Alice", 30);
+
+  return [
+    a.name,
+    a.age,
+    a.kind,
+    a.greet(),
+    a instanceof Adult,
+    a instanceof Person,
+  ];
+}
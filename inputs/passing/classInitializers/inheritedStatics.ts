@@ -0,0 +1,39 @@
+//! test_output(["shape",2,"circle",12])
+
+class Shape {
+  static kind = "shape";
+  static sides = 0;
+
+  static describe() {
+    return "shape";
+  }
+
+  area() {
+    return 0;
+  }
+}
+
+class Square extends Shape {
+  static sides = 4;
+
+  constructor(public size: number) {
+    super();
+  }
+
+  area() {
+    return this.size * this.size;
+  }
+}
+
+class Circle extends Shape {
+  static kind = "circle";
+}
+
+export default function () {
+  return [
+    Square.kind,
+    new Square(3).area() - 7,
+    Circle.kind,
+    Square.sides * 3,
+  ];
+}
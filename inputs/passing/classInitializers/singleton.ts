@@ -0,0 +1,10 @@
+//! test_output([42,true])
+
+class Config {
+  static instance = new Config();
+  answer = 42;
+}
+
+export default function () {
+  return [Config.instance.answer, Config.instance instanceof Config];
+}
@@ -0,0 +1,34 @@
+//! test_output([3,6,[1,2,3],60,"Counter"])
+
+class Counter {
+  static count = [1, 2, 3].length;
+  static doubled: number;
+  static items: number[] = [];
+  static #secret = Counter.count * 10;
+
+  static {
+    Counter.doubled = Counter.count * 2;
+
+    for (let i = 1; i <= Counter.count; i++) {
+      Counter.items.push(i);
+    }
+
+    this.#secret *= 2;
+  }
+
+  static name_ = "Counter";
+
+  static secret() {
+    return Counter.#secret;
+  }
+}
+
+export default function () {
+  return [
+    Counter.count,
+    Counter.doubled,
+    Counter.items,
+    Counter.secret(),
+    Counter.name_,
+  ];
+}
@@ -0,0 +1,29 @@
+//! test_output([1,{"b":2,"c":3},{"id":1,"name":"x"},{"done":true},"b",{"a":1,"c":3},{"y":2},{"1":"b","2":"c"}])
+
+type Todo = { id: number; name: string; done: boolean };
+
+function withoutDone({ done, ...rest }: Todo) {
+  return rest;
+}
+
+function reducer(state: Todo, action: { type: string; done: boolean }) {
+  const { type, ...changes } = action;
+  return type === "update" ? changes : state;
+}
+
+export default function () {
+  const { a, ...rest } = { a: 1, b: 2, c: 3 };
+
+  const todo = { id: 1, name: "x", done: false };
+  const changes = reducer(todo, { type: "update", done: true });
+
+  const key = "b";
+  const { [key]: picked, ...others } = { a: 1, b: "b", c: 3 };
+
+  let y;
+  ({ x: { ...y } } = { x: { y: 2 } });
+
+  const { 0: first, ...tail } = ["a", "b", "c"];
+
+  return [a, rest, withoutDone(todo), changes, picked, others, y, tail];
+}
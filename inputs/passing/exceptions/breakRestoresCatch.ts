@@ -0,0 +1,13 @@
+//! test_output(E: Error{"message":"not caught by the loop"})
+
+export default function () {
+  for (let i = 0; i < 3; i++) {
+    try {
+      break;
+    } catch {
+      return "caught by the loop";
+    }
+  }
+
+  throw new Error("not caught by the loop");
+}
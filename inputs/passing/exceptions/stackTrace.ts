@@ -0,0 +1,22 @@
+//! test_output(E: Error{"message":"boom"})
+//! test_trace(at inner (inputs/passing/exceptions/stackTrace.ts:21:3))
+//! test_trace(at inner (inputs/passing/exceptions/stackTrace.ts:18:5))
+//! test_trace(at outer (inputs/passing/exceptions/stackTrace.ts:13:3))
+//! test_trace(at main (inputs/passing/exceptions/stackTrace.ts:8:3))
+
+export default function main() {
+  return outer(1);
+}
+
+function outer(n: number) {
+  const values = [n];
+  return inner(values[0]) + 1;
+}
+
+function inner(n: number): number {
+  if (n > 0) {
+    return inner(n - 1);
+  }
+
+  throw new Error("boom");
+}
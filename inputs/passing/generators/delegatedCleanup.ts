@@ -0,0 +1,44 @@
+//! test_output([[1,false],["inner cleanup",false],["outer cleanup",false],[undefined,true],[1,false],["inner caught oops",false],["inner cleanup",false],[undefined,true]])
+
+function* inner() {
+  try {
+    yield 1;
+    yield 2;
+  } catch (e) {
+    yield `inner caught ${(e as Error).message}`;
+  } finally {
+    yield "inner cleanup";
+  }
+}
+
+function* outer() {
+  try {
+    yield* inner();
+  } finally {
+    yield "outer cleanup";
+  }
+}
+
+function* quiet() {
+  try {
+    yield* inner();
+  } catch {
+    yield "outer caught";
+  }
+}
+
+export default function () {
+  let gen = outer();
+  let gen2 = quiet();
+
+  return [
+    gen.next(),
+    gen.return("r"),
+    gen.next(),
+    gen.next(),
+    gen2.next(),
+    gen2.throw(new Error("oops")),
+    gen2.return(),
+    gen2.next(),
+  ].map(({ value, done }) => [value, done]);
+}
@@ -0,0 +1,28 @@
+//! test_output([[1,false],["cleanup",false],["early",true],[undefined,true],["not started",true],[undefined,true]])
+
+function* withCleanup() {
+  try {
+    yield 1;
+    yield 2;
+  } catch {
+    yield "caught";
+  } finally {
+    yield "cleanup";
+  }
+
+  yield "after";
+}
+
+export default function () {
+  let gen = withCleanup();
+  let unstarted = withCleanup();
+
+  return [
+    gen.next(),
+    gen.return("early"),
+    gen.next(),
+    gen.next(),
+    unstarted.return("not started"),
+    unstarted.next(),
+  ].map(({ value, done }) => [value, done]);
+}
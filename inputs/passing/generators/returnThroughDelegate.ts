@@ -0,0 +1,60 @@
+//! test_output([[[1,false],["inner cleanup",false],["after Z",false],["outer cleanup",false],[undefined,true]],[[1,false],["inner cleanup",false],["after Z",false],[undefined,true],[undefined,true]],[[1,false],["outer cleanup",false],["Z",true],[undefined,true],[undefined,true]],[[1,false],["Z",true],[undefined,true],[undefined,true],[undefined,true]]])
+
+function* inner() {
+  try {
+    yield 1;
+  } finally {
+    yield "inner cleanup";
+  }
+}
+
+function* quiet() {
+  try {
+    yield 1;
+  } finally {
+    yield* [];
+  }
+}
+
+// The delegate yields while returning, so the outer generator is resumed by next() and the yield*
+// finishes with the return value
+function* outer() {
+  try {
+    const r = yield* inner();
+    yield `after ${r}`;
+  } finally {
+    yield "outer cleanup";
+  }
+}
+
+function* wrapped() {
+  const r = yield* { [Symbol.iterator]: () => inner() };
+  yield `after ${r}`;
+}
+
+// The delegate doesn't yield, so the return continues through the outer generator
+function* viaQuiet() {
+  try {
+    yield* quiet();
+    yield "unreachable";
+  } finally {
+    yield "outer cleanup";
+  }
+}
+
+function* wrappedQuiet() {
+  yield* { [Symbol.iterator]: () => quiet() };
+  yield "unreachable";
+}
+
+function run(gen: () => Generator<unknown, unknown, unknown>) {
+  let it = gen();
+
+  return [it.next(), it.return("Z"), it.next(), it.next(), it.next()].map(
+    ({ value, done }) => [value, done],
+  );
+}
+
+export default function () {
+  return [run(outer), run(wrapped), run(viaQuiet), run(wrappedQuiet)];
+}
@@ -0,0 +1,40 @@
+//! test_output([[1,false],["caught boom",false],[3,false],["caught done",false],[undefined,true],"E: uncaught"])
+
+function* resilient() {
+  let count = 1;
+
+  while (true) {
+    try {
+      yield count;
+    } catch (e) {
+      yield `caught ${(e as Error).message}`;
+      count += 2;
+
+      if (count > 3) {
+        return;
+      }
+    }
+  }
+}
+
+export default function () {
+  let gen = resilient();
+
+  let results: unknown[] = [
+    gen.next(),
+    gen.throw(new Error("boom")),
+    gen.next(),
+    gen.throw(new Error("done")),
+    gen.next(),
+  ].map(({ value, done }) => [value, done]);
+
+  let plain = resilient();
+
+  try {
+    plain.throw(new Error("uncaught"));
+  } catch (e) {
+    results.push(`E: ${(e as Error).message}`);
+  }
+
+  return results;
+}
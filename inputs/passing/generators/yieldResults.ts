@@ -0,0 +1,27 @@
+//! test_output([[0,false],[1,false],[11,false],[11,true],[undefined,true]])
+
+function* accumulate() {
+  let total = 0;
+
+  while (true) {
+    const x = yield total;
+
+    if (x === undefined) {
+      return total;
+    }
+
+    total += x;
+  }
+}
+
+export default function () {
+  let gen = accumulate();
+
+  return [
+    gen.next("ignored"),
+    gen.next(1),
+    gen.next(10),
+    gen.next(),
+    gen.next(100),
+  ].map(({ value, done }) => [value, done]);
+}
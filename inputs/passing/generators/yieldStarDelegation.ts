@@ -0,0 +1,34 @@
+//! test_output([["a",false],["got x",false],["a",false],["got y",false],["inner done, inner done",true]])
+
+function* inner() {
+  const x = yield "a";
+  yield `got ${x}`;
+  return "inner done";
+}
+
+class Inner {
+  *[Symbol.iterator]() {
+    return yield* inner();
+  }
+}
+
+function* outer() {
+  const first = yield* inner();
+
+  // Delegating to an iterable whose iterator method is itself a generator
+  const second = yield* new Inner();
+
+  return `${first}, ${second}`;
+}
+
+export default function () {
+  let gen = outer();
+
+  return [
+    gen.next(),
+    gen.next("x"),
+    gen.next(),
+    gen.next("y"),
+    gen.next(),
+  ].map(({ value, done }) => [value, done]);
+}
@@ -0,0 +1,57 @@
+//! test_output([["2","10","a","b","c"],["0","2"],["0","1","2"],["legs","name"],["count"],[],["x=1"]])
+
+class Animal {
+  legs = 4;
+
+  constructor(public name: string) {}
+
+  speak() {
+    return "...";
+  }
+
+  get description() {
+    return `${this.name} has ${this.legs} legs`;
+  }
+
+  static count = 0;
+
+  static create(name: string) {
+    return new Animal(name);
+  }
+}
+
+export default function () {
+  return [
+    keys({ b: 2, 10: "x", a: 1, 2: "y", get c() { return 3; } }),
+    keys(["p", , "r"]),
+    keys("abc"),
+    keys(new Animal("Rex")),
+    keys(Animal),
+    keys(null),
+    firstEntry({ x: 1, y: 2 }),
+  ];
+}
+
+function keys(value: unknown) {
+  let result: string[] = [];
+
+  for (const key in value as object) {
+    result.push(key);
+  }
+
+  return result;
+}
+
+function firstEntry(obj: Record<string, number>) {
+  let result: string[] = [];
+
+  for (const key in obj) {
+    if (result.length > 0) {
+      break;
+    }
+
+    result.push(`${key}=${obj[key]}`);
+  }
+
+  return result;
+}
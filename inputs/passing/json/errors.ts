@@ -0,0 +1,19 @@
+//! test_output(["Do not know how to serialize a BigInt","Do not know how to serialize a function","Invalid JSON: key must be a string at line 1 column 2"])
+
+export default function main() {
+  return [
+    message(() => JSON.stringify({ a: 1n })),
+    message(() => JSON.stringify({ f: () => 1 })),
+    message(() => JSON.parse("{oops")),
+  ];
+}
+
+function message(f: () => unknown) {
+  try {
+    f();
+  } catch (e) {
+    return (e as Error).message;
+  }
+
+  return "no error";
+}
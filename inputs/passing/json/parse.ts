@@ -0,0 +1,10 @@
+//! test_output([{"a":[1,2,{"b":null}],"c":"d","e":1000},[2,3,4],{"b":2},["",{"x":["x",{"y":["y",1]}],"z":["z",[["0",2]]]}]])
+
+export default function main() {
+  return [
+    JSON.parse('{"a":[1,2,{"b":null}],"c":"d","e":1e3}'),
+    JSON.parse("[1,2,3]", (_key, value) => typeof value === "number" ? value + 1 : value),
+    JSON.parse('{"a":1,"b":2}', (key, value) => key === "a" ? undefined : value),
+    JSON.parse('{"x":{"y":1},"z":[2]}', (key, value) => [key, value]),
+  ];
+}
@@ -0,0 +1,21 @@
+//! test_output(["{\"p\":\"p: (1, 2)\"}","{\"a\":1,\"c\":{\"a\":3}}","{\"a\":10,\"b\":\"x\",\"c\":[20]}","{\"b\":2}"])
+
+class Point {
+  constructor(public x: number, public y: number) {}
+
+  toJSON(key: string) {
+    return `${key}: (${this.x}, ${this.y})`;
+  }
+}
+
+export default function main() {
+  return [
+    JSON.stringify({ p: new Point(1, 2) }),
+    JSON.stringify({ a: 1, b: 2, c: { a: 3, d: 4 } }, ["a", "c"]),
+    JSON.stringify(
+      { a: 1, b: "x", c: [2] },
+      (_key, value) => typeof value === "number" ? value * 10 : value,
+    ),
+    JSON.stringify({ a: 1, b: 2 }, (key, value) => key === "a" ? undefined : value),
+  ];
+}
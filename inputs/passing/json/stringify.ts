@@ -0,0 +1,28 @@
+//! test_output(["{\"9\":0,\"10\":0,\"a\":1.5,\"b\":[1,\"two\",null,true,null]}","[1e+21,1e-7,0.1,0,null,null]",true,"{\n  \"a\": [\n    1,\n    {\n      \"b\": 2\n    }\n  ],\n  \"c\": {},\n  \"d\": []\n}","[\n--1,\n--[\n----2\n--]\n]",undefined,"{\"a\":1,\"b\":2}","{\"a\":[1]}"])
+
+export default function main() {
+  return [
+    JSON.stringify({ b: [1, "two", null, true, undefined], a: 1.5, u: undefined, 10: 0, 9: 0 }),
+    JSON.stringify([1e21, 1e-7, 0.1, -0, NaN, Infinity]),
+    JSON.stringify('a"b\\c\nd\u0001é') === '"a\\"b\\\\c\\nd\\u0001é"',
+    JSON.stringify({ a: [1, { b: 2 }], c: {}, d: [] }, null, 2),
+    JSON.stringify([1, [2]], null, "--"),
+    JSON.stringify(undefined),
+    JSON.stringify({
+      a: 1,
+      get b() {
+        return this.a + 1;
+      },
+      set c(_value: number) {},
+    }),
+    JSON.stringify(
+      {
+        get a() {
+          return [1];
+        },
+        b: 2,
+      },
+      ["a"],
+    ),
+  ];
+}
@@ -0,0 +1,17 @@
+//! test_output([2,6])
+
+export default function () {
+  const target = 12;
+  let result: number[] = [];
+
+  outer: for (let i = 1; i < 10; i++) {
+    for (let j = i; j < 10; j++) {
+      if (i * j === target) {
+        result = [i, j];
+        break outer;
+      }
+    }
+  }
+
+  return result;
+}
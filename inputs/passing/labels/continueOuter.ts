@@ -0,0 +1,17 @@
+//! test_output([2,3,5,7,11,13])
+
+export default function () {
+  let primes: number[] = [];
+
+  candidates: for (let n = 2; n < 15; n++) {
+    for (const p of primes) {
+      if (n % p === 0) {
+        continue candidates;
+      }
+    }
+
+    primes.push(n);
+  }
+
+  return primes;
+}
@@ -0,0 +1,20 @@
+//! test_output(["start","inner","after"])
+
+export default function () {
+  let logs: string[] = [];
+
+  block: {
+    logs.push("start");
+
+    while (true) {
+      inner: {
+        logs.push("inner");
+        break block;
+      }
+    }
+  }
+
+  logs.push("after");
+
+  return logs;
+}
@@ -0,0 +1,32 @@
+//! test_output(["try 0","finally 0","try 1","finally 1","try 2","inner finally 2","finally 2","caught after"])
+
+export default function () {
+  let logs: string[] = [];
+
+  outer: for (let i = 0; i < 5; i++) {
+    try {
+      logs.push(`try ${i}`);
+
+      if (i < 2) {
+        continue outer;
+      }
+
+      try {
+        break outer;
+      } finally {
+        logs.push(`inner finally ${i}`);
+      }
+    } finally {
+      logs.push(`finally ${i}`);
+    }
+  }
+
+  // The finally blocks above must not still be catching exceptions
+  try {
+    throw new Error("after");
+  } catch (e) {
+    logs.push(`caught ${(e as Error).message}`);
+  }
+
+  return logs;
+}
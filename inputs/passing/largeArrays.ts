@@ -0,0 +1,70 @@
+//! test_output([["first","last",1000,1001],[0,999,1000],[0,1,2,3,4,5,6,7,8,9],[101,[49,"x","y",150,151],100,50,149,["start",0]],[0,299,true],[2000,99,undefined,"end"],[499500,499500,999000,500],true,false])
+
+// Arrays past a few dozen elements are stored as radix trees. These exercise them across that
+// boundary.
+
+export default function () {
+  let arr = range(1000);
+  const copy = arr;
+
+  arr[0] = "first";
+  arr[999] = "last";
+  arr.push(1000);
+
+  let shrunk = range(100);
+
+  while (shrunk.length > 10) {
+    shrunk.pop();
+  }
+
+  let spliced = range(200);
+  const removed = spliced.splice(50, 100, "x", "y");
+  spliced.unshift("start");
+  const shifted = [spliced.shift(), spliced.shift()];
+
+  let sorted = range(300);
+  sorted.sort((a, b) => b - a);
+  sorted.reverse();
+
+  let resized = range(100);
+  resized.length = 2000;
+  resized[1999] = "end";
+
+  let iterated = 0;
+
+  for (const x of copy) {
+    iterated += x;
+  }
+
+  return [
+    [arr[0], arr[999], arr[1000], arr.length],
+    [copy[0], copy[999], copy.length],
+    shrunk,
+    [spliced.length, spliced.slice(48, 53), removed.length, removed[0], removed[99], shifted],
+    [sorted[0], sorted[299], sorted.every((x, i) => x === i)],
+    [resized.length, resized[99], resized[100], resized[1999]],
+    [iterated, sum([...copy]), sum(copy.map((x) => 2 * x)), copy.indexOf(500)],
+    range(100) === range(100),
+    range(100) === [...range(99), 100],
+  ];
+}
+
+function range(n: number) {
+  let res = [];
+
+  for (let i = 0; i < n; i++) {
+    res.push(i);
+  }
+
+  return res;
+}
+
+function sum(values: number[]) {
+  let res = 0;
+
+  for (const x of values) {
+    res += x;
+  }
+
+  return res;
+}
@@ -0,0 +1,62 @@
+//! test_output([["changed",1,undefined,true,200],[0,199,undefined,200],["k0","k1","k10","k100","k101"],["k0","k1","k2","k3","k4","k5","k6","k7","k8","k9"],19900,true,false,true,[251,undefined,62500,"y",250],[500,0,249001,false]])
+
+// Objects and maps past a few dozen entries are stored as hash tries. These exercise them across
+// that boundary.
+
+export default function () {
+  let obj = build(200);
+  const copy = obj;
+
+  obj.k0 = "changed";
+  obj.extra = true;
+  delete obj.k199;
+
+  let shrunk = build(100);
+
+  for (let i = 10; i < 100; i++) {
+    delete shrunk[`k${i}`];
+  }
+
+  let total = 0;
+
+  for (const key in copy) {
+    total += copy[key];
+  }
+
+  let map = new Map();
+
+  for (let i = 0; i < 500; i++) {
+    map.set(i, i * i);
+  }
+
+  const mapCopy = map;
+
+  for (let i = 0; i < 250; i++) {
+    map.delete(i);
+  }
+
+  map.set("x", "y");
+
+  return [
+    [obj.k0, obj.k1, obj.k199, obj.extra, Object.keys(obj).length],
+    [copy.k0, copy.k199, copy.extra, Object.keys(copy).length],
+    Object.keys(copy).slice(0, 5),
+    Object.keys(shrunk),
+    total,
+    build(100) === build(100),
+    build(100) === { ...build(99), k99: "other" },
+    JSON.stringify(build(100)) === JSON.stringify(copy).replace(/,"k1\d\d":\d+/g, ""),
+    [map.size, map.get(0), map.get(250), map.get("x"), [...map.keys()][0]],
+    [mapCopy.size, mapCopy.get(0), mapCopy.get(499), mapCopy.has("x")],
+  ];
+}
+
+function build(n: number) {
+  let res: Record<string, unknown> = {};
+
+  for (let i = 0; i < n; i++) {
+    res[`k${i}`] = i;
+  }
+
+  return res;
+}
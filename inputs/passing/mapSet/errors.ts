@@ -0,0 +1,33 @@
+//! test_output([["Cannot assign to \"a\" of Map (use .set() to add entries)","Cannot assign to 0 of Set (use .add() to add values)","Iterator value 1 is not an entry object","1 is not iterable"],undefined,"[object Map]","[object Set]"])
+
+export default function main() {
+  let errors = [];
+  let map = new Map();
+  let set = new Set();
+
+  try {
+    map.a = 1;
+  } catch (e) {
+    errors.push(e.message);
+  }
+
+  try {
+    set[0] = 1;
+  } catch (e) {
+    errors.push(e.message);
+  }
+
+  try {
+    new Map([1]);
+  } catch (e) {
+    errors.push(e.message);
+  }
+
+  try {
+    new Set(1);
+  } catch (e) {
+    errors.push(e.message);
+  }
+
+  return [errors, map["a"], `${map}`, `${set}`];
+}
@@ -0,0 +1,36 @@
+//! test_output([[["b","updated"],[[1,2],"array key"],[{"x":1},"object key"],[NaN,"nan"],[0,"zero"]],[true,false],5,"array key","object key","nan","zero",false,["b",[1,2],{"x":1},NaN,0],["updated","array key","object key","nan","zero"],[["b","updated"],[[1,2],"array key"],[{"x":1},"object key"],[NaN,"nan"],[0,"zero"]],{"x":1,"y":2},[true,false,false]])
+
+export default function main() {
+  let map = new Map<unknown, string>([["b", "first"]]);
+
+  map.set("a", "second");
+  map.set([1, 2], "array key");
+  map.set({ x: 1 }, "object key");
+  map.set(NaN, "nan");
+  map.set(-0, "zero");
+  map.set("b", "updated");
+
+  const deleted = [map.delete("a"), map.delete("a")];
+
+  let entries = [];
+
+  for (const [key, value] of map) {
+    entries.push([key, value]);
+  }
+
+  return [
+    entries,
+    deleted,
+    map.size,
+    map.get([1, 2]),
+    map.get({ x: 1 }),
+    map.get(NaN),
+    map.get(0),
+    map.has("a"),
+    [...map.keys()],
+    [...map.values()],
+    [...map.entries()],
+    Object.fromEntries(new Map([["x", 1], ["y", 2]])),
+    [map instanceof Map, map instanceof Set, {} instanceof Map],
+  ];
+}
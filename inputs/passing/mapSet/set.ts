@@ -0,0 +1,26 @@
+//! test_output([[3,2,[1],NaN,0],[true,false],5,true,true,[[3,3],[2,2],[[1],[1]],[NaN,NaN],[0,0]],new Set(["h","e","l","o"]),new Set([["a",1]]),[true,false,false]])
+
+export default function main() {
+  let set = new Set([3, 1, 3, 2]);
+
+  set.add([1]);
+  set.add([1]);
+  set.add(NaN);
+  set.add(NaN);
+  set.add(-0);
+  set.add(0);
+
+  const deleted = [set.delete(1), set.delete(1)];
+
+  return [
+    [...set],
+    deleted,
+    set.size,
+    set.has([1]),
+    set.has(NaN),
+    [...set.entries()],
+    new Set("hello"),
+    new Set(new Map([["a", 1]])),
+    [set instanceof Set, set instanceof Map, [] instanceof Set],
+  ];
+}
@@ -0,0 +1,30 @@
+//! test_output([1,1,["a","b"],true,false,true,false,false,"set key"])
+
+export default function main() {
+  let map = new Map([["a", 1]]);
+  const mapCopy = map;
+  map.set("b", 2);
+
+  let set = new Set(["a"]);
+  const setCopy = set;
+  set.add("b");
+
+  let iterated = [];
+
+  for (const value of set) {
+    set.add(value + "!");
+    iterated.push(value);
+  }
+
+  return [
+    mapCopy.size,
+    setCopy.size,
+    iterated,
+    map === new Map([["a", 1], ["b", 2]]),
+    map === new Map([["b", 2], ["a", 1]]),
+    new Set([[1]]) === new Set([[1]]),
+    new Map([["a", 1]]) === new Map([["a", 2]]),
+    ({ a: 1 }) === ({ b: 1 }),
+    new Map([[new Set([1]), "set key"]]).get(new Set([1])),
+  ];
+}
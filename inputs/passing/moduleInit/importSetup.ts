@@ -0,0 +1,15 @@
+//! test_output([6,[1,4,9],1,4,3,"boom",6])
+
+import {
+  first,
+  getTotal,
+  label,
+  length,
+  second,
+  squares,
+  total,
+} from "./stuff/setup.ts";
+
+export default function () {
+  return [total, squares, first, second, length, label, getTotal()];
+}
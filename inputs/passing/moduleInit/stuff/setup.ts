@@ -0,0 +1,24 @@
+export let total = 0;
+export let squares: number[] = [];
+
+for (const n of [1, 2, 3]) {
+  squares.push(n * n);
+  total += n;
+}
+
+export const [first, second] = squares;
+export const { length } = squares;
+
+let label = "none";
+
+try {
+  throw new Error("boom");
+} catch (e) {
+  label = (e as Error).message;
+}
+
+export { label };
+
+export function getTotal() {
+  return total;
+}
@@ -0,0 +1,52 @@
+//! test_output([[1,4,9,16],10,"big",["step 1","step 2","step 3","1,1","2,1"],6,[0,1,4]])
+
+let squares: number[] = [];
+let sum = 0;
+
+for (let i = 1; i <= 4; i++) {
+  squares.push(i * i);
+  sum += i;
+}
+
+let size = "small";
+
+if (sum > 5) {
+  size = "big";
+}
+
+let log: string[] = [];
+let n = 0;
+
+while (n < 3) {
+  n++;
+  log.push(`step ${n}`);
+}
+
+outer: for (const x of [1, 2]) {
+  for (const y of [1, 2]) {
+    if (y === 2) {
+      continue outer;
+    }
+
+    log.push(`${x},${y}`);
+  }
+}
+
+var doubled;
+doubled = n * 2;
+
+const table = buildTable(3);
+
+function buildTable(len: number) {
+  let result = [];
+
+  for (let i = 0; i < len; i++) {
+    result.push(i * i);
+  }
+
+  return result;
+}
+
+export default function () {
+  return [squares, sum, size, log, doubled, table];
+}
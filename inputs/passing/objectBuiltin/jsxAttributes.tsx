@@ -0,0 +1,12 @@
+//! test_output([["id","class"],[["id","main"],["class","wide"]],true,false])
+
+export default function () {
+  const element = <div id="main" class="wide">content</div>;
+
+  return [
+    Object.keys(element),
+    Object.entries(element),
+    Object.hasOwn(element, "id"),
+    Object.hasOwn(element, "title"),
+  ];
+}
@@ -0,0 +1,63 @@
+//! test_output([["2","10","a","b"],[1,2],[["x",1],["y",2]],{"a":1,"b":2},[{"a":1},{"a":1,"b":2,"c":3}],[0,1,2],["legs","name"],true,true,[true,false,true,false],[["0","a"],["2","c"]],"Cannot convert undefined to object",[["a","b"],[1,2],[["a",1],["b",2]],{"a":1,"b":2},true]])
+
+class Animal {
+  constructor(public name: string, public legs: number) {}
+
+  describe() {
+    return `${this.name} has ${this.legs} legs`;
+  }
+}
+
+export default function () {
+  const record = { b: 1, a: 2, 10: 3, 2: 4 };
+  const point = { x: 1, y: 2 };
+
+  const target = { a: 1 };
+  const assigned = Object.assign(target, { b: 2 }, { c: 3 });
+
+  const frozen = Object.freeze([0, 1, 2]);
+
+  const dog = new Animal("Rex", 4);
+
+  const withGetter = {
+    a: 1,
+    get b() {
+      return this.a + 1;
+    },
+  };
+
+  let error = "";
+
+  try {
+    Object.keys(undefined as any);
+  } catch (e) {
+    error = e.message;
+  }
+
+  return [
+    Object.keys(record),
+    Object.values(point),
+    Object.entries(point),
+    Object.fromEntries([["a", 1], ["b", 2]]),
+    [target, assigned],
+    frozen,
+    Object.keys(dog),
+    Object.getPrototypeOf(dog) === Animal.prototype,
+    Object.getPrototypeOf({}) === null,
+    [
+      Object.hasOwn(dog, "name"),
+      Object.hasOwn(dog, "describe"),
+      Object.hasOwn([1], 0),
+      Object.hasOwn([1], 1),
+    ],
+    Object.entries(["a", , "c"]),
+    error,
+    [
+      Object.keys(withGetter),
+      Object.values(withGetter),
+      Object.entries(withGetter),
+      Object.assign({}, withGetter),
+      Object.hasOwn(withGetter, "b"),
+    ],
+  ];
+}
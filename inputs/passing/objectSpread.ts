@@ -0,0 +1,21 @@
+//! test_output([{"a":1,"b":2},{"0":"x","1":"y","a":1,"b":3,"c":4,"first":true,"last":5},{"count":2,"name":"todo"},{}])
+
+export default function () {
+  const base = { a: 1, b: 2 };
+  const key = "c";
+
+  const merged = {
+    first: true,
+    ...base,
+    b: 3,
+    [key]: 4,
+    ...["x", "y"],
+    ...undefined,
+    last: 5,
+  };
+
+  let state = { name: "todo", count: 1 };
+  state = { ...state, count: state.count + 1 };
+
+  return [{ ...base }, merged, state, { ...null }];
+}
@@ -0,0 +1,13 @@
+//! test_output(E: TypeError{"message":"Cannot read private member #value from an object whose class did not declare it"})
+
+class Box {
+  #value = 1;
+
+  static read(box: Box) {
+    return box.#value;
+  }
+}
+
+export default function () {
+  return Box.read({ value: 1 } as unknown as Box);
+}
@@ -0,0 +1,20 @@
+//! test_output([true,false,true])
+
+class Secret {
+  #value: number;
+
+  constructor(value: number) {
+    this.#value = value;
+  }
+
+  equals(other: Secret) {
+    return this.#value === other.#value;
+  }
+}
+
+export default function () {
+  const a = new Secret(1);
+  const b = new Secret(2);
+
+  return [a === new Secret(1), a === b, a.equals(new Secret(1))];
+}
@@ -0,0 +1,39 @@
+//! test_output([3,[1,2,3],false,true,true,false])
+
+class Counter {
+  #count = 0;
+  #history: number[] = [];
+
+  increment() {
+    this.#count++;
+    this.#history.push(this.#count);
+  }
+
+  get count() {
+    return this.#count;
+  }
+
+  history() {
+    return this.#history;
+  }
+
+  static isCounter(value: unknown) {
+    return #count in (value as object);
+  }
+}
+
+export default function () {
+  let counter = new Counter();
+  counter.increment();
+  counter.increment();
+  counter.increment();
+
+  return [
+    counter.count,
+    counter.history(),
+    "#count" in counter,
+    Counter.isCounter(counter),
+    Counter.isCounter(new Counter()),
+    Counter.isCounter({ count: 3 }),
+  ];
+}
@@ -0,0 +1,47 @@
+//! test_output(["(1, 2)",5,"Point"])
+
+class Point {
+  #x: number;
+  #y: number;
+
+  static #name = "Point";
+
+  constructor(x: number, y: number) {
+    this.#x = x;
+    this.#y = y;
+  }
+
+  #format() {
+    return `(${this.#x}, ${this.#y})`;
+  }
+
+  #scaled(factor: number) {
+    return new Point(this.#x * factor, this.#y * factor);
+  }
+
+  static #describe() {
+    return Point.#name;
+  }
+
+  toString() {
+    return this.#format();
+  }
+
+  lengthSquared() {
+    return this.#x * this.#x + this.#y * this.#y;
+  }
+
+  scaledLengthSquared(factor: number) {
+    return this.#scaled(factor).lengthSquared() / (factor * factor);
+  }
+
+  static describe() {
+    return Point.#describe();
+  }
+}
+
+export default function () {
+  const p = new Point(1, 2);
+
+  return [p.toString(), p.scaledLengthSquared(3), Point.describe()];
+}
@@ -0,0 +1,21 @@
+//! test_output([true,false,["2023-04-05","2023","04","05"],null,"a+b","gi",[true,true,false],"/a+b/gi","/x/m",true])
+
+const datePattern = /(\d{4})-(\d{2})-(\d{2})/;
+
+export default function () {
+  const re = new RegExp("a+b", "gi");
+  const copy = RegExp(/x/, "m");
+
+  return [
+    re.test("xAAByz"),
+    /^abc$/.test("abcd"),
+    datePattern.exec("on 2023-04-05."),
+    datePattern.exec("no date"),
+    re.source,
+    re.flags,
+    [re.global, re.ignoreCase, re.multiline],
+    re.toString(),
+    copy.toString(),
+    /[/\]]+/.test("a/]b"),
+  ];
+}
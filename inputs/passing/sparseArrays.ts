@@ -0,0 +1,89 @@
+//! test_output([[[0,0,0],[0,1,0],[0,0,2]],6,[true,"x",false,true],[1,2],[1,,],4,[1,2],["Cannot assign to \"foo\" of array (arrays only have elements)","Invalid array length","Array length 1000000001 exceeds the maximum of 16777216","Cannot delete length of array"],[-1,-1,true,["number","undefined","undefined","number"],["undefined","undefined"],4,2,false,2]])
+
+export default function () {
+  let grid = new Array(3);
+
+  for (let i = 0; i < grid.length; i++) {
+    let row = new Array(3);
+    row.fill(0);
+    grid[i] = row;
+  }
+
+  grid[1][1] = 1;
+  grid[2][2] = 2;
+
+  let holes = [];
+  holes[5] = "x";
+
+  let truncated = [1, 2, 3, 4];
+  truncated.length = 2;
+
+  let extended = [1];
+  extended.length = 3;
+
+  return [
+    grid,
+    holes.length,
+    [holes[0] === undefined, holes[5], 0 in holes, 5 in holes],
+    truncated,
+    extended,
+    Array(4).length,
+    new Array(1, 2),
+    errors(),
+    methods(),
+  ];
+}
+
+function errors() {
+  let messages = [];
+  let arr = [1];
+
+  try {
+    (arr as any).foo = 1;
+    messages.push(arr);
+  } catch (e) {
+    messages.push(e.message);
+  }
+
+  try {
+    arr.length = 1.5;
+    messages.push(arr);
+  } catch (e) {
+    messages.push(e.message);
+  }
+
+  try {
+    arr[1e9] = 1;
+    messages.push(arr);
+  } catch (e) {
+    messages.push(e.message);
+  }
+
+  try {
+    delete (arr as any).length;
+    messages.push(arr);
+  } catch (e) {
+    messages.push(e.message);
+  }
+
+  return messages;
+}
+
+function methods() {
+  const empty = new Array(2);
+
+  let sparse = [0];
+  sparse[3] = 1;
+
+  return [
+    empty.indexOf(undefined),
+    empty.lastIndexOf(undefined),
+    empty.includes(undefined),
+    [...sparse].map((x) => typeof x),
+    Array.from(empty).map((x) => typeof x),
+    sparse.map((x) => x + 1).length,
+    empty.map((x) => x).length,
+    1 in sparse.map((x) => x),
+    sparse.map((x) => x + 1)[3],
+  ];
+}
@@ -0,0 +1,15 @@
+//! test_output([[0,1,2,3],{"a":1,"b":2,"c":[1,2]},[1,2,0,1,2,3]])
+
+const numbers = [1, 2];
+const defaults = { a: 1 };
+
+export const list = [0, ...numbers, 3];
+export const options = { ...defaults, b: 2, ...{ c: [...numbers] } };
+
+class Registry {
+  static items = [...numbers, ...list];
+}
+
+export default function () {
+  return [list, options, Registry.items];
+}
@@ -0,0 +1,12 @@
+//! test_output([["xxab12","12"],["12","345","6"],null,["b",undefined],["."],["x"]])
+
+export default function () {
+  return [
+    "xxab12yy".match(/[a-z]+?(\d+)/),
+    "a12b345c6".match(/\d+/g),
+    "abc".match(/\d/g),
+    "abc".match(/b(z)?/),
+    "a.b".match("\\."),
+    "xyz".match("x"),
+  ];
+}
@@ -0,0 +1,11 @@
+//! test_output([[["a1","a"],["b2","b"],["c3","c"]],[["o"],["o"]]])
+
+export default function () {
+  let pairs = [];
+
+  for (const m of "a1-b2-c3".matchAll(/([a-z])\d/g)) {
+    pairs.push([m[0], m[1]]);
+  }
+
+  return [pairs, [..."foo".matchAll("o")]];
+}
@@ -0,0 +1,16 @@
+//! test_output(["b-a","x_y_z","[a]bc","aXc","$1-b","05/04/2023","2023.04.05","Hello, WORLD!","a<b>c","ab"])
+
+export default function () {
+  return [
+    "a-b".replace(/(\w)-(\w)/, "$2-$1"),
+    "x y z".replace(/ /g, "_"),
+    "abc".replace(/a/, "[$&]"),
+    "abc".replace("b", "X"),
+    "a-b".replace(/a/, "$$1"),
+    "2023-04-05".replace(/(?<y>\d+)-(?<m>\d+)-(?<d>\d+)/, "$<d>/$<m>/$<y>"),
+    "2023-04-05".replace(/-/g, () => "."),
+    "Hello, world!".replace(/w\w+/, (m) => m.toUpperCase()),
+    "abc".replace(/b/, "<$&>"),
+    "aXb".replace(/x/i, ""),
+  ];
+}
@@ -0,0 +1,19 @@
+//! test_output(["a.b.c","x-x-x","_a_b_","3,6",{"message":"replaceAll must be called with a global RegExp"}])
+
+export default function () {
+  let err;
+
+  try {
+    "abc".replaceAll(/b/, "x");
+  } catch (e) {
+    err = { message: e.message };
+  }
+
+  return [
+    "a b c".replaceAll(" ", "."),
+    "x+x+x".replaceAll(/\+/g, "-"),
+    "ab".replaceAll("", "_"),
+    "1,2".replaceAll(/\d/g, (d, offset) => `${Number(d) * 3}`),
+    err,
+  ];
+}
@@ -0,0 +1,10 @@
+//! test_output([3,-1,0,1])
+
+export default function () {
+  return [
+    "abc123".search(/\d/),
+    "abc".search(/\d/),
+    "abc".search(/(?:)/),
+    "a.b".search("\\."),
+  ];
+}
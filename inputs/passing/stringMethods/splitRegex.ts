@@ -0,0 +1,13 @@
+//! test_output([["a","b","c"],["a",",","b",";","c"],["a","b"],["","a","b",""],[],["a","b","c"],["one","two"]])
+
+export default function () {
+  return [
+    "a, b;c".split(/[,;]\s*/),
+    "a,b;c".split(/([,;])/),
+    "a1b2c3".split(/\d/, 2),
+    "-a-b-".split(/-/),
+    "".split(/(?:)/),
+    "abc".split(/(?:)/),
+    "one  two".split(/\s+/),
+  ];
+}
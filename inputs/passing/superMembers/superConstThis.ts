@@ -0,0 +1,21 @@
+//! test_output(E: TypeError{"message":"Cannot mutate this because it is const"})
+
+class Counter {
+  count = 0;
+
+  increment() {
+    this.count++;
+    return this.count;
+  }
+}
+
+class LoudCounter extends Counter {
+  increment() {
+    return super.increment() * 100;
+  }
+}
+
+export default function () {
+  const c = new LoudCounter();
+  return c.increment();
+}
@@ -0,0 +1,44 @@
+//! test_output([50,"ab","A:b!"])
+
+class A {
+  v = 1;
+  #secret = "a";
+
+  get x() {
+    return this.v;
+  }
+
+  get secret() {
+    return this.#secret;
+  }
+
+  static tag = "a";
+
+  static get label() {
+    return "A:" + this.tag;
+  }
+}
+
+class B extends A {
+  v = 5;
+
+  get x() {
+    return super.x * 10;
+  }
+
+  get secret() {
+    return super.secret + "b";
+  }
+
+  static tag = "b";
+
+  static get label() {
+    return super.label + "!";
+  }
+}
+
+export default function () {
+  const b = new B();
+
+  return [b.x, b.secret, B.label];
+}
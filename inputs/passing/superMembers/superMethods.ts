@@ -0,0 +1,64 @@
+//! test_output(["Rex says woof!",1,"animal/dog","small Bit says woof!",1,"animal/dog",false,true])
+
+class Animal {
+  sound = "...";
+  count = 0;
+
+  constructor(public name: string) {}
+
+  speak() {
+    this.count++;
+    return `${this.name} says ${this.sound}`;
+  }
+
+  static describe() {
+    return "animal";
+  }
+}
+
+class Dog extends Animal {
+  sound = "woof";
+
+  speak() {
+    return super.speak() + "!";
+  }
+
+  static describe() {
+    return super.describe() + "/dog";
+  }
+
+  parentSpeak() {
+    return super.speak;
+  }
+}
+
+class Puppy extends Dog {
+  speak() {
+    return "small " + super.speak();
+  }
+
+  get usesAnimalSpeak() {
+    return super["speak"] === Animal.prototype.speak;
+  }
+}
+
+export default function () {
+  let d = new Dog("Rex");
+  const s = d.speak();
+
+  let p = new Puppy("Bit");
+  const ps = p.speak();
+
+  const cd = new Dog("Const");
+
+  return [
+    s,
+    d.count,
+    Dog.describe(),
+    ps,
+    p.count,
+    Puppy.describe(),
+    p.usesAnimalSpeak,
+    cd.parentSpeak() === Animal.prototype.speak,
+  ];
+}
@@ -0,0 +1,36 @@
+//! test_output(["a1b",20,2,undefined])
+
+class A {
+  n = 1;
+
+  hello() {
+    return "a" + this.n;
+  }
+
+  bump() {
+    this.n++;
+    return this.n;
+  }
+}
+
+class B extends A {
+  hello() {
+    return super.hello() + "b";
+  }
+
+  bump() {
+    return super.bump() * 10;
+  }
+
+  getN() {
+    // Fields are on the instance, not the prototype
+    return super.n;
+  }
+}
+
+export default function () {
+  const b = new B();
+  let m = new B();
+
+  return [b.hello(), m.bump(), m.n, m.getN()];
+}
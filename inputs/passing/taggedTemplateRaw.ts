@@ -0,0 +1,28 @@
+//! test_output(["Cannot assign to \"raw\" of array (arrays only have elements)","Cannot assign to \"raw\" of array (arrays only have elements)",2,true,false,undefined,undefined])
+
+function strings(strings: TemplateStringsArray) {
+  return strings;
+}
+
+function tryAssignRaw(arr: unknown[]) {
+  try {
+    (arr as any).raw = ["x"];
+    return arr;
+  } catch (e) {
+    return (e as Error).message;
+  }
+}
+
+export default function () {
+  const s = strings`a${1}\n`;
+
+  return [
+    tryAssignRaw([1, 2]),
+    tryAssignRaw(s as unknown as unknown[]),
+    s.raw[1].length,
+    s === strings`a${2}\n`,
+    (s as unknown as string[]) === ["a", "\n"],
+    (s.concat() as unknown as { raw: unknown }).raw,
+    ([1, 2] as unknown as { raw: unknown }).raw,
+  ];
+}
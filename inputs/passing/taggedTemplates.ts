@@ -0,0 +1,54 @@
+//! test_output(["SELECT * FROM users WHERE name = $1 AND age > $2",["Alice",30],["C:","new","table"],12,4,3,"1 + 2 = 3","<b>x</b>",[2,2],true])
+
+function sql(strings: TemplateStringsArray, a: unknown, b: unknown) {
+  let text = strings[0];
+
+  for (let i = 1; i < strings.length; i++) {
+    text += `$${i}${strings[i]}`;
+  }
+
+  return { text, values: [a, b] };
+}
+
+function rawFirst(strings: TemplateStringsArray) {
+  return strings.raw[0];
+}
+
+function cookedFirst(strings: TemplateStringsArray) {
+  return strings[0];
+}
+
+class Markup {
+  count = 0;
+
+  tag(strings: TemplateStringsArray, name: string) {
+    this.count++;
+    return `<${name}>${strings[1]}</${name}>`;
+  }
+}
+
+export default function () {
+  const name = "Alice";
+  const query = sql`SELECT * FROM users WHERE name = ${name} AND age > ${30}`;
+
+  let markup = new Markup();
+  const b = markup.tag`${"b"}x`;
+
+  const lengths = ((strings: TemplateStringsArray) => [
+    strings.length,
+    strings.raw.length,
+  ])`a${1}`;
+
+  return [
+    query.text,
+    query.values,
+    String.raw`C:\new\table`.split("\\"),
+    String.raw`C:\new\table`.length,
+    rawFirst`a\tb`.length,
+    cookedFirst`a\tb`.length,
+    String.raw`${1} + ${2} = ${1 + 2}`,
+    b,
+    lengths,
+    markup.count === 1,
+  ];
+}
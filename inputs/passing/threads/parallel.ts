@@ -0,0 +1,23 @@
+//! test_output([[1,"one"],{"x":2,"y":[3]},6])
+
+export default function main() {
+  const widget = new Widget(3);
+
+  return parallel([
+    () => [1, "one"],
+    () => ({ x: 2, y: [widget.size] }),
+    () => widget.calculate(2),
+  ]);
+}
+
+function parallel<T>(jobs: (() => T)[]): T[] {
+  return Promise.all(jobs.map((job) => vs.thread(job))).wait();
+}
+
+class Widget {
+  constructor(public size: number) {}
+
+  calculate(factor: number) {
+    return this.size * factor;
+  }
+}
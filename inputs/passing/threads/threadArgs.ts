@@ -0,0 +1,10 @@
+//! test_output(["aXbXc",true,4])
+
+export default async function main() {
+  const joined = await vs.thread((parts: string[], sep: string) => parts.join(sep), ["a", "b", "c"], "X");
+  const matched = await vs.thread((re: RegExp) => re.test("abc123"), /\d+$/);
+
+  const raw = await vs.thread((strings: TemplateStringsArray) => strings.raw[0].length, ((strings: TemplateStringsArray) => strings)`a\tb`);
+
+  return [joined, matched, raw];
+}
@@ -0,0 +1,9 @@
+//! test_output(42)
+
+export default function main() {
+  return vs.thread(async () => (await double(20)) + 2).wait();
+}
+
+async function double(x: number) {
+  return 2 * x;
+}
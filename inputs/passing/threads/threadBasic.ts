@@ -0,0 +1,15 @@
+//! test_output(4950)
+
+export default async function main() {
+  return await vs.thread(() => sum(100));
+}
+
+function sum(n: number) {
+  let total = 0;
+
+  for (let i = 0; i < n; i++) {
+    total += i;
+  }
+
+  return total;
+}
@@ -0,0 +1,13 @@
+//! test_output(["caught","from thread"])
+
+export default async function main() {
+  try {
+    await vs.thread(() => {
+      throw new Error("from thread");
+    });
+  } catch (e) {
+    return ["caught", (e as Error).message];
+  }
+
+  return ["not caught"];
+}
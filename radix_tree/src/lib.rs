@@ -1,10 +1,13 @@
 mod strict_radix_tree;
 mod strict_radix_tree_iterator;
 
-pub use crate::strict_radix_tree::StrictRadixTree;
+pub use crate::strict_radix_tree::{StrictRadixTree, StrictRadixTreeData};
+pub use crate::strict_radix_tree_iterator::StrictRadixTreeIterator;
 
 #[cfg(test)]
 mod tests {
+  use std::rc::Rc;
+
   use super::*;
 
   #[test]
@@ -138,4 +141,83 @@ mod tests {
     assert_eq!(tree.len(), 1);
     assert_eq!(tree.depth(), 1);
   }
+
+  #[test]
+  fn resize() {
+    let mut tree = StrictRadixTree::<usize, 4>::new();
+
+    tree.resize(70, 7);
+    assert_eq!(tree.len(), 70);
+    assert_eq!(tree.depth(), 4);
+    assert!(tree.iter().all(|v| *v == 7));
+
+    tree.resize(3, 0);
+    assert_eq!(tree.to_vec(), vec![7, 7, 7]);
+    assert_eq!(tree.depth(), 1);
+
+    tree.resize(0, 0);
+    assert!(tree.is_empty());
+  }
+
+  #[test]
+  fn from_vec() {
+    let tree = StrictRadixTree::<usize, 4>::from((0..100).collect::<Vec<_>>());
+    assert_eq!(tree.len(), 100);
+    assert_eq!(tree.to_vec(), (0..100).collect::<Vec<_>>());
+    assert_eq!(
+      format!("{:?}", StrictRadixTree::<usize, 4>::from(vec![1, 2])),
+      "[1, 2]"
+    );
+  }
+
+  #[test]
+  fn structural_sharing() {
+    let mut tree = (0..100).collect::<StrictRadixTree<usize, 4>>();
+    let copy = tree.clone();
+
+    tree[0] = 1000;
+
+    assert_eq!(copy[0], 0);
+    assert_eq!(tree[0], 1000);
+
+    let (StrictRadixTreeData::Meta(tree_meta), StrictRadixTreeData::Meta(copy_meta)) =
+      (tree.data(), copy.data())
+    else {
+      panic!("Expected meta nodes");
+    };
+
+    assert!(!Rc::ptr_eq(tree_meta[0].rc(), copy_meta[0].rc()));
+    assert!(Rc::ptr_eq(tree_meta[1].rc(), copy_meta[1].rc()));
+  }
+
+  #[test]
+  fn from_parts() {
+    let leaves = |range: std::ops::Range<usize>| {
+      StrictRadixTree::<usize, 4>::from_leaves(range.collect()).unwrap()
+    };
+
+    let tree = StrictRadixTree::from_meta(vec![leaves(0..4), leaves(4..6)]).unwrap();
+    assert_eq!(tree.len(), 6);
+    assert_eq!(tree.to_vec(), (0..6).collect::<Vec<_>>());
+
+    let deep = StrictRadixTree::from_meta(vec![leaves(6..8)]).unwrap();
+
+    // Too many leaves
+    assert!(StrictRadixTree::<usize, 4>::from_leaves((0..5).collect()).is_none());
+
+    // No subtrees
+    assert!(StrictRadixTree::<usize, 4>::from_meta(vec![]).is_none());
+
+    // Non-last subtree not full
+    assert!(StrictRadixTree::from_meta(vec![leaves(0..2), leaves(2..4)]).is_none());
+
+    // Empty last subtree
+    assert!(StrictRadixTree::from_meta(vec![leaves(0..4), leaves(0..0)]).is_none());
+
+    // Subtrees at different depths
+    assert!(StrictRadixTree::from_meta(vec![leaves(0..4), deep]).is_none());
+
+    // Too many subtrees
+    assert!(StrictRadixTree::from_meta(vec![leaves(0..4); 5]).is_none());
+  }
 }
@@ -1,4 +1,5 @@
 use std::{
+  fmt,
   mem::swap,
   ops::{Index, IndexMut},
   rc::Rc,
@@ -8,8 +9,10 @@ use arrayvec::ArrayVec;
 
 use crate::strict_radix_tree_iterator::StrictRadixTreeIterator;
 
+/// The contents of a node. Meta nodes hold subtrees, and all of the leaves are at the same depth.
+/// Every node except the last one at each level is full.
 #[derive(Clone)]
-pub(crate) enum StrictRadixTreeData<T, const N: usize> {
+pub enum StrictRadixTreeData<T, const N: usize> {
   Meta(ArrayVec<StrictRadixTree<T, N>, N>),
   Leaves(ArrayVec<T, N>),
 }
@@ -24,6 +27,41 @@ impl<T: Clone, const N: usize> StrictRadixTree<T, N> {
     ))
   }
 
+  /// A single leaf node, or `None` if there are more than `N` leaves.
+  pub fn from_leaves(leaves: Vec<T>) -> Option<Self> {
+    if leaves.len() > N {
+      return None;
+    }
+
+    Some(StrictRadixTree(Rc::new(StrictRadixTreeData::Leaves(
+      leaves.into_iter().collect(),
+    ))))
+  }
+
+  /// A meta node holding `meta`, or `None` if the subtrees don't keep the tree's invariants: there
+  /// must be between 1 and `N` non-empty subtrees of the same depth, and all but the last must be
+  /// full.
+  pub fn from_meta(meta: Vec<StrictRadixTree<T, N>>) -> Option<Self> {
+    let (last, rest) = meta.split_last()?;
+
+    if meta.len() > N || last.is_empty() {
+      return None;
+    }
+
+    let depth = last.depth();
+    let full_len = N.checked_pow(depth as u32);
+
+    for subtree in rest {
+      if subtree.depth() != depth || Some(subtree.len()) != full_len {
+        return None;
+      }
+    }
+
+    Some(StrictRadixTree(Rc::new(StrictRadixTreeData::Meta(
+      meta.into_iter().collect(),
+    ))))
+  }
+
   pub fn clear(&mut self) {
     match self.data_mut() {
       StrictRadixTreeData::Leaves(leaves) => leaves.clear(),
@@ -222,6 +260,23 @@ impl<T: Clone, const N: usize> StrictRadixTree<T, N> {
     }
   }
 
+  /// Truncates the tree, or extends it with clones of `value`.
+  pub fn resize(&mut self, len: usize, value: T) {
+    for _ in self.len()..len {
+      self.push(value.clone());
+    }
+
+    self.truncate(len);
+  }
+
+  pub fn iter(&self) -> StrictRadixTreeIterator<'_, T, N> {
+    StrictRadixTreeIterator::new(self)
+  }
+
+  pub fn to_vec(&self) -> Vec<T> {
+    self.iter().cloned().collect()
+  }
+
   pub fn first(&self) -> Option<&T> {
     let mut tree = self;
 
@@ -314,7 +369,12 @@ impl<T: Clone, const N: usize> StrictRadixTree<T, N> {
     StrictRadixTree::<T, N>(Rc::new(StrictRadixTreeData::<T, N>::Meta(ArrayVec::new())))
   }
 
-  pub(crate) fn data(&self) -> &StrictRadixTreeData<T, N> {
+  pub fn data(&self) -> &StrictRadixTreeData<T, N> {
+    &self.0
+  }
+
+  /// The node's `Rc`, which identifies it. Trees which share a node share its `Rc`.
+  pub fn rc(&self) -> &Rc<StrictRadixTreeData<T, N>> {
     &self.0
   }
 
@@ -334,18 +394,39 @@ impl<T: Clone, const N: usize> StrictRadixTree<T, N> {
     res
   }
 
-  fn index_path(&self, mut i: usize) -> Option<Vec<usize>> {
-    let mut path = vec![0; self.depth()];
+  /// The position of index `i` within each node from the root down to the leaves, or `None` if
+  /// `i` doesn't fit in a tree of this depth.
+  fn index_path(&self, i: usize) -> Option<IndexPath<N>> {
+    let mut divisor = 1usize;
+
+    for _ in 1..self.depth() {
+      divisor = divisor.checked_mul(N)?;
+    }
 
-    for p in path.iter_mut().rev() {
-      *p = i % N;
-      i /= N;
+    match i / divisor < N {
+      true => Some(IndexPath { i, divisor }),
+      false => None,
     }
+  }
+}
+
+struct IndexPath<const N: usize> {
+  i: usize,
+  divisor: usize,
+}
+
+impl<const N: usize> Iterator for IndexPath<N> {
+  type Item = usize;
 
-    match i {
-      0 => Some(path),
-      _ => None,
+  fn next(&mut self) -> Option<usize> {
+    if self.divisor == 0 {
+      return None;
     }
+
+    let p = (self.i / self.divisor) % N;
+    self.divisor /= N;
+
+    Some(p)
   }
 }
 
@@ -355,6 +436,35 @@ impl<T: Clone, const N: usize> Default for StrictRadixTree<T, N> {
   }
 }
 
+impl<T: Clone, const N: usize> From<Vec<T>> for StrictRadixTree<T, N> {
+  fn from(values: Vec<T>) -> Self {
+    values.into_iter().collect()
+  }
+}
+
+impl<T: Clone, const N: usize> FromIterator<T> for StrictRadixTree<T, N> {
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    let mut tree = Self::new();
+    tree.extend(iter);
+
+    tree
+  }
+}
+
+impl<T: Clone, const N: usize> Extend<T> for StrictRadixTree<T, N> {
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    for value in iter {
+      self.push(value);
+    }
+  }
+}
+
+impl<T: Clone + fmt::Debug, const N: usize> fmt::Debug for StrictRadixTree<T, N> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_list().entries(self.iter()).finish()
+  }
+}
+
 impl<T: Clone, const N: usize> Index<usize> for StrictRadixTree<T, N> {
   type Output = T;
 
@@ -34,6 +34,23 @@ pub enum BuiltinName {
 
   #[allow(non_camel_case_types)]
   console,
+
+  RegExp,
+  Promise,
+
+  #[allow(non_camel_case_types)]
+  vs,
+
+  SymbolGeneratorReturn,
+  SymbolAsyncIterator,
+
+  Object,
+  JSON,
+  Map,
+  Set,
+
+  TemplateStrings,
+  SuperGet,
 }
 
 pub const BUILTIN_NAMES: [&str; BuiltinName::COUNT] = [
@@ -55,6 +72,17 @@ pub const BUILTIN_NAMES: [&str; BuiltinName::COUNT] = [
   "SymbolIterator",
   "BigInt",
   "console",
+  "RegExp",
+  "Promise",
+  "vs",
+  "SymbolGeneratorReturn",
+  "SymbolAsyncIterator",
+  "Object",
+  "JSON",
+  "Map",
+  "Set",
+  "TemplateStrings",
+  "SuperGet",
 ];
 
 pub const BUILTIN_COUNT: usize = BuiltinName::COUNT;
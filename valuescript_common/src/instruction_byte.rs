@@ -61,13 +61,24 @@ pub enum InstructionByte {
   YieldStar = 0x3a,
   Delete = 0x3b,
   Jsx = 0x3c,
+  Await = 0x3d,
+  ForInKeys = 0x3e,
+  ObjectRest = 0x3f,
+  ObjectSpread = 0x40,
 }
 
 impl InstructionByte {
   pub fn from_byte(byte: u8) -> InstructionByte {
+    match InstructionByte::try_from_byte(byte) {
+      Some(instruction) => instruction,
+      None => panic!("Unrecognized instruction: {}", byte),
+    }
+  }
+
+  pub fn try_from_byte(byte: u8) -> Option<InstructionByte> {
     use InstructionByte::*;
 
-    match byte {
+    Some(match byte {
       0x00 => End,
       0x01 => Mov,
       0x02 => OpInc,
@@ -129,8 +140,12 @@ impl InstructionByte {
       0x3a => YieldStar,
       0x3b => Delete,
       0x3c => Jsx,
+      0x3d => Await,
+      0x3e => ForInKeys,
+      0x3f => ObjectRest,
+      0x40 => ObjectSpread,
 
-      _ => panic!("Unrecognized instruction: {}", byte),
-    }
+      _ => return None,
+    })
   }
 }
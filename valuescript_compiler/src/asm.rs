@@ -6,7 +6,7 @@ use std::{
 };
 
 use num_bigint::BigInt;
-use valuescript_vm::{vs_value::Val, Bytecode, DecoderMaker};
+use valuescript_vm::{position_table::SourcePosition, vs_value::Val, Bytecode, DecoderMaker};
 
 use crate::{
   assemble, assembler::ValueType, expression_compiler::CompiledExpression,
@@ -350,8 +350,13 @@ impl StructuredFormattable for Pointer {
 #[derive(Default, Debug, Clone)]
 pub struct Function {
   pub is_generator: bool,
+  pub is_async: bool,
   pub meta: Option<Pointer>,
   pub parameters: Vec<Register>,
+
+  /// Whether the last parameter collects the remaining arguments into an array.
+  pub has_rest_param: bool,
+
   pub body: Vec<FnLine>,
 }
 
@@ -362,6 +367,10 @@ impl StructuredFormattable for Function {
       Some(p) => format!(" {}", Structured(p)),
     };
 
+    if self.is_async {
+      sf.write("async ")?;
+    }
+
     match self.is_generator {
       false => sf.write(&format!("function{}(", meta_str))?,
       true => sf.write(&format!("function*{}(", meta_str))?,
@@ -371,6 +380,11 @@ impl StructuredFormattable for Function {
       if i > 0 {
         sf.write(", ")?;
       }
+
+      if self.has_rest_param && i == self.parameters.len() - 1 {
+        sf.write("...")?;
+      }
+
       sf.write(parameter)?;
     }
     sf.write(") {")?;
@@ -399,6 +413,9 @@ pub struct Class {
   pub constructor: Value,
   pub prototype: Value,
   pub static_: Value,
+
+  /// The class being extended, or void.
+  pub extends: Value,
 }
 
 impl StructuredFormattable for Class {
@@ -411,6 +428,10 @@ impl StructuredFormattable for Class {
       sf.write_line(&[&"prototype: ", &MultilineValue(&self.prototype), &","])?;
       sf.write_line(&[&"static: ", &MultilineValue(&self.static_), &","])?;
 
+      if self.extends != Value::Void {
+        sf.write_line(&[&"extends: ", &self.extends, &","])?;
+      }
+
       Ok(())
     })?;
 
@@ -513,6 +534,9 @@ pub enum FnLine {
   Empty,
   Comment(String),
   Release(Register),
+
+  /// Marks where the following instructions came from in the source.
+  Position(SourcePosition),
 }
 
 impl StructuredFormattable for FnLine {
@@ -523,6 +547,12 @@ impl StructuredFormattable for FnLine {
       FnLine::Empty => Ok(()),
       FnLine::Comment(message) => sf.write(&format!("// {}", message)),
       FnLine::Release(reg) => sf.write(&format!("(release {})", Structured(reg))),
+      FnLine::Position(position) => sf.write(&format!(
+        "(position {} {}:{})",
+        serde_json::to_string(&position.path).expect("Failed json serialization"),
+        position.line,
+        position.col,
+      )),
     }
   }
 }
@@ -573,6 +603,8 @@ pub enum Value {
   Register(Register),
   Pointer(Pointer),
   Builtin(Builtin),
+  RegExp(Box<RegExp>),
+  PrivateName(Box<PrivateName>),
 }
 
 #[derive(Debug, Clone)]
@@ -622,7 +654,7 @@ impl Value {
         }
       }
       Value::Object(object) => {
-        for (k, v) in &mut object.properties {
+        for (k, v) in object.kvs_mut() {
           k.visit_values_mut(visit);
           v.visit_values_mut(visit);
         }
@@ -631,6 +663,7 @@ impl Value {
         class.constructor.visit_values_mut(visit);
         class.prototype.visit_values_mut(visit);
         class.static_.visit_values_mut(visit);
+        class.extends.visit_values_mut(visit);
       }
       Value::Void => {}
       Value::Undefined => {}
@@ -642,6 +675,8 @@ impl Value {
       Value::Register(..) => {}
       Value::Pointer(..) => {}
       Value::Builtin(..) => {}
+      Value::RegExp(..) => {}
+      Value::PrivateName(..) => {}
     }
   }
 
@@ -656,15 +691,16 @@ impl Value {
         }
       }
       Value::Object(object) => {
-        for (k, v) in &mut object.properties.iter_mut().rev() {
+        for (k, v) in object.kvs_mut().rev() {
           v.visit_registers_mut_rev(visit);
           k.visit_registers_mut_rev(visit);
         }
       }
       Value::Class(class) => {
-        class.constructor.visit_registers_mut_rev(visit);
-        class.prototype.visit_registers_mut_rev(visit);
+        class.extends.visit_registers_mut_rev(visit);
         class.static_.visit_registers_mut_rev(visit);
+        class.prototype.visit_registers_mut_rev(visit);
+        class.constructor.visit_registers_mut_rev(visit);
       }
       Value::Void => {}
       Value::Undefined => {}
@@ -678,6 +714,8 @@ impl Value {
       }
       Value::Pointer(..) => {}
       Value::Builtin(..) => {}
+      Value::RegExp(..) => {}
+      Value::PrivateName(..) => {}
     }
   }
 }
@@ -710,6 +748,8 @@ impl StructuredFormattable for Value {
       Value::Register(value) => sf.write(value),
       Value::Pointer(value) => sf.write(value),
       Value::Builtin(value) => sf.write(value),
+      Value::RegExp(value) => sf.write(&**value),
+      Value::PrivateName(value) => sf.write(&**value),
     }
   }
 }
@@ -740,16 +780,16 @@ impl<'a> StructuredFormattable for MultilineValue<'a> {
         sf.write("]")
       }
       Value::Object(object) => {
-        if object.properties.is_empty() {
+        if object.is_empty() {
           return sf.write("{}");
         }
 
         sf.write("{")?;
 
         sf.nest(|sf| {
-          for (key, value) in &object.properties {
+          for (prefix, key, value) in object.entries() {
             sf.newline()?;
-            sf.write(key)?;
+            sf.write_slice(&[&prefix, key])?;
             sf.write(": ")?;
             sf.write(value)?;
             sf.write(",")?;
@@ -800,6 +840,37 @@ impl StructuredFormattable for Builtin {
   }
 }
 
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct RegExp {
+  pub source: String,
+  pub flags: String,
+}
+
+impl StructuredFormattable for RegExp {
+  fn structured_fmt(&self, sf: &mut StructuredFormatter<'_, '_>) -> std::fmt::Result {
+    sf.write_slice(&[&"/", &self.source, &"/", &self.flags])
+  }
+}
+
+/// The key of a `#private` class member. The brand is the source hash of the declaring class.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PrivateName {
+  pub name: String,
+  pub brand: Hash,
+}
+
+impl StructuredFormattable for PrivateName {
+  fn structured_fmt(&self, sf: &mut StructuredFormatter<'_, '_>) -> std::fmt::Result {
+    sf.write_slice(&[
+      &"private(",
+      &serde_json::to_string(&self.name).expect("Failed json serialization"),
+      &", ",
+      &self.brand,
+      &")",
+    ])
+  }
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Array {
   pub values: Vec<Value>,
@@ -824,22 +895,24 @@ impl StructuredFormattable for Array {
 #[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Object {
   pub properties: Vec<(Value, Value)>,
+  pub getters: Vec<(Value, Value)>,
+  pub setters: Vec<(Value, Value)>,
 }
 
 impl StructuredFormattable for Object {
   fn structured_fmt(&self, sf: &mut StructuredFormatter<'_, '_>) -> std::fmt::Result {
-    if self.properties.is_empty() {
+    if self.is_empty() {
       return sf.write("{}");
     }
 
     sf.write("{ ")?;
 
-    for (i, (key, value)) in self.properties.iter().enumerate() {
+    for (i, (prefix, key, value)) in self.entries().enumerate() {
       if i > 0 {
         sf.write(", ")?;
       }
 
-      sf.write_slice(&[key, &": ", value])?;
+      sf.write_slice(&[&prefix, key, &": ", value])?;
     }
 
     sf.write(" }")
@@ -847,6 +920,33 @@ impl StructuredFormattable for Object {
 }
 
 impl Object {
+  pub fn is_empty(&self) -> bool {
+    self.properties.is_empty() && self.getters.is_empty() && self.setters.is_empty()
+  }
+
+  pub fn has_accessors(&self) -> bool {
+    !self.getters.is_empty() || !self.setters.is_empty()
+  }
+
+  /// The properties followed by the getters and setters, along with the prefix used for each in
+  /// assembly.
+  pub fn entries(&self) -> impl Iterator<Item = (&'static str, &Value, &Value)> {
+    let properties = self.properties.iter().map(|(k, v)| ("", k, v));
+    let getters = self.getters.iter().map(|(k, v)| ("get ", k, v));
+    let setters = self.setters.iter().map(|(k, v)| ("set ", k, v));
+
+    properties.chain(getters).chain(setters)
+  }
+
+  /// Visits the keys and values of the properties, getters and setters.
+  pub fn kvs_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut (Value, Value)> {
+    self
+      .properties
+      .iter_mut()
+      .chain(self.getters.iter_mut())
+      .chain(self.setters.iter_mut())
+  }
+
   pub fn try_resolve_key(&self, key: &String) -> Option<&Value> {
     let mut result: Option<&Value> = None;
 
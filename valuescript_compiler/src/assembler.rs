@@ -6,14 +6,21 @@ use std::{
 use num_bigint::{BigInt, Sign};
 
 use valuescript_common::BuiltinName;
+use valuescript_vm::position_table::PositionTable;
 
 use crate::asm::{
   Array, Builtin, Class, ContentHashable, Definition, DefinitionContent, FnLine, Function, Hash,
-  Instruction, Label, LabelRef, Lazy, Meta, Module, Number, Object, Pointer, Register, Structured,
-  StructuredFormattable, Value,
+  Instruction, Label, LabelRef, Lazy, Meta, Module, Number, Object, Pointer, PrivateName, RegExp,
+  Register, Structured, StructuredFormattable, Value,
 };
 
 pub fn assemble(module: &Module) -> Vec<u8> {
+  assemble_with_positions(module).0
+}
+
+/// Like `assemble`, but also returns a table mapping bytecode offsets to the source positions
+/// recorded by the module's `(position ...)` lines.
+pub fn assemble_with_positions(module: &Module) -> (Vec<u8>, PositionTable) {
   let mut assembler = Assembler {
     output: Vec::new(),
     fn_data: Default::default(),
@@ -21,17 +28,19 @@ pub fn assemble(module: &Module) -> Vec<u8> {
       references: HashMap::new(),
       found_locations: HashMap::new(),
     },
+    positions: Default::default(),
   };
 
   assembler.module(module);
 
-  assembler.output
+  (assembler.output, assembler.positions)
 }
 
 struct Assembler {
   output: Vec<u8>,
   fn_data: AssemblerFnData,
   definitions_map: LocationMap,
+  positions: PositionTable,
 }
 
 impl Assembler {
@@ -76,10 +85,17 @@ impl Assembler {
   }
 
   fn function(&mut self, function: &Function) {
-    self.output.push(match function.is_generator {
-      false => ValueType::Function,
-      true => ValueType::GeneratorFunction,
-    } as u8);
+    let start = self.output.len();
+    self.positions.push(start, None);
+
+    self
+      .output
+      .push(match (function.is_generator, function.is_async) {
+        (false, false) => ValueType::Function,
+        (true, false) => ValueType::GeneratorFunction,
+        (false, true) => ValueType::AsyncFunction,
+        (true, true) => ValueType::AsyncGeneratorFunction,
+      } as u8);
 
     match &function.meta {
       Some(p) => {
@@ -96,7 +112,11 @@ impl Assembler {
     self.fn_data.register_count_pos = self.output.len();
     self.output.push(0xff); // Placeholder for register count
 
-    self.output.push(function.parameters.len() as u8);
+    // The high bit marks the last parameter as a rest parameter
+    self.output.push(match function.has_rest_param {
+      false => function.parameters.len() as u8,
+      true => function.parameters.len() as u8 | 0x80,
+    });
 
     let mut param_set = HashSet::<Register>::new();
 
@@ -120,6 +140,11 @@ impl Assembler {
         FnLine::Label(label) => {
           self.label(label);
         }
+        FnLine::Position(position) => {
+          self
+            .positions
+            .push(self.output.len(), Some(position.clone()));
+        }
         FnLine::Empty | FnLine::Comment(..) | FnLine::Release(..) => {}
       }
     }
@@ -131,6 +156,7 @@ impl Assembler {
     self.output[self.fn_data.register_count_pos] = (self.fn_data.register_map.len() + 3) as u8;
 
     self.fn_data.labels_map.resolve(&mut self.output);
+    self.register_names(start);
   }
 
   fn meta(&mut self, meta: &Meta) {
@@ -168,6 +194,8 @@ impl Assembler {
   }
 
   fn lazy(&mut self, lazy: &Lazy) {
+    let start = self.output.len();
+    self.positions.push(start, None);
     self.output.push(ValueType::Lazy as u8);
 
     self.fn_data = Default::default();
@@ -182,6 +210,11 @@ impl Assembler {
         FnLine::Label(label) => {
           self.label(label);
         }
+        FnLine::Position(position) => {
+          self
+            .positions
+            .push(self.output.len(), Some(position.clone()));
+        }
         FnLine::Empty | FnLine::Comment(..) | FnLine::Release(..) => {}
       }
     }
@@ -193,6 +226,7 @@ impl Assembler {
     self.output[self.fn_data.register_count_pos] = (self.fn_data.register_map.len() + 3) as u8;
 
     self.fn_data.labels_map.resolve(&mut self.output);
+    self.register_names(start);
   }
 
   fn class(&mut self, class: &Class) {
@@ -201,6 +235,7 @@ impl Assembler {
     self.value(&class.constructor);
     self.value(&class.prototype);
     self.value(&class.static_);
+    self.value(&class.extends);
   }
 
   fn label(&mut self, label: &Label) {
@@ -225,10 +260,12 @@ impl Assembler {
       | OpNot(arg, dst)
       | OpBitNot(arg, dst)
       | TypeOf(arg, dst)
+      | ForInKeys(arg, dst)
       | UnaryPlus(arg, dst)
       | UnaryMinus(arg, dst)
       | Import(arg, dst)
-      | ImportStar(arg, dst) => {
+      | ImportStar(arg, dst)
+      | ObjectSpread(arg, dst) => {
         self.value(arg);
         self.register(dst);
       }
@@ -258,6 +295,7 @@ impl Assembler {
       | OpRightShiftUnsigned(arg1, arg2, dst)
       | InstanceOf(arg1, arg2, dst)
       | In(arg1, arg2, dst)
+      | ObjectRest(arg1, arg2, dst)
       | Call(arg1, arg2, dst)
       | Bind(arg1, arg2, dst)
       | Sub(arg1, arg2, dst)
@@ -326,6 +364,10 @@ impl Assembler {
         self.value(value);
         self.register(dst);
       }
+      Await(value, dst) => {
+        self.value(value);
+        self.register(dst);
+      }
       Delete(obj, sub, dst) => {
         self.register(obj);
         self.value(sub);
@@ -361,6 +403,8 @@ impl Assembler {
       Value::Class(class) => self.class(class),
       Value::Pointer(pointer) => self.pointer(pointer),
       Value::Builtin(builtin) => self.builtin(builtin),
+      Value::RegExp(regexp) => self.regexp(regexp),
+      Value::PrivateName(private_name) => self.private_name(private_name),
     }
   }
 
@@ -376,6 +420,17 @@ impl Assembler {
     self.output.push(reg_index);
   }
 
+  fn register_names(&mut self, fn_start: usize) {
+    let mut names = vec!["return".to_string(), "this".to_string()];
+    names.resize(self.fn_data.register_map.len() + 2, "".to_string());
+
+    for (name, index) in &self.fn_data.register_map {
+      names[*index as usize] = name.clone();
+    }
+
+    self.positions.push_register_names(fn_start, names);
+  }
+
   fn lookup_register(&mut self, register: &Register) -> u8 {
     match register.name.as_str() {
       "return" => 0,
@@ -455,6 +510,32 @@ impl Assembler {
     }
   }
 
+  fn regexp(&mut self, value: &RegExp) {
+    self.output.push(ValueType::RegExp as u8);
+
+    for part in [&value.source, &value.flags] {
+      self.varsize_uint(part.len());
+
+      for b in part.as_bytes() {
+        self.output.push(*b);
+      }
+    }
+  }
+
+  fn private_name(&mut self, value: &PrivateName) {
+    self.output.push(ValueType::PrivateName as u8);
+
+    for b in value.brand.0 {
+      self.output.push(b);
+    }
+
+    self.varsize_uint(value.name.len());
+
+    for b in value.name.as_bytes() {
+      self.output.push(*b);
+    }
+  }
+
   fn pointer(&mut self, value: &Pointer) {
     self.output.push(ValueType::Pointer as u8);
     self
@@ -489,6 +570,18 @@ impl Assembler {
       self.value(value);
     }
 
+    for (key, value) in &object.getters {
+      self.output.push(ValueType::Getter as u8);
+      self.value(key);
+      self.value(value);
+    }
+
+    for (key, value) in &object.setters {
+      self.output.push(ValueType::Setter as u8);
+      self.value(key);
+      self.value(value);
+    }
+
     self.output.push(ValueType::End as u8);
   }
 }
@@ -517,6 +610,12 @@ pub enum ValueType {
   GeneratorFunction = 0x14,
   ExportStar = 0x15,
   Meta = 0x16,
+  RegExp = 0x17,
+  AsyncFunction = 0x18,
+  Getter = 0x19,
+  Setter = 0x1a,
+  PrivateName = 0x1b,
+  AsyncGeneratorFunction = 0x1c,
   // External = TBD,
 }
 
@@ -577,3 +676,55 @@ struct AssemblerFnData {
   register_count_pos: usize,
   labels_map: LocationMap,
 }
+
+#[cfg(test)]
+mod tests {
+  use valuescript_vm::position_table::SourcePosition;
+
+  use crate::{asm::Structured, assembly_parser::parse_module};
+
+  use super::assemble_with_positions;
+
+  #[test]
+  fn positions_round_trip() {
+    let module = parse_module(
+      "export @main {}
+
+@main = function (%n) {
+  (position \"main.ts\" 2:3)
+  mov 1 %x
+  (position \"main.ts\" 3:5)
+  op+ %n %x %return
+}",
+    );
+
+    let (code, positions) = assemble_with_positions(&module);
+
+    let at = |line, col| SourcePosition {
+      path: "main.ts".to_string(),
+      line,
+      col,
+    };
+
+    let [first] = positions.line_offsets("main.ts", 2)[..] else {
+      panic!("Expected one offset for line 2");
+    };
+
+    let [second] = positions.line_offsets("main.ts", 3)[..] else {
+      panic!("Expected one offset for line 3");
+    };
+
+    assert!(first < second && second < code.len());
+    assert_eq!(positions.lookup(first), Some(&at(2, 3)));
+    assert_eq!(positions.lookup(second - 1), Some(&at(2, 3)));
+    assert_eq!(positions.lookup(second), Some(&at(3, 5)));
+    assert_eq!(
+      positions.register_names(first),
+      ["return", "this", "n", "x"]
+    );
+
+    // Printing the assembly keeps the positions
+    let reparsed = parse_module(&Structured(&module).to_string());
+    assert_eq!(assemble_with_positions(&reparsed), (code, positions));
+  }
+}
@@ -3,11 +3,12 @@ use std::str::FromStr;
 
 use num_bigint::BigInt;
 use valuescript_common::{InstructionByte, BUILTIN_NAMES};
+use valuescript_vm::position_table::SourcePosition;
 
 use crate::asm::{
   Array, Builtin, Class, ContentHashable, Definition, DefinitionContent, ExportStar, FnLine,
-  Function, Hash, Instruction, Label, LabelRef, Meta, Module, Number, Object, Pointer, Register,
-  Value,
+  Function, Hash, Instruction, Label, LabelRef, Lazy, Meta, Module, Number, Object, Pointer,
+  PrivateName, RegExp, Register, Value,
 };
 
 pub struct AssemblyParser<'a> {
@@ -182,7 +183,7 @@ impl<'a> AssemblyParser<'a> {
     self.parse_optional_whitespace();
 
     let content = 'b: {
-      if self.test_chars("function") {
+      if self.test_chars("function") || self.test_chars("async") {
         break 'b DefinitionContent::Function(self.assemble_function());
       }
 
@@ -190,6 +191,10 @@ impl<'a> AssemblyParser<'a> {
         break 'b DefinitionContent::Meta(self.assemble_fn_meta());
       }
 
+      if self.test_chars("lazy") {
+        break 'b DefinitionContent::Lazy(self.assemble_lazy());
+      }
+
       DefinitionContent::Value(self.assemble_value())
     };
 
@@ -262,6 +267,10 @@ impl<'a> AssemblyParser<'a> {
       ("yield*", InstructionByte::YieldStar),
       ("delete", InstructionByte::Delete),
       ("jsx", InstructionByte::Jsx),
+      ("await", InstructionByte::Await),
+      ("for_in_keys", InstructionByte::ForInKeys),
+      ("object_rest", InstructionByte::ObjectRest),
+      ("object_spread", InstructionByte::ObjectSpread),
     ]);
 
     for (word, instruction) in instruction_word_map {
@@ -387,6 +396,21 @@ impl<'a> AssemblyParser<'a> {
           result.push('\n');
         } else if c == 't' {
           result.push('\t');
+        } else if c == 'r' {
+          result.push('\r');
+        } else if c == 'b' {
+          result.push('\u{8}');
+        } else if c == 'f' {
+          result.push('\u{c}');
+        } else if c == '/' {
+          result.push('/');
+        } else if c == 'u' {
+          let hex: String = (0..4).filter_map(|_| self.pos.next()).collect();
+
+          match u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+            Some(c) => result.push(c),
+            None => panic!("{}", self.render_pos(0, "Invalid unicode escape sequence")),
+          }
         } else {
           panic!("{}", self.render_pos(-1, "Unimplemented escape sequence"));
         }
@@ -414,6 +438,12 @@ impl<'a> AssemblyParser<'a> {
   fn assemble_function(&mut self) -> Function {
     let mut function = Function::default();
 
+    if self.test_chars("async") {
+      self.parse_exact("async");
+      self.parse_whitespace();
+      function.is_async = true;
+    }
+
     self.parse_exact("function");
 
     if self.test_chars("*") {
@@ -421,7 +451,8 @@ impl<'a> AssemblyParser<'a> {
       function.is_generator = true;
     }
 
-    self.parse_whitespace();
+    // Functions without meta are printed as `function(...)`
+    self.parse_optional_whitespace();
 
     if self.test_chars("(") {
       // Leave meta as void
@@ -434,12 +465,17 @@ impl<'a> AssemblyParser<'a> {
 
     loop {
       self.parse_optional_whitespace();
-      let mut next = self.parse_one_of(&["%", ")"]);
+      let mut next = self.parse_one_of(&["%", "...%", ")"]);
 
       if next == ")" {
         break;
       }
 
+      if next == "...%" {
+        function.has_rest_param = true;
+        next = "%".to_string();
+      }
+
       if next != "%" {
         panic!("Expected this to be impossible");
       }
@@ -462,6 +498,24 @@ impl<'a> AssemblyParser<'a> {
 
     self.parse_optional_whitespace();
     self.parse_exact("{");
+    function.body = self.assemble_body();
+
+    function
+  }
+
+  fn assemble_lazy(&mut self) -> Lazy {
+    self.parse_exact("lazy");
+    self.parse_optional_whitespace();
+    self.parse_exact("{");
+
+    Lazy {
+      body: self.assemble_body(),
+    }
+  }
+
+  fn assemble_body(&mut self) -> Vec<FnLine> {
+    let mut body = Vec::<FnLine>::new();
+
     self.parse_line();
 
     loop {
@@ -474,7 +528,7 @@ impl<'a> AssemblyParser<'a> {
 
       if c == '\n' {
         self.pos.next();
-        function.body.push(FnLine::Empty);
+        body.push(FnLine::Empty);
         continue;
       }
 
@@ -495,33 +549,63 @@ impl<'a> AssemblyParser<'a> {
           }
         }
 
-        function.body.push(FnLine::Comment(msg.trim().to_string()));
+        body.push(FnLine::Comment(msg.trim().to_string()));
 
         continue;
       }
 
       if c == '(' {
-        self.parse_exact("(release");
+        if self.parse_one_of(&["(release", "(position"]) == "(position" {
+          body.push(FnLine::Position(self.assemble_position()));
+          continue;
+        }
+
         self.parse_whitespace();
 
         let reg = self.assemble_register();
         self.parse_optional_whitespace();
         self.parse_exact(")\n");
 
-        function.body.push(FnLine::Release(reg));
+        body.push(FnLine::Release(reg));
 
         continue;
       }
 
       let optional_label = self.test_label();
 
-      function.body.push(match optional_label {
+      body.push(match optional_label {
         Some(label) => FnLine::Label(self.assemble_label(label)),
         None => FnLine::Instruction(self.assemble_instruction()),
       });
     }
 
-    function
+    body
+  }
+
+  fn assemble_position(&mut self) -> SourcePosition {
+    self.parse_whitespace();
+    let path = self.parse_string_literal();
+    self.parse_whitespace();
+    let line = self.parse_u32();
+    self.parse_exact(":");
+    let col = self.parse_u32();
+    self.parse_optional_whitespace();
+    self.parse_exact(")\n");
+
+    SourcePosition { path, line, col }
+  }
+
+  fn parse_u32(&mut self) -> u32 {
+    let mut digits = String::new();
+
+    while let Some('0'..='9') = self.pos.peek() {
+      digits.push(self.pos.next().unwrap());
+    }
+
+    match digits.parse() {
+      Ok(value) => value,
+      Err(_) => panic!("{}", self.render_pos(0, "Expected integer")),
+    }
   }
 
   fn assemble_fn_meta(&mut self) -> Meta {
@@ -602,6 +686,15 @@ impl<'a> AssemblyParser<'a> {
     self.parse_exact(",");
     self.parse_optional_whitespace();
 
+    let mut extends = Value::Void;
+
+    if self.test_chars("extends: ") {
+      self.parse_exact("extends: ");
+      extends = self.assemble_value();
+      self.parse_exact(",");
+      self.parse_optional_whitespace();
+    }
+
     self.parse_exact("}");
 
     Class {
@@ -609,6 +702,7 @@ impl<'a> AssemblyParser<'a> {
       constructor,
       prototype,
       static_,
+      extends,
     }
   }
 
@@ -830,6 +924,14 @@ impl<'a> AssemblyParser<'a> {
       Cat => Instruction::Cat(self.assemble_value(), self.assemble_register()),
       Yield => Instruction::Yield(self.assemble_value(), self.assemble_register()),
       YieldStar => Instruction::YieldStar(self.assemble_value(), self.assemble_register()),
+      Await => Instruction::Await(self.assemble_value(), self.assemble_register()),
+      ForInKeys => Instruction::ForInKeys(self.assemble_value(), self.assemble_register()),
+      ObjectRest => Instruction::ObjectRest(
+        self.assemble_value(),
+        self.assemble_value(),
+        self.assemble_register(),
+      ),
+      ObjectSpread => Instruction::ObjectSpread(self.assemble_value(), self.assemble_register()),
       Delete => Instruction::Delete(
         self.assemble_register(),
         self.assemble_value(),
@@ -863,6 +965,7 @@ impl<'a> AssemblyParser<'a> {
       Some('"') => Value::String(self.parse_string_literal()),
       Some('{') => Value::Object(Box::new(self.assemble_object())),
       Some('c') => Value::Class(Box::new(self.assemble_class())),
+      Some('/') => Value::RegExp(Box::new(self.assemble_regexp())),
       Some(ref_c) => {
         let c = *ref_c;
 
@@ -874,6 +977,7 @@ impl<'a> AssemblyParser<'a> {
           "true",
           "Infinity",
           "NaN",
+          "private(",
           "",
         ]);
 
@@ -885,6 +989,7 @@ impl<'a> AssemblyParser<'a> {
           "true" => Value::Bool(true),
           "Infinity" => Value::Number(Number(f64::INFINITY)),
           "NaN" => Value::Number(Number(f64::NAN)),
+          "private(" => Value::PrivateName(Box::new(self.assemble_private_name())),
 
           // TODO: Finish implementing the different values
           _ => {
@@ -901,6 +1006,19 @@ impl<'a> AssemblyParser<'a> {
     }
   }
 
+  fn assemble_private_name(&mut self) -> PrivateName {
+    self.parse_optional_whitespace();
+    let name = self.parse_string_literal();
+    self.parse_optional_whitespace();
+    self.parse_exact(",");
+    self.parse_optional_whitespace();
+    let brand = self.assemble_hash();
+    self.parse_optional_whitespace();
+    self.parse_exact(")");
+
+    PrivateName { name, brand }
+  }
+
   fn assemble_pointer(&mut self) -> Pointer {
     self.parse_exact("@");
     let name = self.parse_identifier();
@@ -955,6 +1073,48 @@ impl<'a> AssemblyParser<'a> {
     Register { take, name }
   }
 
+  fn assemble_regexp(&mut self) -> RegExp {
+    self.parse_exact("/");
+
+    let mut source = String::new();
+    let mut escaping = false;
+    let mut in_class = false;
+
+    loop {
+      let c = match self.pos.next() {
+        Some(c) => c,
+        None => panic!("{}", self.render_pos(0, "Unterminated regex")),
+      };
+
+      if escaping {
+        escaping = false;
+      } else if c == '\\' {
+        escaping = true;
+      } else if c == '[' {
+        in_class = true;
+      } else if c == ']' {
+        in_class = false;
+      } else if c == '/' && !in_class {
+        break;
+      }
+
+      source.push(c);
+    }
+
+    let mut flags = String::new();
+
+    while let Some(c) = self.pos.peek() {
+      if !c.is_ascii_alphabetic() {
+        break;
+      }
+
+      flags.push(*c);
+      self.pos.next();
+    }
+
+    RegExp { source, flags }
+  }
+
   fn assemble_builtin(&mut self) -> Builtin {
     self.parse_exact("$");
 
@@ -1036,9 +1196,17 @@ impl<'a> AssemblyParser<'a> {
     self.parse_exact("{");
 
     loop {
+      self.parse_optional_whitespace();
+
+      let entries = match self.parse_one_of(&["get ", "set ", ""]).as_str() {
+        "get " => &mut object.getters,
+        "set " => &mut object.setters,
+        _ => &mut object.properties,
+      };
+
       match self.assemble_object_kv() {
         None => break object,
-        Some(kv) => object.properties.push(kv),
+        Some(kv) => entries.push(kv),
       };
 
       self.parse_optional_whitespace();
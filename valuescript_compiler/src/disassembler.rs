@@ -0,0 +1,665 @@
+use std::{
+  collections::{BTreeSet, HashSet},
+  rc::Rc,
+};
+
+use num_bigint::BigInt;
+use valuescript_common::{InstructionByte, BUILTIN_NAMES};
+use valuescript_vm::{
+  bytecode_decoder::{BytecodeDecoder, BytecodeType},
+  Bytecode, DecoderMaker,
+};
+
+use crate::{
+  asm::{
+    Array, Builtin, Class, ContentHashable, Definition, DefinitionContent, ExportStar, FnLine,
+    Function, Hash, Instruction, Label, LabelRef, Lazy, Meta, Module, Number, Object, Pointer,
+    PrivateName, RegExp, Register, Value,
+  },
+  assembler::ValueType,
+};
+
+/// Converts bytecode back into assembly.
+///
+/// The original names are not part of the bytecode, so definitions, registers and labels are named
+/// after their positions. Reassembling the result reproduces the original bytecode. Malformed or
+/// truncated bytecode gives an error describing where decoding failed.
+pub fn disassemble(bytecode: &[u8]) -> Result<Module, String> {
+  let bytecode = Rc::new(Bytecode::new(bytecode.to_vec()));
+
+  let mut disassembler = Disassembler {
+    decoder: bytecode.decoder(0),
+    definition_starts: HashSet::new(),
+    unvisited: Vec::new(),
+    exploring: true,
+  };
+
+  disassembler.find_definitions()?;
+
+  disassembler.decoder.pos = 0;
+  disassembler.exploring = false;
+
+  disassembler.module()
+}
+
+struct Disassembler {
+  decoder: BytecodeDecoder,
+
+  /// Positions referenced by pointers. Bytecode doesn't record where a function ends, so these are
+  /// used to tell the end of a function apart from an explicit `end` followed by unreachable code.
+  definition_starts: HashSet<usize>,
+  unvisited: Vec<usize>,
+
+  /// Whether we're still finding definition starts, in which case functions are assumed to end at
+  /// the first `end` that nothing jumps past.
+  exploring: bool,
+}
+
+impl Disassembler {
+  fn find_definitions(&mut self) -> Result<(), String> {
+    self.value()?;
+    self.export_star()?;
+
+    while let Some(pos) = self.unvisited.pop() {
+      self.decoder.pos = pos;
+      self.definition()?;
+    }
+
+    Ok(())
+  }
+
+  fn module(&mut self) -> Result<Module, String> {
+    let export_default = self.value()?;
+    let export_star = self.export_star()?;
+
+    let mut definitions = Vec::<Definition>::new();
+
+    while self.decoder.pos < self.decoder.bytecode.code.len() {
+      definitions.push(self.definition()?);
+    }
+
+    Ok(Module {
+      export_default,
+      export_star,
+      definitions,
+    })
+  }
+
+  fn export_star(&mut self) -> Result<ExportStar, String> {
+    if self.decode_byte()? != ValueType::ExportStar as u8 {
+      return Err(format!("Expected export star at {}", self.decoder.pos - 1));
+    }
+
+    let include_count = self.decode_varsize_uint()?;
+    let mut includes = Vec::<Pointer>::new();
+
+    for _ in 0..include_count {
+      match self.value()? {
+        Value::Pointer(pointer) => includes.push(pointer),
+        _ => return Err("Expected pointer in export star includes".to_string()),
+      }
+    }
+
+    let local = match self.value()? {
+      Value::Object(object) => *object,
+      _ => return Err("Expected object in export star".to_string()),
+    };
+
+    Ok(ExportStar { includes, local })
+  }
+
+  fn definition(&mut self) -> Result<Definition, String> {
+    let pointer = pointer_at(self.decoder.pos);
+
+    let content = match self.peek_byte()? {
+      b if b == ValueType::Meta as u8 => DefinitionContent::Meta(self.meta()?),
+      b if b == ValueType::Lazy as u8 => {
+        self.decode_byte()?;
+        self.decode_byte()?; // Register count (implied by the registers used)
+
+        DefinitionContent::Lazy(Lazy { body: self.body()? })
+      }
+      _ => match self.peek_type()? {
+        BytecodeType::Function
+        | BytecodeType::GeneratorFunction
+        | BytecodeType::AsyncFunction
+        | BytecodeType::AsyncGeneratorFunction => DefinitionContent::Function(self.function()?),
+        _ => DefinitionContent::Value(self.value()?),
+      },
+    };
+
+    Ok(Definition { pointer, content })
+  }
+
+  fn function(&mut self) -> Result<Function, String> {
+    let (is_generator, is_async) = match self.decode_type()? {
+      BytecodeType::Function => (false, false),
+      BytecodeType::GeneratorFunction => (true, false),
+      BytecodeType::AsyncFunction => (false, true),
+      BytecodeType::AsyncGeneratorFunction => (true, true),
+      _ => return Err(format!("Expected function at {}", self.decoder.pos - 1)),
+    };
+
+    let meta = match self.decode_byte()? {
+      0 => None,
+      _ => match self.value()? {
+        Value::Pointer(pointer) => Some(pointer),
+        _ => return Err("Unexpected non-pointer function meta".to_string()),
+      },
+    };
+
+    self.decode_byte()?; // Register count (implied by the registers used)
+    let parameter_byte = self.decode_byte()?;
+    let parameter_count = parameter_byte & 0x7f;
+
+    Ok(Function {
+      is_generator,
+      is_async,
+      meta,
+      parameters: (0..parameter_count).map(|i| register_at(i + 2)).collect(),
+      has_rest_param: parameter_byte & 0x80 != 0,
+      body: self.body()?,
+    })
+  }
+
+  /// Decodes instructions up to the `End` which closes the body. Labels are inserted wherever a
+  /// jump lands.
+  fn body(&mut self) -> Result<Vec<FnLine>, String> {
+    let mut instructions = Vec::<(usize, Instruction)>::new();
+    let mut label_positions = BTreeSet::<usize>::new();
+
+    loop {
+      let pos = self.decoder.pos;
+      let instruction = self.instruction(&mut label_positions)?;
+      let is_end = matches!(instruction, Instruction::End);
+
+      instructions.push((pos, instruction));
+
+      if is_end
+        && label_positions
+          .last()
+          .is_none_or(|last| *last < self.decoder.pos)
+        && self.at_body_end()?
+      {
+        break;
+      }
+    }
+
+    // The assembler adds the final End itself
+    let (end_pos, _) = instructions.pop().unwrap();
+
+    let mut body = Vec::<FnLine>::new();
+
+    for (pos, instruction) in instructions {
+      if label_positions.remove(&pos) {
+        body.push(FnLine::Label(Label {
+          name: label_name(pos),
+        }));
+      }
+
+      body.push(FnLine::Instruction(instruction));
+    }
+
+    if label_positions.remove(&end_pos) {
+      body.push(FnLine::Label(Label {
+        name: label_name(end_pos),
+      }));
+    }
+
+    if let Some(pos) = label_positions.first() {
+      return Err(format!("Jump to {} is not at an instruction boundary", pos));
+    }
+
+    Ok(body)
+  }
+
+  fn at_body_end(&self) -> Result<bool, String> {
+    let pos = self.decoder.pos;
+
+    if pos >= self.decoder.bytecode.code.len() || self.definition_starts.contains(&pos) {
+      return Ok(true);
+    }
+
+    // Definitions never start with 0x00, so a following end still belongs to this body
+    Ok(self.exploring && self.peek_byte()? != InstructionByte::End as u8)
+  }
+
+  fn meta(&mut self) -> Result<Meta, String> {
+    if self.decode_byte()? != ValueType::Meta as u8 {
+      return Err(format!("Expected meta at {}", self.decoder.pos - 1));
+    }
+
+    let name = match self.value()? {
+      Value::String(name) => name,
+      _ => return Err("Expected string for meta name".to_string()),
+    };
+
+    let content_hashable = match self.decode_byte()? {
+      0x00 => ContentHashable::Empty,
+      0x01 => {
+        let src_hash = self.hash()?;
+        let dep_count = self.decode_varsize_uint()?;
+        let deps = (0..dep_count)
+          .map(|_| self.value())
+          .collect::<Result<_, _>>()?;
+
+        ContentHashable::Src(src_hash, deps)
+      }
+      0x02 => ContentHashable::Content(self.hash()?),
+      _ => return Err("Unrecognized ContentHashable case".to_string()),
+    };
+
+    Ok(Meta {
+      name,
+      content_hashable,
+    })
+  }
+
+  fn hash(&mut self) -> Result<Hash, String> {
+    let mut res = [0u8; 32];
+
+    for b in &mut res {
+      *b = self.decode_byte()?;
+    }
+
+    Ok(Hash(res))
+  }
+
+  fn instruction(&mut self, label_positions: &mut BTreeSet<usize>) -> Result<Instruction, String> {
+    use InstructionByte as B;
+
+    Ok(match self.decode_instruction()? {
+      B::End => Instruction::End,
+      B::Mov => Instruction::Mov(self.value()?, self.register()?),
+      B::OpInc => Instruction::OpInc(self.register()?),
+      B::OpDec => Instruction::OpDec(self.register()?),
+      B::OpPlus => Instruction::OpPlus(self.value()?, self.value()?, self.register()?),
+      B::OpMinus => Instruction::OpMinus(self.value()?, self.value()?, self.register()?),
+      B::OpMul => Instruction::OpMul(self.value()?, self.value()?, self.register()?),
+      B::OpDiv => Instruction::OpDiv(self.value()?, self.value()?, self.register()?),
+      B::OpMod => Instruction::OpMod(self.value()?, self.value()?, self.register()?),
+      B::OpExp => Instruction::OpExp(self.value()?, self.value()?, self.register()?),
+      B::OpEq => Instruction::OpEq(self.value()?, self.value()?, self.register()?),
+      B::OpNe => Instruction::OpNe(self.value()?, self.value()?, self.register()?),
+      B::OpTripleEq => Instruction::OpTripleEq(self.value()?, self.value()?, self.register()?),
+      B::OpTripleNe => Instruction::OpTripleNe(self.value()?, self.value()?, self.register()?),
+      B::OpAnd => Instruction::OpAnd(self.value()?, self.value()?, self.register()?),
+      B::OpOr => Instruction::OpOr(self.value()?, self.value()?, self.register()?),
+      B::OpNot => Instruction::OpNot(self.value()?, self.register()?),
+      B::OpLess => Instruction::OpLess(self.value()?, self.value()?, self.register()?),
+      B::OpLessEq => Instruction::OpLessEq(self.value()?, self.value()?, self.register()?),
+      B::OpGreater => Instruction::OpGreater(self.value()?, self.value()?, self.register()?),
+      B::OpGreaterEq => Instruction::OpGreaterEq(self.value()?, self.value()?, self.register()?),
+      B::OpNullishCoalesce => {
+        Instruction::OpNullishCoalesce(self.value()?, self.value()?, self.register()?)
+      }
+      B::OpOptionalChain => {
+        Instruction::OpOptionalChain(self.value()?, self.value()?, self.register()?)
+      }
+      B::OpBitAnd => Instruction::OpBitAnd(self.value()?, self.value()?, self.register()?),
+      B::OpBitOr => Instruction::OpBitOr(self.value()?, self.value()?, self.register()?),
+      B::OpBitNot => Instruction::OpBitNot(self.value()?, self.register()?),
+      B::OpBitXor => Instruction::OpBitXor(self.value()?, self.value()?, self.register()?),
+      B::OpLeftShift => Instruction::OpLeftShift(self.value()?, self.value()?, self.register()?),
+      B::OpRightShift => Instruction::OpRightShift(self.value()?, self.value()?, self.register()?),
+      B::OpRightShiftUnsigned => {
+        Instruction::OpRightShiftUnsigned(self.value()?, self.value()?, self.register()?)
+      }
+      B::TypeOf => Instruction::TypeOf(self.value()?, self.register()?),
+      B::InstanceOf => Instruction::InstanceOf(self.value()?, self.value()?, self.register()?),
+      B::In => Instruction::In(self.value()?, self.value()?, self.register()?),
+      B::Call => Instruction::Call(self.value()?, self.value()?, self.register()?),
+      B::Apply => Instruction::Apply(
+        self.value()?,
+        self.register()?,
+        self.value()?,
+        self.register()?,
+      ),
+      B::ConstApply => Instruction::ConstApply(
+        self.value()?,
+        self.value()?,
+        self.value()?,
+        self.register()?,
+      ),
+      B::Bind => Instruction::Bind(self.value()?, self.value()?, self.register()?),
+      B::Sub => Instruction::Sub(self.value()?, self.value()?, self.register()?),
+      B::SubMov => Instruction::SubMov(self.value()?, self.value()?, self.register()?),
+      B::SubCall => Instruction::SubCall(
+        self.register()?,
+        self.value()?,
+        self.value()?,
+        self.register()?,
+      ),
+      B::Jmp => Instruction::Jmp(self.label_ref(label_positions)?),
+      B::JmpIf => Instruction::JmpIf(self.value()?, self.label_ref(label_positions)?),
+      B::JmpIfNot => Instruction::JmpIfNot(self.value()?, self.label_ref(label_positions)?),
+      B::UnaryPlus => Instruction::UnaryPlus(self.value()?, self.register()?),
+      B::UnaryMinus => Instruction::UnaryMinus(self.value()?, self.register()?),
+      B::New => Instruction::New(self.value()?, self.value()?, self.register()?),
+      B::Throw => Instruction::Throw(self.value()?),
+      B::Import => Instruction::Import(self.value()?, self.register()?),
+      B::ImportStar => Instruction::ImportStar(self.value()?, self.register()?),
+      B::SetCatch => Instruction::SetCatch(self.label_ref(label_positions)?, self.register()?),
+      B::UnsetCatch => Instruction::UnsetCatch,
+      B::ConstSubCall => Instruction::ConstSubCall(
+        self.value()?,
+        self.value()?,
+        self.value()?,
+        self.register()?,
+      ),
+      B::RequireMutableThis => Instruction::RequireMutableThis,
+      B::ThisSubCall => Instruction::ThisSubCall(
+        self.register()?,
+        self.value()?,
+        self.value()?,
+        self.register()?,
+      ),
+      B::Next => Instruction::Next(self.register()?, self.register()?),
+      B::UnpackIterRes => {
+        Instruction::UnpackIterRes(self.register()?, self.register()?, self.register()?)
+      }
+      B::Cat => Instruction::Cat(self.value()?, self.register()?),
+      B::Yield => Instruction::Yield(self.value()?, self.register()?),
+      B::YieldStar => Instruction::YieldStar(self.value()?, self.register()?),
+      B::Delete => Instruction::Delete(self.register()?, self.value()?, self.register()?),
+      B::Jsx => Instruction::Jsx(
+        self.value()?,
+        self.value()?,
+        self.value()?,
+        self.register()?,
+      ),
+      B::Await => Instruction::Await(self.value()?, self.register()?),
+      B::ForInKeys => Instruction::ForInKeys(self.value()?, self.register()?),
+      B::ObjectRest => Instruction::ObjectRest(self.value()?, self.value()?, self.register()?),
+      B::ObjectSpread => Instruction::ObjectSpread(self.value()?, self.register()?),
+    })
+  }
+
+  fn label_ref(&mut self, label_positions: &mut BTreeSet<usize>) -> Result<LabelRef, String> {
+    let pos = self.decode_pos()?;
+    label_positions.insert(pos);
+
+    Ok(LabelRef {
+      name: label_name(pos),
+    })
+  }
+
+  fn register(&mut self) -> Result<Register, String> {
+    Ok(match self.decode_register_index()? {
+      Some(index) => register_at(index as u8),
+      None => Register::ignore(),
+    })
+  }
+
+  fn value(&mut self) -> Result<Value, String> {
+    let type_pos = self.decoder.pos;
+
+    Ok(match self.decode_type()? {
+      BytecodeType::End => return Err(format!("Unexpected end at {}", type_pos)),
+      BytecodeType::Void => Value::Void,
+      BytecodeType::Undefined => Value::Undefined,
+      BytecodeType::Null => Value::Null,
+      BytecodeType::False => Value::Bool(false),
+      BytecodeType::True => Value::Bool(true),
+      BytecodeType::SignedByte => Value::Number(Number(self.decode_signed_byte()? as f64)),
+      BytecodeType::Number => Value::Number(Number(self.decode_number()?)),
+      BytecodeType::String => Value::String(self.decode_string()?),
+      BytecodeType::Array => {
+        let mut values = Vec::<Value>::new();
+
+        while self.peek_type()? != BytecodeType::End {
+          values.push(self.value()?);
+        }
+
+        self.decode_type()?; // End
+
+        Value::Array(Box::new(Array { values }))
+      }
+      BytecodeType::Object => {
+        let mut object = Object::default();
+
+        while self.peek_type()? != BytecodeType::End {
+          let entries = match self.peek_type()? {
+            BytecodeType::Getter => {
+              self.decode_type()?;
+              &mut object.getters
+            }
+            BytecodeType::Setter => {
+              self.decode_type()?;
+              &mut object.setters
+            }
+            _ => &mut object.properties,
+          };
+
+          entries.push((self.value()?, self.value()?));
+        }
+
+        self.decode_type()?; // End
+
+        Value::Object(Box::new(object))
+      }
+      BytecodeType::Pointer => {
+        let pos = self.decode_pos()?;
+
+        if self.definition_starts.insert(pos) {
+          self.unvisited.push(pos);
+        }
+
+        Value::Pointer(pointer_at(pos))
+      }
+      BytecodeType::Register => Value::Register(self.register()?),
+      BytecodeType::TakeRegister => Value::Register(self.register()?.take()),
+      BytecodeType::Builtin => {
+        let code = self.decode_varsize_uint()?;
+
+        match BUILTIN_NAMES.get(code) {
+          Some(name) => Value::Builtin(Builtin {
+            name: name.to_string(),
+          }),
+          None => {
+            return Err(format!(
+              "Unrecognized builtin code {} at {}",
+              code, type_pos
+            ))
+          }
+        }
+      }
+      BytecodeType::Class => {
+        let meta = self.meta()?;
+
+        Value::Class(Box::new(Class {
+          meta,
+          constructor: self.value()?,
+          prototype: self.value()?,
+          static_: self.value()?,
+          extends: self.value()?,
+        }))
+      }
+      BytecodeType::BigInt => Value::BigInt(self.decode_bigint()?),
+      BytecodeType::RegExp => Value::RegExp(Box::new(RegExp {
+        source: self.decode_string()?,
+        flags: self.decode_string()?,
+      })),
+      BytecodeType::PrivateName => {
+        let brand = self.hash()?;
+
+        Value::PrivateName(Box::new(PrivateName {
+          name: self.decode_string()?,
+          brand,
+        }))
+      }
+      BytecodeType::Function
+      | BytecodeType::GeneratorFunction
+      | BytecodeType::AsyncFunction
+      | BytecodeType::AsyncGeneratorFunction => {
+        return Err(format!("Unexpected inline function at {}", type_pos))
+      }
+      BytecodeType::Getter | BytecodeType::Setter => {
+        return Err(format!(
+          "Unexpected accessor outside object at {}",
+          type_pos
+        ))
+      }
+      BytecodeType::Lazy => return Err(format!("Unexpected lazy value at {}", type_pos)),
+      BytecodeType::Unrecognized => {
+        return Err(format!("Unrecognized bytecode type at {}", type_pos))
+      }
+    })
+  }
+
+  // The decoder panics on malformed bytecode, so these check the bytes are there (and valid) first.
+
+  fn require(&self, len: usize) -> Result<(), String> {
+    match self.decoder.pos.checked_add(len) {
+      Some(end) if end <= self.decoder.bytecode.code.len() => Ok(()),
+      _ => Err(format!(
+        "Unexpected end of bytecode at {}",
+        self.decoder.pos
+      )),
+    }
+  }
+
+  fn decode_byte(&mut self) -> Result<u8, String> {
+    self.require(1)?;
+    Ok(self.decoder.decode_byte())
+  }
+
+  fn peek_byte(&self) -> Result<u8, String> {
+    self.require(1)?;
+    Ok(self.decoder.peek_byte())
+  }
+
+  fn decode_type(&mut self) -> Result<BytecodeType, String> {
+    self.require(1)?;
+    Ok(self.decoder.decode_type())
+  }
+
+  fn peek_type(&self) -> Result<BytecodeType, String> {
+    self.require(1)?;
+    Ok(self.decoder.peek_type())
+  }
+
+  fn decode_instruction(&mut self) -> Result<InstructionByte, String> {
+    let pos = self.decoder.pos;
+    let byte = self.decode_byte()?;
+
+    InstructionByte::try_from_byte(byte)
+      .ok_or_else(|| format!("Unrecognized instruction {} at {}", byte, pos))
+  }
+
+  fn decode_signed_byte(&mut self) -> Result<i8, String> {
+    self.require(1)?;
+    Ok(self.decoder.decode_signed_byte())
+  }
+
+  fn decode_number(&mut self) -> Result<f64, String> {
+    self.require(8)?;
+    Ok(self.decoder.decode_number())
+  }
+
+  fn decode_pos(&mut self) -> Result<usize, String> {
+    self.require(2)?;
+    Ok(self.decoder.decode_pos())
+  }
+
+  fn decode_register_index(&mut self) -> Result<Option<usize>, String> {
+    self.require(1)?;
+    Ok(self.decoder.decode_register_index())
+  }
+
+  fn decode_varsize_uint(&mut self) -> Result<usize, String> {
+    let start = self.decoder.pos;
+    let mut res = 0_usize;
+    let mut mul = Some(1_usize);
+
+    loop {
+      let byte = self.decode_byte()?;
+
+      res = mul
+        .and_then(|mul| mul.checked_mul((byte % 128) as usize))
+        .and_then(|part| res.checked_add(part))
+        .ok_or_else(|| format!("Size at {} is too large", start))?;
+
+      if byte & 128 == 0 {
+        return Ok(res);
+      }
+
+      mul = mul.and_then(|mul| mul.checked_mul(128));
+    }
+  }
+
+  fn decode_string(&mut self) -> Result<String, String> {
+    let start = self.decoder.pos;
+    let len = self.decode_varsize_uint()?;
+    self.require(len)?;
+
+    self.decoder.pos = start;
+    Ok(self.decoder.decode_string())
+  }
+
+  fn decode_bigint(&mut self) -> Result<BigInt, String> {
+    let start = self.decoder.pos;
+
+    if self.decode_byte()? > 2 {
+      return Err(format!("Invalid sign for bigint at {}", start));
+    }
+
+    let len = self.decode_varsize_uint()?;
+    self.require(len)?;
+
+    self.decoder.pos = start;
+    Ok(self.decoder.decode_bigint())
+  }
+}
+
+fn pointer_at(pos: usize) -> Pointer {
+  Pointer {
+    name: format!("d{}", pos),
+  }
+}
+
+fn label_name(pos: usize) -> String {
+  format!("L{}", pos)
+}
+
+fn register_at(index: u8) -> Register {
+  match index {
+    0 => Register::return_(),
+    1 => Register::this(),
+    _ => Register::named(format!("r{}", index)),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{assemble, parse_module};
+
+  use super::disassemble;
+
+  #[test]
+  fn malformed_bytecode_is_an_error() {
+    let bytecode = assemble(&parse_module(
+      "export @main {}
+
+@main = function (%n) {
+  op!== %n 0 %notZero
+  jmpif %notZero :recurse
+  mov [\"done\", 12345678901234567890n, 1.5] %return
+  end
+recurse:
+  call @main [%n] %return
+}",
+    ));
+
+    assert!(disassemble(&bytecode).is_ok());
+
+    for len in 0..bytecode.len() {
+      assert!(disassemble(&bytecode[..len]).is_err());
+    }
+
+    let mut bad_instruction = bytecode.clone();
+    let last = bad_instruction.len() - 1;
+    bad_instruction[last] = 0xfe;
+    assert!(disassemble(&bad_instruction).is_err());
+  }
+}
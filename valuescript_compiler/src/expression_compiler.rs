@@ -2,14 +2,19 @@ use std::cell::RefCell;
 use std::mem::take;
 
 use swc_common::Spanned;
+use valuescript_vm::vs_regexp::VsRegExp;
 
-use crate::asm::{Array, Instruction, Label, Number, Object, Register, Structured, Value};
+use crate::asm::{
+  Array, Builtin, Instruction, Label, Number, Object, RegExp, Register, Structured, Value,
+};
 use crate::diagnostic::{Diagnostic, DiagnosticContainer, DiagnosticReporter};
 use crate::function_compiler::{FunctionCompiler, Functionish};
 use crate::get_span_text::get_span_text;
 use crate::ident::Ident as CrateIdent;
 use crate::scope::{NameId, OwnerId};
-use crate::scope_analysis::{fn_to_owner_id, NameType};
+use crate::scope_analysis::{
+  fn_to_owner_id, getter_to_function, prop_name_ident, setter_to_function, NameType,
+};
 use crate::target_accessor::TargetAccessor;
 
 #[derive(Debug, Default)]
@@ -103,19 +108,20 @@ impl<'a, 'fnc> ExpressionCompiler<'a, 'fnc> {
       Bin(bin_exp) => self.binary_expression(bin_exp, target_register),
       Assign(assign_exp) => self.assign_expression(assign_exp, false, target_register),
       Member(member_exp) => self.member_expression(member_exp, target_register),
-      SuperProp(super_prop) => {
-        self.todo(super_prop.span, "SuperProp expression");
-        CompiledExpression::empty()
-      }
+      SuperProp(super_prop) => self.super_prop_expression(super_prop, target_register),
       Cond(cond_exp) => self.cond_expression(cond_exp, target_register),
       Call(call_exp) => match &call_exp.callee {
         swc_ecma_ast::Callee::Expr(callee_expr) => match &**callee_expr {
           swc_ecma_ast::Expr::Member(member_expr) => {
             self.method_call_expression(member_expr, &call_exp.args, target_register)
           }
+          swc_ecma_ast::Expr::SuperProp(super_prop) => {
+            self.super_method_call_expression(super_prop, &call_exp.args, target_register)
+          }
           _ => self.call_expression(call_exp, target_register),
         },
-        _ => {
+        swc_ecma_ast::Callee::Super(_) => self.super_call_expression(call_exp),
+        swc_ecma_ast::Callee::Import(_) => {
           self.todo(call_exp.callee.span(), "non-expression callee");
 
           CompiledExpression::empty()
@@ -132,10 +138,7 @@ impl<'a, 'fnc> ExpressionCompiler<'a, 'fnc> {
       Ident(ident) => self.ident(&CrateIdent::from_swc_ident(ident), target_register),
       Lit(lit) => self.compile_literal(lit).to_ce(),
       Tpl(tpl) => self.template_literal(tpl, target_register),
-      TaggedTpl(tagged_tpl) => {
-        self.todo(tagged_tpl.span, "TaggedTpl expression");
-        CompiledExpression::empty()
-      }
+      TaggedTpl(tagged_tpl) => self.tagged_template(tagged_tpl, target_register),
       Arrow(arrow) => self.arrow_expression(arrow, target_register),
       Class(class_exp) => {
         // TODO: Handle captures
@@ -151,10 +154,7 @@ impl<'a, 'fnc> ExpressionCompiler<'a, 'fnc> {
         self.todo(meta_prop.span, "MetaProp expression");
         CompiledExpression::empty()
       }
-      Await(await_exp) => {
-        self.todo(await_exp.span, "Await expression");
-        CompiledExpression::empty()
-      }
+      Await(await_expr) => self.await_expr(await_expr, target_register),
       Paren(p) => self.compile(&p.expr, target_register),
       JSXMember(jsx_member) => {
         self.todo(jsx_member.span(), "JSXMember expression");
@@ -181,10 +181,8 @@ impl<'a, 'fnc> ExpressionCompiler<'a, 'fnc> {
 
         CompiledExpression::empty()
       }
-      PrivateName(private_name) => {
-        self.todo(private_name.span, "PrivateName expression");
-        CompiledExpression::empty()
-      }
+      // Only valid in `#x in obj`, where it's compiled into the key being checked
+      PrivateName(private_name) => self.private_name(private_name).to_ce(),
       OptChain(opt_chain) => {
         self.todo(opt_chain.span, "OptChain expression");
         CompiledExpression::empty()
@@ -346,6 +344,9 @@ impl<'a, 'fnc> ExpressionCompiler<'a, 'fnc> {
   pub fn get_register_for_ident_mutation(&mut self, ident: &CrateIdent) -> Register {
     let (reg, err_msg) = match self.fnc.lookup_value(ident) {
       Some(Value::Register(reg)) => (Some(reg), None),
+      Some(Value::Pointer(p)) if self.fnc.class_being_initialized.as_ref() == Some(&p) => {
+        (Some(Register::this()), None)
+      }
       lookup_result => (
         None,
         Some(format!(
@@ -519,6 +520,30 @@ impl<'a, 'fnc> ExpressionCompiler<'a, 'fnc> {
     self.args(args, target_register)
   }
 
+  /// Compiles the key and function of a method, getter or setter in an object literal.
+  fn method_prop(
+    &mut self,
+    key: &swc_ecma_ast::PropName,
+    function: swc_ecma_ast::Function,
+    sub_nested_registers: &mut Vec<Register>,
+  ) -> (Value, Value) {
+    let mut compiled_key = self.prop_name(key);
+    compiled_key.release_checker.has_unreleased_registers = false;
+    sub_nested_registers.append(&mut compiled_key.nested_registers);
+
+    let fn_ident = prop_name_ident(key).cloned();
+    let fn_name = fn_ident.clone().map(|ident| ident.sym.to_string());
+
+    let p = match &fn_name {
+      Some(name) => self.fnc.allocate_defn(name),
+      None => self.fnc.allocate_defn_numbered("_anon"),
+    };
+
+    FunctionCompiler::new(self.fnc.mc).compile(p.clone(), Functionish::Fn(fn_ident, function));
+
+    (compiled_key.value, Value::Pointer(p))
+  }
+
   pub fn object_expression(
     &mut self,
     object_exp: &swc_ecma_ast::ObjectLit,
@@ -528,13 +553,42 @@ impl<'a, 'fnc> ExpressionCompiler<'a, 'fnc> {
 
     let mut sub_nested_registers = Vec::<Register>::new();
 
+    // Once there's a spread, the object is built up in this register. Properties in between spreads
+    // are collected in `object_asm` and copied in as a group.
+    let mut spread_reg: Option<Register> = None;
+
     for i in 0..object_exp.props.len() {
       use swc_ecma_ast::Prop;
       use swc_ecma_ast::PropOrSpread;
 
       match &object_exp.props[i] {
         PropOrSpread::Spread(spread) => {
-          self.todo(spread.span(), "spread expression");
+          let obj_reg = match &spread_reg {
+            Some(obj_reg) => {
+              self.object_spread_segment(take(&mut object_asm), obj_reg);
+              obj_reg.clone()
+            }
+            None => {
+              let obj_reg = self.fnc.allocate_tmp();
+
+              self.fnc.push(Instruction::Mov(
+                Value::Object(Box::new(take(&mut object_asm))),
+                obj_reg.clone(),
+              ));
+
+              spread_reg = Some(obj_reg.clone());
+              obj_reg
+            }
+          };
+
+          let compiled_spread = self.compile(&spread.expr, None);
+
+          self.fnc.push(Instruction::ObjectSpread(
+            compiled_spread.value.clone(),
+            obj_reg,
+          ));
+
+          self.fnc.release_ce(compiled_spread);
         }
         PropOrSpread::Prop(prop) => match &**prop {
           Prop::Shorthand(ident) => {
@@ -562,38 +616,64 @@ impl<'a, 'fnc> ExpressionCompiler<'a, 'fnc> {
             object_asm.properties.push((prop_key, prop_value));
           }
           Prop::Assign(assign) => self.todo(assign.span(), "Assign prop"),
-          Prop::Getter(getter) => self.todo(getter.span(), "Getter prop"),
-          Prop::Setter(setter) => self.todo(setter.span(), "Setter prop"),
-          Prop::Method(method) => {
-            let mut compiled_key = self.prop_name(&method.key);
-            compiled_key.release_checker.has_unreleased_registers = false;
-            sub_nested_registers.append(&mut compiled_key.nested_registers);
-
-            let prop_key = compiled_key.value;
-
-            let fn_ident = match &method.key {
-              swc_ecma_ast::PropName::Ident(ident) => Some(ident.clone()),
-              _ => None,
-            };
-
-            let fn_name = fn_ident.clone().map(|ident| ident.sym.to_string());
+          Prop::Getter(_) | Prop::Setter(_) if spread_reg.is_some() => {
+            self.todo(prop.span(), "Accessor after spread in object literal");
+          }
+          Prop::Getter(getter) => {
+            let kv = self.method_prop(
+              &getter.key,
+              getter_to_function(getter),
+              &mut sub_nested_registers,
+            );
 
-            let p = match &fn_name {
-              Some(name) => self.fnc.allocate_defn(name),
-              None => self.fnc.allocate_defn_numbered("_anon"),
-            };
+            object_asm.getters.push(kv);
+          }
+          Prop::Setter(setter) => {
+            let kv = self.method_prop(
+              &setter.key,
+              setter_to_function(setter),
+              &mut sub_nested_registers,
+            );
 
-            FunctionCompiler::new(self.fnc.mc).compile(
-              p.clone(),
-              Functionish::Fn(fn_ident, method.function.clone()),
+            object_asm.setters.push(kv);
+          }
+          Prop::Method(method) => {
+            let kv = self.method_prop(
+              &method.key,
+              method.function.clone(),
+              &mut sub_nested_registers,
             );
 
-            object_asm.properties.push((prop_key, Value::Pointer(p)));
+            object_asm.properties.push(kv);
           }
         },
       }
     }
 
+    if let Some(obj_reg) = spread_reg {
+      self.object_spread_segment(object_asm, &obj_reg);
+
+      for reg in sub_nested_registers {
+        self.fnc.release_reg(&reg);
+      }
+
+      // The object is built in its own register (rather than the target) because the target might
+      // be spread into it, as in `x = { ...x, a: 1 }`
+      return match target_register {
+        None => CompiledExpression::new(Value::Register(obj_reg.clone()), vec![obj_reg]),
+        Some(tr) => {
+          self.fnc.push(Instruction::Mov(
+            Value::Register(obj_reg.clone()),
+            tr.clone(),
+          ));
+
+          self.fnc.release_reg(&obj_reg);
+
+          CompiledExpression::new(Value::Register(tr), vec![])
+        }
+      };
+    }
+
     match target_register {
       None => CompiledExpression::new(Value::Object(Box::new(object_asm)), sub_nested_registers),
       Some(tr) => {
@@ -611,6 +691,17 @@ impl<'a, 'fnc> ExpressionCompiler<'a, 'fnc> {
     }
   }
 
+  fn object_spread_segment(&mut self, object_asm: Object, obj_reg: &Register) {
+    if object_asm.properties.is_empty() {
+      return;
+    }
+
+    self.fnc.push(Instruction::ObjectSpread(
+      Value::Object(Box::new(object_asm)),
+      obj_reg.clone(),
+    ));
+  }
+
   pub fn prop_name(&mut self, prop_name: &swc_ecma_ast::PropName) -> CompiledExpression {
     use swc_ecma_ast::PropName;
 
@@ -649,9 +740,21 @@ impl<'a, 'fnc> ExpressionCompiler<'a, 'fnc> {
       swc_ecma_ast::MemberProp::Ident(ident) => Value::String(ident.sym.to_string()).to_ce(),
       swc_ecma_ast::MemberProp::Computed(computed) => self.compile(&computed.expr, target_register),
       swc_ecma_ast::MemberProp::PrivateName(private_name) => {
-        self.todo(private_name.span(), "private name member property");
+        self.private_name(private_name).to_ce()
+      }
+    }
+  }
 
-        CompiledExpression::empty()
+  pub fn private_name(&mut self, private_name: &swc_ecma_ast::PrivateName) -> Value {
+    match self.fnc.mc.lookup_private_name(private_name) {
+      Some(private_scope) => private_scope.key(private_name),
+      None => {
+        self.error(
+          private_name.span,
+          &format!("Private name #{} is not defined", private_name.id.sym),
+        );
+
+        Value::Undefined
       }
     }
   }
@@ -825,19 +928,35 @@ impl<'a, 'fnc> ExpressionCompiler<'a, 'fnc> {
     call_exp: &swc_ecma_ast::CallExpr,
     target_register: Option<Register>,
   ) -> CompiledExpression {
-    let mut nested_registers = Vec::<Register>::new();
-
     let callee = match &call_exp.callee {
-      swc_ecma_ast::Callee::Expr(expr) => self.compile(expr, None),
+      swc_ecma_ast::Callee::Expr(expr) => expr,
       _ => {
         self.todo(call_exp.callee.span(), "non-expression callee");
 
-        CompiledExpression::empty()
+        return CompiledExpression::empty();
       }
     };
 
-    let args = call_exp.args.iter().map(Some);
-    let compiled_args = self.args(args, None);
+    self.call_with_args(
+      callee,
+      |ec| ec.args(call_exp.args.iter().map(Some), None),
+      target_register,
+    )
+  }
+
+  fn call_with_args<F>(
+    &mut self,
+    callee_expr: &swc_ecma_ast::Expr,
+    compile_args: F,
+    target_register: Option<Register>,
+  ) -> CompiledExpression
+  where
+    F: FnOnce(&mut Self) -> CompiledExpression,
+  {
+    let mut nested_registers = Vec::<Register>::new();
+
+    let callee = self.compile(callee_expr, None);
+    let compiled_args = compile_args(self);
 
     let dest = match target_register {
       Some(tr) => tr,
@@ -861,6 +980,133 @@ impl<'a, 'fnc> ExpressionCompiler<'a, 'fnc> {
     CompiledExpression::new(Value::Register(dest), nested_registers)
   }
 
+  pub fn super_call_expression(&mut self, call_exp: &swc_ecma_ast::CallExpr) -> CompiledExpression {
+    let args = call_exp.args.iter().map(Some);
+    let compiled_args = self.args(args, None);
+
+    self
+      .fnc
+      .super_call(call_exp.span, compiled_args.value.clone());
+
+    self.fnc.release_ce(compiled_args);
+
+    Value::Undefined.to_ce()
+  }
+
+  /// The object which `super.x` is looked up on: the parent class's prototype, or the parent class
+  /// itself for static members.
+  fn super_object(&mut self, span: swc_common::Span) -> CompiledExpression {
+    let (extends, in_static) = match self.fnc.mc.class_scopes.last() {
+      Some(class_scope) if class_scope.extends != Value::Void => {
+        (class_scope.extends.clone(), class_scope.in_static)
+      }
+      _ => {
+        self.error(span, "super is only valid in derived classes");
+        return CompiledExpression::empty();
+      }
+    };
+
+    if in_static {
+      return extends.to_ce();
+    }
+
+    let prototype = self.fnc.allocate_tmp();
+
+    self.fnc.push(Instruction::Sub(
+      extends,
+      Value::String("prototype".to_string()),
+      prototype.clone(),
+    ));
+
+    CompiledExpression::new(Value::Register(prototype.clone()), vec![prototype])
+  }
+
+  fn super_prop_key(&mut self, prop: &swc_ecma_ast::SuperProp) -> CompiledExpression {
+    match prop {
+      swc_ecma_ast::SuperProp::Ident(ident) => Value::String(ident.sym.to_string()).to_ce(),
+      swc_ecma_ast::SuperProp::Computed(computed) => self.compile(&computed.expr, None),
+    }
+  }
+
+  pub fn super_prop_expression(
+    &mut self,
+    super_prop: &swc_ecma_ast::SuperPropExpr,
+    target_register: Option<Register>,
+  ) -> CompiledExpression {
+    let mut nested_registers = Vec::<Register>::new();
+
+    let obj = self.super_object(super_prop.span);
+    let key = self.super_prop_key(&super_prop.prop);
+
+    let dest = match target_register {
+      Some(tr) => tr,
+      None => {
+        let tmp = self.fnc.allocate_tmp();
+        nested_registers.push(tmp.clone());
+
+        tmp
+      }
+    };
+
+    // Getters on the parent are called with the current `this`, so this goes through a builtin
+    self.fnc.push(Instruction::Call(
+      Value::Builtin(Builtin {
+        name: "SuperGet".to_string(),
+      }),
+      Value::Array(Box::new(Array {
+        values: vec![
+          obj.value.clone(),
+          key.value.clone(),
+          Value::Register(Register::this()),
+        ],
+      })),
+      dest.clone(),
+    ));
+
+    self.fnc.release_ce(obj);
+    self.fnc.release_ce(key);
+
+    CompiledExpression::new(Value::Register(dest), nested_registers)
+  }
+
+  /// Calls the parent class's version of a method on `this`. The call is an `apply` so that
+  /// changes the method makes to `this` are kept.
+  pub fn super_method_call_expression(
+    &mut self,
+    super_prop: &swc_ecma_ast::SuperPropExpr,
+    args: &[swc_ecma_ast::ExprOrSpread],
+    target_register: Option<Register>,
+  ) -> CompiledExpression {
+    let mut nested_registers = Vec::<Register>::new();
+
+    let method = self.super_prop_expression(super_prop, None);
+
+    let args = args.iter().map(Some);
+    let compiled_args = self.args(args, None);
+
+    let dest = match target_register {
+      Some(tr) => tr,
+      None => {
+        let tmp = self.fnc.allocate_tmp();
+        nested_registers.push(tmp.clone());
+
+        tmp
+      }
+    };
+
+    self.fnc.push(Instruction::Apply(
+      method.value.clone(),
+      Register::this(),
+      compiled_args.value.clone(),
+      dest.clone(),
+    ));
+
+    self.fnc.release_ce(method);
+    self.fnc.release_ce(compiled_args);
+
+    CompiledExpression::new(Value::Register(dest), nested_registers)
+  }
+
   pub fn new_expression(
     &mut self,
     new_exp: &swc_ecma_ast::NewExpr,
@@ -908,6 +1154,22 @@ impl<'a, 'fnc> ExpressionCompiler<'a, 'fnc> {
     args: &[swc_ecma_ast::ExprOrSpread],
     target_register: Option<Register>,
   ) -> CompiledExpression {
+    self.method_call_with_args(
+      callee_expr,
+      |ec| ec.args(args.iter().map(Some), None),
+      target_register,
+    )
+  }
+
+  fn method_call_with_args<F>(
+    &mut self,
+    callee_expr: &swc_ecma_ast::MemberExpr,
+    compile_args: F,
+    target_register: Option<Register>,
+  ) -> CompiledExpression
+  where
+    F: FnOnce(&mut Self) -> CompiledExpression,
+  {
     let mut nested_registers = Vec::<Register>::new();
     let mut sub_nested_registers = Vec::<Register>::new();
 
@@ -937,10 +1199,7 @@ impl<'a, 'fnc> ExpressionCompiler<'a, 'fnc> {
     prop.release_checker.has_unreleased_registers = false;
     sub_nested_registers.append(&mut prop.nested_registers);
 
-    let compiled_args = {
-      let args_iter = args.iter().map(Some);
-      self.args(args_iter, None)
-    };
+    let compiled_args = compile_args(self);
 
     let dest = match target_register {
       Some(tr) => tr,
@@ -1248,6 +1507,71 @@ impl<'a, 'fnc> ExpressionCompiler<'a, 'fnc> {
     CompiledExpression::new(Value::Register(acc_reg), nested_registers)
   }
 
+  /// Compiles `` tag`...` `` as a call to `tag`, using method call semantics when `tag` is a member
+  /// expression.
+  pub fn tagged_template(
+    &mut self,
+    tagged_tpl: &swc_ecma_ast::TaggedTpl,
+    target_register: Option<Register>,
+  ) -> CompiledExpression {
+    let compile_args = |ec: &mut Self| ec.template_args(&tagged_tpl.tpl);
+
+    match &*tagged_tpl.tag {
+      swc_ecma_ast::Expr::Member(member_expr) => {
+        self.method_call_with_args(member_expr, compile_args, target_register)
+      }
+      tag => self.call_with_args(tag, compile_args, target_register),
+    }
+  }
+
+  /// The arguments of a tagged template call: the strings array (which also carries the `raw`
+  /// strings) followed by the substitutions.
+  fn template_args(&mut self, tpl: &swc_ecma_ast::Tpl) -> CompiledExpression {
+    let strings = self.fnc.allocate_tmp();
+
+    let cooked = tpl
+      .quasis
+      .iter()
+      .map(|quasi| match &quasi.cooked {
+        Some(cooked) => Value::String(cooked.to_string()),
+        None => Value::Undefined,
+      })
+      .collect();
+
+    let raw = tpl
+      .quasis
+      .iter()
+      .map(|quasi| Value::String(quasi.raw.to_string()))
+      .collect();
+
+    // The `raw` strings can't be assigned after the array is created, so this goes through a builtin
+    self.fnc.push(Instruction::Call(
+      Value::Builtin(Builtin {
+        name: "TemplateStrings".to_string(),
+      }),
+      Value::Array(Box::new(Array {
+        values: vec![
+          Value::Array(Box::new(Array { values: cooked })),
+          Value::Array(Box::new(Array { values: raw })),
+        ],
+      })),
+      strings.clone(),
+    ));
+
+    let mut values = vec![Value::Register(strings.clone())];
+    let mut nested_registers = vec![strings];
+
+    for expr in &tpl.exprs {
+      let mut compiled = self.compile(expr, None);
+
+      values.push(compiled.value);
+      nested_registers.append(&mut compiled.nested_registers);
+      compiled.release_checker.has_unreleased_registers = false;
+    }
+
+    CompiledExpression::new(Value::Array(Box::new(Array { values })), nested_registers)
+  }
+
   pub fn yield_expr(
     &mut self,
     yield_expr: &swc_ecma_ast::YieldExpr,
@@ -1269,6 +1593,22 @@ impl<'a, 'fnc> ExpressionCompiler<'a, 'fnc> {
       }
     };
 
+    let arg_compiled = match self.fnc.fn_.is_async && !yield_expr.delegate {
+      // Async generators yield the results of promises rather than the promises themselves
+      true => {
+        let awaited = self.fnc.allocate_tmp();
+
+        self.fnc.push(Instruction::Await(
+          arg_compiled.value.clone(),
+          awaited.clone(),
+        ));
+
+        self.fnc.release_ce(arg_compiled);
+        CompiledExpression::new(Value::Register(awaited.clone()), vec![awaited])
+      }
+      false => arg_compiled,
+    };
+
     self.fnc.push(match yield_expr.delegate {
       false => Instruction::Yield(arg_compiled.value.clone(), dst.clone()),
       true => Instruction::YieldStar(arg_compiled.value.clone(), dst.clone()),
@@ -1279,6 +1619,33 @@ impl<'a, 'fnc> ExpressionCompiler<'a, 'fnc> {
     CompiledExpression::new(Value::Register(dst), nested_registers)
   }
 
+  pub fn await_expr(
+    &mut self,
+    await_expr: &swc_ecma_ast::AwaitExpr,
+    target_register: Option<Register>,
+  ) -> CompiledExpression {
+    let mut nested_registers = Vec::<Register>::new();
+
+    let arg_compiled = self.compile(&await_expr.arg, None);
+
+    let dst = match target_register {
+      Some(t) => t,
+      None => {
+        let tmp = self.fnc.allocate_tmp();
+        nested_registers.push(tmp.clone());
+        tmp
+      }
+    };
+
+    self
+      .fnc
+      .push(Instruction::Await(arg_compiled.value.clone(), dst.clone()));
+
+    self.fnc.release_ce(arg_compiled);
+
+    CompiledExpression::new(Value::Register(dst), nested_registers)
+  }
+
   pub fn jsx_element(
     &mut self,
     jsx_element: &swc_ecma_ast::JSXElement,
@@ -1454,6 +1821,12 @@ impl<'a, 'fnc> ExpressionCompiler<'a, 'fnc> {
 
     let value = self.fnc.lookup_value(ident).unwrap_or_default();
 
+    if let Value::Pointer(pointer) = &value {
+      if self.fnc.class_being_initialized.as_ref() == Some(pointer) {
+        return Value::Register(Register::this()).to_ce();
+      }
+    }
+
     let name = match self.fnc.lookup(ident) {
       Some(v) => v,
       None => {
@@ -1498,6 +1871,12 @@ impl<'a, 'fnc> ExpressionCompiler<'a, 'fnc> {
   }
 
   pub fn compile_literal(&mut self, lit: &swc_ecma_ast::Lit) -> Value {
+    if let swc_ecma_ast::Lit::Regex(regex) = lit {
+      if let Err(msg) = VsRegExp::validate(&regex.exp, &regex.flags) {
+        self.error(regex.span, &msg);
+      }
+    }
+
     match value_from_literal(lit) {
       Ok(value) => value,
       Err(err) => {
@@ -1558,6 +1937,10 @@ impl<'a, 'fnc> ExpressionCompiler<'a, 'fnc> {
         }
       }
       Pat::Object(object) => {
+        // The keys picked out so far, which a rest pattern leaves out
+        let mut picked_keys = Vec::<Value>::new();
+        let mut compiled_keys = Vec::<CompiledExpression>::new();
+
         for prop in &object.props {
           use swc_ecma_ast::ObjectPatProp;
 
@@ -1572,7 +1955,8 @@ impl<'a, 'fnc> ExpressionCompiler<'a, 'fnc> {
                 param_reg.clone(),
               ));
 
-              self.fnc.release_ce(compiled_key);
+              picked_keys.push(compiled_key.value.clone());
+              compiled_keys.push(compiled_key);
 
               self.pat(&kv.value, &param_reg, false);
             }
@@ -1584,20 +1968,36 @@ impl<'a, 'fnc> ExpressionCompiler<'a, 'fnc> {
 
               self.fnc.push(Instruction::Sub(
                 Value::Register(register.clone()),
-                Value::String(key),
+                Value::String(key.clone()),
                 reg.clone(),
               ));
 
+              picked_keys.push(Value::String(key));
+
               if let Some(value) = &assign.value {
                 self.default_expr(value, &reg);
               }
             }
             ObjectPatProp::Rest(rest) => {
-              self.todo(rest.span, "Rest pattern in object destructuring");
+              let rest_reg = self.fnc.get_pattern_register(&rest.arg);
+
+              self.fnc.push(Instruction::ObjectRest(
+                Value::Register(register.clone()),
+                Value::Array(Box::new(Array {
+                  values: picked_keys.clone(),
+                })),
+                rest_reg.clone(),
+              ));
+
+              self.pat(&rest.arg, &rest_reg, false);
             }
           }
         }
 
+        for compiled_key in compiled_keys {
+          self.fnc.release_ce(compiled_key);
+        }
+
         if !skip_release {
           self.fnc.release_reg(register);
         }
@@ -1879,7 +2279,10 @@ pub fn value_from_literal(lit: &swc_ecma_ast::Lit) -> Result<Value, &'static str
     Null(_) => Value::Null,
     Num(num) => Value::Number(Number(num.value)),
     BigInt(bigint) => Value::BigInt(bigint.value.clone()),
-    Regex(_) => return Err("Regex literals"),
+    Regex(regex) => Value::RegExp(Box::new(RegExp {
+      source: regex.exp.to_string(),
+      flags: regex.flags.to_string(),
+    })),
     JSXText(_) => return Err("JSXText literals"),
   })
 }
@@ -1,12 +1,12 @@
 use std::cell::RefCell;
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap};
 use std::mem::take;
 
 use swc_common::Spanned;
 
 use crate::asm::{
   Builtin, ContentHashable, Definition, DefinitionContent, FnLine, Function, Instruction, Label,
-  Meta, Pointer, Register, Value,
+  Meta, Number, Pointer, Register, Value,
 };
 use crate::diagnostic::{Diagnostic, DiagnosticContainer, DiagnosticReporter};
 use crate::expression_compiler::CompiledExpression;
@@ -63,6 +63,32 @@ impl Functionish {
 pub struct LoopLabels {
   pub continue_: Option<Label>,
   pub break_: Label,
+
+  /// The statement labels (as in `outer: for (...)`) that refer to this loop, switch or block.
+  pub names: Vec<String>,
+
+  /// Labeled blocks (and other non-loop statements) can only be exited by a labeled break.
+  pub is_block: bool,
+
+  pub finally_depth: usize,
+  pub catch_depth: usize,
+}
+
+/// A jump out of one or more try statements. Any finally blocks that are exited need to run
+/// first, and the catch setting needs to be restored to what it was at the target.
+#[derive(Clone)]
+pub struct JumpTarget {
+  pub label: Label,
+  pub finally_depth: usize,
+  pub catch_depth: usize,
+}
+
+pub struct FinallyLabels {
+  pub label: Label,
+
+  /// Jumps that continue after the finally block runs. The jump code register identifies which
+  /// one (if any) is happening, using the index + 1.
+  pub jumps: Vec<JumpTarget>,
 }
 
 pub struct CatchSetting {
@@ -80,7 +106,19 @@ pub struct FunctionCompiler<'a> {
   pub catch_settings: Vec<CatchSetting>,
   pub end_label: Option<Label>,
   pub is_returning_register: Option<Register>,
-  pub finally_labels: Vec<Label>,
+  pub jump_code_register: Option<Register>,
+  pub finally_labels: Vec<FinallyLabels>,
+
+  /// Statement labels waiting for the loop or switch they're attached to.
+  pub pending_labels: Vec<String>,
+
+  /// When compiling the initialization of a class's static members, the class's definition.
+  /// References to it refer to `this` instead, since the definition isn't available yet.
+  pub class_being_initialized: Option<Pointer>,
+
+  /// When compiling the module's initialization code, the registers holding the module-level
+  /// variables it initializes. Everywhere else, these are read through their definitions.
+  pub module_variables: HashMap<NameId, Register>,
 }
 
 impl<'a> DiagnosticContainer for FunctionCompiler<'a> {
@@ -101,7 +139,11 @@ impl<'a> FunctionCompiler<'a> {
       catch_settings: vec![],
       end_label: None,
       is_returning_register: None,
+      jump_code_register: None,
       finally_labels: vec![],
+      pending_labels: vec![],
+      class_being_initialized: None,
+      module_variables: HashMap::new(),
     }
   }
 
@@ -126,6 +168,16 @@ impl<'a> FunctionCompiler<'a> {
     self.fn_.body.push(FnLine::Comment(message));
   }
 
+  /// Records that the following instructions were compiled from the source at `span`.
+  pub fn position(&mut self, span: swc_common::Span) {
+    if span.is_dummy() {
+      return;
+    }
+
+    let position = self.mc.source_position(span);
+    self.fn_.body.push(FnLine::Position(position));
+  }
+
   pub fn lookup(&self, ident: &Ident) -> Option<&Name> {
     let name = self.mc.scope_analysis.lookup(ident);
 
@@ -140,10 +192,15 @@ impl<'a> FunctionCompiler<'a> {
   }
 
   pub fn lookup_value(&self, ident: &Ident) -> Option<Value> {
-    self.mc.scope_analysis.lookup_value(&self.owner_id, ident)
+    let name_id = &self.mc.scope_analysis.refs.get(&ident.span)?.name_id;
+    self.lookup_by_name_id(name_id)
   }
 
   pub fn lookup_by_name_id(&self, name_id: &NameId) -> Option<Value> {
+    if let Some(reg) = self.module_variables.get(name_id) {
+      return Some(Value::Register(reg.clone()));
+    }
+
     self
       .mc
       .scope_analysis
@@ -222,6 +279,12 @@ impl<'a> FunctionCompiler<'a> {
       Functionish::Constructor(..) => false,
     };
 
+    self.fn_.is_async = match &functionish {
+      Functionish::Fn(_, fn_) => fn_.is_async,
+      Functionish::Arrow(arrow_expr) => arrow_expr.is_async,
+      Functionish::Constructor(..) => false,
+    };
+
     let meta_ptr = self
       .mc
       .allocate_defn(&format!("{}_meta", definition_pointer.name));
@@ -280,6 +343,8 @@ impl<'a> FunctionCompiler<'a> {
           self.handle_block_body(block);
         }
         swc_ecma_ast::BlockStmtOrExpr::Expr(expr) => {
+          self.position(expr.span());
+
           let mut expression_compiler = ExpressionCompiler { fnc: self };
 
           expression_compiler.compile_into(expr, Register::return_());
@@ -297,7 +362,22 @@ impl<'a> FunctionCompiler<'a> {
           // This case is constructed artificially when there is no explicit constructor but there
           // are member initializer expressions which need to be compiled into a constructor. I'm
           // not sure whether SWC ever produces this case.
-          None => {}
+          None => {
+            let is_derived = self
+              .mc
+              .class_scopes
+              .last()
+              .is_some_and(|class_scope| class_scope.extends != Value::Void);
+
+            if is_derived {
+              // Equivalent to `constructor(...args) { super(...args); }`
+              let args = self.allocate_reg("args");
+              self.fn_.parameters.push(args.clone());
+              self.fn_.has_rest_param = true;
+
+              self.super_call(constructor.span, Value::Register(args));
+            }
+          }
         };
       }
     };
@@ -322,6 +402,37 @@ impl<'a> FunctionCompiler<'a> {
     });
   }
 
+  /// Runs the parent class's constructor on `this`, followed by the class's own member
+  /// initializers.
+  pub fn super_call(&mut self, span: swc_common::Span, args: Value) {
+    let (extends, member_initializers) = match self.mc.class_scopes.last() {
+      Some(class_scope) if class_scope.extends != Value::Void => (
+        class_scope.extends.clone(),
+        class_scope.member_initializers.clone(),
+      ),
+      _ => {
+        self.error(span, "super() is only valid in derived classes");
+        return;
+      }
+    };
+
+    self.push(Instruction::Apply(
+      extends,
+      Register::this(),
+      args,
+      Register::ignore(),
+    ));
+
+    if let Some(member_initializers) = member_initializers {
+      self.push(Instruction::Apply(
+        Value::Pointer(member_initializers),
+        Register::this(),
+        Value::Array(Box::default()),
+        Register::ignore(),
+      ));
+    }
+  }
+
   fn handle_block_body(&mut self, block: &swc_ecma_ast::BlockStmt) {
     for i in 0..block.stmts.len() {
       self.statement(&block.stmts[i], i == block.stmts.len() - 1);
@@ -399,7 +510,7 @@ impl<'a> FunctionCompiler<'a> {
   }
 
   pub fn get_variable_register(&mut self, ident: &Ident) -> Register {
-    match self.mc.scope_analysis.lookup_value(&self.owner_id, ident) {
+    match self.lookup_value(ident) {
       Some(Value::Register(reg)) => reg,
       lookup_result => {
         self.internal_error(
@@ -474,9 +585,13 @@ impl<'a> FunctionCompiler<'a> {
     };
   }
 
-  fn statement(&mut self, statement: &swc_ecma_ast::Stmt, fn_last: bool) {
+  pub fn statement(&mut self, statement: &swc_ecma_ast::Stmt, fn_last: bool) {
     use swc_ecma_ast::Stmt::*;
 
+    if !matches!(statement, Block(_) | Empty(_)) {
+      self.position(statement.span());
+    }
+
     match statement {
       Block(block) => self.block_statement(block),
       Empty(_) => {}
@@ -491,11 +606,19 @@ impl<'a> FunctionCompiler<'a> {
           Some(expr) => {
             let mut ec = ExpressionCompiler { fnc: self };
             ec.compile_into(expr, Register::return_());
+
+            // Async generators await their return value like they await what they yield
+            if self.fn_.is_async && self.fn_.is_generator {
+              self.push(Instruction::Await(
+                Value::Register(Register::return_()),
+                Register::return_(),
+              ));
+            }
           }
         }
 
         if !fn_last {
-          if let Some(finally_label) = self.finally_labels.last().cloned() {
+          if let Some(finally_label) = self.finally_labels.last().map(|f| f.label.clone()) {
             let is_returning = match self.is_returning_register.clone() {
               Some(is_returning) => is_returning,
               None => {
@@ -514,45 +637,9 @@ impl<'a> FunctionCompiler<'a> {
         }
       }
 
-      Labeled(labeled) => self.todo(labeled.span, "Labeled statement"),
-
-      Break(break_) => {
-        if break_.label.is_some() {
-          self.todo(break_.span, "labeled break statement");
-
-          return;
-        }
-
-        let loop_labels = self.loop_labels.last();
-
-        match loop_labels {
-          Some(loop_labels) => {
-            self.push(Instruction::Jmp(loop_labels.break_.ref_()));
-          }
-          None => {
-            self.error(break_.span, "break statement outside loop");
-          }
-        }
-      }
-      Continue(continue_) => {
-        if continue_.label.is_some() {
-          self.todo(continue_.span, "labeled continue statement");
-
-          return;
-        }
-
-        for label_pair in self.loop_labels.iter().rev() {
-          match &label_pair.continue_ {
-            Some(continue_label) => {
-              self.push(Instruction::Jmp(continue_label.ref_()));
-              return;
-            }
-            None => {}
-          }
-        }
-
-        self.error(continue_.span, "continue statement outside loop");
-      }
+      Labeled(labeled) => self.labeled(labeled),
+      Break(break_) => self.break_(break_),
+      Continue(continue_) => self.continue_(continue_),
       If(if_) => {
         self.if_(if_);
       }
@@ -582,7 +669,7 @@ impl<'a> FunctionCompiler<'a> {
       For(for_) => {
         self.for_(for_);
       }
-      ForIn(for_in) => self.todo(for_in.span, "ForIn statement"),
+      ForIn(for_in) => self.for_in(for_in),
       ForOf(for_of) => {
         self.for_of(for_of);
       }
@@ -595,7 +682,7 @@ impl<'a> FunctionCompiler<'a> {
     }
   }
 
-  fn block_statement(&mut self, block: &swc_ecma_ast::BlockStmt) {
+  pub fn block_statement(&mut self, block: &swc_ecma_ast::BlockStmt) {
     for stmt in &block.stmts {
       self.statement(stmt, false);
     }
@@ -648,10 +735,7 @@ impl<'a> FunctionCompiler<'a> {
       name: ec.fnc.label_allocator.allocate_numbered("sw_end"),
     };
 
-    ec.fnc.loop_labels.push(LoopLabels {
-      continue_: None,
-      break_: end_label.clone(),
-    });
+    ec.fnc.push_loop_labels(None, end_label.clone());
 
     let case_labels = (0..switch.cases.len())
       .map(&mut |i| Label {
@@ -738,7 +822,10 @@ impl<'a> FunctionCompiler<'a> {
       // We use a fresh register here because if we don't put anything in it, it's meaningful. It
       // tells finally not to re-throw.
       let reg = self.allocate_numbered_reg_fresh("_finally_error");
-      self.finally_labels.push(label.clone());
+      self.finally_labels.push(FinallyLabels {
+        label: label.clone(),
+        jumps: vec![],
+      });
 
       finally_error_reg = Some(reg.clone());
 
@@ -751,14 +838,17 @@ impl<'a> FunctionCompiler<'a> {
     let mut catch_error_reg: Option<Register> = None;
 
     if let Some(label) = &catch_label {
-      let reg = match try_
+      let has_param = try_
         .handler
         .as_ref()
         .expect("catch label without handler")
         .param
-      {
-        Some(_) => self.allocate_numbered_reg("_error"),
-        None => Register::ignore(),
+        .is_some();
+
+      // Generators need the error even without a param to rethrow `generator.return()`'s signal.
+      let reg = match has_param || self.fn_.is_generator {
+        true => self.allocate_numbered_reg("_error"),
+        false => Register::ignore(),
       };
 
       catch_error_reg = Some(reg.clone());
@@ -799,6 +889,10 @@ impl<'a> FunctionCompiler<'a> {
       self.label(catch_label.unwrap());
       self.apply_catch_setting(); // TODO: Avoid redundant unset_catch
 
+      if self.fn_.is_generator {
+        self.rethrow_generator_return(catch_error_reg.clone().unwrap());
+      }
+
       for (reg, snap_reg) in snap_pairs {
         self.push(Instruction::Mov(Value::Register(snap_reg), reg));
       }
@@ -830,9 +924,26 @@ impl<'a> FunctionCompiler<'a> {
 
     if let Some(finally_clause) = &try_.finalizer {
       self.label(finally_label.unwrap());
-      self.finally_labels.pop();
+      let jumps = self.finally_labels.pop().unwrap().jumps;
       self.apply_catch_setting();
 
+      let local_jump_code = match jumps.is_empty() {
+        true => None,
+        false => {
+          let jump_code = self.jump_code_register();
+          let local_jump_code = self.allocate_numbered_reg_fresh("_local_jump_code");
+
+          self.push(Instruction::Mov(
+            Value::Register(jump_code.clone()),
+            local_jump_code.clone(),
+          ));
+
+          self.push(Instruction::Mov(Value::Number(Number(0.0)), jump_code));
+
+          Some(local_jump_code)
+        }
+      };
+
       let local_is_returning = match self.is_returning_register.clone() {
         Some(is_returning) => {
           let local_is_returning = self.allocate_numbered_reg_fresh("_local_is_returning");
@@ -894,12 +1005,218 @@ impl<'a> FunctionCompiler<'a> {
             self.is_returning_register.clone().unwrap(),
           ));
 
-          self.push(Instruction::Jmp(self.finally_labels.last().unwrap().ref_()));
+          self.push(Instruction::Jmp(
+            self.finally_labels.last().unwrap().label.ref_(),
+          ));
 
           self.label(after_finally_label);
         }
       }
+
+      if let Some(local_jump_code) = local_jump_code {
+        for (i, target) in jumps.into_iter().enumerate() {
+          let is_jump = self.allocate_numbered_reg("_is_jump");
+
+          let after_jump_label = Label {
+            name: self.label_allocator.allocate_numbered("after_jump"),
+          };
+
+          self.push(Instruction::OpTripleEq(
+            Value::Register(local_jump_code.clone()),
+            Value::Number(Number((i + 1) as f64)),
+            is_jump.clone(),
+          ));
+
+          self.push(Instruction::JmpIfNot(
+            Value::Register(is_jump.clone()),
+            after_jump_label.ref_(),
+          ));
+
+          self.release_reg(&is_jump);
+          self.jump_out(target);
+          self.label(after_jump_label);
+        }
+      }
+    }
+  }
+
+  fn jump_code_register(&mut self) -> Register {
+    match self.jump_code_register.clone() {
+      Some(jump_code) => jump_code,
+      None => {
+        let jump_code = self.allocate_reg_fresh("_jump_code");
+        self.jump_code_register = Some(jump_code.clone());
+        jump_code
+      }
+    }
+  }
+
+  /// Jumps to `target`. If that exits a finally block, this jumps to the innermost one instead,
+  /// which then continues the jump after running.
+  fn jump_out(&mut self, target: JumpTarget) {
+    if self.finally_labels.len() > target.finally_depth {
+      let jump_code_register = self.jump_code_register();
+      let finally_labels = self.finally_labels.last_mut().unwrap();
+      let finally_label = finally_labels.label.clone();
+
+      finally_labels.jumps.push(target);
+      let jump_code = finally_labels.jumps.len();
+
+      self.push(Instruction::Mov(
+        Value::Number(Number(jump_code as f64)),
+        jump_code_register,
+      ));
+
+      self.push(Instruction::Jmp(finally_label.ref_()));
+
+      return;
     }
+
+    if self.catch_settings.len() != target.catch_depth {
+      match self.catch_settings[..target.catch_depth].last() {
+        Some(catch_setting) => self.push(Instruction::SetCatch(
+          catch_setting.label.ref_(),
+          catch_setting.reg.clone(),
+        )),
+        None => self.push(Instruction::UnsetCatch),
+      }
+    }
+
+    self.push(Instruction::Jmp(target.label.ref_()));
+  }
+
+  fn push_loop_labels(&mut self, continue_: Option<Label>, break_: Label) {
+    let names = take(&mut self.pending_labels);
+
+    self.loop_labels.push(LoopLabels {
+      continue_,
+      break_,
+      names,
+      is_block: false,
+      finally_depth: self.finally_labels.len(),
+      catch_depth: self.catch_settings.len(),
+    });
+  }
+
+  fn labeled(&mut self, labeled: &swc_ecma_ast::LabeledStmt) {
+    use swc_ecma_ast::Stmt::*;
+
+    self.pending_labels.push(labeled.label.sym.to_string());
+
+    match &*labeled.body {
+      While(_) | DoWhile(_) | For(_) | ForIn(_) | ForOf(_) | Switch(_) | Labeled(_) => {
+        self.statement(&labeled.body, false);
+
+        // In case the statement failed to compile before it could use them
+        self.pending_labels.clear();
+      }
+      _ => {
+        let end_label = Label {
+          name: self.label_allocator.allocate_numbered("label_end"),
+        };
+
+        self.loop_labels.push(LoopLabels {
+          continue_: None,
+          break_: end_label.clone(),
+          names: take(&mut self.pending_labels),
+          is_block: true,
+          finally_depth: self.finally_labels.len(),
+          catch_depth: self.catch_settings.len(),
+        });
+
+        self.statement(&labeled.body, false);
+        self.loop_labels.pop();
+        self.label(end_label);
+      }
+    }
+  }
+
+  fn find_loop_labels(&self, label: &swc_ecma_ast::Ident) -> Option<&LoopLabels> {
+    self
+      .loop_labels
+      .iter()
+      .rev()
+      .find(|ll| ll.names.iter().any(|name| *name == *label.sym))
+  }
+
+  fn break_(&mut self, break_: &swc_ecma_ast::BreakStmt) {
+    let loop_labels = match &break_.label {
+      Some(label) => self.find_loop_labels(label),
+      None => self.loop_labels.iter().rev().find(|ll| !ll.is_block),
+    };
+
+    let target = loop_labels.map(|ll| JumpTarget {
+      label: ll.break_.clone(),
+      finally_depth: ll.finally_depth,
+      catch_depth: ll.catch_depth,
+    });
+
+    match (target, &break_.label) {
+      (Some(target), _) => self.jump_out(target),
+      (None, Some(label)) => self.error(label.span, &format!("Undefined label {}", label.sym)),
+      (None, None) => self.error(break_.span, "break statement outside loop"),
+    }
+  }
+
+  fn continue_(&mut self, continue_: &swc_ecma_ast::ContinueStmt) {
+    let loop_labels = match &continue_.label {
+      Some(label) => match self.find_loop_labels(label) {
+        Some(ll) => Some(ll),
+        None => {
+          self.error(label.span, &format!("Undefined label {}", label.sym));
+          return;
+        }
+      },
+      None => self
+        .loop_labels
+        .iter()
+        .rev()
+        .find(|ll| ll.continue_.is_some()),
+    };
+
+    let target = loop_labels.and_then(|ll| {
+      Some(JumpTarget {
+        label: ll.continue_.clone()?,
+        finally_depth: ll.finally_depth,
+        catch_depth: ll.catch_depth,
+      })
+    });
+
+    match (target, &continue_.label) {
+      (Some(target), _) => self.jump_out(target),
+      (None, Some(label)) => self.error(
+        label.span,
+        &format!("Label {} does not refer to a loop", label.sym),
+      ),
+      (None, None) => self.error(continue_.span, "continue statement outside loop"),
+    }
+  }
+
+  /// Catch blocks don't apply to `generator.return()`, which unwinds the generator by throwing a
+  /// signal so that only finally blocks run.
+  fn rethrow_generator_return(&mut self, error_reg: Register) {
+    let is_return = self.allocate_tmp();
+
+    let catch_body_label = Label {
+      name: self.label_allocator.allocate_numbered("catch_body"),
+    };
+
+    self.push(Instruction::OpTripleEq(
+      Value::Register(error_reg.clone()),
+      Value::Builtin(Builtin {
+        name: "SymbolGeneratorReturn".to_string(),
+      }),
+      is_return.clone(),
+    ));
+
+    self.push(Instruction::JmpIfNot(
+      Value::Register(is_return.clone()),
+      catch_body_label.ref_(),
+    ));
+
+    self.push(Instruction::Throw(Value::Register(error_reg)));
+    self.label(catch_body_label);
+    self.release_reg(&is_return);
   }
 
   fn apply_catch_setting(&mut self) {
@@ -927,10 +1244,7 @@ impl<'a> FunctionCompiler<'a> {
       name: self.label_allocator.allocate_numbered("while_end"),
     };
 
-    self.loop_labels.push(LoopLabels {
-      continue_: Some(start_label.clone()),
-      break_: end_label.clone(),
-    });
+    self.push_loop_labels(Some(start_label.clone()), end_label.clone());
 
     self.label(start_label.clone());
 
@@ -965,10 +1279,7 @@ impl<'a> FunctionCompiler<'a> {
       name: self.label_allocator.allocate_numbered("do_while_end"),
     };
 
-    self.loop_labels.push(LoopLabels {
-      continue_: Some(continue_label.clone()),
-      break_: end_label.clone(),
-    });
+    self.push_loop_labels(Some(continue_label.clone()), end_label.clone());
 
     self.label(start_label.clone());
 
@@ -1019,10 +1330,7 @@ impl<'a> FunctionCompiler<'a> {
 
     self.label(for_test_label.clone());
 
-    self.loop_labels.push(LoopLabels {
-      continue_: Some(for_continue_label.clone()),
-      break_: for_end_label.clone(),
-    });
+    self.push_loop_labels(Some(for_continue_label.clone()), for_end_label.clone());
 
     match &for_.test {
       Some(cond) => {
@@ -1058,12 +1366,29 @@ impl<'a> FunctionCompiler<'a> {
   }
 
   fn for_of(&mut self, for_of: &swc_ecma_ast::ForOfStmt) {
+    let is_await = for_of.await_token.is_some();
+    self.for_each(&for_of.left, &for_of.right, &for_of.body, false, is_await);
+  }
+
+  /// `for...in` loops iterate over the keys given by the `for_in_keys` instruction.
+  fn for_in(&mut self, for_in: &swc_ecma_ast::ForInStmt) {
+    self.for_each(&for_in.left, &for_in.right, &for_in.body, true, false);
+  }
+
+  fn for_each(
+    &mut self,
+    left: &swc_ecma_ast::VarDeclOrPat,
+    right: &swc_ecma_ast::Expr,
+    body: &swc_ecma_ast::Stmt,
+    is_keys: bool,
+    is_await: bool,
+  ) {
     let mut ec = ExpressionCompiler { fnc: self };
 
-    let pat = match &for_of.left {
+    let pat = match left {
       swc_ecma_ast::VarDeclOrPat::VarDecl(var_decl) => {
         if var_decl.decls.len() != 1 {
-          panic!("Unexpected number of declarations on left side of for-of loop");
+          panic!("Unexpected number of declarations on left side of for-in/for-of loop");
         }
 
         &var_decl.decls[0].name
@@ -1077,16 +1402,26 @@ impl<'a> FunctionCompiler<'a> {
     let iter_res_reg = ec.fnc.allocate_numbered_reg("_iter_res");
     let done_reg = ec.fnc.allocate_numbered_reg("_done");
 
-    ec.compile_into(&for_of.right, iter_reg.clone());
+    ec.compile_into(right, iter_reg.clone());
 
-    ec.fnc.push(Instruction::ConstSubCall(
-      Value::Register(iter_reg.clone()),
-      Value::Builtin(Builtin {
-        name: "SymbolIterator".to_string(),
-      }),
-      Value::Array(Box::default()),
-      iter_reg.clone(),
-    ));
+    if is_keys {
+      ec.fnc.push(Instruction::ForInKeys(
+        Value::Register(iter_reg.clone()),
+        iter_reg.clone(),
+      ));
+    }
+
+    match is_await {
+      false => ec.fnc.push(Instruction::ConstSubCall(
+        Value::Register(iter_reg.clone()),
+        Value::Builtin(Builtin {
+          name: "SymbolIterator".to_string(),
+        }),
+        Value::Array(Box::default()),
+        iter_reg.clone(),
+      )),
+      true => ec.fnc.async_iterator(&iter_reg),
+    }
 
     let for_test_label = Label {
       name: ec.fnc.label_allocator.allocate_numbered("for_test"),
@@ -1104,22 +1439,36 @@ impl<'a> FunctionCompiler<'a> {
 
     ec.fnc.label(for_test_label.clone());
 
-    ec.fnc.loop_labels.push(LoopLabels {
-      continue_: Some(for_continue_label.clone()),
-      break_: for_end_label.clone(),
-    });
+    ec.fnc
+      .push_loop_labels(Some(for_continue_label.clone()), for_end_label.clone());
 
     ec.fnc.push(Instruction::JmpIf(
       Value::Register(done_reg.clone()),
       for_end_label.ref_(),
     ));
 
+    if is_await {
+      // Values from sync iterators are awaited. Values from async iterators are already settled
+      // (async generators await what they yield), so awaiting them again has no effect.
+      ec.fnc.push(Instruction::Await(
+        Value::Register(value_reg.clone()),
+        value_reg.clone(),
+      ));
+    }
+
     ec.pat(pat, &value_reg, true);
 
-    self.statement(&for_of.body, false);
+    self.statement(body, false);
 
     self.label(for_continue_label);
-    self.push(Instruction::Next(iter_reg, iter_res_reg.clone()));
+    self.push(Instruction::Next(iter_reg.clone(), iter_res_reg.clone()));
+
+    if is_await {
+      self.push(Instruction::Await(
+        Value::Register(iter_res_reg.clone()),
+        iter_res_reg.clone(),
+      ));
+    }
 
     self.push(Instruction::UnpackIterRes(
       iter_res_reg.clone(),
@@ -1136,6 +1485,55 @@ impl<'a> FunctionCompiler<'a> {
     self.loop_labels.pop();
   }
 
+  /// Replaces the iterable in `iterable_reg` with its async iterator for `for await`, falling back
+  /// to its sync iterator.
+  fn async_iterator(&mut self, iterable_reg: &Register) {
+    let make_iter = self.allocate_tmp();
+
+    let sync_label = Label {
+      name: self.label_allocator.allocate_numbered("sync_iter"),
+    };
+
+    let iter_ready_label = Label {
+      name: self.label_allocator.allocate_numbered("iter_ready"),
+    };
+
+    self.push(Instruction::Sub(
+      Value::Register(iterable_reg.clone()),
+      Value::Builtin(Builtin {
+        name: "SymbolAsyncIterator".to_string(),
+      }),
+      make_iter.clone(),
+    ));
+
+    self.push(Instruction::JmpIfNot(
+      Value::Register(make_iter.clone()),
+      sync_label.ref_(),
+    ));
+
+    self.push(Instruction::Apply(
+      Value::Register(make_iter.clone()),
+      iterable_reg.clone(),
+      Value::Array(Box::default()),
+      iterable_reg.clone(),
+    ));
+
+    self.push(Instruction::Jmp(iter_ready_label.ref_()));
+    self.label(sync_label);
+
+    self.push(Instruction::ConstSubCall(
+      Value::Register(iterable_reg.clone()),
+      Value::Builtin(Builtin {
+        name: "SymbolIterator".to_string(),
+      }),
+      Value::Array(Box::default()),
+      iterable_reg.clone(),
+    ));
+
+    self.label(iter_ready_label);
+    self.release_reg(&make_iter);
+  }
+
   fn declaration(&mut self, decl: &swc_ecma_ast::Decl) {
     use swc_ecma_ast::Decl::*;
 
@@ -1288,6 +1686,13 @@ fn instruction_needs_mutable_this(
     return dst.is_this();
   }
 
+  if let Instruction::Apply(_, this, _, dst) = instruction {
+    // Similarly, applying a function to `this` passes on its constness (see `super.method()`)
+    if this.is_this() {
+      return dst.is_this();
+    }
+  }
+
   let mut result = false;
 
   instruction.visit_registers_mut_rev(&mut |rvm| {
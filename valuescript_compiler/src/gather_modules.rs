@@ -3,7 +3,7 @@ use std::collections::{HashMap, HashSet};
 use queues::{IsQueue, Queue};
 
 use crate::{
-  asm::Module,
+  asm::{DefinitionContent, FnLine, Module},
   compile_module,
   import_pattern::ImportPattern,
   resolve_path::{resolve_path, ResolvedPath},
@@ -80,6 +80,8 @@ where
     let mut compiler_output = compile_module(&file_contents);
     // println!("{}: {}", dependency.path, compiler_output.module);
 
+    set_position_paths(&mut compiler_output.module, &dependency.path.path);
+
     gm.diagnostics
       .entry(dependency.path.clone())
       .or_default()
@@ -125,3 +127,19 @@ pub fn get_imported_paths(path_and_module: &PathAndModule) -> HashSet<ResolvedPa
 
   imported_paths
 }
+
+fn set_position_paths(module: &mut Module, path: &str) {
+  for definition in &mut module.definitions {
+    let body = match &mut definition.content {
+      DefinitionContent::Function(fn_) => &mut fn_.body,
+      DefinitionContent::Lazy(lazy) => &mut lazy.body,
+      DefinitionContent::Value(_) | DefinitionContent::Meta(_) => continue,
+    };
+
+    for line in body {
+      if let FnLine::Position(position) = line {
+        position.path = path.to_string();
+      }
+    }
+  }
+}
@@ -65,6 +65,10 @@ pub enum Instruction {
   YieldStar(Value, Register),
   Delete(Register, Value, Register),
   Jsx(Value, Value, Value, Register),
+  Await(Value, Register),
+  ForInKeys(Value, Register),
+  ObjectRest(Value, Value, Register),
+  ObjectSpread(Value, Register),
 }
 
 pub enum InstructionFieldMut<'a> {
@@ -118,13 +122,16 @@ impl Instruction {
       | OpNot(arg, dst)
       | OpBitNot(arg, dst)
       | TypeOf(arg, dst)
+      | ForInKeys(arg, dst)
       | UnaryPlus(arg, dst)
       | UnaryMinus(arg, dst)
       | Import(arg, dst)
       | ImportStar(arg, dst)
       | Cat(arg, dst)
       | Yield(arg, dst)
-      | YieldStar(arg, dst) => {
+      | YieldStar(arg, dst)
+      | Await(arg, dst)
+      | ObjectSpread(arg, dst) => {
         visit(InstructionFieldMut::Value(arg));
         visit(InstructionFieldMut::Register(dst));
       }
@@ -159,6 +166,7 @@ impl Instruction {
       | OpRightShiftUnsigned(left, right, dst)
       | InstanceOf(left, right, dst)
       | In(left, right, dst)
+      | ObjectRest(left, right, dst)
       | Call(left, right, dst)
       | Bind(left, right, dst)
       | Sub(left, right, dst)
@@ -248,13 +256,15 @@ impl Instruction {
       | OpNot(arg, dst)
       | OpBitNot(arg, dst)
       | TypeOf(arg, dst)
+      | ForInKeys(arg, dst)
       | UnaryPlus(arg, dst)
       | UnaryMinus(arg, dst)
       | Import(arg, dst)
       | ImportStar(arg, dst)
       | Cat(arg, dst)
       | Yield(arg, dst)
-      | YieldStar(arg, dst) => {
+      | YieldStar(arg, dst)
+      | Await(arg, dst) => {
         visit(RegisterVisitMut::write(dst));
         arg.visit_registers_mut_rev(visit);
       }
@@ -289,6 +299,7 @@ impl Instruction {
       | OpRightShiftUnsigned(left, right, dst)
       | InstanceOf(left, right, dst)
       | In(left, right, dst)
+      | ObjectRest(left, right, dst)
       | Call(left, right, dst)
       | Bind(left, right, dst)
       | Sub(left, right, dst)
@@ -307,6 +318,11 @@ impl Instruction {
         key.visit_registers_mut_rev(visit);
       }
 
+      ObjectSpread(source, obj) => {
+        visit(RegisterVisitMut::read_and_write(obj));
+        source.visit_registers_mut_rev(visit);
+      }
+
       Apply(fn_, this, args, dst) => {
         visit(RegisterVisitMut::write(dst));
         args.visit_registers_mut_rev(visit);
@@ -449,6 +465,10 @@ impl Instruction {
       YieldStar(..) => InstructionByte::YieldStar,
       Delete(..) => InstructionByte::Delete,
       Jsx(..) => InstructionByte::Jsx,
+      Await(..) => InstructionByte::Await,
+      ForInKeys(..) => InstructionByte::ForInKeys,
+      ObjectRest(..) => InstructionByte::ObjectRest,
+      ObjectSpread(..) => InstructionByte::ObjectSpread,
     }
   }
 }
@@ -617,6 +637,18 @@ impl StructuredFormattable for Instruction {
       Instruction::Jsx(tag, attrs, children, dst) => {
         sf.write_slice_joined(" ", &[&"jsx", tag, attrs, children, dst])
       }
+      Instruction::Await(value, register) => {
+        sf.write_slice_joined(" ", &[&"await", value, register])
+      }
+      Instruction::ForInKeys(value, register) => {
+        sf.write_slice_joined(" ", &[&"for_in_keys", value, register])
+      }
+      Instruction::ObjectRest(value, keys, register) => {
+        sf.write_slice_joined(" ", &[&"object_rest", value, keys, register])
+      }
+      Instruction::ObjectSpread(value, register) => {
+        sf.write_slice_joined(" ", &[&"object_spread", value, register])
+      }
     }
   }
 }
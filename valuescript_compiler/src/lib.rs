@@ -4,6 +4,7 @@ pub mod assembly_parser;
 mod compile;
 mod constants;
 mod diagnostic;
+mod disassembler;
 mod expression_compiler;
 mod function_compiler;
 mod gather_modules;
@@ -24,12 +25,13 @@ mod static_expression_compiler;
 mod target_accessor;
 mod visit_pointers;
 
-pub use assembler::assemble;
+pub use assembler::{assemble, assemble_with_positions};
 pub use assembly_parser::parse_module;
 pub use compile::CompileResult;
 pub use compile::{compile, compile_str};
 pub use diagnostic::Diagnostic;
 pub use diagnostic::DiagnosticLevel;
+pub use disassembler::disassemble;
 pub use gather_modules::gather_modules;
 pub use inline_valuescript::inline_valuescript;
 pub use link_module::link_module;
@@ -4,11 +4,12 @@ use std::mem::swap;
 use tiny_keccak::{Hasher, Keccak};
 
 use crate::asm::{
-  ContentHashable, Definition, DefinitionContent, ExportStar, FnLine, Hash, Instruction, Object,
-  Pointer, Structured, Value,
+  ContentHashable, Definition, DefinitionContent, ExportStar, FnLine, Hash, Instruction, Lazy,
+  Object, Pointer, Structured, Value,
 };
 use crate::gather_modules::PathAndModule;
 use crate::import_pattern::{ImportKind, ImportPattern};
+use crate::instruction::InstructionFieldMut;
 use crate::name_allocator::NameAllocator;
 use crate::optimization::optimize;
 use crate::resolve_path::{resolve_path, ResolvedPath};
@@ -420,7 +421,12 @@ fn calculate_content_hashes(module: &mut Module, _diagnostics: &mut Vec<Diagnost
           class.meta.content_hashable = ContentHashable::Content(content_hash);
         }
       }
-      DefinitionContent::Lazy(_) => {}
+      DefinitionContent::Lazy(lazy) => {
+        if let Some(Value::Class(class)) = lazy_class_mut(lazy) {
+          let content_hash = calculate_content_hash(&ptr_to_src_trace, &defn.pointer);
+          class.meta.content_hashable = ContentHashable::Content(content_hash);
+        }
+      }
     }
   }
 }
@@ -477,7 +483,61 @@ fn find_ptr_src_trace(
     }
     DefinitionContent::Meta(_fn_meta) => None,
     DefinitionContent::Value(value) => find_value_src_trace(module, ptr_to_index, value),
-    DefinitionContent::Lazy(_) => None,
+    DefinitionContent::Lazy(lazy) => match lazy_class(lazy) {
+      Some(value) => find_value_src_trace(module, ptr_to_index, value),
+      None => Some(lazy_src_trace(lazy)),
+    },
+  }
+}
+
+/// Other lazy definitions (e.g. module initialization) are traced by their code, which depends on
+/// the definitions it refers to.
+fn lazy_src_trace(lazy: &Lazy) -> (String, Vec<Value>) {
+  let mut lazy = lazy.clone();
+  lazy
+    .body
+    .retain(|line| !matches!(line, FnLine::Position(..)));
+
+  let mut deps = Vec::<Value>::new();
+
+  for line in &mut lazy.body {
+    if let FnLine::Instruction(instr) = line {
+      instr.visit_fields_mut(&mut |field| {
+        if let InstructionFieldMut::Value(value) = field {
+          value.visit_values_mut(&mut |value| {
+            if let Value::Pointer(_) = value {
+              deps.push(value.clone());
+            }
+          });
+        }
+      });
+    }
+  }
+
+  (Structured(&lazy).to_string(), deps)
+}
+
+/// Classes which need to run initialization code are lazy definitions which start by moving the
+/// class into a register.
+fn lazy_class(lazy: &Lazy) -> Option<&Value> {
+  match lazy
+    .body
+    .iter()
+    .find(|line| matches!(line, FnLine::Instruction(..)))
+  {
+    Some(FnLine::Instruction(Instruction::Mov(value @ Value::Class(..), _))) => Some(value),
+    _ => None,
+  }
+}
+
+fn lazy_class_mut(lazy: &mut Lazy) -> Option<&mut Value> {
+  match lazy
+    .body
+    .iter_mut()
+    .find(|line| matches!(line, FnLine::Instruction(..)))
+  {
+    Some(FnLine::Instruction(Instruction::Mov(value @ Value::Class(..), _))) => Some(value),
+    _ => None,
   }
 }
 
@@ -502,7 +562,9 @@ fn find_value_src_trace(
     | Value::Number(_)
     | Value::BigInt(_)
     | Value::String(_)
-    | Value::Builtin(_) => Some((Structured(value).to_string(), vec![])),
+    | Value::Builtin(_)
+    | Value::RegExp(_)
+    | Value::PrivateName(_) => Some((Structured(value).to_string(), vec![])),
     Value::Array(array) => {
       let mut src_tags = Vec::<String>::new();
       let mut deps = Vec::<Value>::new();
@@ -521,11 +583,11 @@ fn find_value_src_trace(
       let mut src_tags = Vec::<String>::new();
       let mut deps = Vec::<Value>::new();
 
-      for (k, v) in &object.properties {
+      for (prefix, k, v) in object.entries() {
         let (src_tag, mut item_deps) = find_value_src_trace(module, ptr_to_index, v)
           .expect("Couldn't get required source trace");
 
-        src_tags.push(format!("{}:{}", Structured(k), src_tag));
+        src_tags.push(format!("{}{}:{}", prefix, Structured(k), src_tag));
         deps.append(&mut item_deps);
       }
 
@@ -566,7 +628,9 @@ fn calculate_content_hash(
       | Value::Array(_)
       | Value::Object(_)
       | Value::Class(_)
-      | Value::Register(_) => {
+      | Value::Register(_)
+      | Value::RegExp(_)
+      | Value::PrivateName(_) => {
         // undefined, Infinity, and NaN are treated as global variables, which lead them to be the
         // resolution of dependencies. All other dependencies should be builtins or pointers.
         panic!("Unexpected dependency ({})", Structured(&dep))
@@ -613,7 +677,9 @@ fn calculate_content_hash(
       | Value::Array(_)
       | Value::Object(_)
       | Value::Class(_)
-      | Value::Register(_) => {
+      | Value::Register(_)
+      | Value::RegExp(_)
+      | Value::PrivateName(_) => {
         // undefined, Infinity, and NaN are treated as global variables, which lead them to be the
         // resolution of dependencies. All other dependencies should be builtins or pointers.
         panic!("Unexpected dependency ({})", Structured(dep))
@@ -636,7 +702,9 @@ fn calculate_content_hash(
     | Value::Array(_)
     | Value::Object(_)
     | Value::Class(_)
-    | Value::Register(_) => {
+    | Value::Register(_)
+    | Value::RegExp(_)
+    | Value::PrivateName(_) => {
       // undefined, Infinity, and NaN are treated as global variables, which lead them to be the
       // resolution of dependencies. All other dependencies should be builtins or pointers.
       panic!("Unexpected dependency ({})", Structured(dep))
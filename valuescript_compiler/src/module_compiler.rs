@@ -1,25 +1,29 @@
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::mem::take;
 use std::sync::{Arc, Mutex};
 
 use swc_common::errors::{DiagnosticBuilder, Emitter};
-use swc_common::{errors::Handler, FileName, SourceMap, Spanned};
+use swc_common::{errors::Handler, FileName, SourceMap, Span, Spanned};
 use swc_ecma_ast::EsVersion;
 use swc_ecma_parser::{Syntax, TsConfig};
+use valuescript_vm::position_table::SourcePosition;
 
 use crate::asm::{
-  Class, ContentHashable, Definition, DefinitionContent, FnLine, Instruction, Lazy, Meta, Module,
-  Number, Object, Pointer, Register, Structured, Value,
+  Class, ContentHashable, Definition, DefinitionContent, FnLine, Function, Hash, Instruction, Lazy,
+  Meta, Module, Number, Object, Pointer, PrivateName, Register, Structured, Value,
 };
 use crate::diagnostic::{Diagnostic, DiagnosticContainer, DiagnosticReporter};
 use crate::expression_compiler::{CompiledExpression, ExpressionCompiler};
 use crate::function_compiler::{FunctionCompiler, Functionish};
 use crate::ident::Ident;
 use crate::name_allocator::{ident_from_str, NameAllocator};
-use crate::scope::OwnerId;
+use crate::scope::{NameId, OwnerId};
 use crate::scope_analysis::{class_to_owner_id, ScopeAnalysis};
 use crate::src_hash::src_hash;
-use crate::static_expression_compiler::StaticExpressionCompiler;
+use crate::static_expression_compiler::{
+  is_static_expr, is_static_prop_name, StaticExpressionCompiler,
+};
 
 struct DiagnosticCollector {
   diagnostics: Arc<Mutex<Vec<Diagnostic>>>,
@@ -106,7 +110,45 @@ pub struct ModuleCompiler {
   pub scope_analysis: ScopeAnalysis,
   pub constants_map: HashMap<Pointer, Value>,
   pub source: String,
+  pub line_starts: Vec<usize>,
   pub module: Module,
+  pub class_scopes: Vec<ClassScope>,
+
+  /// Module-level code which needs to run when the module is loaded. It's compiled into a single
+  /// lazy definition by `compile_module_init`.
+  pub init_stmts: Vec<swc_ecma_ast::Stmt>,
+}
+
+/// A class whose body is being compiled: the `#private` names it declares and what `super`
+/// refers to.
+pub struct ClassScope {
+  pub brand: Hash,
+  pub fields: HashSet<String>,
+  pub methods: HashSet<String>,
+
+  /// The class being extended, or void.
+  pub extends: Value,
+
+  /// In derived classes, the function which initializes instance fields. `super(...)` calls it
+  /// after running the parent constructor.
+  pub member_initializers: Option<Pointer>,
+
+  /// Whether the member being compiled is static, in which case `super.x` refers to the parent
+  /// class itself rather than its prototype.
+  pub in_static: bool,
+}
+
+impl ClassScope {
+  pub fn key(&self, private_name: &swc_ecma_ast::PrivateName) -> Value {
+    Value::PrivateName(Box::new(PrivateName {
+      name: private_name.id.sym.to_string(),
+      brand: self.brand.clone(),
+    }))
+  }
+
+  fn declares(&self, name: &str) -> bool {
+    self.fields.contains(name) || self.methods.contains(name)
+  }
 }
 
 impl DiagnosticContainer for ModuleCompiler {
@@ -132,6 +174,20 @@ impl ModuleCompiler {
     }
   }
 
+  /// The path is left empty because the module compiler doesn't know it. It's filled in by
+  /// `gather_modules`.
+  pub fn source_position(&self, span: Span) -> SourcePosition {
+    let pos = (span.lo.0 as usize).min(self.source.len());
+    let line = self.line_starts.partition_point(|start| *start <= pos);
+    let line_start = self.line_starts[line - 1];
+
+    SourcePosition {
+      path: "".to_string(),
+      line: line as u32,
+      col: self.source[line_start..pos].chars().count() as u32 + 1,
+    }
+  }
+
   fn compile_program(source: &str, program: &swc_ecma_ast::Program) -> Self {
     use swc_ecma_ast::Program::*;
 
@@ -152,6 +208,9 @@ impl ModuleCompiler {
       scope_analysis,
       diagnostics: RefCell::new(diagnostics),
       source: source.to_string(),
+      line_starts: std::iter::once(0)
+        .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+        .collect(),
       ..Default::default()
     };
 
@@ -164,6 +223,8 @@ impl ModuleCompiler {
     for module_item in &module.body {
       self.compile_module_item(module_item);
     }
+
+    self.compile_module_init();
   }
 
   fn compile_module_item(&mut self, module_item: &swc_ecma_ast::ModuleItem) {
@@ -198,24 +259,9 @@ impl ModuleCompiler {
 
     match stmt {
       Decl(decl) => self.compile_module_level_decl(decl),
-      Block(block) => self.not_supported(block.span, "module level Block statement"),
       Empty(_) => {}
-      Debugger(debugger) => self.not_supported(debugger.span, "module level Debugger statement"),
-      With(with) => self.not_supported(with.span, "module level With statement"),
       Return(return_) => self.not_supported(return_.span, "module level Return statement"),
-      Labeled(labeled) => self.not_supported(labeled.span, "module level Labeled statement"),
-      Break(break_) => self.not_supported(break_.span, "module level Break statement"),
-      Continue(continue_) => self.not_supported(continue_.span, "module level Continue statement"),
-      If(if_) => self.not_supported(if_.span, "module level If statement"),
-      Switch(switch) => self.not_supported(switch.span, "module level Switch statement"),
-      Throw(throw) => self.not_supported(throw.span, "module level Throw statement"),
-      Try(try_) => self.not_supported(try_.span, "module level Try statement"),
-      While(while_) => self.not_supported(while_.span, "module level While statement"),
-      DoWhile(do_while) => self.not_supported(do_while.span, "module level DoWhile statement"),
-      For(for_) => self.not_supported(for_.span, "module level For statement"),
-      ForIn(for_in) => self.not_supported(for_in.span, "module level ForIn statement"),
-      ForOf(for_of) => self.not_supported(for_of.span, "module level ForOf statement"),
-      Expr(expr) => self.not_supported(expr.span, "module level Expr statement"),
+      _ => self.init_stmts.push(stmt.clone()),
     };
   }
 
@@ -243,62 +289,180 @@ impl ModuleCompiler {
       return;
     }
 
-    if var_decl.kind != swc_ecma_ast::VarDeclKind::Const {
-      // Only `const` variables in the global area. They cannot be mutated, so might as well
-      // insist they are `const` for clarity.
-      self.not_supported(var_decl.span, "non-const module level variable");
+    if !self.is_static_var_decl(var_decl) {
+      self
+        .init_stmts
+        .push(swc_ecma_ast::Stmt::Decl(swc_ecma_ast::Decl::Var(
+          var_decl.clone(),
+        )));
+
+      if export {
+        for decl in &var_decl.decls {
+          for ident in self.scope_analysis.get_pat_idents(&decl.name) {
+            if let Some(pointer) = self.module_variable_pointer(&ident) {
+              self.export_variable(&ident, pointer);
+            }
+          }
+        }
+      }
+
+      return;
     }
 
     for decl in &var_decl.decls {
       let ident = match &decl.name {
-        swc_ecma_ast::Pat::Ident(bi) => Some(&bi.id),
+        swc_ecma_ast::Pat::Ident(bi) => &bi.id,
         _ => {
-          self.todo(decl.name.span(), "Module level destructuring");
-          None
+          self.internal_error(decl.name.span(), "Expected identifier for static variable");
+          continue;
         }
       };
 
-      let init = match &decl.init {
-        Some(_) => &decl.init,
-        _ => {
-          self.error(decl.init.span(), "const variable without initializer");
+      let value = match &decl.init {
+        Some(init) => self.static_ec().expr(init),
+        None => {
+          if var_decl.kind == swc_ecma_ast::VarDeclKind::Const {
+            self.error(decl.span, "const variable without initializer");
+          }
 
-          &None
+          Value::Undefined
         }
       };
 
-      if let (Some(ident), Some(init)) = (ident, init) {
-        let value = self.static_ec().expr(init);
+      let pointer = match self.module_variable_pointer(ident) {
+        Some(pointer) => pointer,
+        None => continue,
+      };
 
-        let pointer = match self.scope_analysis.lookup(&Ident::from_swc_ident(ident)) {
-          Some(name) => match &name.value {
-            Value::Pointer(p) => p.clone(),
-            _ => {
-              self.internal_error(ident.span(), "Expected pointer for module constant");
-              continue;
-            }
-          },
-          None => {
-            self.internal_error(ident.span(), "Failed to lookup name");
-            continue;
-          }
-        };
+      self.constants_map.insert(pointer.clone(), value.clone());
 
-        self.constants_map.insert(pointer.clone(), value.clone());
+      self.module.definitions.push(Definition {
+        pointer: pointer.clone(),
+        content: DefinitionContent::Value(value),
+      });
 
-        self.module.definitions.push(Definition {
-          pointer: pointer.clone(),
-          content: DefinitionContent::Value(value),
-        });
+      if export {
+        self.export_variable(ident, pointer);
+      }
+    }
+  }
 
-        if export {
-          self.module.export_star.local.properties.push((
-            Value::String(ident.sym.to_string()),
-            Value::Pointer(pointer),
-          ));
+  /// Whether the variables of `var_decl` can be evaluated at compile time. Otherwise, they're
+  /// initialized by the module's initialization code.
+  fn is_static_var_decl(&self, var_decl: &swc_ecma_ast::VarDecl) -> bool {
+    var_decl.decls.iter().all(|decl| {
+      let ident = match &decl.name {
+        swc_ecma_ast::Pat::Ident(bi) => &bi.id,
+        _ => return false,
+      };
+
+      let is_mutated = self
+        .scope_analysis
+        .lookup(&Ident::from_swc_ident(ident))
+        .is_some_and(|name| !name.mutations.is_empty());
+
+      !is_mutated && decl.init.as_deref().is_none_or(is_static_expr)
+    })
+  }
+
+  fn module_variable_pointer(&self, ident: &swc_ecma_ast::Ident) -> Option<Pointer> {
+    match self.scope_analysis.lookup(&Ident::from_swc_ident(ident)) {
+      Some(name) => match &name.value {
+        Value::Pointer(p) => Some(p.clone()),
+        _ => {
+          self.internal_error(ident.span(), "Expected pointer for module variable");
+          None
+        }
+      },
+      None => {
+        self.internal_error(ident.span(), "Failed to lookup name");
+        None
+      }
+    }
+  }
+
+  fn export_variable(&mut self, ident: &swc_ecma_ast::Ident, pointer: Pointer) {
+    self.module.export_star.local.properties.push((
+      Value::String(ident.sym.to_string()),
+      Value::Pointer(pointer),
+    ));
+  }
+
+  /// Compiles the module's top-level code into a lazy definition which runs it and evaluates to an
+  /// object of the variables it initializes. Each of these variables is then defined as a property
+  /// of that object, so the code runs once, when the first of them is needed.
+  fn compile_module_init(&mut self) {
+    let init_stmts = take(&mut self.init_stmts);
+
+    if init_stmts.is_empty() {
+      return;
+    }
+
+    let mut variables = Vec::<(String, NameId, Pointer)>::new();
+
+    for stmt in &init_stmts {
+      if let swc_ecma_ast::Stmt::Decl(swc_ecma_ast::Decl::Var(var_decl)) = stmt {
+        for decl in &var_decl.decls {
+          for ident in self.scope_analysis.get_pat_idents(&decl.name) {
+            let name_id = match self.scope_analysis.lookup(&Ident::from_swc_ident(&ident)) {
+              Some(name) => name.id.clone(),
+              None => continue,
+            };
+
+            if let Some(pointer) = self.module_variable_pointer(&ident) {
+              variables.push((ident.sym.to_string(), name_id, pointer));
+            }
+          }
         }
       }
     }
+
+    let init_pointer = self.allocate_defn_numbered("_module_init");
+
+    let mut fnc = FunctionCompiler::new(self);
+    fnc.set_owner_id(OwnerId::Module);
+
+    let mut result = Object::default();
+
+    for (name, name_id, _) in &variables {
+      let reg = fnc.allocate_reg(name);
+      fnc.module_variables.insert(name_id.clone(), reg.clone());
+
+      result
+        .properties
+        .push((Value::String(name.clone()), Value::Register(reg)));
+    }
+
+    for stmt in &init_stmts {
+      fnc.statement(stmt, false);
+    }
+
+    fnc.push(Instruction::Mov(
+      Value::Object(Box::new(result)),
+      Register::return_(),
+    ));
+
+    fnc.insert_all_releases();
+
+    let body = take(&mut fnc.fn_.body);
+
+    self.module.definitions.push(Definition {
+      pointer: init_pointer.clone(),
+      content: DefinitionContent::Lazy(Lazy { body }),
+    });
+
+    for (name, _, pointer) in variables {
+      self.module.definitions.push(Definition {
+        pointer,
+        content: DefinitionContent::Lazy(Lazy {
+          body: vec![FnLine::Instruction(Instruction::Sub(
+            Value::Pointer(init_pointer.clone()),
+            Value::String(name),
+            Register::return_(),
+          ))],
+        }),
+      });
+    }
   }
 
   fn compile_fn_decl(&mut self, export: bool, fn_: &swc_ecma_ast::FnDecl) {
@@ -707,6 +871,34 @@ impl ModuleCompiler {
     }
   }
 
+  /// Finds the innermost class declaring `private_name`.
+  pub fn lookup_private_name(
+    &self,
+    private_name: &swc_ecma_ast::PrivateName,
+  ) -> Option<&ClassScope> {
+    self
+      .class_scopes
+      .iter()
+      .rev()
+      .find(|scope| scope.declares(&private_name.id.sym))
+  }
+
+  /// The key for a private member of the class currently being compiled.
+  fn class_private_key(&self, private_name: &swc_ecma_ast::PrivateName) -> Value {
+    self
+      .class_scopes
+      .last()
+      .expect("Class scope should have been pushed")
+      .key(private_name)
+  }
+
+  fn class_scope_mut(&mut self) -> &mut ClassScope {
+    self
+      .class_scopes
+      .last_mut()
+      .expect("Class scope should have been pushed")
+  }
+
   pub fn compile_fn(&mut self, defn_pointer: Pointer, functionish: Functionish) {
     FunctionCompiler::new(self).compile(defn_pointer, functionish);
   }
@@ -746,14 +938,73 @@ impl ModuleCompiler {
       ));
     }
 
+    let extends = match class.super_class.as_deref() {
+      None => Value::Void,
+      Some(swc_ecma_ast::Expr::Ident(super_ident)) => match self
+        .scope_analysis
+        .lookup_value(&OwnerId::Module, &Ident::from_swc_ident(super_ident))
+      {
+        Some(value @ (Value::Pointer(..) | Value::Builtin(..))) => value,
+        _ => {
+          self.todo(super_ident.span, "Extending a non-constant class");
+          Value::Void
+        }
+      },
+      Some(super_class) => {
+        self.todo(super_class.span(), "Extending a non-identifier expression");
+        Value::Void
+      }
+    };
+
+    let mut class_scope = ClassScope {
+      brand: src_hash(&self.source, class.span),
+      fields: HashSet::new(),
+      methods: HashSet::new(),
+      extends: extends.clone(),
+      member_initializers: None,
+      in_static: false,
+    };
+
+    for class_member in &class.body {
+      match class_member {
+        swc_ecma_ast::ClassMember::PrivateProp(private_prop) => {
+          class_scope
+            .fields
+            .insert(private_prop.key.id.sym.to_string());
+        }
+        swc_ecma_ast::ClassMember::PrivateMethod(private_method) => {
+          class_scope
+            .methods
+            .insert(private_method.key.id.sym.to_string());
+        }
+        _ => {}
+      }
+    }
+
+    self.class_scopes.push(class_scope);
+
     // member initializers function compiler
     let mut mi_fnc = FunctionCompiler::new(self);
-    mi_fnc.set_owner_id(OwnerId::Span(class.span));
+    mi_fnc.set_owner_id(class_to_owner_id(ident, class));
+
+    // Static members from the first one which can't be evaluated at compile time onwards. These
+    // are compiled into code which runs when the class is first accessed.
+    let mut static_initializers = Vec::<&swc_ecma_ast::ClassMember>::new();
 
     for class_member in &class.body {
+      mi_fnc.mc.class_scope_mut().in_static = is_static_member(class_member);
+
       match class_member {
         swc_ecma_ast::ClassMember::ClassProp(class_prop) => {
           if class_prop.is_static {
+            if !static_initializers.is_empty()
+              || !is_static_prop_name(&class_prop.key)
+              || !class_prop.value.as_deref().is_none_or(is_static_expr)
+            {
+              static_initializers.push(class_member);
+              continue;
+            }
+
             let key = mi_fnc.mc.static_ec().prop_name(&class_prop.key);
 
             let value = match &class_prop.value {
@@ -783,8 +1034,46 @@ impl ModuleCompiler {
           }
         }
         swc_ecma_ast::ClassMember::PrivateProp(private_prop) => {
-          mi_fnc.todo(private_prop.span, "private props")
+          let key = mi_fnc.mc.class_private_key(&private_prop.key);
+
+          if private_prop.is_static {
+            if !static_initializers.is_empty()
+              || !private_prop.value.as_deref().is_none_or(is_static_expr)
+            {
+              // Declaring the field allows the initialization code to assign it
+              static_.properties.push((key, Value::Undefined));
+              static_initializers.push(class_member);
+              continue;
+            }
+
+            let value = match &private_prop.value {
+              Some(expr) => mi_fnc.mc.static_ec().expr(expr),
+              None => Value::Undefined,
+            };
+
+            static_.properties.push((key, value));
+          } else {
+            let mut ec = ExpressionCompiler { fnc: &mut mi_fnc };
+
+            let compiled_value = match &private_prop.value {
+              None => CompiledExpression::new(Value::Undefined, vec![]),
+              Some(expr) => ec.compile(expr, None),
+            };
+
+            ec.fnc.push(Instruction::SubMov(
+              key.clone(),
+              compiled_value.value.clone(),
+              Register::this(),
+            ));
+
+            ec.fnc.release_ce(compiled_value);
+
+            // Declaring the field on the prototype gives instances the class's brand, which is
+            // what allows the field to be assigned.
+            prototype.properties.push((key, Value::Undefined));
+          }
         }
+        swc_ecma_ast::ClassMember::StaticBlock(_) => static_initializers.push(class_member),
         _ => {}
       }
     }
@@ -792,6 +1081,32 @@ impl ModuleCompiler {
     let mut member_initializers_assembly = Vec::<FnLine>::new();
     member_initializers_assembly.append(&mut mi_fnc.fn_.body);
 
+    if extends != Value::Void && !member_initializers_assembly.is_empty() {
+      // Derived classes initialize their fields when `super(...)` returns, so the initializers
+      // go in their own function
+      let initializers_defn_name = self.allocate_defn(&format!("{}_initializers", defn_name.name));
+      let meta_ptr = self.allocate_defn(&format!("{}_meta", initializers_defn_name.name));
+
+      self.module.definitions.push(Definition {
+        pointer: initializers_defn_name.clone(),
+        content: DefinitionContent::Function(Function {
+          meta: Some(meta_ptr.clone()),
+          body: take(&mut member_initializers_assembly),
+          ..Default::default()
+        }),
+      });
+
+      self.module.definitions.push(Definition {
+        pointer: meta_ptr,
+        content: DefinitionContent::Meta(Meta {
+          name: "".to_string(),
+          content_hashable: ContentHashable::Empty,
+        }),
+      });
+
+      self.class_scope_mut().member_initializers = Some(initializers_defn_name);
+    }
+
     let mut ctor = swc_ecma_ast::Constructor {
       span: class.span,
       key: swc_ecma_ast::PropName::Str(swc_ecma_ast::Str {
@@ -811,7 +1126,10 @@ impl ModuleCompiler {
       }
     }
 
-    if !member_initializers_assembly.is_empty() || ctor.body.is_some() {
+    self.class_scope_mut().in_static = false;
+    let has_member_initializers = self.class_scope_mut().member_initializers.is_some();
+
+    if !member_initializers_assembly.is_empty() || has_member_initializers || ctor.body.is_some() {
       let ctor_defn_name = self.allocate_defn(&format!("{}_constructor", defn_name.name));
 
       self.compile_fn(
@@ -829,6 +1147,8 @@ impl ModuleCompiler {
     for class_member in &class.body {
       use swc_ecma_ast::ClassMember::*;
 
+      self.class_scope_mut().in_static = is_static_member(class_member);
+
       match class_member {
         Constructor(_) => {}
         Method(method) => {
@@ -841,9 +1161,16 @@ impl ModuleCompiler {
             }
           };
 
+          let kind_prefix = match method.kind {
+            swc_ecma_ast::MethodKind::Method => "",
+            swc_ecma_ast::MethodKind::Getter => "get_",
+            swc_ecma_ast::MethodKind::Setter => "set_",
+          };
+
           let method_defn_name = self.allocate_defn(&ident_from_str(&format!(
-            "{}_{}",
+            "{}_{}{}",
             defn_name.name,
+            kind_prefix,
             Structured(&name)
           )));
 
@@ -862,26 +1189,49 @@ impl ModuleCompiler {
             true => &mut static_,
           };
 
-          dst
-            .properties
-            .push((name, Value::Pointer(method_defn_name)));
+          let entries = match method.kind {
+            swc_ecma_ast::MethodKind::Method => &mut dst.properties,
+            swc_ecma_ast::MethodKind::Getter => &mut dst.getters,
+            swc_ecma_ast::MethodKind::Setter => &mut dst.setters,
+          };
+
+          entries.push((name, Value::Pointer(method_defn_name)));
+        }
+        PrivateMethod(private_method) => {
+          if private_method.kind != swc_ecma_ast::MethodKind::Method {
+            self.todo(private_method.span, "Private getters and setters");
+            continue;
+          }
+
+          let method_defn_name = self.allocate_defn(&ident_from_str(&format!(
+            "{}_private_{}",
+            defn_name.name, private_method.key.id.sym
+          )));
+
+          self.compile_fn(
+            method_defn_name.clone(),
+            Functionish::Fn(
+              Some(private_method.key.id.clone()),
+              private_method.function.clone(),
+            ),
+          );
+
+          let key = self.class_private_key(&private_method.key);
+
+          let dst = match private_method.is_static {
+            false => &mut prototype,
+            true => &mut static_,
+          };
+
+          dst.properties.push((key, Value::Pointer(method_defn_name)));
         }
-        PrivateMethod(private_method) => self.todo(private_method.span, "PrivateMethod"),
 
         // Handled first because they need to be compiled before the
         // constructor, regardless of syntax order
-        ClassProp(_) => {}
+        ClassProp(_) | PrivateProp(_) | StaticBlock(_) => {}
 
-        PrivateProp(prop) => {
-          if prop.value.is_some() {
-            self.todo(prop.span, "class property initializers");
-          }
-        }
         TsIndexSignature(_) => {}
         Empty(_) => {}
-        StaticBlock(static_block) => {
-          self.todo(static_block.span, "StaticBlock");
-        }
       }
     }
 
@@ -896,18 +1246,107 @@ impl ModuleCompiler {
       constructor,
       prototype: Value::Object(Box::new(prototype)),
       static_: Value::Object(Box::new(static_)),
+      extends,
     }));
 
-    self.module.definitions.push(Definition {
-      pointer: defn_name.clone(),
-      content: DefinitionContent::Value(class_value.clone()),
-    });
+    if static_initializers.is_empty() {
+      self.module.definitions.push(Definition {
+        pointer: defn_name.clone(),
+        content: DefinitionContent::Value(class_value.clone()),
+      });
+
+      self.constants_map.insert(defn_name.clone(), class_value);
+    } else {
+      let body = self.compile_static_initializers(
+        &defn_name,
+        class_to_owner_id(ident, class),
+        class_value,
+        &static_initializers,
+      );
+
+      self.module.definitions.push(Definition {
+        pointer: defn_name.clone(),
+        content: DefinitionContent::Lazy(Lazy { body }),
+      });
+    }
 
-    self.constants_map.insert(defn_name.clone(), class_value);
+    self.class_scopes.pop();
 
     defn_name
   }
 
+  /// Compiles the code which initializes a class that has static members which can't be evaluated
+  /// at compile time. It runs with the class as `this`, which becomes the class's value once the
+  /// static members have been assigned.
+  fn compile_static_initializers(
+    &mut self,
+    defn_name: &Pointer,
+    owner_id: OwnerId,
+    class_value: Value,
+    static_initializers: &[&swc_ecma_ast::ClassMember],
+  ) -> Vec<FnLine> {
+    self.class_scope_mut().in_static = true;
+
+    let mut fnc = FunctionCompiler::new(self);
+    fnc.set_owner_id(owner_id);
+    fnc.class_being_initialized = Some(defn_name.clone());
+
+    fnc.push_raw(Instruction::Mov(class_value, Register::this()));
+
+    for class_member in static_initializers {
+      match class_member {
+        swc_ecma_ast::ClassMember::ClassProp(class_prop) => {
+          let mut ec = ExpressionCompiler { fnc: &mut fnc };
+          let key = ec.prop_name(&class_prop.key);
+
+          let value = match &class_prop.value {
+            Some(expr) => ec.compile(expr, None),
+            None => CompiledExpression::new(Value::Undefined, vec![]),
+          };
+
+          ec.fnc.push(Instruction::SubMov(
+            key.value.clone(),
+            value.value.clone(),
+            Register::this(),
+          ));
+
+          ec.fnc.release_ce(key);
+          ec.fnc.release_ce(value);
+        }
+        swc_ecma_ast::ClassMember::PrivateProp(private_prop) => {
+          let key = fnc.mc.class_private_key(&private_prop.key);
+          let mut ec = ExpressionCompiler { fnc: &mut fnc };
+
+          let value = match &private_prop.value {
+            Some(expr) => ec.compile(expr, None),
+            None => CompiledExpression::new(Value::Undefined, vec![]),
+          };
+
+          ec.fnc.push(Instruction::SubMov(
+            key,
+            value.value.clone(),
+            Register::this(),
+          ));
+
+          ec.fnc.release_ce(value);
+        }
+        swc_ecma_ast::ClassMember::StaticBlock(static_block) => {
+          fnc.block_statement(&static_block.body);
+        }
+        _ => {}
+      }
+    }
+
+    fnc.push(Instruction::Mov(
+      Value::Register(Register::this()),
+      Register::return_(),
+    ));
+
+    fnc.insert_all_releases();
+
+    take(&mut fnc.fn_.body)
+  }
+
   pub fn compile_enum_value(&mut self, ts_enum: &swc_ecma_ast::TsEnumDecl) -> Value {
     let mut properties = Vec::<(Value, Value)>::new();
     let mut next_default_id: Option<f64> = Some(0.0);
@@ -956,10 +1395,26 @@ impl ModuleCompiler {
       properties.push((value, Value::String(key)));
     }
 
-    Value::Object(Box::new(Object { properties }))
+    Value::Object(Box::new(Object {
+      properties,
+      ..Default::default()
+    }))
   }
 
   pub fn static_ec(&mut self) -> StaticExpressionCompiler {
     StaticExpressionCompiler::new(self)
   }
 }
+
+fn is_static_member(class_member: &swc_ecma_ast::ClassMember) -> bool {
+  use swc_ecma_ast::ClassMember::*;
+
+  match class_member {
+    Method(method) => method.is_static,
+    PrivateMethod(private_method) => private_method.is_static,
+    ClassProp(class_prop) => class_prop.is_static,
+    PrivateProp(private_prop) => private_prop.is_static,
+    StaticBlock(_) => true,
+    Constructor(_) | TsIndexSignature(_) | Empty(_) => false,
+  }
+}
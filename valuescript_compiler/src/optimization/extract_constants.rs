@@ -89,18 +89,15 @@ fn should_extract_value_as_constant(value: &Value) -> Option<String> {
       }
     }
     Value::Object(object) => {
-      if !object.properties.is_empty()
-        && object
-          .properties
-          .iter()
-          .all(|(k, v)| is_constant(k) && is_constant(v))
-      {
+      if !object.is_empty() && is_constant(value) {
         Some("object".to_string())
       } else {
         None
       }
     }
     Value::Class(_) => Some("class".to_string()),
+    Value::RegExp(_) => Some("regexp".to_string()),
+    Value::PrivateName(_) => Some("private_name".to_string()),
   }
 }
 
@@ -114,17 +111,22 @@ fn is_constant(value: &Value) -> bool {
     | Value::BigInt(..)
     | Value::String(..)
     | Value::Pointer(..)
-    | Value::Builtin(..) => true,
+    | Value::Builtin(..)
+    | Value::RegExp(..)
+    | Value::PrivateName(..) => true,
     Value::Register(..) => false,
     Value::Array(array) => array.values.iter().all(is_constant),
     Value::Object(object) => object
       .properties
       .iter()
+      .chain(object.getters.iter())
+      .chain(object.setters.iter())
       .all(|(k, v)| is_constant(k) && is_constant(v)),
     Value::Class(class) => {
       is_constant(&class.constructor)
         && is_constant(&class.prototype)
         && is_constant(&class.static_)
+        && is_constant(&class.extends)
     }
   }
 }
@@ -130,6 +130,8 @@ impl Kal {
       Value::Array(array) => Kal::Array(Box::new(Array {
         values: array.values.iter().map(Kal::from_value).collect(),
       })),
+      // Accessors run code when the object is used, which Kal doesn't model
+      Value::Object(object) if object.has_accessors() => Kal::Unknown,
       Value::Object(object) => Kal::Object(Box::new(Object {
         properties: object
           .properties
@@ -137,6 +139,8 @@ impl Kal {
           .map(|(k, v)| (Kal::from_value(k), Kal::from_value(v)))
           .collect(),
       })),
+      // Inheritance is resolved by the VM, so Kal doesn't know the members of derived classes
+      Value::Class(class) if class.extends != Value::Void => Kal::Unknown,
       Value::Class(class) => Kal::Class(Box::new(Class {
         meta: class.meta.clone(),
         constructor: Kal::from_value(&class.constructor),
@@ -146,6 +150,10 @@ impl Kal {
       Value::Register(reg) => Kal::Register(reg.clone()),
       Value::Pointer(p) => Kal::Pointer(p.clone()),
       Value::Builtin(b) => Kal::Builtin(b.clone()),
+
+      // Regexes are only constructed at runtime, so the optimizer treats them as opaque
+      Value::RegExp(..) => Kal::Unknown,
+      Value::PrivateName(..) => Kal::Unknown,
     }
   }
 
@@ -209,6 +217,7 @@ impl Kal {
 
           properties
         },
+        ..Default::default()
       }))),
       Kal::Function(_) => None,
       Kal::Class(class) => Some(Value::Class(Box::new(asm::Class {
@@ -216,6 +225,7 @@ impl Kal {
         constructor: class.constructor.try_to_value()?,
         prototype: class.prototype.try_to_value()?,
         static_: class.static_.try_to_value()?,
+        extends: Value::Void,
       }))),
       Kal::Register(x) => Some(Value::Register(x.clone())),
       Kal::Pointer(x) => Some(Value::Pointer(x.clone())),
@@ -249,8 +259,11 @@ impl Kal {
         }
 
         VsObject {
-          string_map,
+          string_map: string_map.into(),
           symbol_map: Default::default(),
+          getter_map: Default::default(),
+          setter_map: Default::default(),
+          private_map: Default::default(),
           prototype: Val::Void,
         }
         .to_val()
@@ -351,7 +364,10 @@ impl Kal {
 
         Kal::Undefined
       }
-      Kal::Class(class) => class.static_.sub(key),
+      Kal::Class(class) => match key.to_known_string().as_deref() {
+        Some("prototype") => class.prototype.clone(),
+        _ => class.static_.sub(key),
+      },
       _ => Kal::Unknown, // TODO: Implement more cases
     }
   }
@@ -387,6 +403,7 @@ impl FnState {
         asm::FnLine::Label(_) => {}
         asm::FnLine::Empty => {}
         asm::FnLine::Comment(_) => {}
+        asm::FnLine::Position(_) => {}
         asm::FnLine::Release(reg) => reg_allocator.alloc.mark_used(&reg.name),
       }
     }
@@ -500,6 +517,7 @@ impl FnState {
       OpNot(a1, dst) => self.apply_unary_op(a1, dst, operations::op_not),
       OpBitNot(a1, dst) => self.apply_unary_op(a1, dst, operations::op_bit_not),
       TypeOf(a1, dst) => self.apply_unary_op(a1, dst, operations::op_typeof),
+      ForInKeys(a1, dst) => self.apply_unary_op(a1, dst, operations::op_for_in_keys),
       UnaryPlus(a1, dst) => self.apply_unary_op(a1, dst, operations::op_unary_plus),
       UnaryMinus(a1, dst) => self.apply_unary_op(a1, dst, operations::op_unary_minus),
       Import(a1, dst) | ImportStar(a1, dst) | Cat(a1, dst) => {
@@ -509,7 +527,7 @@ impl FnState {
         self.set(dst.name.clone(), Kal::Unknown);
       }
 
-      Yield(a1, dst) | YieldStar(a1, dst) => {
+      Yield(a1, dst) | YieldStar(a1, dst) | Await(a1, dst) | ObjectSpread(a1, dst) => {
         self.eval_arg(a1);
         self.set(dst.name.clone(), Kal::Unknown);
       }
@@ -547,6 +565,7 @@ impl FnState {
       }
       InstanceOf(a1, a2, dst) => self.apply_binary_op(a1, a2, dst, operations::op_instance_of),
       In(a1, a2, dst) => self.apply_binary_op(a1, a2, dst, operations::op_in),
+      ObjectRest(a1, a2, dst) => self.apply_binary_op(a1, a2, dst, operations::op_object_rest),
 
       OpOptionalChain(a1, a2, dst) => {
         self.eval_arg(a1);
@@ -702,6 +721,7 @@ impl FnState {
       OpNot(_, dst)
       | OpBitNot(_, dst)
       | TypeOf(_, dst)
+      | ForInKeys(_, dst)
       | UnaryPlus(_, dst)
       | UnaryMinus(_, dst)
       | OpPlus(_, _, dst)
@@ -729,6 +749,7 @@ impl FnState {
       | OpRightShiftUnsigned(_, _, dst)
       | InstanceOf(_, _, dst)
       | In(_, _, dst)
+      | ObjectRest(_, _, dst)
       | Sub(_, _, dst) => {
         if let Some(mut value) = self.get(dst.name.clone()).try_to_value() {
           value.visit_registers_mut_rev(&mut |rvm| {
@@ -755,6 +776,7 @@ impl FnState {
       | ConstApply(_, _, _, _)
       | Bind(_, _, _)
       | SubMov(_, _, _)
+      | ObjectSpread(_, _)
       | SubCall(_, _, _, _)
       | Jmp(_)
       | JmpIf(_, _)
@@ -773,6 +795,7 @@ impl FnState {
       | Cat(_, _)
       | Yield(_, _)
       | YieldStar(_, _)
+      | Await(_, _)
       | Delete(..)
       | Jsx(..) => {}
     }
@@ -787,7 +810,9 @@ impl FnState {
       | Value::Number(_)
       | Value::BigInt(_)
       | Value::String(_)
-      | Value::Builtin(_) => Kal::from_value(arg),
+      | Value::Builtin(_)
+      | Value::RegExp(_)
+      | Value::PrivateName(_) => Kal::from_value(arg),
       Value::Pointer(p) => match self.pointer_kals.get(p) {
         Some(kal) => {
           if let Some(new_arg) = kal.try_to_value() {
@@ -816,14 +841,32 @@ impl FnState {
           properties.push((k, v));
         }
 
+        if object.has_accessors() {
+          for (k, v) in object.getters.iter_mut().chain(object.setters.iter_mut()) {
+            self.eval_arg(k);
+            self.eval_arg(v);
+          }
+
+          return Kal::Unknown;
+        }
+
         Kal::Object(Box::new(Object { properties }))
       }
-      Value::Class(class) => Kal::Class(Box::new(Class {
-        meta: class.meta.clone(),
-        constructor: self.eval_arg(&mut class.constructor),
-        prototype: self.eval_arg(&mut class.prototype),
-        static_: self.eval_arg(&mut class.static_),
-      })),
+      Value::Class(class) => {
+        let class_kal = Class {
+          meta: class.meta.clone(),
+          constructor: self.eval_arg(&mut class.constructor),
+          prototype: self.eval_arg(&mut class.prototype),
+          static_: self.eval_arg(&mut class.static_),
+        };
+
+        if class.extends != Value::Void {
+          self.eval_arg(&mut class.extends);
+          return Kal::Unknown;
+        }
+
+        Kal::Class(Box::new(class_kal))
+      }
       Value::Register(reg) => {
         let kal = self.get(reg.name.clone()).clone();
 
@@ -27,7 +27,9 @@ fn reduce(instr: Instruction) -> Option<Instruction> {
   match &instr {
     End | OpInc(..) | OpDec(..) | Call(..) | Apply(..) | ConstApply(..) | SubCall(..) | Jmp(..)
     | New(..) | Throw(..) | SetCatch(..) | UnsetCatch | ConstSubCall(..) | RequireMutableThis
-    | ThisSubCall(..) | Next(..) | Yield(..) | YieldStar(..) | Delete(..) | Jsx(..) => Some(instr),
+    | ThisSubCall(..) | Next(..) | Yield(..) | YieldStar(..) | Delete(..) | Jsx(..) | Await(..) => {
+      Some(instr)
+    }
 
     Mov(arg, dst) => 'b: {
       if dst.is_ignore() {
@@ -70,11 +72,14 @@ fn reduce(instr: Instruction) -> Option<Instruction> {
     | OpRightShift(_, _, dst)
     | OpRightShiftUnsigned(_, _, dst)
     | TypeOf(_, dst)
+    | ForInKeys(_, dst)
     | InstanceOf(_, _, dst)
     | In(_, _, dst)
+    | ObjectRest(_, _, dst)
     | Bind(_, _, dst)
     | Sub(_, _, dst)
     | SubMov(_, _, dst)
+    | ObjectSpread(_, dst)
     | UnaryPlus(_, dst)
     | UnaryMinus(_, dst)
     | Import(_, dst)
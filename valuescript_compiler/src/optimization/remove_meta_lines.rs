@@ -8,6 +8,14 @@ pub fn remove_meta_lines(module: &mut Module) {
       for line in take(&mut fn_.body) {
         match &line {
           FnLine::Instruction(_) | FnLine::Label(_) | FnLine::Empty => fn_.body.push(line),
+          FnLine::Position(_) => {
+            // A position is only needed for the instructions that follow it
+            if let Some(FnLine::Position(_)) = fn_.body.last() {
+              fn_.body.pop();
+            }
+
+            fn_.body.push(line);
+          }
           FnLine::Comment(_) | FnLine::Release(_) => continue,
         }
       }
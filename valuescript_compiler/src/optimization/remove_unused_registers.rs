@@ -98,7 +98,9 @@ fn reg_is_always_returned(body: &mut Vec<FnLine>, reg: &String) -> bool {
         false
       }
       FnLine::Instruction(_) | FnLine::Label(_) => false,
-      FnLine::Empty | FnLine::Comment(_) | FnLine::Release(_) => last_was_return_reg,
+      FnLine::Empty | FnLine::Comment(_) | FnLine::Release(_) | FnLine::Position(_) => {
+        last_was_return_reg
+      }
     };
 
     if let FnLine::Instruction(Instruction::End) = line {
@@ -259,6 +261,7 @@ fn identify_primary_registers(fn_: &mut Function) -> HashSet<String> {
       Instruction::Cat(value, _) // TODO: Why is the argument to cat a source of primary registers?
       | Instruction::Yield(value, _)
       | Instruction::YieldStar(value, _)
+      | Instruction::Await(value, _)
       | Instruction::JmpIf(value, _)
       | Instruction::JmpIfNot(value, _)
       | Instruction::Throw(value) => {
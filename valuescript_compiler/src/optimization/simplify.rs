@@ -61,7 +61,11 @@ fn handle_mutation_releases(body: &mut [FnLine], i: usize, take_registers: bool)
         }
       });
     }
-    FnLine::Release(_) | FnLine::Label(_) | FnLine::Empty | FnLine::Comment(_) => {}
+    FnLine::Release(_)
+    | FnLine::Label(_)
+    | FnLine::Empty
+    | FnLine::Comment(_)
+    | FnLine::Position(_) => {}
   };
 
   for (released_reg, skips) in calls {
@@ -166,7 +170,7 @@ fn simplify_fn(mut state: FnState, fn_: &mut Function, take_registers: bool) {
         }
       }
       FnLine::Label(_) => state.clear_local(),
-      FnLine::Empty | FnLine::Comment(_) => {}
+      FnLine::Empty | FnLine::Comment(_) | FnLine::Position(_) => {}
       FnLine::Release(reg) => {
         pending_releases.push(reg.clone());
 
@@ -196,7 +200,7 @@ fn is_jmp_or_label(line: &FnLine) -> bool {
   match line {
     FnLine::Instruction(instr) => is_jmp_instr(instr),
     FnLine::Label(_) => true,
-    FnLine::Empty | FnLine::Comment(_) | FnLine::Release(_) => false,
+    FnLine::Empty | FnLine::Comment(_) | FnLine::Release(_) | FnLine::Position(_) => false,
   }
 }
 
@@ -236,14 +240,17 @@ fn is_jmp_instr(instr: &Instruction) -> bool {
     | Instruction::OpRightShift(..)
     | Instruction::OpRightShiftUnsigned(..)
     | Instruction::TypeOf(..)
+    | Instruction::ForInKeys(..)
     | Instruction::InstanceOf(..)
     | Instruction::In(..)
+    | Instruction::ObjectRest(..)
     | Instruction::Call(..)
     | Instruction::Apply(..)
     | Instruction::ConstApply(..)
     | Instruction::Bind(..)
     | Instruction::Sub(..)
     | Instruction::SubMov(..)
+    | Instruction::ObjectSpread(..)
     | Instruction::SubCall(..)
     | Instruction::UnaryPlus(..)
     | Instruction::UnaryMinus(..)
@@ -262,6 +269,7 @@ fn is_jmp_instr(instr: &Instruction) -> bool {
     | Instruction::Yield(..)
     | Instruction::YieldStar(..)
     | Instruction::Delete(..)
-    | Instruction::Jsx(..) => false,
+    | Instruction::Jsx(..)
+    | Instruction::Await(..) => false,
   }
 }
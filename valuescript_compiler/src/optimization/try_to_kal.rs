@@ -20,11 +20,7 @@ impl TryToKal for Val {
       Val::Bool(b) => Kal::Bool(*b),
       Val::Number(n) => Kal::Number(Number(*n)),
       Val::BigInt(n) => Kal::BigInt(n.clone()),
-      Val::Symbol(sym) => match sym {
-        VsSymbol::ITERATOR => Kal::Builtin(Builtin {
-          name: "SymbolIterator".to_string(),
-        }),
-      },
+      Val::Symbol(sym) => symbol_to_kal(sym),
       Val::String(s) => Kal::String(s.to_string()),
       Val::Array(arr) => {
         let mut values = Vec::<Kal>::new();
@@ -41,16 +37,14 @@ impl TryToKal for Val {
           return None;
         }
 
+        if !obj.getter_map.is_empty() || !obj.setter_map.is_empty() || !obj.private_map.is_empty() {
+          return None;
+        }
+
         let mut properties = Vec::<(Kal, Kal)>::new();
 
         for (k, v) in &obj.symbol_map {
-          let k = match k {
-            VsSymbol::ITERATOR => Kal::Builtin(Builtin {
-              name: "SymbolIterator".to_string(),
-            }),
-          };
-
-          properties.push((k, v.try_to_kal()?));
+          properties.push((symbol_to_kal(k), v.try_to_kal()?));
         }
 
         for (k, v) in &obj.string_map {
@@ -60,7 +54,9 @@ impl TryToKal for Val {
         Kal::Object(Box::new(Object { properties }))
       }
       // TODO: support more of these
-      Val::Function(..)
+      Val::Map(..)
+      | Val::Set(..)
+      | Val::Function(..)
       | Val::Class(..)
       | Val::Static(..)
       | Val::Dynamic(..)
@@ -69,3 +65,14 @@ impl TryToKal for Val {
     })
   }
 }
+
+fn symbol_to_kal(symbol: &VsSymbol) -> Kal {
+  Kal::Builtin(Builtin {
+    name: match symbol {
+      VsSymbol::ITERATOR => "SymbolIterator",
+      VsSymbol::GENERATOR_RETURN => "SymbolGeneratorReturn",
+      VsSymbol::ASYNC_ITERATOR => "SymbolAsyncIterator",
+    }
+    .to_string(),
+  })
+}
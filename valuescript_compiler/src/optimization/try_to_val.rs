@@ -3,7 +3,9 @@ use std::collections::BTreeMap;
 use valuescript_vm::{
   vs_class::VsClass,
   vs_object::VsObject,
-  vs_value::{ToVal, Val},
+  vs_private_name::VsPrivateName,
+  vs_regexp::VsRegExp,
+  vs_value::{ToDynamicVal, ToVal, Val, ValTrait},
 };
 
 use crate::asm::{ContentHashable, Number, Value};
@@ -31,30 +33,60 @@ impl TryToVal for Value {
         result.to_val()
       }
       Value::Object(obj) => {
+        if obj.has_accessors() {
+          return Err("Invalid argument".to_val());
+        }
+
         let mut string_map = BTreeMap::<String, Val>::new();
+        let mut private_map = BTreeMap::<VsPrivateName, Val>::new();
 
         for (key, value) in obj.properties {
-          string_map.insert(key.try_to_val()?.to_string(), value.try_to_val()?);
+          let key = key.try_to_val()?;
+          let value = value.try_to_val()?;
+
+          match VsPrivateName::from_val(&key) {
+            Some(name) => private_map.insert(name.clone(), value),
+            None => string_map.insert(key.to_string(), value),
+          };
         }
 
         VsObject {
-          string_map,
+          string_map: string_map.into(),
           symbol_map: Default::default(),
+          getter_map: Default::default(),
+          setter_map: Default::default(),
+          private_map,
           prototype: Val::Void,
         }
         .to_val()
       }
-      Value::Class(class) => VsClass {
-        name: class.meta.name,
-        content_hash: match class.meta.content_hashable {
-          ContentHashable::Empty | ContentHashable::Src(_, _) => None,
-          ContentHashable::Content(hash) => Some(hash.0),
-        },
-        constructor: class.constructor.try_to_val()?,
-        prototype: class.prototype.try_to_val()?,
-        static_: class.static_.try_to_val()?,
+      Value::Class(class) => {
+        let mut vs_class = VsClass {
+          name: class.meta.name,
+          content_hash: match class.meta.content_hashable {
+            ContentHashable::Empty | ContentHashable::Src(_, _) => None,
+            ContentHashable::Content(hash) => Some(hash.0),
+          },
+          constructor: class.constructor.try_to_val()?,
+          prototype: class.prototype.try_to_val()?,
+          static_: class.static_.try_to_val()?,
+        };
+
+        if class.extends != Value::Void {
+          match class.extends.try_to_val()?.as_class_data() {
+            Some(parent) => vs_class.extend(&parent),
+            None => return Err("Invalid argument".to_val()),
+          }
+        }
+
+        vs_class.to_val()
+      }
+      Value::RegExp(regexp) => VsRegExp::new(&regexp.source, &regexp.flags)?.to_dynamic_val(),
+      Value::PrivateName(private_name) => VsPrivateName {
+        brand: private_name.brand.0,
+        name: private_name.name,
       }
-      .to_val(),
+      .to_dynamic_val(),
 
       Value::Void | Value::Register(..) | Value::Pointer(..) | Value::Builtin(..) => {
         return Err("Invalid argument".to_val());
@@ -56,6 +56,15 @@ pub struct Name {
   pub captures: Vec<Capture>,
 }
 
+impl Name {
+  /// Module-level variables are captured through their pointer, which refers to their value at
+  /// the end of the module's initialization. This means they can still be mutated while the
+  /// module is being initialized.
+  pub fn is_module_variable(&self) -> bool {
+    matches!(self.type_, NameType::Let | NameType::Var) && matches!(self.value, Value::Pointer(_))
+  }
+}
+
 #[derive(Debug)]
 pub struct Ref {
   pub name_id: NameId,
@@ -546,17 +555,7 @@ impl ScopeAnalysis {
             self.insert_pointer_name(scope, NameType::Function, &fn_decl.ident);
           }
           swc_ecma_ast::Decl::Var(var_decl) => {
-            let name_type = match var_decl.kind {
-              swc_ecma_ast::VarDeclKind::Const => NameType::Const,
-              swc_ecma_ast::VarDeclKind::Let => NameType::Let,
-              swc_ecma_ast::VarDeclKind::Var => NameType::Var,
-            };
-
-            for decl in &var_decl.decls {
-              for ident in self.get_pat_idents(&decl.name) {
-                self.insert_pointer_name(scope, name_type, &ident);
-              }
-            }
+            self.module_level_hoists_var_decl(scope, var_decl);
           }
           swc_ecma_ast::Decl::TsInterface(_) => {}
           swc_ecma_ast::Decl::TsTypeAlias(_) => {}
@@ -593,6 +592,11 @@ impl ScopeAnalysis {
           // Diagnostic emitted after hoist processing
         }
       },
+      ModuleItem::Stmt(swc_ecma_ast::Stmt::Decl(swc_ecma_ast::Decl::Var(var_decl))) => {
+        if !var_decl.declare {
+          self.module_level_hoists_var_decl(scope, var_decl);
+        }
+      }
       ModuleItem::Stmt(stmt) => {
         self.function_level_hoists_stmt(scope, stmt);
         self.block_level_hoists_stmt(scope, stmt);
@@ -600,6 +604,20 @@ impl ScopeAnalysis {
     };
   }
 
+  fn module_level_hoists_var_decl(&mut self, scope: &Scope, var_decl: &swc_ecma_ast::VarDecl) {
+    let name_type = match var_decl.kind {
+      swc_ecma_ast::VarDeclKind::Const => NameType::Const,
+      swc_ecma_ast::VarDeclKind::Let => NameType::Let,
+      swc_ecma_ast::VarDeclKind::Var => NameType::Var,
+    };
+
+    for decl in &var_decl.decls {
+      for ident in self.get_pat_idents(&decl.name) {
+        self.insert_pointer_name(scope, name_type, &ident);
+      }
+    }
+  }
+
   fn function_level_hoists(&mut self, scope: &Scope, block: &swc_ecma_ast::BlockStmt) {
     for stmt in &block.stmts {
       self.function_level_hoists_stmt(scope, stmt);
@@ -754,19 +772,16 @@ impl ScopeAnalysis {
       swc_ecma_ast::VarDeclKind::Const => NameType::Const,
     };
 
+    // Variables directly in the module scope are hoisted as pointers by `module_level_hoists`, so
+    // these are always in a function or in a block of the module's initialization code
     for decl in &var_decl.decls {
       for ident in self.get_pat_idents(&decl.name) {
-        let owner_id = scope.borrow().owner_id.clone();
-
-        match owner_id {
-          OwnerId::Span(..) => self.insert_reg_name(scope, name_type, &ident, Some(decl.span.hi)),
-          OwnerId::Module => self.insert_pointer_name(scope, name_type, &ident),
-        }
+        self.insert_reg_name(scope, name_type, &ident, Some(decl.span.hi));
       }
     }
   }
 
-  fn get_pat_idents(&self, pat: &swc_ecma_ast::Pat) -> Vec<swc_ecma_ast::Ident> {
+  pub fn get_pat_idents(&self, pat: &swc_ecma_ast::Pat) -> Vec<swc_ecma_ast::Ident> {
     let mut idents = Vec::new();
     self.get_pat_idents_impl(&mut idents, pat);
     idents
@@ -905,6 +920,10 @@ impl ScopeAnalysis {
       None => class_.span,
     };
 
+    if let Some(super_class) = &class_.super_class {
+      self.expr(scope, super_class);
+    }
+
     let child_scope = scope.nest(Some(OwnerId::Span(owner_span)));
     self.insert_this_name(&child_scope, owner_span);
 
@@ -1072,7 +1091,7 @@ impl ScopeAnalysis {
           self.expr(scope, arg);
         }
       }
-      Expr::Await(await_) => self.todo(await_.span, "async/await"),
+      Expr::Await(await_) => self.expr(scope, &await_.arg),
       Expr::Member(member) => self.member(scope, member),
       Expr::Call(call) => self.call(scope, call),
       Expr::New(new) => {
@@ -1090,8 +1109,16 @@ impl ScopeAnalysis {
           self.expr(scope, elem);
         }
       }
-      Expr::TaggedTpl(_) => {
-        // TODO (diagnostic emitted elsewhere)
+      Expr::TaggedTpl(tagged_tpl) => {
+        self.expr(scope, &tagged_tpl.tag);
+
+        if let Expr::Member(member) = &*tagged_tpl.tag {
+          self.mutate_expr(scope, &member.obj, true);
+        }
+
+        for elem in &tagged_tpl.tpl.exprs {
+          self.expr(scope, elem);
+        }
       }
       Expr::Arrow(arrow) => {
         self.arrow(scope, arrow);
@@ -1119,7 +1146,13 @@ impl ScopeAnalysis {
           OptChainBase::Member(member) => self.member(scope, member),
         }
       }
-      Expr::SuperProp(super_prop) => self.todo(super_prop.span, "super_prop"),
+      Expr::SuperProp(super_prop) => {
+        self.ident(scope, &Ident::this(super_prop.span));
+
+        if let swc_ecma_ast::SuperProp::Computed(computed) = &super_prop.prop {
+          self.expr(scope, &computed.expr);
+        }
+      }
       Expr::JSXEmpty(_) => {}
       Expr::JSXNamespacedName(_) => self.todo(expr.span(), "JSXNamespacedName"),
       Expr::JSXMember(_) => self.todo(expr.span(), "JSXMember"),
@@ -1128,7 +1161,7 @@ impl ScopeAnalysis {
       Expr::TsInstantiation(ts_instantiation) => {
         self.todo(ts_instantiation.span, "TsInstantiation")
       }
-      Expr::PrivateName(private_name) => self.todo(private_name.span, "PrivateName"),
+      Expr::PrivateName(_) => {}
     }
   }
 
@@ -1210,8 +1243,12 @@ impl ScopeAnalysis {
       swc_ecma_ast::Callee::Expr(expr) => {
         self.expr(scope, expr);
 
-        if let swc_ecma_ast::Expr::Member(member) = &**expr {
-          self.mutate_expr(scope, &member.obj, true);
+        match &**expr {
+          swc_ecma_ast::Expr::Member(member) => self.mutate_expr(scope, &member.obj, true),
+          swc_ecma_ast::Expr::SuperProp(super_prop) => {
+            self.mutate_ident(scope, &Ident::this(super_prop.span), true)
+          }
+          _ => {}
         };
       }
     }
@@ -1369,7 +1406,10 @@ impl ScopeAnalysis {
       Expr::Bin(bin) => diagnostic = Some(Diagnostic::todo(bin.span, "mutate binary exp")),
       Expr::Assign(assign) => diagnostic = Some(Diagnostic::todo(assign.span, "mutate assignment")),
       Expr::SuperProp(super_prop) => {
-        diagnostic = Some(Diagnostic::todo(super_prop.span, "mutate super_prop"))
+        diagnostic = Some(Diagnostic::not_supported(
+          super_prop.span,
+          "assigning to super properties",
+        ))
       }
       Expr::Cond(cond) => diagnostic = Some(Diagnostic::todo(cond.span, "mutate cond")),
       Expr::Seq(seq) => diagnostic = Some(Diagnostic::todo(seq.span, "mutate seq")),
@@ -1461,7 +1501,12 @@ impl ScopeAnalysis {
       }
     };
 
-    if optional {
+    // Static initializers run with the class as `this`, so mutating the class there is really
+    // mutating `this`
+    let is_class_initialization = name.type_ == NameType::Class
+      && matches!(&name_id, NameId::Span(span) if scope.borrow().owner_id == OwnerId::Span(*span));
+
+    if optional || is_class_initialization {
       self.optional_mutations.insert(ident.span, name_id.clone());
     } else {
       name.mutations.push(ident.span);
@@ -1536,17 +1581,22 @@ impl ScopeAnalysis {
         swc_ecma_ast::Prop::Getter(getter) => {
           self.prop_key(scope, &getter.key);
 
-          if let Some(body) = &getter.body {
-            self.block_stmt(scope, body);
-          }
+          self.function(
+            scope,
+            prop_name_ident(&getter.key),
+            &getter_to_function(getter),
+            true,
+          );
         }
         swc_ecma_ast::Prop::Setter(setter) => {
           self.prop_key(scope, &setter.key);
-          self.param_pat(scope, &setter.param);
 
-          if let Some(body) = &setter.body {
-            self.block_stmt(scope, body);
-          }
+          self.function(
+            scope,
+            prop_name_ident(&setter.key),
+            &setter_to_function(setter),
+            true,
+          );
         }
         swc_ecma_ast::Prop::Method(method) => {
           self.prop_key(scope, &method.key);
@@ -1778,7 +1828,7 @@ impl ScopeAnalysis {
 
   fn find_capture_mutations(&self) {
     for (name_id, name) in &self.names {
-      if !name.captures.is_empty() {
+      if !name.captures.is_empty() && !name.is_module_variable() {
         if name.type_ == NameType::Let {
           match name_id {
             NameId::Span(span) => {
@@ -1911,7 +1961,7 @@ impl ScopeAnalysis {
 
   fn expand_effectively_const(&mut self) {
     for name in self.names.values_mut() {
-      if !name.captures.is_empty() {
+      if !name.captures.is_empty() && !name.is_module_variable() {
         name.effectively_const = true;
       }
     }
@@ -2108,6 +2158,45 @@ pub fn fn_owner_span(
   }
 }
 
+pub fn prop_name_ident(prop_name: &swc_ecma_ast::PropName) -> Option<&swc_ecma_ast::Ident> {
+  match prop_name {
+    swc_ecma_ast::PropName::Ident(ident) => Some(ident),
+    _ => None,
+  }
+}
+
+/// Getters are compiled as functions without parameters.
+pub fn getter_to_function(getter: &swc_ecma_ast::GetterProp) -> swc_ecma_ast::Function {
+  swc_ecma_ast::Function {
+    params: vec![],
+    decorators: vec![],
+    span: getter.span,
+    body: getter.body.clone(),
+    is_generator: false,
+    is_async: false,
+    type_params: None,
+    return_type: getter.type_ann.clone(),
+  }
+}
+
+/// Setters are compiled as functions with a single parameter.
+pub fn setter_to_function(setter: &swc_ecma_ast::SetterProp) -> swc_ecma_ast::Function {
+  swc_ecma_ast::Function {
+    params: vec![swc_ecma_ast::Param {
+      span: setter.param.span(),
+      decorators: vec![],
+      pat: setter.param.clone(),
+    }],
+    decorators: vec![],
+    span: setter.span,
+    body: setter.body.clone(),
+    is_generator: false,
+    is_async: false,
+    type_params: None,
+    return_type: None,
+  }
+}
+
 pub fn fn_to_owner_id(
   name: Option<&swc_ecma_ast::Ident>,
   function: &swc_ecma_ast::Function,
@@ -1,15 +1,21 @@
 use std::cell::RefCell;
+use std::mem::take;
 
 use swc_common::Spanned;
 use valuescript_common::to_i32;
+use valuescript_vm::vs_regexp::VsRegExp;
 
 use crate::{
-  asm::{Array, Builtin, Number, Object, Value},
+  asm::{
+    Array, Builtin, Definition, DefinitionContent, FnLine, Lazy, Number, Object, Register, Value,
+  },
   diagnostic::{DiagnosticContainer, DiagnosticReporter},
   expression_compiler::value_from_literal,
   function_compiler::Functionish,
   ident::Ident,
+  instruction::Instruction,
   module_compiler::ModuleCompiler,
+  scope_analysis::{getter_to_function, prop_name_ident, setter_to_function},
   Diagnostic,
 };
 
@@ -29,14 +35,19 @@ impl<'a> StaticExpressionCompiler<'a> {
   }
 
   pub fn expr(&mut self, expr: &swc_ecma_ast::Expr) -> Value {
-    let symbol_iterator_opt = as_symbol_iterator(expr);
-
-    if let Some(symbol_iterator) = symbol_iterator_opt {
-      return symbol_iterator;
+    if let Some(symbol) = as_well_known_symbol(expr) {
+      return symbol;
     }
 
     match expr {
       swc_ecma_ast::Expr::Lit(lit) => match value_from_literal(lit) {
+        Ok(Value::RegExp(regexp)) => {
+          if let Err(msg) = VsRegExp::validate(&regexp.source, &regexp.flags) {
+            self.error(lit.span(), &msg);
+          }
+
+          Value::RegExp(regexp)
+        }
         Ok(value) => value,
         Err(msg) => {
           self.internal_error(expr.span(), &format!("Failed to compile literal: {}", msg));
@@ -44,34 +55,66 @@ impl<'a> StaticExpressionCompiler<'a> {
         }
       },
       swc_ecma_ast::Expr::Array(array) => {
+        let mut segments = Vec::<Value>::new();
         let mut values = Vec::<Value>::new();
 
         for item in &array.elems {
-          values.push(match item {
-            Some(item) => {
-              if item.spread.is_some() {
-                self.todo(expr.span(), "item.spread in static expression");
-                return Value::String("(error)".to_string());
+          match item {
+            Some(item) if item.spread.is_some() => {
+              if !values.is_empty() {
+                segments.push(Value::Array(Box::new(Array {
+                  values: take(&mut values),
+                })));
               }
 
-              self.expr(&item.expr)
+              segments.push(self.expr(&item.expr));
             }
-            None => Value::Void,
-          });
+            Some(item) => values.push(self.expr(&item.expr)),
+            None => values.push(Value::Void),
+          };
+        }
+
+        if segments.is_empty() {
+          return Value::Array(Box::new(Array { values }));
+        }
+
+        if !values.is_empty() {
+          segments.push(Value::Array(Box::new(Array { values })));
         }
 
-        Value::Array(Box::new(Array { values }))
+        self.lazy(vec![Instruction::Cat(
+          Value::Array(Box::new(Array { values: segments })),
+          Register::return_(),
+        )])
       }
       swc_ecma_ast::Expr::Object(object) => {
-        let mut properties = Vec::<(Value, Value)>::new();
+        let mut object_asm = Object::default();
+
+        // Spreads need to be evaluated at runtime, so they're built up by a lazy definition
+        let mut spread_body = Vec::<Instruction>::new();
 
         for prop in &object.props {
-          let (key, value) = match prop {
-            swc_ecma_ast::PropOrSpread::Spread(_) => {
-              self.todo(prop.span(), "Static spread");
-              return Value::String("(error)".to_string());
+          let (entries, key, value) = match prop {
+            swc_ecma_ast::PropOrSpread::Spread(spread) => {
+              let segment = Value::Object(Box::new(take(&mut object_asm)));
+
+              spread_body.push(match spread_body.is_empty() {
+                true => Instruction::Mov(segment, Register::return_()),
+                false => Instruction::ObjectSpread(segment, Register::return_()),
+              });
+
+              let spread_value = self.expr(&spread.expr);
+              spread_body.push(Instruction::ObjectSpread(spread_value, Register::return_()));
+
+              continue;
             }
             swc_ecma_ast::PropOrSpread::Prop(prop) => match &**prop {
+              swc_ecma_ast::Prop::Getter(_) | swc_ecma_ast::Prop::Setter(_)
+                if !spread_body.is_empty() =>
+              {
+                self.todo(prop.span(), "Accessor after spread in object literal");
+                return Value::String("(error)".to_string());
+              }
               swc_ecma_ast::Prop::Shorthand(_) => {
                 self.todo(prop.span(), "Static object shorthand");
                 return Value::String("(error)".to_string());
@@ -87,43 +130,42 @@ impl<'a> StaticExpressionCompiler<'a> {
 
                 let value = self.expr(&kv.value);
 
-                (key, value)
+                (&mut object_asm.properties, key, value)
               }
-              swc_ecma_ast::Prop::Assign(_)
-              | swc_ecma_ast::Prop::Getter(_)
-              | swc_ecma_ast::Prop::Setter(_) => {
+              swc_ecma_ast::Prop::Assign(_) => {
                 self.todo(prop.span(), "This type of static prop");
                 return Value::String("(error)".to_string());
               }
+              swc_ecma_ast::Prop::Getter(getter) => {
+                let (key, value) = self.method(&getter.key, getter_to_function(getter));
+                (&mut object_asm.getters, key, value)
+              }
+              swc_ecma_ast::Prop::Setter(setter) => {
+                let (key, value) = self.method(&setter.key, setter_to_function(setter));
+                (&mut object_asm.setters, key, value)
+              }
               swc_ecma_ast::Prop::Method(method) => {
-                let key = self.prop_name(&method.key);
-
-                let fn_ident = match &method.key {
-                  swc_ecma_ast::PropName::Ident(ident) => Some(ident.clone()),
-                  _ => None,
-                };
-
-                let fn_name = fn_ident.clone().map(|ident| ident.sym.to_string());
-
-                let p = match &fn_name {
-                  Some(name) => self.mc.allocate_defn(name),
-                  None => self.mc.allocate_defn_numbered("_anon"),
-                };
-
-                self.mc.compile_fn(
-                  p.clone(),
-                  Functionish::Fn(fn_ident, method.function.clone()),
-                );
-
-                (key, Value::Pointer(p))
+                let (key, value) = self.method(&method.key, method.function.clone());
+                (&mut object_asm.properties, key, value)
               }
             },
           };
 
-          properties.push((key, value));
+          entries.push((key, value));
+        }
+
+        if spread_body.is_empty() {
+          return Value::Object(Box::new(object_asm));
+        }
+
+        if !object_asm.properties.is_empty() {
+          spread_body.push(Instruction::ObjectSpread(
+            Value::Object(Box::new(object_asm)),
+            Register::return_(),
+          ));
         }
 
-        Value::Object(Box::new(Object { properties }))
+        self.lazy(spread_body)
       }
       swc_ecma_ast::Expr::This(_)
       | swc_ecma_ast::Expr::Update(_)
@@ -267,6 +309,28 @@ impl<'a> StaticExpressionCompiler<'a> {
     }
   }
 
+  fn method(
+    &mut self,
+    key: &swc_ecma_ast::PropName,
+    function: swc_ecma_ast::Function,
+  ) -> (Value, Value) {
+    let key_value = self.prop_name(key);
+
+    let fn_ident = prop_name_ident(key).cloned();
+    let fn_name = fn_ident.clone().map(|ident| ident.sym.to_string());
+
+    let p = match &fn_name {
+      Some(name) => self.mc.allocate_defn(name),
+      None => self.mc.allocate_defn_numbered("_anon"),
+    };
+
+    self
+      .mc
+      .compile_fn(p.clone(), Functionish::Fn(fn_ident, function));
+
+    (key_value, Value::Pointer(p))
+  }
+
   pub fn prop_name(&mut self, prop_name: &swc_ecma_ast::PropName) -> Value {
     match prop_name {
       swc_ecma_ast::PropName::Ident(ident) => Value::String(ident.sym.to_string()),
@@ -276,9 +340,25 @@ impl<'a> StaticExpressionCompiler<'a> {
       swc_ecma_ast::PropName::BigInt(bi) => Value::String(bi.value.to_string()),
     }
   }
+
+  /// Defines a value that's computed when it's first used, for static values that can't be written
+  /// out directly (like those containing spreads).
+  fn lazy(&mut self, body: Vec<Instruction>) -> Value {
+    let pointer = self.mc.allocate_defn_numbered("_lazy");
+
+    self.mc.module.definitions.push(Definition {
+      pointer: pointer.clone(),
+      content: DefinitionContent::Lazy(Lazy {
+        body: body.into_iter().map(FnLine::Instruction).collect(),
+      }),
+    });
+
+    Value::Pointer(pointer)
+  }
 }
 
-fn as_symbol_iterator(expr: &swc_ecma_ast::Expr) -> Option<Value> {
+/// Recognizes `Symbol.iterator` and `Symbol.asyncIterator`.
+fn as_well_known_symbol(expr: &swc_ecma_ast::Expr) -> Option<Value> {
   let member_expr = match expr {
     swc_ecma_ast::Expr::Member(member_expr) => member_expr,
     _ => return None,
@@ -293,16 +373,69 @@ fn as_symbol_iterator(expr: &swc_ecma_ast::Expr) -> Option<Value> {
     _ => return None,
   };
 
-  match &member_expr.prop {
-    swc_ecma_ast::MemberProp::Ident(ident) => {
-      if ident.sym.to_string() != "iterator" {
-        return None;
-      }
-    }
+  let name = match &member_expr.prop {
+    swc_ecma_ast::MemberProp::Ident(ident) => match ident.sym.as_ref() {
+      "iterator" => "SymbolIterator",
+      "asyncIterator" => "SymbolAsyncIterator",
+      _ => return None,
+    },
     _ => return None,
-  }
+  };
 
   Some(Value::Builtin(Builtin {
-    name: "SymbolIterator".to_string(),
+    name: name.to_string(),
   }))
 }
+
+/// Whether `expr` can be evaluated at compile time by `StaticExpressionCompiler`.
+pub fn is_static_expr(expr: &swc_ecma_ast::Expr) -> bool {
+  use swc_ecma_ast::Expr;
+
+  if as_well_known_symbol(expr).is_some() {
+    return true;
+  }
+
+  match expr {
+    Expr::Lit(_) | Expr::Ident(_) | Expr::Fn(_) | Expr::Arrow(_) | Expr::Class(_) => true,
+    Expr::Array(array) => array.elems.iter().all(|item| match item {
+      Some(item) => is_static_expr(&item.expr),
+      None => true,
+    }),
+    Expr::Object(object) => object.props.iter().all(|prop| match prop {
+      swc_ecma_ast::PropOrSpread::Spread(spread) => is_static_expr(&spread.expr),
+      swc_ecma_ast::PropOrSpread::Prop(prop) => match &**prop {
+        swc_ecma_ast::Prop::KeyValue(kv) => {
+          is_static_prop_name(&kv.key) && is_static_expr(&kv.value)
+        }
+        swc_ecma_ast::Prop::Getter(getter) => is_static_prop_name(&getter.key),
+        swc_ecma_ast::Prop::Setter(setter) => is_static_prop_name(&setter.key),
+        swc_ecma_ast::Prop::Method(method) => is_static_prop_name(&method.key),
+        swc_ecma_ast::Prop::Shorthand(_) | swc_ecma_ast::Prop::Assign(_) => false,
+      },
+    }),
+    Expr::Unary(unary) => match unary.op {
+      swc_ecma_ast::UnaryOp::Minus | swc_ecma_ast::UnaryOp::Plus | swc_ecma_ast::UnaryOp::Tilde => {
+        matches!(
+          &*unary.arg,
+          Expr::Lit(swc_ecma_ast::Lit::Num(_) | swc_ecma_ast::Lit::BigInt(_))
+        )
+      }
+      _ => false,
+    },
+    Expr::Seq(seq) => seq.exprs.iter().all(|expr| is_static_expr(expr)),
+    Expr::Tpl(tpl) => tpl.exprs.is_empty(),
+    Expr::Paren(paren) => is_static_expr(&paren.expr),
+    Expr::TsTypeAssertion(tta) => is_static_expr(&tta.expr),
+    Expr::TsConstAssertion(tca) => is_static_expr(&tca.expr),
+    Expr::TsNonNull(tnn) => is_static_expr(&tnn.expr),
+    Expr::TsAs(ta) => is_static_expr(&ta.expr),
+    _ => false,
+  }
+}
+
+pub fn is_static_prop_name(prop_name: &swc_ecma_ast::PropName) -> bool {
+  match prop_name {
+    swc_ecma_ast::PropName::Computed(computed) => is_static_expr(&computed.expr),
+    _ => true,
+  }
+}
@@ -5,6 +5,7 @@ use crate::{
   diagnostic::DiagnosticReporter,
   expression_compiler::{CompiledExpression, ExpressionCompiler},
   ident::Ident as CrateIdent,
+  scope_analysis::Name,
 };
 use swc_common::Spanned;
 
@@ -25,6 +26,10 @@ impl TargetAccessor {
 
     return match expr {
       Ident(ident) => match ec.fnc.lookup(&crate::ident::Ident::from_swc_ident(ident)) {
+        Some(Name {
+          value: Value::Pointer(p),
+          ..
+        }) if ec.fnc.class_being_initialized.as_ref() == Some(p) => true,
         Some(name) => !name.effectively_const,
         _ => false, // TODO: InternalError?
       },
@@ -60,6 +65,17 @@ impl TargetAccessor {
       Ident(ident) => TargetAccessor::compile_ident(ec, &CrateIdent::from_swc_ident(ident)),
       This(this) => TargetAccessor::compile_ident(ec, &CrateIdent::this(this.span)),
       Member(member) => {
+        if let swc_ecma_ast::MemberProp::PrivateName(private_name) = &member.prop {
+          if let Some(private_scope) = ec.fnc.mc.lookup_private_name(private_name) {
+            if private_scope.methods.contains(&*private_name.id.sym) {
+              ec.error(
+                private_name.span,
+                &format!("Cannot assign to private method #{}", private_name.id.sym),
+              );
+            }
+          }
+        }
+
         let obj = TargetAccessor::compile(ec, &member.obj, false);
         let subscript = ec.member_prop(&member.prop, None);
 
@@ -85,10 +101,8 @@ impl TargetAccessor {
       TsNonNull(e) => TargetAccessor::compile(ec, &e.expr, false),
       TsAs(e) => TargetAccessor::compile(ec, &e.expr, false),
 
-      SuperProp(super_prop) => {
-        ec.fnc.todo(super_prop.span, "SuperProp expressions");
-        TargetAccessor::make_todo(ec)
-      }
+      // Scope analysis reports that assigning to super properties isn't supported
+      SuperProp(_) => TargetAccessor::make_bad(ec),
       _ => {
         ec.error(
           expr.span(),
@@ -108,10 +122,6 @@ impl TargetAccessor {
     TargetAccessor::Register(ec.fnc.allocate_numbered_reg("_bad_lvalue"))
   }
 
-  pub fn make_todo(ec: &mut ExpressionCompiler) -> TargetAccessor {
-    TargetAccessor::Register(ec.fnc.allocate_numbered_reg("_todo_lvalue"))
-  }
-
   pub fn assign_and_packup(
     &mut self,
     ec: &mut ExpressionCompiler,
@@ -82,7 +82,7 @@ where
   }
 
   fn object(&mut self, owner: Option<&Pointer>, object: &mut Object) {
-    for (key, value) in object.properties.iter_mut() {
+    for (key, value) in object.kvs_mut() {
       self.value(owner, key);
       self.value(owner, value);
     }
@@ -101,7 +101,7 @@ where
 
     match value {
       Void | Undefined | Null | Bool(_) | Number(_) | BigInt(_) | String(_) | Register(_)
-      | Builtin(_) => {}
+      | Builtin(_) | RegExp(_) | PrivateName(_) => {}
       Array(array) => {
         self.array(owner, array);
       }
@@ -113,6 +113,7 @@ where
         self.value(owner, &mut class.constructor);
         self.value(owner, &mut class.prototype);
         self.value(owner, &mut class.static_);
+        self.value(owner, &mut class.extends);
       }
       Pointer(pointer) => {
         self.pointer(owner, pointer);
@@ -137,6 +138,7 @@ where
       | OpNot(arg, _)
       | OpBitNot(arg, _)
       | TypeOf(arg, _)
+      | ForInKeys(arg, _)
       | UnaryPlus(arg, _)
       | UnaryMinus(arg, _)
       | Import(arg, _)
@@ -144,7 +146,9 @@ where
       | Throw(arg)
       | Cat(arg, _)
       | Yield(arg, _)
-      | YieldStar(arg, _) => {
+      | YieldStar(arg, _)
+      | Await(arg, _)
+      | ObjectSpread(arg, _) => {
         self.value(Some(owner), arg);
       }
       OpPlus(arg1, arg2, _)
@@ -173,6 +177,7 @@ where
       | OpRightShiftUnsigned(arg1, arg2, _)
       | InstanceOf(arg1, arg2, _)
       | In(arg1, arg2, _)
+      | ObjectRest(arg1, arg2, _)
       | Call(arg1, arg2, _)
       | Bind(arg1, arg2, _)
       | Sub(arg1, arg2, _)
@@ -219,7 +224,11 @@ where
         FnLine::Instruction(instruction) => {
           self.instruction(owner, instruction);
         }
-        FnLine::Label(..) | FnLine::Empty | FnLine::Comment(..) | FnLine::Release(..) => {}
+        FnLine::Label(..)
+        | FnLine::Empty
+        | FnLine::Comment(..)
+        | FnLine::Release(..)
+        | FnLine::Position(..) => {}
       }
     }
   }
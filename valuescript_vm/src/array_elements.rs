@@ -0,0 +1,244 @@
+use std::ops::{Index, IndexMut};
+
+use radix_tree::{StrictRadixTree, StrictRadixTreeIterator};
+
+use crate::vs_value::Val;
+
+pub const ARRAY_TREE_BRANCHING: usize = 32;
+
+pub type ArrayTree<T = Val> = StrictRadixTree<T, ARRAY_TREE_BRANCHING>;
+
+/// The elements of an array (or the entries of a large `Map`, see `VsMap`).
+///
+/// Small arrays are a plain `Vec`. Past `SMALL_MAX_LEN` elements they become a persistent radix
+/// tree, so that updating a shared array (which makes a copy, since arrays are values) only copies
+/// the path to the updated element instead of the whole array.
+#[derive(Clone, Debug)]
+pub enum ArrayElements<T: Clone = Val> {
+  Small(Vec<T>),
+  Tree(ArrayTree<T>),
+}
+
+impl<T: Clone> ArrayElements<T> {
+  /// Arrays become trees when they grow past this length, and only go back to being small when they
+  /// shrink to half of it, so that pushing and popping around the boundary doesn't convert back
+  /// and forth.
+  pub const SMALL_MAX_LEN: usize = 2 * ARRAY_TREE_BRANCHING;
+
+  pub fn new() -> Self {
+    ArrayElements::Small(Vec::new())
+  }
+
+  pub fn len(&self) -> usize {
+    match self {
+      ArrayElements::Small(vec) => vec.len(),
+      ArrayElements::Tree(tree) => tree.len(),
+    }
+  }
+
+  pub fn is_empty(&self) -> bool {
+    match self {
+      ArrayElements::Small(vec) => vec.is_empty(),
+      ArrayElements::Tree(tree) => tree.is_empty(),
+    }
+  }
+
+  pub fn get(&self, i: usize) -> Option<&T> {
+    match self {
+      ArrayElements::Small(vec) => vec.get(i),
+      ArrayElements::Tree(tree) => tree.get(i),
+    }
+  }
+
+  pub fn get_mut(&mut self, i: usize) -> Option<&mut T> {
+    match self {
+      ArrayElements::Small(vec) => vec.get_mut(i),
+      ArrayElements::Tree(tree) => tree.get_mut(i),
+    }
+  }
+
+  pub fn first(&self) -> Option<&T> {
+    match self {
+      ArrayElements::Small(vec) => vec.first(),
+      ArrayElements::Tree(tree) => tree.first(),
+    }
+  }
+
+  pub fn last(&self) -> Option<&T> {
+    match self {
+      ArrayElements::Small(vec) => vec.last(),
+      ArrayElements::Tree(tree) => tree.last(),
+    }
+  }
+
+  pub fn push(&mut self, value: T) {
+    match self {
+      ArrayElements::Small(vec) => {
+        vec.push(value);
+        self.fit();
+      }
+      ArrayElements::Tree(tree) => tree.push(value),
+    }
+  }
+
+  pub fn pop(&mut self) -> Option<T> {
+    let res = match self {
+      ArrayElements::Small(vec) => vec.pop(),
+      ArrayElements::Tree(tree) => tree.pop(),
+    };
+
+    self.fit();
+
+    res
+  }
+
+  pub fn truncate(&mut self, len: usize) {
+    match self {
+      ArrayElements::Small(vec) => vec.truncate(len),
+      ArrayElements::Tree(tree) => tree.truncate(len),
+    }
+
+    self.fit();
+  }
+
+  /// Truncates the elements, or extends them with clones of `value`.
+  pub fn resize(&mut self, len: usize, value: T) {
+    match self {
+      ArrayElements::Small(vec) => vec.resize(len, value),
+      ArrayElements::Tree(tree) => tree.resize(len, value),
+    }
+
+    self.fit();
+  }
+
+  /// Inserts `value` at `i`, shifting the elements after it. This is O(n) for trees too.
+  pub fn insert(&mut self, i: usize, value: T) {
+    self.splice(i, 0, vec![value]);
+  }
+
+  /// Removes the element at `i`, shifting the elements after it. This is O(n) for trees too.
+  pub fn remove(&mut self, i: usize) -> T {
+    self.splice(i, 1, vec![]).pop().expect("Out of bounds")
+  }
+
+  /// Replaces `delete_count` elements at `start` with `items`, returning the removed elements.
+  pub fn splice(&mut self, start: usize, delete_count: usize, items: Vec<T>) -> Vec<T> {
+    let mut vec = self.take_vec();
+    let deleted = vec.splice(start..(start + delete_count), items).collect();
+    *self = vec.into();
+
+    deleted
+  }
+
+  pub fn iter(&self) -> ArrayElementsIter<'_, T> {
+    match self {
+      ArrayElements::Small(vec) => ArrayElementsIter::Small(vec.iter()),
+      ArrayElements::Tree(tree) => ArrayElementsIter::Tree(tree.iter()),
+    }
+  }
+
+  pub fn to_vec(&self) -> Vec<T> {
+    match self {
+      ArrayElements::Small(vec) => vec.clone(),
+      ArrayElements::Tree(tree) => tree.to_vec(),
+    }
+  }
+
+  /// Moves the elements out into a `Vec`, leaving this empty.
+  pub fn take_vec(&mut self) -> Vec<T> {
+    match std::mem::take(self) {
+      ArrayElements::Small(vec) => vec,
+      ArrayElements::Tree(tree) => tree.to_vec(),
+    }
+  }
+
+  /// Switches representation if the length calls for it.
+  fn fit(&mut self) {
+    match self {
+      ArrayElements::Small(vec) if vec.len() > Self::SMALL_MAX_LEN => {
+        *self = ArrayElements::Tree(std::mem::take(vec).into());
+      }
+      ArrayElements::Tree(tree) if tree.len() <= Self::SMALL_MAX_LEN / 2 => {
+        *self = ArrayElements::Small(tree.to_vec());
+      }
+      _ => {}
+    }
+  }
+}
+
+impl<T: Clone> Default for ArrayElements<T> {
+  fn default() -> Self {
+    ArrayElements::new()
+  }
+}
+
+impl<T: Clone> From<Vec<T>> for ArrayElements<T> {
+  fn from(vec: Vec<T>) -> Self {
+    let mut elements = ArrayElements::Small(vec);
+    elements.fit();
+
+    elements
+  }
+}
+
+impl<T: Clone> FromIterator<T> for ArrayElements<T> {
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    iter.into_iter().collect::<Vec<T>>().into()
+  }
+}
+
+impl<T: Clone> Extend<T> for ArrayElements<T> {
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    match self {
+      ArrayElements::Small(vec) => vec.extend(iter),
+      ArrayElements::Tree(tree) => tree.extend(iter),
+    }
+
+    self.fit();
+  }
+}
+
+impl<T: Clone> Index<usize> for ArrayElements<T> {
+  type Output = T;
+
+  fn index(&self, i: usize) -> &T {
+    match self {
+      ArrayElements::Small(vec) => &vec[i],
+      ArrayElements::Tree(tree) => &tree[i],
+    }
+  }
+}
+
+impl<T: Clone> IndexMut<usize> for ArrayElements<T> {
+  fn index_mut(&mut self, i: usize) -> &mut T {
+    match self {
+      ArrayElements::Small(vec) => &mut vec[i],
+      ArrayElements::Tree(tree) => &mut tree[i],
+    }
+  }
+}
+
+impl<'a, T: Clone> IntoIterator for &'a ArrayElements<T> {
+  type Item = &'a T;
+  type IntoIter = ArrayElementsIter<'a, T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+pub enum ArrayElementsIter<'a, T> {
+  Small(std::slice::Iter<'a, T>),
+  Tree(StrictRadixTreeIterator<'a, T, ARRAY_TREE_BRANCHING>),
+}
+
+impl<'a, T: Clone> Iterator for ArrayElementsIter<'a, T> {
+  type Item = &'a T;
+
+  fn next(&mut self) -> Option<&'a T> {
+    match self {
+      ArrayElementsIter::Small(iter) => iter.next(),
+      ArrayElementsIter::Tree(iter) => iter.next(),
+    }
+  }
+}
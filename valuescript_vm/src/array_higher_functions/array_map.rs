@@ -20,6 +20,11 @@ impl ArrayMappingState for MapState {
     None
   }
 
+  /// Holes stay holes, so the result has the same length.
+  fn skip_hole(&mut self, _i: usize) {
+    self.map_results.push(Val::Void);
+  }
+
   fn finish(&mut self) -> Val {
     let mut map_results = Vec::new();
     std::mem::swap(&mut self.map_results, &mut map_results);
@@ -10,6 +10,10 @@ use crate::vs_value::{LoadFunctionResult, Val, ValTrait};
 
 pub trait ArrayMappingState {
   fn process(&mut self, i: usize, element: &Val, mapped: Val) -> Option<Val>;
+
+  /// Called instead of `process` for holes, which aren't passed to the mapper.
+  fn skip_hole(&mut self, _i: usize) {}
+
   fn finish(&mut self) -> Val;
   fn clone_to_array_mapping_state(&self) -> Box<dyn ArrayMappingState>;
 }
@@ -87,7 +91,10 @@ impl StackFrameTrait for ArrayMappingFrame {
 
     match array_data.elements.get(array_i) {
       Some(el) => match el {
-        Val::Void => Ok(FrameStepOk::Continue),
+        Val::Void => {
+          self.state.skip_hole(array_i);
+          Ok(FrameStepOk::Continue)
+        }
         _ => match self.mapper.load_function() {
           LoadFunctionResult::NotAFunction => Err("map fn is not a function".to_type_error()),
           LoadFunctionResult::NativeFunction(native_fn) => {
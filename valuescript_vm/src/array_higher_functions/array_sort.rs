@@ -230,7 +230,9 @@ impl StackFrameTrait for SortFrame {
         Val::Void => {
           let array_data_mut = Rc::make_mut(array_data);
 
-          array_data_mut.elements.sort_by_key(|a| a.to_string());
+          let mut elements = array_data_mut.elements.take_vec();
+          elements.sort_by_key(|a| a.to_string());
+          array_data_mut.elements = elements.into();
 
           return Ok(FrameStepOk::Pop(CallResult {
             return_: Val::Array(array_data.clone()),
@@ -238,10 +240,12 @@ impl StackFrameTrait for SortFrame {
           }));
         }
         _ => {
+          let elements = array_data.elements.to_vec();
+
           self.tree = SortTreeNode::new(VecSlice {
-            vec: &array_data.elements,
+            vec: &elements,
             start: 0,
-            end: array_data.elements.len(),
+            end: elements.len(),
           });
 
           self.started = true;
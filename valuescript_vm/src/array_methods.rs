@@ -1,4 +1,4 @@
-use std::cmp::{max, min};
+use std::cmp::min;
 use std::mem::take;
 use std::rc::Rc;
 
@@ -24,12 +24,10 @@ pub fn op_sub_array(array: &mut Rc<VsArray>, key: &Val) -> Result<Val, Val> {
   }
 
   if let Val::Symbol(symbol) = key {
-    return Ok(
-      match symbol {
-        VsSymbol::ITERATOR => &VALUES,
-      }
-      .to_val(),
-    );
+    return Ok(match symbol {
+      VsSymbol::ITERATOR => VALUES.to_val(),
+      VsSymbol::GENERATOR_RETURN | VsSymbol::ASYNC_ITERATOR => Val::Undefined,
+    });
   }
 
   Ok(Val::Static(match key.to_string().as_str() {
@@ -54,6 +52,12 @@ pub fn op_sub_array(array: &mut Rc<VsArray>, key: &Val) -> Result<Val, Val> {
     "length" => return Ok((array.elements.len() as f64).to_val()),
     "map" => &MAP,
     "pop" => &POP,
+    "raw" => {
+      return Ok(match &array.raw {
+        Some(raw) => Val::Array(raw.clone()),
+        None => Val::Undefined,
+      })
+    }
     "push" => &PUSH,
     "reduce" => &REDUCE,
     "reduceRight" => &REDUCE_RIGHT,
@@ -100,7 +104,10 @@ static AT: NativeFunction = native_fn(|this, params| {
 static CONCAT: NativeFunction = native_fn(|this, params| {
   Ok(match this.get() {
     Val::Array(array_data) => {
-      let mut new_array = array_data.as_ref().clone();
+      let mut new_array = VsArray {
+        elements: array_data.elements.clone(),
+        raw: None,
+      };
 
       for p in params {
         match &p.as_array_data() {
@@ -260,12 +267,9 @@ static INCLUDES: NativeFunction = native_fn(|this, params| {
     Val::Array(array_data) => {
       let search_param = params.first().unwrap_or(&Val::Undefined);
 
+      // Holes are found as undefined, unlike in `indexOf`
       for elem in &array_data.elements {
-        let is_eq = op_triple_eq_impl(elem, search_param)
-          .map_err(|e| e.to_string())
-          .unwrap(); // TODO: Exception
-
-        if is_eq {
+        if op_triple_eq_impl(elem, search_param)? {
           return Ok(Val::Bool(true));
         }
       }
@@ -282,11 +286,10 @@ static INDEX_OF: NativeFunction = native_fn(|this, params| {
       let search_param = params.first().unwrap_or(&Val::Undefined);
 
       for i in 0..array_data.elements.len() {
-        let is_eq = op_triple_eq_impl(&array_data.elements[i], search_param)
-          .map_err(|e| e.to_string())
-          .unwrap(); // TODO: Exception
+        let elem = &array_data.elements[i];
 
-        if is_eq {
+        // Holes are skipped, so they aren't found as undefined
+        if !matches!(elem, Val::Void) && op_triple_eq_impl(elem, search_param)? {
           return Ok(Val::Number(i as f64));
         }
       }
@@ -339,11 +342,9 @@ static LAST_INDEX_OF: NativeFunction = native_fn(|this, params| {
       let search_param = params.first().unwrap_or(&Val::Undefined);
 
       for i in (0..array_data.elements.len()).rev() {
-        let is_eq = op_triple_eq_impl(&array_data.elements[i], search_param)
-          .map_err(|e| e.to_string())
-          .unwrap(); // TODO: Exception
+        let elem = &array_data.elements[i];
 
-        if is_eq {
+        if !matches!(elem, Val::Void) && op_triple_eq_impl(elem, search_param)? {
           return Ok(Val::Number(i as f64));
         }
       }
@@ -378,13 +379,13 @@ static POP: NativeFunction = native_fn(|mut this, _params| {
   })
 });
 
-static PUSH: NativeFunction = native_fn(|mut this, mut params| {
+static PUSH: NativeFunction = native_fn(|mut this, params| {
   let this = this.get_mut()?;
 
   Ok(match this {
     Val::Array(array_data) => {
       let array_data_mut = Rc::make_mut(array_data);
-      array_data_mut.elements.append(&mut params);
+      array_data_mut.elements.extend(params);
       (array_data_mut.elements.len() as f64).to_val()
     }
     _ => return Err("array indirection".to_internal_error()),
@@ -486,49 +487,12 @@ static SPLICE: NativeFunction = native_fn(|mut this, params| {
         false => min(delete_count_f64.floor() as usize, len - start),
       };
 
-      let mut deleted_elements = Vec::<Val>::new();
-
-      for i in 0..delete_count {
-        deleted_elements.push(array_data_mut.elements[start + i].clone());
-      }
-
-      let insert_len = max(2, params.len()) - 2;
-      let replace_len = min(insert_len, delete_count);
-
-      if insert_len > replace_len {
-        array_data_mut.elements[start..(replace_len + start)]
-          .clone_from_slice(&params[2..(replace_len + 2)]);
-
-        let gap = insert_len - replace_len;
-
-        for _ in 0..gap {
-          array_data_mut.elements.push(Val::Void);
-        }
-
-        for i in ((start + replace_len)..len).rev() {
-          array_data_mut.elements[i + gap] = array_data_mut.elements[i].clone();
-        }
-
-        array_data_mut.elements[(replace_len + start)..(insert_len + start)]
-          .clone_from_slice(&params[(replace_len + 2)..(insert_len + 2)]);
-      } else {
-        array_data_mut.elements[start..(insert_len + start)]
-          .clone_from_slice(&params[2..(insert_len + 2)]);
-
-        let gap = delete_count - insert_len;
-
-        if gap != 0 {
-          for i in (start + insert_len)..(len - gap) {
-            array_data_mut.elements[i] = array_data_mut.elements[i + gap].clone();
-          }
+      let items = params.into_iter().skip(2).collect();
 
-          for _ in 0..gap {
-            array_data_mut.elements.pop();
-          }
-        }
-      }
-
-      deleted_elements.to_val()
+      array_data_mut
+        .elements
+        .splice(start, delete_count, items)
+        .to_val()
     }
     _ => return Err("array indirection".to_internal_error()),
   })
@@ -544,9 +508,7 @@ static UNSHIFT: NativeFunction = native_fn(|mut this, params| {
     Val::Array(array_data) => {
       let array_data_mut = Rc::make_mut(array_data);
 
-      for (i, p) in params.into_iter().enumerate() {
-        array_data_mut.elements.insert(i, p);
-      }
+      array_data_mut.elements.splice(0, 0, params);
 
       Val::Number(array_data_mut.elements.len() as f64)
     }
@@ -4,6 +4,7 @@ use crate::{
   native_function::{native_fn, NativeFunction, ThisWrapper},
   vs_array::VsArray,
   vs_class::VsClass,
+  vs_object::VsObject,
   vs_value::{LoadFunctionResult, ToVal, Val},
   ValTrait,
 };
@@ -34,7 +35,13 @@ impl BuiltinObject for ArrayBuiltin {
   }
 
   fn bo_as_class_data() -> Option<Rc<VsClass>> {
-    None
+    Some(Rc::new(VsClass {
+      name: "Array".to_string(),
+      content_hash: None,
+      constructor: CONSTRUCTOR.to_val(),
+      prototype: VsObject::default().to_val(),
+      static_: VsObject::default().to_val(),
+    }))
   }
 }
 
@@ -54,6 +61,20 @@ static IS_ARRAY: NativeFunction = native_fn(|_this, params| {
   })
 });
 
+/// Creates the strings array passed to a template tag from its cooked and raw strings. The compiler
+/// uses this for tagged templates, since the `raw` strings can't be assigned afterwards.
+pub static TEMPLATE_STRINGS: NativeFunction = native_fn(|_this, params| {
+  match (
+    params.first().and_then(|p| p.as_array_data()),
+    params.get(1).and_then(|p| p.as_array_data()),
+  ) {
+    (Some(cooked), Some(raw)) => {
+      Ok(VsArray::template_strings(cooked.elements.to_vec(), raw.elements.to_vec()).to_val())
+    }
+    _ => Err("Expected arrays of cooked and raw strings".to_type_error()),
+  }
+});
+
 static FROM: NativeFunction = native_fn(|_this, params| {
   let mut first_param = match params.first() {
     None => return Err("undefined is not iterable".to_type_error()),
@@ -69,8 +90,27 @@ static FROM: NativeFunction = native_fn(|_this, params| {
   }
 
   Ok(match first_param {
-    Val::Array(arr) => Val::Array(arr.clone()),
+    Val::Array(arr) => match arr.elements.iter().any(|el| matches!(el, Val::Void)) {
+      false => Val::Array(arr.clone()),
+
+      // Holes become undefined
+      true => arr
+        .elements
+        .iter()
+        .map(|el| match el {
+          Val::Void => Val::Undefined,
+          _ => el.clone(),
+        })
+        .collect::<Vec<Val>>()
+        .to_val(),
+    },
     Val::String(s) => s.chars().map(|c| c.to_val()).collect::<Vec<Val>>().to_val(),
+    Val::Map(map) => map
+      .iter()
+      .map(|(key, value)| vec![key.clone(), value.clone()].to_val())
+      .collect::<Vec<Val>>()
+      .to_val(),
+    Val::Set(set) => set.iter().cloned().collect::<Vec<Val>>().to_val(),
     Val::Void | Val::Undefined | Val::Null | Val::CopyCounter(..) => {
       return Err("items is not iterable".to_type_error())
     }
@@ -114,6 +154,13 @@ static FROM: NativeFunction = native_fn(|_this, params| {
   })
 });
 
+static CONSTRUCTOR: NativeFunction = native_fn(|mut this, params| {
+  // The instance is replaced entirely since arrays aren't ordinary objects
+  *this.get_mut()? = to_array(ThisWrapper::new(true, &mut Val::Undefined), params)?;
+
+  Ok(Val::Undefined)
+});
+
 static OF: NativeFunction = native_fn(|_this, params| Ok(VsArray::from(params).to_val()));
 
 fn to_array(_: ThisWrapper, params: Vec<Val>) -> Result<Val, Val> {
@@ -123,19 +170,10 @@ fn to_array(_: ThisWrapper, params: Vec<Val>) -> Result<Val, Val> {
 
   Ok(match params[0] {
     Val::Number(number) => {
-      if number.is_sign_negative() || number != number.floor() {
-        return Err("Invalid array length".to_range_error());
-      }
-
-      let len = number as usize;
-
-      let mut arr = Vec::with_capacity(len);
-
-      for _ in 0..len {
-        arr.push(Val::Void);
-      }
+      let mut arr = VsArray::default();
+      arr.set_length(VsArray::to_length(number)?);
 
-      VsArray::from(arr).to_val()
+      arr.to_val()
     }
     _ => VsArray::from(params).to_val(),
   })
@@ -1,7 +1,7 @@
-use std::collections::BTreeMap;
 use std::fmt;
 use std::rc::Rc;
 
+use crate::hybrid_map::HybridMap;
 use crate::native_function::{native_fn, ThisWrapper};
 use crate::vs_class::VsClass;
 use crate::vs_value::ToVal;
@@ -62,8 +62,11 @@ pub trait ToError {
 impl ToError for Val {
   fn to_error(self) -> Val {
     VsObject {
-      string_map: BTreeMap::from([("message".to_string(), self.to_val_string())]),
+      string_map: HybridMap::from([("message".to_string(), self.to_val_string())]),
       symbol_map: Default::default(),
+      getter_map: Default::default(),
+      setter_map: Default::default(),
+      private_map: Default::default(),
       prototype: make_error_prototype(),
     }
     .to_val()
@@ -85,11 +88,14 @@ impl ToError for &str {
 // TODO: Static? (Rc -> Arc?)
 fn make_error_prototype() -> Val {
   VsObject {
-    string_map: BTreeMap::from([
+    string_map: HybridMap::from([
       ("name".to_string(), "Error".to_val()),
       ("toString".to_string(), ERROR_TO_STRING.to_val()),
     ]),
     symbol_map: Default::default(),
+    getter_map: Default::default(),
+    setter_map: Default::default(),
+    private_map: Default::default(),
     prototype: Val::Void,
   }
   .to_val()
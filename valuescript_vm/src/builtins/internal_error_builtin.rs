@@ -1,6 +1,7 @@
 use std::fmt;
-use std::{collections::BTreeMap, rc::Rc};
+use std::rc::Rc;
 
+use crate::hybrid_map::HybridMap;
 use crate::native_function::{native_fn, ThisWrapper};
 use crate::vs_value::ToVal;
 use crate::ValTrait;
@@ -57,11 +58,14 @@ impl fmt::Display for InternalErrorBuiltin {
 // TODO: Static? (Rc -> Arc?)
 fn make_internal_error_prototype() -> Val {
   VsObject {
-    string_map: BTreeMap::from([
+    string_map: HybridMap::from([
       ("name".to_string(), "InternalError".to_val()),
       ("toString".to_string(), INTERNAL_ERROR_TO_STRING.to_val()),
     ]),
     symbol_map: Default::default(),
+    getter_map: Default::default(),
+    setter_map: Default::default(),
+    private_map: Default::default(),
     prototype: Val::Void,
   }
   .to_val()
@@ -102,8 +106,11 @@ impl ToInternalError for String {
 impl ToInternalError for Val {
   fn to_internal_error(self) -> Val {
     VsObject {
-      string_map: BTreeMap::from([("message".to_string(), self)]),
+      string_map: HybridMap::from([("message".to_string(), self)]),
       symbol_map: Default::default(),
+      getter_map: Default::default(),
+      setter_map: Default::default(),
+      private_map: Default::default(),
       prototype: make_internal_error_prototype(),
     }
     .to_val()
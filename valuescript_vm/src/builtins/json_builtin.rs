@@ -0,0 +1,613 @@
+use std::any::Any;
+use std::fmt;
+use std::rc::Rc;
+
+use crate::native_frame_function::{call, Call, NativeFrameFunction};
+use crate::number_methods::format_number;
+use crate::operations::{op_delete, op_sub_accessor, op_submov};
+use crate::own_properties::{own_properties, OwnProperty};
+use crate::stack_frame::{CallResult, FrameStepOk, FrameStepResult, StackFrame, StackFrameTrait};
+use crate::vs_class::VsClass;
+use crate::vs_object::VsObject;
+use crate::vs_value::{LoadFunctionResult, ToVal, Val, ValTrait, VsType};
+
+use super::builtin_object::BuiltinObject;
+use super::type_error_builtin::ToTypeError;
+
+pub struct JsonBuiltin {}
+
+impl BuiltinObject for JsonBuiltin {
+  fn bo_name() -> &'static str {
+    "JSON"
+  }
+
+  fn bo_sub(key: &str) -> Val {
+    match key {
+      "parse" => PARSE.to_val(),
+      "stringify" => STRINGIFY.to_val(),
+      _ => Val::Undefined,
+    }
+  }
+
+  fn bo_load_function() -> LoadFunctionResult {
+    LoadFunctionResult::NotAFunction
+  }
+
+  fn bo_as_class_data() -> Option<Rc<VsClass>> {
+    None
+  }
+}
+
+impl fmt::Display for JsonBuiltin {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "[object JSON]")
+  }
+}
+
+static PARSE: NativeFrameFunction = NativeFrameFunction {
+  make_frame: || Box::<ParseFrame>::default(),
+};
+
+static STRINGIFY: NativeFrameFunction = NativeFrameFunction {
+  make_frame: || Box::<StringifyFrame>::default(),
+};
+
+/// Implements `JSON.parse`. This needs to be a frame because the reviver is called for every value,
+/// innermost values first.
+#[derive(Clone, Default)]
+struct ParseFrame {
+  text: Val,
+  reviver: Val,
+  param_i: usize,
+
+  /// The values being revived, starting with a wrapper object holding the parsed value at `""`.
+  /// `None` until the text has been parsed.
+  stack: Option<Vec<ReviveNode>>,
+  error: Option<Val>,
+}
+
+#[derive(Clone)]
+struct ReviveNode {
+  key: String,
+  value: Val,
+  keys: Vec<String>,
+  key_i: usize,
+}
+
+impl ReviveNode {
+  fn new(key: String, value: Val) -> Result<ReviveNode, Val> {
+    let keys = match value.typeof_() {
+      VsType::Array | VsType::Object => own_properties(&value, false)?
+        .into_iter()
+        .map(|(key, _)| key.to_string())
+        .collect(),
+      _ => vec![],
+    };
+
+    Ok(ReviveNode {
+      key,
+      value,
+      keys,
+      key_i: 0,
+    })
+  }
+}
+
+impl StackFrameTrait for ParseFrame {
+  fn write_this(&mut self, _const: bool, _this: Val) -> Result<(), Val> {
+    Ok(())
+  }
+
+  fn write_param(&mut self, param: Val) {
+    match self.param_i {
+      0 => self.text = param,
+      1 => self.reviver = param,
+      _ => {}
+    };
+
+    self.param_i += 1;
+  }
+
+  fn step(&mut self) -> FrameStepResult {
+    if let Some(error) = self.error.take() {
+      return Err(error);
+    }
+
+    let stack = match &mut self.stack {
+      Some(stack) => stack,
+      None => {
+        let value = parse(&self.text.to_string())?;
+
+        if !is_function(&self.reviver) {
+          return Ok(pop(value));
+        }
+
+        let wrapper = VsObject {
+          string_map: [(String::new(), value)].into(),
+          ..Default::default()
+        }
+        .to_val();
+
+        self
+          .stack
+          .insert(vec![ReviveNode::new(String::new(), wrapper)?])
+      }
+    };
+
+    let node = stack.last().expect("Revive stack should not be empty");
+
+    if let Some(key) = node.keys.get(node.key_i) {
+      let child = ReviveNode::new(key.clone(), node.value.sub(&key.clone().to_val())?)?;
+      stack.push(child);
+
+      return Ok(FrameStepOk::Continue);
+    }
+
+    if stack.len() == 1 {
+      return Ok(pop(node.value.sub(&"".to_val())?));
+    }
+
+    let holder = stack[stack.len() - 2].value.clone();
+    let args = vec![node.key.clone().to_val(), node.value.clone()];
+
+    match call(&self.reviver, holder, args)? {
+      Call::Native(result) => {
+        self.apply_call_result(CallResult {
+          return_: result,
+          this: Val::Undefined,
+        });
+
+        Ok(FrameStepOk::Continue)
+      }
+      Call::Frame(frame) => Ok(FrameStepOk::Push(frame)),
+    }
+  }
+
+  fn apply_call_result(&mut self, call_result: CallResult) {
+    let stack = self
+      .stack
+      .as_mut()
+      .expect("Call result without revive stack");
+    let node = stack.pop().expect("Call result without revive node");
+    let parent = stack.last_mut().expect("Call result for wrapper");
+
+    let key = node.key.to_val();
+
+    let result = match call_result.return_ {
+      Val::Undefined => op_delete(&mut parent.value, &key),
+      value => op_submov(&mut parent.value, &key, value),
+    };
+
+    if let Err(error) = result {
+      self.error = Some(error);
+    }
+
+    parent.key_i += 1;
+  }
+
+  fn get_call_result(&mut self) -> CallResult {
+    panic!("Not appropriate for ParseFrame")
+  }
+
+  fn can_catch_exception(&self, _exception: &Val) -> bool {
+    false
+  }
+
+  fn catch_exception(&mut self, _exception: &mut Val) {}
+
+  fn clone_to_stack_frame(&self) -> StackFrame {
+    Box::new(self.clone())
+  }
+
+  fn as_any(&self) -> &dyn Any {
+    self
+  }
+
+  fn as_any_mut(&mut self) -> &mut dyn Any {
+    self
+  }
+}
+
+/// Implements `JSON.stringify`. This needs to be a frame because `toJSON` methods and the replacer
+/// can be called for every property.
+#[derive(Clone, Default)]
+struct StringifyFrame {
+  value: Val,
+  replacer: Val,
+  space: Val,
+  param_i: usize,
+
+  started: bool,
+  wrapper: Val,
+  property_list: Option<Vec<String>>,
+  gap: String,
+
+  /// The arrays and objects being serialized, outermost first.
+  stack: Vec<StringifyNode>,
+
+  /// The property currently being prepared for serialization.
+  property: Option<Property>,
+}
+
+#[derive(Clone)]
+struct StringifyNode {
+  key: String,
+  value: Val,
+  is_array: bool,
+  entries: Vec<(String, OwnProperty)>,
+  entry_i: usize,
+  parts: Vec<String>,
+}
+
+#[derive(Clone)]
+struct Property {
+  key: String,
+  value: Val,
+  stage: PropertyStage,
+}
+
+#[derive(Clone)]
+enum PropertyStage {
+  /// Calling the getter of an accessor property to get its value.
+  Get {
+    getter: Val,
+    this: Val,
+  },
+
+  ToJson,
+  Replacer,
+  Serialize,
+}
+
+impl StringifyFrame {
+  fn start(&mut self) {
+    self.started = true;
+
+    self.gap = match &self.space {
+      Val::Number(n) => " ".repeat(n.clamp(0.0, 10.0) as usize),
+      Val::String(s) => s.chars().take(10).collect(),
+      _ => String::new(),
+    };
+
+    if !is_function(&self.replacer) {
+      if let Some(replacer) = self.replacer.as_array_data() {
+        let mut property_list = Vec::<String>::new();
+
+        for key in &replacer.elements {
+          let key = match key {
+            Val::String(s) => s.to_string(),
+            Val::Number(_) => key.to_string(),
+            _ => continue,
+          };
+
+          if !property_list.contains(&key) {
+            property_list.push(key);
+          }
+        }
+
+        self.property_list = Some(property_list);
+      }
+    }
+
+    self.wrapper = VsObject {
+      string_map: [(String::new(), self.value.clone())].into(),
+      ..Default::default()
+    }
+    .to_val();
+
+    self.property = Some(Property {
+      key: String::new(),
+      value: self.value.clone(),
+      stage: PropertyStage::ToJson,
+    });
+  }
+
+  fn call(&mut self, function: &Val, this: Val, args: Vec<Val>) -> FrameStepResult {
+    match call(function, this, args)? {
+      Call::Native(result) => {
+        self.apply_call_result(CallResult {
+          return_: result,
+          this: Val::Undefined,
+        });
+
+        Ok(FrameStepOk::Continue)
+      }
+      Call::Frame(frame) => Ok(FrameStepOk::Push(frame)),
+    }
+  }
+
+  fn serialize(&mut self, property: Property) -> FrameStepResult {
+    let Property { key, value, .. } = property;
+    let value = value.not_ptr();
+
+    let json = match value.typeof_() {
+      VsType::Undefined | VsType::Symbol => None,
+      VsType::Null => Some("null".to_string()),
+      VsType::Bool => Some(value.to_string()),
+      VsType::Number => Some(match value.to_number() {
+        n if n.is_finite() => format_number(n),
+        _ => "null".to_string(),
+      }),
+      VsType::String => Some(quote(&value.to_string())),
+      VsType::BigInt => return Err("Do not know how to serialize a BigInt".to_type_error()),
+      VsType::Function | VsType::Class => {
+        return Err("Do not know how to serialize a function".to_type_error())
+      }
+      VsType::Array => {
+        let entries = match value.as_array_data() {
+          Some(array) => array
+            .elements
+            .iter()
+            .enumerate()
+            .map(|(i, element)| match element {
+              Val::Void => (i.to_string(), OwnProperty::Value(Val::Undefined)),
+              _ => (i.to_string(), OwnProperty::Value(element.clone())),
+            })
+            .collect(),
+          None => vec![],
+        };
+
+        self.push_node(key, value, true, entries);
+        return Ok(FrameStepOk::Continue);
+      }
+      VsType::Object => {
+        let entries = match &self.property_list {
+          Some(property_list) => property_list
+            .iter()
+            .map(|k| {
+              let key = k.clone().to_val();
+
+              let property = match op_sub_accessor(&value, &key) {
+                Some((getter, _)) => OwnProperty::Getter {
+                  getter,
+                  this: value.clone(),
+                },
+                None => OwnProperty::Value(value.sub(&key)?),
+              };
+
+              Ok((k.clone(), property))
+            })
+            .collect::<Result<Vec<_>, Val>>()?,
+          None => own_properties(&value, false)?
+            .into_iter()
+            .map(|(key, property)| (key.to_string(), property))
+            .collect(),
+        };
+
+        self.push_node(key, value, false, entries);
+        return Ok(FrameStepOk::Continue);
+      }
+    };
+
+    Ok(self.emit(key, json))
+  }
+
+  fn push_node(
+    &mut self,
+    key: String,
+    value: Val,
+    is_array: bool,
+    entries: Vec<(String, OwnProperty)>,
+  ) {
+    self.stack.push(StringifyNode {
+      key,
+      value,
+      is_array,
+      entries,
+      entry_i: 0,
+      parts: vec![],
+    });
+  }
+
+  fn next_property(&mut self) -> FrameStepResult {
+    let node = self
+      .stack
+      .last_mut()
+      .expect("Stringify stack should not be empty");
+
+    if let Some((key, property)) = node.entries.get(node.entry_i) {
+      let (value, stage) = match property {
+        OwnProperty::Value(value) => (value.clone(), PropertyStage::ToJson),
+        OwnProperty::Getter { getter, this } => (
+          Val::Undefined,
+          PropertyStage::Get {
+            getter: getter.clone(),
+            this: this.clone(),
+          },
+        ),
+      };
+
+      self.property = Some(Property {
+        key: key.clone(),
+        value,
+        stage,
+      });
+
+      node.entry_i += 1;
+
+      return Ok(FrameStepOk::Continue);
+    }
+
+    let node = self.stack.pop().unwrap();
+
+    let (open, close) = match node.is_array {
+      true => ('[', ']'),
+      false => ('{', '}'),
+    };
+
+    let json = if node.parts.is_empty() {
+      format!("{}{}", open, close)
+    } else if self.gap.is_empty() {
+      format!("{}{}{}", open, node.parts.join(","), close)
+    } else {
+      let indent = format!("\n{}", self.gap.repeat(self.stack.len() + 1));
+      let stepback = format!("\n{}", self.gap.repeat(self.stack.len()));
+      let separator = format!(",{}", indent);
+
+      format!(
+        "{}{}{}{}{}",
+        open,
+        indent,
+        node.parts.join(&separator),
+        stepback,
+        close
+      )
+    };
+
+    Ok(self.emit(node.key, Some(json)))
+  }
+
+  /// Adds the serialized property to the enclosing array or object, or finishes if this was the
+  /// outermost value. `None` means the property isn't serializable (e.g. `undefined`).
+  fn emit(&mut self, key: String, json: Option<String>) -> FrameStepOk {
+    let node = match self.stack.last_mut() {
+      Some(node) => node,
+      None => return pop(json.map_or(Val::Undefined, |json| json.to_val())),
+    };
+
+    if node.is_array {
+      node.parts.push(json.unwrap_or_else(|| "null".to_string()));
+    } else if let Some(json) = json {
+      let colon = match self.gap.is_empty() {
+        true => ":",
+        false => ": ",
+      };
+
+      node.parts.push(format!("{}{}{}", quote(&key), colon, json));
+    }
+
+    FrameStepOk::Continue
+  }
+}
+
+impl StackFrameTrait for StringifyFrame {
+  fn write_this(&mut self, _const: bool, _this: Val) -> Result<(), Val> {
+    Ok(())
+  }
+
+  fn write_param(&mut self, param: Val) {
+    match self.param_i {
+      0 => self.value = param,
+      1 => self.replacer = param,
+      2 => self.space = param,
+      _ => {}
+    };
+
+    self.param_i += 1;
+  }
+
+  fn step(&mut self) -> FrameStepResult {
+    if !self.started {
+      self.start();
+    }
+
+    let property = match &mut self.property {
+      Some(property) => property,
+      None => return self.next_property(),
+    };
+
+    match &property.stage {
+      PropertyStage::Get { getter, this } => {
+        let (getter, this) = (getter.clone(), this.clone());
+        property.stage = PropertyStage::ToJson;
+
+        // Accessors without a getter read as undefined
+        if let Val::Void = getter {
+          return Ok(FrameStepOk::Continue);
+        }
+
+        self.call(&getter, this, vec![])
+      }
+      PropertyStage::ToJson => {
+        property.stage = PropertyStage::Replacer;
+
+        if let Val::Object(_) = &property.value {
+          let to_json = property.value.sub(&"toJSON".to_val())?;
+
+          if is_function(&to_json) {
+            let this = property.value.clone();
+            let args = vec![property.key.clone().to_val()];
+
+            return self.call(&to_json, this, args);
+          }
+        }
+
+        Ok(FrameStepOk::Continue)
+      }
+      PropertyStage::Replacer => {
+        property.stage = PropertyStage::Serialize;
+
+        if !is_function(&self.replacer) {
+          return Ok(FrameStepOk::Continue);
+        }
+
+        let holder = match self.stack.last() {
+          Some(node) => node.value.clone(),
+          None => self.wrapper.clone(),
+        };
+
+        let args = vec![property.key.clone().to_val(), property.value.clone()];
+        let replacer = self.replacer.clone();
+
+        self.call(&replacer, holder, args)
+      }
+      PropertyStage::Serialize => {
+        let property = self.property.take().unwrap();
+        self.serialize(property)
+      }
+    }
+  }
+
+  fn apply_call_result(&mut self, call_result: CallResult) {
+    let property = self
+      .property
+      .as_mut()
+      .expect("Call result without property");
+    property.value = call_result.return_;
+  }
+
+  fn get_call_result(&mut self) -> CallResult {
+    panic!("Not appropriate for StringifyFrame")
+  }
+
+  fn can_catch_exception(&self, _exception: &Val) -> bool {
+    false
+  }
+
+  fn catch_exception(&mut self, _exception: &mut Val) {}
+
+  fn clone_to_stack_frame(&self) -> StackFrame {
+    Box::new(self.clone())
+  }
+
+  fn as_any(&self) -> &dyn Any {
+    self
+  }
+
+  fn as_any_mut(&mut self) -> &mut dyn Any {
+    self
+  }
+}
+
+fn parse(text: &str) -> Result<Val, Val> {
+  match serde_json::from_str::<serde_json::Value>(text) {
+    Ok(json) => Ok(Val::from_json(&json)),
+    Err(err) => Err(format!("Invalid JSON: {}", err).to_type_error()),
+  }
+}
+
+/// Quotes and escapes a string. serde_json escapes the same characters as JS.
+fn quote(string: &str) -> String {
+  serde_json::to_string(string).expect("Strings should serialize")
+}
+
+fn is_function(val: &Val) -> bool {
+  val.typeof_() == VsType::Function
+}
+
+fn pop(value: Val) -> FrameStepOk {
+  FrameStepOk::Pop(CallResult {
+    return_: value,
+    this: Val::Undefined,
+  })
+}
@@ -0,0 +1,92 @@
+use std::{fmt, rc::Rc};
+
+use crate::{
+  native_function::{native_fn, NativeFunction},
+  vs_class::VsClass,
+  vs_map::VsMap,
+  vs_object::VsObject,
+  vs_value::{LoadFunctionResult, ToVal, Val},
+  ValTrait,
+};
+
+use super::{
+  builtin_object::BuiltinObject, internal_error_builtin::ToInternalError,
+  type_error_builtin::ToTypeError,
+};
+
+pub struct MapBuiltin {}
+
+impl BuiltinObject for MapBuiltin {
+  fn bo_name() -> &'static str {
+    "Map"
+  }
+
+  fn bo_sub(_key: &str) -> Val {
+    Val::Undefined
+  }
+
+  fn bo_load_function() -> LoadFunctionResult {
+    LoadFunctionResult::NotAFunction
+  }
+
+  fn bo_as_class_data() -> Option<Rc<VsClass>> {
+    Some(Rc::new(VsClass {
+      name: "Map".to_string(),
+      content_hash: None,
+      constructor: CONSTRUCTOR.to_val(),
+      prototype: VsObject::default().to_val(),
+      static_: VsObject::default().to_val(),
+    }))
+  }
+}
+
+impl fmt::Display for MapBuiltin {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "function Map() {{ [native code] }}")
+  }
+}
+
+static CONSTRUCTOR: NativeFunction = native_fn(|mut this, params| {
+  // The instance is replaced entirely since maps aren't ordinary objects
+  *this.get_mut()? = to_map(params.into_iter().next().unwrap_or(Val::Undefined))?;
+
+  Ok(Val::Undefined)
+});
+
+fn to_map(entries: Val) -> Result<Val, Val> {
+  let mut map = VsMap::default();
+
+  match entries {
+    Val::Undefined | Val::Null => {}
+    Val::Array(arr) => {
+      for entry in arr.elements.iter() {
+        let (key, value) = to_entry(entry)?;
+        map.insert(key, value);
+      }
+    }
+    Val::Map(map) => return Ok(Val::Map(map)),
+    Val::Set(set) => {
+      for entry in set.iter() {
+        let (key, value) = to_entry(entry)?;
+        map.insert(key, value);
+      }
+    }
+    Val::StoragePtr(ptr) => return to_map(ptr.get()),
+    Val::Dynamic(..) | Val::Static(..) => {
+      return Err("TODO: Map from non-array iterable".to_internal_error())
+    }
+    _ => return Err(format!("{} is not iterable", entries.codify()).to_type_error()),
+  }
+
+  Ok(map.to_val())
+}
+
+fn to_entry(entry: &Val) -> Result<(Val, Val), Val> {
+  if entry.is_primitive() {
+    return Err(
+      format!("Iterator value {} is not an entry object", entry.codify()).to_type_error(),
+    );
+  }
+
+  Ok((entry.sub(&0.0.to_val())?, entry.sub(&1.0.to_val())?))
+}
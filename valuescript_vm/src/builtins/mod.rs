@@ -6,12 +6,19 @@ mod console_builtin;
 mod debug_builtin;
 pub mod error_builtin;
 pub mod internal_error_builtin;
+mod json_builtin;
+mod map_builtin;
 mod math_builtin;
 mod number_builtin;
+mod object_builtin;
+mod promise_builtin;
 pub mod range_error_builtin;
+mod regexp_builtin;
+mod set_builtin;
 mod string_builtin;
 mod symbol_builtin;
 pub mod type_error_builtin;
+mod vs_builtin;
 
 use valuescript_common::BUILTIN_COUNT;
 
@@ -23,10 +30,11 @@ use crate::{
 use self::{
   array_builtin::ArrayBuiltin, bigint_builtin::BigIntBuiltin, boolean_builtin::BooleanBuiltin,
   console_builtin::ConsoleBuiltin, debug_builtin::DebugBuiltin, error_builtin::ErrorBuiltin,
-  internal_error_builtin::InternalErrorBuiltin, math_builtin::MathBuiltin,
-  number_builtin::NumberBuiltin, range_error_builtin::RangeErrorBuiltin,
-  string_builtin::StringBuiltin, symbol_builtin::SymbolBuiltin,
-  type_error_builtin::TypeErrorBuiltin,
+  internal_error_builtin::InternalErrorBuiltin, json_builtin::JsonBuiltin, map_builtin::MapBuiltin,
+  math_builtin::MathBuiltin, number_builtin::NumberBuiltin, object_builtin::ObjectBuiltin,
+  promise_builtin::PromiseBuiltin, range_error_builtin::RangeErrorBuiltin,
+  regexp_builtin::RegExpBuiltin, set_builtin::SetBuiltin, string_builtin::StringBuiltin,
+  symbol_builtin::SymbolBuiltin, type_error_builtin::TypeErrorBuiltin, vs_builtin::VsBuiltin,
 };
 
 pub static BUILTIN_VALS: [fn() -> Val; BUILTIN_COUNT] = [
@@ -48,4 +56,15 @@ pub static BUILTIN_VALS: [fn() -> Val; BUILTIN_COUNT] = [
   || VsSymbol::ITERATOR.to_val(),
   || BigIntBuiltin {}.to_val(),
   || ConsoleBuiltin {}.to_val(),
+  || RegExpBuiltin {}.to_val(),
+  || PromiseBuiltin {}.to_val(),
+  || VsBuiltin {}.to_val(),
+  || VsSymbol::GENERATOR_RETURN.to_val(),
+  || VsSymbol::ASYNC_ITERATOR.to_val(),
+  || ObjectBuiltin {}.to_val(),
+  || JsonBuiltin {}.to_val(),
+  || MapBuiltin {}.to_val(),
+  || SetBuiltin {}.to_val(),
+  || array_builtin::TEMPLATE_STRINGS.to_val(),
+  || object_builtin::SUPER_GET.to_val(),
 ];
@@ -0,0 +1,251 @@
+use std::fmt;
+use std::mem::take;
+use std::rc::Rc;
+
+use crate::jsx_element::JsxElement;
+use crate::native_frame_function::NativeFrameFunction;
+use crate::native_function::{native_fn, NativeFunction};
+use crate::operations::{op_sub, op_sub_accessor, op_submov};
+use crate::own_properties::{own_properties, OwnProperty};
+use crate::read_properties_frame::{ReadProperties, ReadPropertiesFrame};
+use crate::vs_array::VsArray;
+use crate::vs_class::VsClass;
+use crate::vs_object::VsObject;
+use crate::vs_value::{LoadFunctionResult, ToVal, Val, ValTrait};
+
+use super::builtin_object::BuiltinObject;
+use super::type_error_builtin::ToTypeError;
+
+pub struct ObjectBuiltin {}
+
+impl BuiltinObject for ObjectBuiltin {
+  fn bo_name() -> &'static str {
+    "Object"
+  }
+
+  fn bo_sub(key: &str) -> Val {
+    match key {
+      "assign" => ASSIGN.to_val(),
+      "entries" => ENTRIES.to_val(),
+      "freeze" => FREEZE.to_val(),
+      "fromEntries" => FROM_ENTRIES.to_val(),
+      "getPrototypeOf" => GET_PROTOTYPE_OF.to_val(),
+      "hasOwn" => HAS_OWN.to_val(),
+      "keys" => KEYS.to_val(),
+      "values" => VALUES.to_val(),
+      _ => Val::Undefined,
+    }
+  }
+
+  fn bo_load_function() -> LoadFunctionResult {
+    LoadFunctionResult::NotAFunction
+  }
+
+  fn bo_as_class_data() -> Option<Rc<VsClass>> {
+    None
+  }
+}
+
+impl fmt::Display for ObjectBuiltin {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "function Object() {{ [native code] }}")
+  }
+}
+
+static KEYS: NativeFunction = native_fn(|_this, params| {
+  let keys = own_properties(&param(&params, 0), false)?
+    .into_iter()
+    .map(|(key, _)| key)
+    .collect::<Vec<Val>>();
+
+  Ok(keys.to_val())
+});
+
+/// `Object.values` and `Object.entries` are frames because they call getters.
+static VALUES: NativeFrameFunction = NativeFrameFunction {
+  make_frame: || Box::new(ReadPropertiesFrame::new(&READ_VALUES)),
+};
+
+static READ_VALUES: ReadProperties = ReadProperties {
+  list: |params| own_properties(&param(params, 0), false),
+  finish: |_params, properties| {
+    let values = properties
+      .into_iter()
+      .map(|(_, value)| value)
+      .collect::<Vec<Val>>();
+
+    Ok(values.to_val())
+  },
+};
+
+static ENTRIES: NativeFrameFunction = NativeFrameFunction {
+  make_frame: || Box::new(ReadPropertiesFrame::new(&READ_ENTRIES)),
+};
+
+static READ_ENTRIES: ReadProperties = ReadProperties {
+  list: |params| own_properties(&param(params, 0), false),
+  finish: |_params, properties| {
+    let entries = properties
+      .into_iter()
+      .map(|(key, value)| vec![key, value].to_val())
+      .collect::<Vec<Val>>();
+
+    Ok(entries.to_val())
+  },
+};
+
+static FROM_ENTRIES: NativeFunction = native_fn(|_this, params| {
+  let entries = param(&params, 0);
+
+  let entries = match (&entries, entries.as_array_data()) {
+    (Val::Map(map), _) => Rc::new(VsArray::from(
+      map
+        .iter()
+        .map(|(key, value)| vec![key.clone(), value.clone()].to_val())
+        .collect::<Vec<Val>>(),
+    )),
+    (_, Some(entries)) => entries,
+    (_, None) => {
+      return Err(format!("{} is not an array of entries", entries.codify()).to_type_error())
+    }
+  };
+
+  let mut object = VsObject::default();
+
+  for entry in &entries.elements {
+    if entry.is_primitive() {
+      return Err(format!("{} is not an entry", entry.codify()).to_type_error());
+    }
+
+    let key = entry.sub(&0.0.to_val())?;
+    let value = entry.sub(&1.0.to_val())?;
+
+    match key {
+      Val::Symbol(symbol) => object.symbol_map.insert(symbol, value),
+      _ => object.string_map.insert(key.to_string(), value),
+    };
+  }
+
+  Ok(object.to_val())
+});
+
+/// Values can't be modified in place, so instead of changing `target`, this returns a copy of it
+/// with the properties of the sources copied in. Like `Object.values`, this is a frame because it
+/// calls getters.
+static ASSIGN: NativeFrameFunction = NativeFrameFunction {
+  make_frame: || Box::new(ReadPropertiesFrame::new(&READ_ASSIGN)),
+};
+
+static READ_ASSIGN: ReadProperties = ReadProperties {
+  list: |params| {
+    require_object_coercible(&param(params, 0))?;
+
+    let mut properties = vec![];
+
+    for source in params.iter().skip(1) {
+      if !source.is_nullish() {
+        properties.extend(own_properties(source, true)?);
+      }
+    }
+
+    Ok(properties)
+  },
+  finish: |mut params, properties| {
+    let mut target = take(&mut params[0]).not_ptr();
+
+    for (key, value) in properties {
+      op_submov(&mut target, &key, value)?;
+    }
+
+    Ok(target)
+  },
+};
+
+/// Values are already immutable, so there's nothing to do.
+static FREEZE: NativeFunction = native_fn(|_this, params| Ok(param(&params, 0)));
+
+/// Only objects (including class instances) have prototypes that can be accessed, so other values
+/// give `null`.
+static GET_PROTOTYPE_OF: NativeFunction = native_fn(|_this, params| {
+  let object = param(&params, 0);
+  require_object_coercible(&object)?;
+
+  Ok(match object {
+    Val::Object(object) => match &object.prototype {
+      Val::Void => Val::Null,
+      prototype => prototype.clone(),
+    },
+    _ => Val::Null,
+  })
+});
+
+static HAS_OWN: NativeFunction = native_fn(|_this, params| {
+  let object = param(&params, 0);
+  require_object_coercible(&object)?;
+
+  let key = param(&params, 1);
+
+  let has_own = match &object {
+    Val::Object(object) => object.has_own(&key),
+    Val::Class(class) => match &class.static_ {
+      Val::Object(static_) => static_.has_own(&key),
+      _ => false,
+    },
+    Val::Array(array) => match key.to_index() {
+      Some(i) => i < array.elements.len() && !matches!(array.elements[i], Val::Void),
+      None => key.to_string() == "length",
+    },
+    Val::String(string) => match key.to_index() {
+      Some(i) => i < string.chars().count(),
+      None => key.to_string() == "length",
+    },
+    Val::Dynamic(dynamic) => match dynamic.as_any().downcast_ref::<JsxElement>() {
+      Some(jsx) => jsx.attrs.iter().any(|(name, _)| *name == key.to_string()),
+      None => false,
+    },
+    _ => false,
+  };
+
+  Ok(has_own.to_val())
+});
+
+/// Reads `super[key]`, given the parent prototype (or class, in static methods), the key and `this`.
+/// Getters found on the parent are called with `this` rather than the parent, so this is a frame.
+pub static SUPER_GET: NativeFrameFunction = NativeFrameFunction {
+  make_frame: || Box::new(ReadPropertiesFrame::new(&READ_SUPER)),
+};
+
+static READ_SUPER: ReadProperties = ReadProperties {
+  list: |params| {
+    let mut parent = param(params, 0);
+    let key = param(params, 1);
+
+    let property = match op_sub_accessor(&parent, &key) {
+      Some((getter, _)) => OwnProperty::Getter {
+        getter,
+        this: param(params, 2),
+      },
+      None => OwnProperty::Value(op_sub(&mut parent, &key)?),
+    };
+
+    Ok(vec![(key, property)])
+  },
+  finish: |_params, properties| Ok(properties.into_iter().next().unwrap_or_default().1),
+};
+
+fn param(params: &[Val], i: usize) -> Val {
+  match params.get(i) {
+    Some(Val::StoragePtr(ptr)) => ptr.get(),
+    Some(param) => param.clone(),
+    None => Val::Undefined,
+  }
+}
+
+fn require_object_coercible(val: &Val) -> Result<(), Val> {
+  match val {
+    Val::Undefined | Val::Null => {
+      Err(format!("Cannot convert {} to object", val.codify()).to_type_error())
+    }
+    _ => Ok(()),
+  }
+}
@@ -0,0 +1,223 @@
+use std::{any::Any, fmt, mem::take, rc::Rc};
+
+use crate::{
+  builtins::type_error_builtin::ToTypeError,
+  make_promise_frame::MakePromiseFrame,
+  native_frame_function::NativeFrameFunction,
+  native_function::{native_fn, NativeFunction},
+  stack_frame::{CallResult, FrameStepOk, FrameStepResult, StackFrame, StackFrameTrait},
+  vs_class::VsClass,
+  vs_promise::{as_promise, await_val, AwaitResult, VsPromise},
+  vs_value::{LoadFunctionResult, ToDynamicVal, ToVal, Val, ValTrait},
+};
+
+use super::builtin_object::BuiltinObject;
+
+pub struct PromiseBuiltin {}
+
+impl BuiltinObject for PromiseBuiltin {
+  fn bo_name() -> &'static str {
+    "Promise"
+  }
+
+  fn bo_sub(key: &str) -> Val {
+    match key {
+      "resolve" => RESOLVE.to_val(),
+      "reject" => REJECT.to_val(),
+      "all" => ALL.to_val(),
+      "race" => RACE.to_val(),
+      _ => Val::Undefined,
+    }
+  }
+
+  fn bo_load_function() -> LoadFunctionResult {
+    LoadFunctionResult::NotAFunction
+  }
+
+  fn bo_as_class_data() -> Option<Rc<VsClass>> {
+    // TODO: new Promise(executor)
+    None
+  }
+}
+
+impl fmt::Display for PromiseBuiltin {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "function Promise() {{ [native code] }}")
+  }
+}
+
+static RESOLVE: NativeFunction = native_fn(|_this, params| {
+  let value = params.into_iter().next().unwrap_or(Val::Undefined);
+
+  Ok(match as_promise(&value) {
+    Some(_) => value,
+    None => VsPromise::fulfilled(value).to_dynamic_val(),
+  })
+});
+
+static REJECT: NativeFunction = native_fn(|_this, params| {
+  let reason = params.into_iter().next().unwrap_or(Val::Undefined);
+
+  Ok(VsPromise::rejected(reason).to_dynamic_val())
+});
+
+static ALL: NativeFrameFunction = NativeFrameFunction {
+  make_frame: || Box::new(MakePromiseFrame::new(Box::<AllFrame>::default())),
+};
+
+static RACE: NativeFrameFunction = NativeFrameFunction {
+  make_frame: || Box::new(MakePromiseFrame::new(Box::<RaceFrame>::default())),
+};
+
+fn promise_elements(name: &str, iterable: &Val) -> Result<Vec<Val>, Val> {
+  match iterable.as_array_data() {
+    Some(array) => Ok(array.elements.to_vec()),
+    None => Err(format!("Promise.{} requires an array", name).to_type_error()),
+  }
+}
+
+fn pop(value: Val) -> FrameStepOk {
+  FrameStepOk::Pop(CallResult {
+    return_: value,
+    this: Val::Undefined,
+  })
+}
+
+/// The job for `Promise.all`: awaits each element in order. The first rejection is thrown, which
+/// rejects the resulting promise.
+#[derive(Clone, Default)]
+struct AllFrame {
+  iterable: Val,
+  elements: Option<Vec<Val>>,
+  results: Vec<Val>,
+}
+
+impl StackFrameTrait for AllFrame {
+  fn write_this(&mut self, _const: bool, _this: Val) -> Result<(), Val> {
+    Ok(())
+  }
+
+  fn write_param(&mut self, param: Val) {
+    if let Val::Void = self.iterable {
+      self.iterable = param;
+    }
+  }
+
+  fn step(&mut self) -> FrameStepResult {
+    if self.elements.is_none() {
+      self.elements = Some(promise_elements("all", &self.iterable)?);
+    }
+
+    let elements = self.elements.as_mut().unwrap();
+
+    while self.results.len() < elements.len() {
+      let element = take(&mut elements[self.results.len()]);
+
+      match await_val(element)? {
+        AwaitResult::Value(value) => self.results.push(value),
+        AwaitResult::Push(frame) => return Ok(FrameStepOk::Push(frame)),
+      }
+    }
+
+    Ok(pop(take(&mut self.results).to_val()))
+  }
+
+  fn apply_call_result(&mut self, call_result: CallResult) {
+    self.results.push(call_result.return_);
+  }
+
+  fn get_call_result(&mut self) -> CallResult {
+    panic!("Not appropriate for AllFrame")
+  }
+
+  fn can_catch_exception(&self, _exception: &Val) -> bool {
+    false
+  }
+
+  fn catch_exception(&mut self, _exception: &mut Val) {}
+
+  fn clone_to_stack_frame(&self) -> StackFrame {
+    Box::new(self.clone())
+  }
+
+  fn as_any(&self) -> &dyn Any {
+    self
+  }
+
+  fn as_any_mut(&mut self) -> &mut dyn Any {
+    self
+  }
+}
+
+/// The job for `Promise.race`. To keep things deterministic, the winner is the first element which
+/// is already settled (or isn't a promise). If none are settled, the first element's job is run
+/// and it wins.
+#[derive(Clone, Default)]
+struct RaceFrame {
+  iterable: Val,
+  result: Option<Val>,
+}
+
+impl StackFrameTrait for RaceFrame {
+  fn write_this(&mut self, _const: bool, _this: Val) -> Result<(), Val> {
+    Ok(())
+  }
+
+  fn write_param(&mut self, param: Val) {
+    if let Val::Void = self.iterable {
+      self.iterable = param;
+    }
+  }
+
+  fn step(&mut self) -> FrameStepResult {
+    if let Some(result) = take(&mut self.result) {
+      return Ok(pop(result));
+    }
+
+    let elements = promise_elements("race", &self.iterable)?;
+
+    let winner = elements
+      .iter()
+      .find(|element| match as_promise(element) {
+        Some(promise) => promise.is_settled(),
+        None => true,
+      })
+      .or(elements.first());
+
+    let winner = match winner {
+      Some(winner) => winner.clone(),
+      None => return Err("Promise.race of an empty array never settles".to_type_error()),
+    };
+
+    match await_val(winner)? {
+      AwaitResult::Value(value) => Ok(pop(value)),
+      AwaitResult::Push(frame) => Ok(FrameStepOk::Push(frame)),
+    }
+  }
+
+  fn apply_call_result(&mut self, call_result: CallResult) {
+    self.result = Some(call_result.return_);
+  }
+
+  fn get_call_result(&mut self) -> CallResult {
+    panic!("Not appropriate for RaceFrame")
+  }
+
+  fn can_catch_exception(&self, _exception: &Val) -> bool {
+    false
+  }
+
+  fn catch_exception(&mut self, _exception: &mut Val) {}
+
+  fn clone_to_stack_frame(&self) -> StackFrame {
+    Box::new(self.clone())
+  }
+
+  fn as_any(&self) -> &dyn Any {
+    self
+  }
+
+  fn as_any_mut(&mut self) -> &mut dyn Any {
+    self
+  }
+}
@@ -1,6 +1,7 @@
 use std::fmt;
-use std::{collections::BTreeMap, rc::Rc};
+use std::rc::Rc;
 
+use crate::hybrid_map::HybridMap;
 use crate::native_function::{native_fn, ThisWrapper};
 use crate::vs_value::ToVal;
 use crate::ValTrait;
@@ -49,11 +50,14 @@ impl fmt::Display for RangeErrorBuiltin {
 // TODO: Static? (Rc -> Arc?)
 fn make_range_error_prototype() -> Val {
   VsObject {
-    string_map: BTreeMap::from([
+    string_map: HybridMap::from([
       ("name".to_string(), "RangeError".to_val()),
       ("toString".to_string(), Val::Static(&RANGE_ERROR_TO_STRING)),
     ]),
     symbol_map: Default::default(),
+    getter_map: Default::default(),
+    setter_map: Default::default(),
+    private_map: Default::default(),
     prototype: Val::Void,
   }
   .to_val()
@@ -73,7 +77,7 @@ static SET_MESSAGE: NativeFunction = native_fn(|mut this, params| {
 pub fn to_range_error(_: ThisWrapper, params: Vec<Val>) -> Result<Val, Val> {
   Ok(
     VsObject {
-      string_map: BTreeMap::from([(
+      string_map: HybridMap::from([(
         "message".to_string(),
         match params.first() {
           Some(param) => param.clone().to_val_string(),
@@ -81,6 +85,9 @@ pub fn to_range_error(_: ThisWrapper, params: Vec<Val>) -> Result<Val, Val> {
         },
       )]),
       symbol_map: Default::default(),
+      getter_map: Default::default(),
+      setter_map: Default::default(),
+      private_map: Default::default(),
       prototype: make_range_error_prototype(),
     }
     .to_val(),
@@ -111,8 +118,11 @@ impl ToRangeError for String {
 impl ToRangeError for Val {
   fn to_range_error(self) -> Val {
     VsObject {
-      string_map: BTreeMap::from([("message".to_string(), self)]),
+      string_map: HybridMap::from([("message".to_string(), self)]),
       symbol_map: Default::default(),
+      getter_map: Default::default(),
+      setter_map: Default::default(),
+      private_map: Default::default(),
       prototype: make_range_error_prototype(),
     }
     .to_val()
@@ -0,0 +1,70 @@
+use std::{fmt, rc::Rc};
+
+use crate::{
+  native_function::{native_fn, NativeFunction, ThisWrapper},
+  vs_class::VsClass,
+  vs_object::VsObject,
+  vs_regexp::{as_regexp, VsRegExp},
+  vs_value::{LoadFunctionResult, ToDynamicVal, ToVal, Val},
+};
+
+use super::builtin_object::BuiltinObject;
+
+pub struct RegExpBuiltin {}
+
+impl BuiltinObject for RegExpBuiltin {
+  fn bo_name() -> &'static str {
+    "RegExp"
+  }
+
+  fn bo_sub(_key: &str) -> Val {
+    Val::Undefined
+  }
+
+  fn bo_load_function() -> LoadFunctionResult {
+    LoadFunctionResult::NativeFunction(to_regexp)
+  }
+
+  fn bo_as_class_data() -> Option<Rc<VsClass>> {
+    Some(Rc::new(VsClass {
+      name: "RegExp".to_string(),
+      content_hash: None,
+      constructor: CONSTRUCTOR.to_val(),
+      prototype: VsObject::default().to_val(),
+      static_: VsObject::default().to_val(),
+    }))
+  }
+}
+
+impl fmt::Display for RegExpBuiltin {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "function RegExp() {{ [native code] }}")
+  }
+}
+
+fn to_regexp(_: ThisWrapper, params: Vec<Val>) -> Result<Val, Val> {
+  let flags = match params.get(1) {
+    None | Some(Val::Undefined) => None,
+    Some(flags) => Some(flags.to_string()),
+  };
+
+  let regexp = match params.first() {
+    None | Some(Val::Undefined) => VsRegExp::new("(?:)", flags.as_deref().unwrap_or(""))?,
+    Some(pattern) => match as_regexp(pattern) {
+      Some(regexp) => match flags {
+        None => regexp.clone(),
+        Some(flags) => VsRegExp::new(&regexp.source, &flags)?,
+      },
+      None => VsRegExp::new(&pattern.to_string(), flags.as_deref().unwrap_or(""))?,
+    },
+  };
+
+  Ok(regexp.to_dynamic_val())
+}
+
+static CONSTRUCTOR: NativeFunction = native_fn(|mut this, params| {
+  // The instance is replaced entirely since regexes aren't ordinary objects
+  *this.get_mut()? = to_regexp(ThisWrapper::new(true, &mut Val::Undefined), params)?;
+
+  Ok(Val::Undefined)
+});
@@ -0,0 +1,85 @@
+use std::{fmt, rc::Rc};
+
+use crate::{
+  native_function::{native_fn, NativeFunction},
+  vs_class::VsClass,
+  vs_object::VsObject,
+  vs_set::VsSet,
+  vs_value::{LoadFunctionResult, ToVal, Val},
+  ValTrait,
+};
+
+use super::{
+  builtin_object::BuiltinObject, internal_error_builtin::ToInternalError,
+  type_error_builtin::ToTypeError,
+};
+
+pub struct SetBuiltin {}
+
+impl BuiltinObject for SetBuiltin {
+  fn bo_name() -> &'static str {
+    "Set"
+  }
+
+  fn bo_sub(_key: &str) -> Val {
+    Val::Undefined
+  }
+
+  fn bo_load_function() -> LoadFunctionResult {
+    LoadFunctionResult::NotAFunction
+  }
+
+  fn bo_as_class_data() -> Option<Rc<VsClass>> {
+    Some(Rc::new(VsClass {
+      name: "Set".to_string(),
+      content_hash: None,
+      constructor: CONSTRUCTOR.to_val(),
+      prototype: VsObject::default().to_val(),
+      static_: VsObject::default().to_val(),
+    }))
+  }
+}
+
+impl fmt::Display for SetBuiltin {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "function Set() {{ [native code] }}")
+  }
+}
+
+static CONSTRUCTOR: NativeFunction = native_fn(|mut this, params| {
+  // The instance is replaced entirely since sets aren't ordinary objects
+  *this.get_mut()? = to_set(params.into_iter().next().unwrap_or(Val::Undefined))?;
+
+  Ok(Val::Undefined)
+});
+
+fn to_set(values: Val) -> Result<Val, Val> {
+  let mut set = VsSet::default();
+
+  match values {
+    Val::Undefined | Val::Null => {}
+    Val::Array(arr) => {
+      for value in arr.elements.iter() {
+        set.insert(value.clone());
+      }
+    }
+    Val::String(s) => {
+      for c in s.chars() {
+        set.insert(c.to_val());
+      }
+    }
+    Val::Map(map) => {
+      for (key, value) in map.iter() {
+        set.insert(vec![key.clone(), value.clone()].to_val());
+      }
+    }
+    Val::Set(set) => return Ok(Val::Set(set)),
+    Val::StoragePtr(ptr) => return to_set(ptr.get()),
+    Val::Dynamic(..) | Val::Static(..) => {
+      return Err("TODO: Set from non-array iterable".to_internal_error())
+    }
+    _ => return Err(format!("{} is not iterable", values.codify()).to_type_error()),
+  }
+
+  Ok(set.to_val())
+}
@@ -27,7 +27,7 @@ impl BuiltinObject for StringBuiltin {
     match key {
       "fromCodePoint" => Val::Static(&FROM_CODE_POINT),
       // "fromCharCode" => Val::Static(&FROM_CHAR_CODE),
-      // "raw" => Val::Static(&RAW),                     // TODO
+      "raw" => Val::Static(&RAW),
       _ => Val::Undefined,
     }
   }
@@ -64,6 +64,30 @@ static FROM_CODE_POINT: NativeFunction = native_fn(|_this, params| {
   Ok(result.to_val())
 });
 
+static RAW: NativeFunction = native_fn(|_this, params| {
+  let raw = match params.first() {
+    Some(strings) => strings.sub(&"raw".to_val())?,
+    None => Val::Undefined,
+  };
+
+  let len = raw.sub(&"length".to_val())?.to_number();
+  let mut result = String::new();
+  let mut i = 0;
+
+  while (i as f64) < len {
+    if i > 0 {
+      if let Some(substitution) = params.get(i) {
+        result.push_str(&substitution.to_string());
+      }
+    }
+
+    result.push_str(&raw.sub(&(i as f64).to_val())?.to_string());
+    i += 1;
+  }
+
+  Ok(result.to_val())
+});
+
 fn to_string(_: ThisWrapper, params: Vec<Val>) -> Result<Val, Val> {
   Ok(if let Some(value) = params.first() {
     value.clone().to_val_string()
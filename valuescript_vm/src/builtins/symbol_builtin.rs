@@ -18,6 +18,7 @@ impl BuiltinObject for SymbolBuiltin {
   fn bo_sub(key: &str) -> Val {
     match key {
       "iterator" => VsSymbol::ITERATOR.to_val(),
+      "asyncIterator" => VsSymbol::ASYNC_ITERATOR.to_val(),
       _ => Val::Undefined,
     }
   }
@@ -1,6 +1,7 @@
 use std::fmt;
-use std::{collections::BTreeMap, rc::Rc};
+use std::rc::Rc;
 
+use crate::hybrid_map::HybridMap;
 use crate::native_function::{native_fn, ThisWrapper};
 use crate::vs_value::ToVal;
 use crate::ValTrait;
@@ -57,11 +58,14 @@ impl fmt::Display for TypeErrorBuiltin {
 // TODO: Static? (Rc -> Arc?)
 fn make_type_error_prototype() -> Val {
   VsObject {
-    string_map: BTreeMap::from([
+    string_map: HybridMap::from([
       ("name".to_string(), "TypeError".to_val()),
       ("toString".to_string(), TYPE_ERROR_TO_STRING.to_val()),
     ]),
     symbol_map: Default::default(),
+    getter_map: Default::default(),
+    setter_map: Default::default(),
+    private_map: Default::default(),
     prototype: Val::Void,
   }
   .to_val()
@@ -102,8 +106,11 @@ impl ToTypeError for String {
 impl ToTypeError for Val {
   fn to_type_error(self) -> Val {
     VsObject {
-      string_map: BTreeMap::from([("message".to_string(), self)]),
+      string_map: HybridMap::from([("message".to_string(), self)]),
       symbol_map: Default::default(),
+      getter_map: Default::default(),
+      setter_map: Default::default(),
+      private_map: Default::default(),
       prototype: make_type_error_prototype(),
     }
     .to_val()
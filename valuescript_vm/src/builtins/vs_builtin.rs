@@ -0,0 +1,222 @@
+use std::{
+  any::Any,
+  cell::RefCell,
+  fmt,
+  mem::take,
+  rc::Rc,
+  thread::{self, JoinHandle},
+};
+
+use crate::{
+  builtins::internal_error_builtin::ToInternalError,
+  native_frame_function::NativeFrameFunction,
+  sendable_val::SendableVal,
+  stack_frame::{CallResult, FrameStepOk, FrameStepResult, StackFrame, StackFrameTrait},
+  vs_class::VsClass,
+  vs_promise::VsPromise,
+  vs_value::{LoadFunctionResult, ToDynamicVal, ToVal, Val},
+  VirtualMachine,
+};
+
+use super::builtin_object::BuiltinObject;
+
+/// ValueScript-specific functionality which doesn't have a JavaScript equivalent.
+pub struct VsBuiltin {}
+
+impl BuiltinObject for VsBuiltin {
+  fn bo_name() -> &'static str {
+    "vs"
+  }
+
+  fn bo_sub(key: &str) -> Val {
+    match key {
+      "thread" => THREAD.to_val(),
+      _ => Val::Undefined,
+    }
+  }
+
+  fn bo_load_function() -> LoadFunctionResult {
+    LoadFunctionResult::NotAFunction
+  }
+
+  fn bo_as_class_data() -> Option<Rc<VsClass>> {
+    None
+  }
+}
+
+impl fmt::Display for VsBuiltin {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "[object vs]")
+  }
+}
+
+type ThreadResult = Result<SendableVal, SendableVal>;
+
+static THREAD: NativeFrameFunction = NativeFrameFunction {
+  make_frame: || Box::<ThreadFrame>::default(),
+};
+
+/// Implements `vs.thread(fn, ...args)`. The function and arguments are copied to a new thread
+/// which runs them in its own `VirtualMachine`. The result is a promise which joins the thread.
+#[derive(Clone, Default)]
+struct ThreadFrame {
+  fn_: Val,
+  args: Vec<Val>,
+  promise: Option<VsPromise>,
+}
+
+impl ThreadFrame {
+  fn spawn(&mut self) -> Result<JoinHandle<ThreadResult>, Val> {
+    let fn_ = SendableVal::from_val(&self.fn_)?;
+
+    let args = self
+      .args
+      .iter()
+      .map(SendableVal::from_val)
+      .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(thread::spawn(move || {
+      let mut vm = VirtualMachine::default();
+
+      let fn_ = fn_.into_val().map_err(send_error)?;
+
+      let args = args
+        .into_iter()
+        .map(SendableVal::into_val)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(send_error)?;
+
+      let result = vm.run(None, &mut Val::Undefined, fn_, args);
+
+      match result {
+        Ok(value) => SendableVal::from_val(&value).map_err(send_error),
+        Err(exception) => Err(send_error(exception)),
+      }
+    }))
+  }
+}
+
+/// Prepare an exception to be sent back from a thread. If it can't be sent, the error describing
+/// why is sent instead.
+fn send_error(exception: Val) -> SendableVal {
+  SendableVal::from_val(&exception).unwrap_or_else(|err| match SendableVal::from_val(&err) {
+    Ok(err) => err,
+    Err(_) => SendableVal::String(err.to_string()),
+  })
+}
+
+impl StackFrameTrait for ThreadFrame {
+  fn write_this(&mut self, _const: bool, _this: Val) -> Result<(), Val> {
+    Ok(())
+  }
+
+  fn write_param(&mut self, param: Val) {
+    match self.fn_ {
+      Val::Void => self.fn_ = param,
+      _ => self.args.push(param),
+    };
+  }
+
+  fn step(&mut self) -> FrameStepResult {
+    if let Some(promise) = take(&mut self.promise) {
+      return Ok(FrameStepOk::Pop(CallResult {
+        return_: promise.to_dynamic_val(),
+        this: Val::Undefined,
+      }));
+    }
+
+    let handle = self.spawn()?;
+
+    let promise = VsPromise::new(Box::new(JoinFrame {
+      handle: Rc::new(RefCell::new(Some(handle))),
+    }));
+
+    self.promise = Some(promise.clone());
+
+    Ok(FrameStepOk::Spawn(promise))
+  }
+
+  fn apply_call_result(&mut self, _call_result: CallResult) {
+    panic!("Not appropriate for ThreadFrame")
+  }
+
+  fn get_call_result(&mut self) -> CallResult {
+    panic!("Not appropriate for ThreadFrame")
+  }
+
+  fn can_catch_exception(&self, _exception: &Val) -> bool {
+    false
+  }
+
+  fn catch_exception(&mut self, _exception: &mut Val) {}
+
+  fn clone_to_stack_frame(&self) -> StackFrame {
+    Box::new(self.clone())
+  }
+
+  fn as_any(&self) -> &dyn Any {
+    self
+  }
+
+  fn as_any_mut(&mut self) -> &mut dyn Any {
+    self
+  }
+}
+
+/// The job of a promise returned by `vs.thread`: waits for the thread to finish.
+#[derive(Clone)]
+struct JoinFrame {
+  handle: Rc<RefCell<Option<JoinHandle<ThreadResult>>>>,
+}
+
+impl StackFrameTrait for JoinFrame {
+  fn write_this(&mut self, _const: bool, _this: Val) -> Result<(), Val> {
+    panic!("Not appropriate for JoinFrame")
+  }
+
+  fn write_param(&mut self, _param: Val) {
+    panic!("Not appropriate for JoinFrame")
+  }
+
+  fn step(&mut self) -> FrameStepResult {
+    let handle = match self.handle.borrow_mut().take() {
+      Some(handle) => handle,
+      None => return Err("Thread was already joined".to_internal_error()),
+    };
+
+    match handle.join() {
+      Ok(Ok(value)) => Ok(FrameStepOk::Pop(CallResult {
+        return_: value.into_val()?,
+        this: Val::Undefined,
+      })),
+      Ok(Err(exception)) => Err(exception.into_val()?),
+      Err(_) => Err("Thread panicked".to_internal_error()),
+    }
+  }
+
+  fn apply_call_result(&mut self, _call_result: CallResult) {
+    panic!("Not appropriate for JoinFrame")
+  }
+
+  fn get_call_result(&mut self) -> CallResult {
+    panic!("Not appropriate for JoinFrame")
+  }
+
+  fn can_catch_exception(&self, _exception: &Val) -> bool {
+    false
+  }
+
+  fn catch_exception(&mut self, _exception: &mut Val) {}
+
+  fn clone_to_stack_frame(&self) -> StackFrame {
+    Box::new(self.clone())
+  }
+
+  fn as_any(&self) -> &dyn Any {
+    self
+  }
+
+  fn as_any_mut(&mut self) -> &mut dyn Any {
+    self
+  }
+}
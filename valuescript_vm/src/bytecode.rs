@@ -1,12 +1,27 @@
-use std::{cell::RefCell, collections::HashMap, fmt, ops::Index, rc::Rc, slice::SliceIndex};
+use std::{
+  cell::RefCell,
+  collections::{HashMap, HashSet},
+  fmt,
+  ops::Index,
+  rc::Rc,
+  slice::SliceIndex,
+};
 
 use storage::{GenericError, StorageBackend, StorageEntity, StorageReader, StorageTxMut};
 
-use crate::{bytecode_decoder::BytecodeDecoder, vs_value::Val};
+use crate::{bytecode_decoder::BytecodeDecoder, position_table::PositionTable, vs_value::Val};
 
 pub struct Bytecode {
   pub code: Vec<u8>,
+  pub positions: PositionTable,
   pub cache: RefCell<HashMap<usize, Val>>,
+
+  /// Lazy definitions which are currently running their initialization code.
+  pub initializing: RefCell<HashSet<usize>>,
+
+  /// An exception raised while decoding a value (eg by a lazy definition's initialization code).
+  /// Decoding can't fail, so the frame which was decoding throws this after its current step.
+  pub decode_error: RefCell<Option<Val>>,
 }
 
 impl<I: SliceIndex<[u8]>> Index<I> for Bytecode {
@@ -25,9 +40,16 @@ impl fmt::Debug for Bytecode {
 
 impl Bytecode {
   pub fn new(code: Vec<u8>) -> Bytecode {
+    Bytecode::with_positions(code, PositionTable::default())
+  }
+
+  pub fn with_positions(code: Vec<u8>, positions: PositionTable) -> Bytecode {
     Bytecode {
       code,
+      positions,
       cache: RefCell::new(HashMap::new()),
+      initializing: RefCell::new(HashSet::new()),
+      decode_error: RefCell::new(None),
     }
   }
 }
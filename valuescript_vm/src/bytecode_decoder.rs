@@ -6,14 +6,21 @@ use num_bigint::BigInt;
 use num_bigint::Sign;
 use valuescript_common::InstructionByte;
 
+use crate::builtins::type_error_builtin::ToTypeError;
 use crate::builtins::BUILTIN_VALS;
 use crate::bytecode::Bytecode;
+use crate::hybrid_map::HybridMap;
 use crate::vs_class::VsClass;
 use crate::vs_function::VsFunction;
-use crate::vs_object::VsObject;
+use crate::vs_object::{PropertyKey, VsObject};
+use crate::vs_private_name::VsPrivateName;
+use crate::vs_regexp::VsRegExp;
 use crate::vs_symbol::VsSymbol;
+use crate::vs_value::ToDynamicVal;
 use crate::vs_value::ToVal;
 use crate::vs_value::Val;
+use crate::vs_value::ValTrait;
+use crate::VirtualMachine;
 
 #[derive(Clone)]
 pub struct BytecodeDecoder {
@@ -42,10 +49,17 @@ pub enum BytecodeType {
   TakeRegister = 0x0f,
   Builtin = 0x10,
   Class = 0x11,
+  Lazy = 0x12,
   BigInt = 0x13,
   GeneratorFunction = 0x14,
   // ExportStar = 0x15,
   // Meta = 0x16,
+  RegExp = 0x17,
+  AsyncFunction = 0x18,
+  Getter = 0x19,
+  Setter = 0x1a,
+  PrivateName = 0x1b,
+  AsyncGeneratorFunction = 0x1c,
   Unrecognized = 0xff,
 }
 
@@ -71,10 +85,17 @@ impl BytecodeType {
       0x0f => TakeRegister,
       0x10 => Builtin,
       0x11 => Class,
-
+      0x12 => Lazy,
       0x13 => BigInt,
       0x14 => GeneratorFunction,
 
+      0x17 => RegExp,
+      0x18 => AsyncFunction,
+      0x19 => Getter,
+      0x1a => Setter,
+      0x1b => PrivateName,
+      0x1c => AsyncGeneratorFunction,
+
       _ => Unrecognized,
     }
   }
@@ -102,6 +123,7 @@ impl BytecodeDecoder {
   pub fn decode_val(&mut self, registers: &mut Vec<Val>) -> Val {
     match self.decode_type() {
       BytecodeType::End => panic!("Cannot decode end"),
+      BytecodeType::Lazy => panic!("Lazy values can only be decoded via pointers"),
       BytecodeType::Void => Val::Void,
       BytecodeType::Undefined => Val::Undefined,
       BytecodeType::Null => Val::Null,
@@ -112,17 +134,44 @@ impl BytecodeDecoder {
       BytecodeType::String => self.decode_string().to_val(),
       BytecodeType::Array => self.decode_vec_val(registers).to_val(),
       BytecodeType::Object => {
-        let mut string_map: BTreeMap<String, Val> = BTreeMap::new();
+        let mut string_map: HybridMap<String, Val> = HybridMap::new();
         let mut symbol_map: BTreeMap<VsSymbol, Val> = BTreeMap::new();
+        let mut getter_map: BTreeMap<PropertyKey, Val> = BTreeMap::new();
+        let mut setter_map: BTreeMap<PropertyKey, Val> = BTreeMap::new();
+        let mut private_map: BTreeMap<VsPrivateName, Val> = BTreeMap::new();
 
         while self.peek_type() != BytecodeType::End {
+          let accessor_map = match self.peek_type() {
+            BytecodeType::Getter => Some(&mut getter_map),
+            BytecodeType::Setter => Some(&mut setter_map),
+            _ => None,
+          };
+
+          if let Some(accessor_map) = accessor_map {
+            self.decode_type();
+            let key = PropertyKey::from_val(&self.decode_val(registers));
+            let value = self.decode_val(registers);
+
+            match &key {
+              PropertyKey::String(string) => string_map.remove(string),
+              PropertyKey::Symbol(symbol) => symbol_map.remove(symbol),
+            };
+
+            accessor_map.insert(key, value);
+
+            continue;
+          }
+
           let key = self.decode_val(registers);
           let value = self.decode_val(registers);
 
           match key {
             Val::String(string) => string_map.insert(string.to_string(), value),
             Val::Symbol(symbol) => symbol_map.insert(symbol, value),
-            key => string_map.insert(key.to_string(), value),
+            key => match VsPrivateName::from_val(&key) {
+              Some(name) => private_map.insert(name.clone(), value),
+              None => string_map.insert(key.to_string(), value),
+            },
           };
         }
 
@@ -131,11 +180,14 @@ impl BytecodeDecoder {
         VsObject {
           string_map,
           symbol_map,
+          getter_map,
+          setter_map,
+          private_map,
           prototype: Val::Void,
         }
         .to_val()
       }
-      BytecodeType::Function => self.decode_function(false),
+      BytecodeType::Function => self.decode_function(false, false),
       BytecodeType::Pointer => self.decode_pointer(registers),
       BytecodeType::Register => match registers[self.decode_register_index().unwrap()].clone() {
         Val::Void => Val::Undefined,
@@ -149,17 +201,61 @@ impl BytecodeDecoder {
       BytecodeType::Class => {
         let meta = self.decode_meta();
 
-        VsClass {
+        let mut class = VsClass {
           name: meta.name,
           content_hash: meta.content_hash,
           constructor: self.decode_val(registers),
           prototype: self.decode_val(registers),
           static_: self.decode_val(registers),
+        };
+
+        match self.decode_val(registers) {
+          Val::Void | Val::Null => {}
+          parent => match parent.as_class_data() {
+            Some(parent) => class.extend(&parent),
+            None => {
+              self.bytecode.decode_error.replace(Some(
+                format!(
+                  "Class extends value {} is not a constructor or null",
+                  parent.codify()
+                )
+                .to_type_error(),
+              ));
+            }
+          },
         }
-        .to_val()
+
+        class.to_val()
       }
       BytecodeType::BigInt => self.decode_bigint().to_val(),
-      BytecodeType::GeneratorFunction => self.decode_function(true),
+      BytecodeType::GeneratorFunction => self.decode_function(true, false),
+      BytecodeType::AsyncFunction => self.decode_function(false, true),
+      BytecodeType::AsyncGeneratorFunction => self.decode_function(true, true),
+      BytecodeType::RegExp => {
+        let source = self.decode_string();
+        let flags = self.decode_string();
+
+        match VsRegExp::new(&source, &flags) {
+          Ok(regexp) => regexp.to_dynamic_val(),
+          Err(err) => panic!("Invalid regex in bytecode at {}: {}", self.pos, err),
+        }
+      }
+      BytecodeType::PrivateName => {
+        let mut brand = [0u8; 32];
+
+        for b in &mut brand {
+          *b = self.decode_byte();
+        }
+
+        VsPrivateName {
+          brand,
+          name: self.decode_string(),
+        }
+        .to_dynamic_val()
+      }
+      BytecodeType::Getter | BytecodeType::Setter => {
+        panic!("Unexpected accessor outside object at {}", self.pos - 1)
+      }
       BytecodeType::Unrecognized => panic!("Unrecognized bytecode type at {}", self.pos - 1),
     }
   }
@@ -267,7 +363,10 @@ impl BytecodeDecoder {
       match type_ {
         BytecodeType::Function
         | BytecodeType::GeneratorFunction
+        | BytecodeType::AsyncFunction
+        | BytecodeType::AsyncGeneratorFunction
         | BytecodeType::Class
+        | BytecodeType::Lazy
         | BytecodeType::Unrecognized => {}
         _ => {
           panic!("Invalid: {:?} pointer that points backwards", type_);
@@ -280,7 +379,20 @@ impl BytecodeDecoder {
     match cached_val {
       Some(val) => val,
       None => {
-        let val = self.clone_at(pos).decode_val(registers);
+        let mut decoder = self.clone_at(pos);
+
+        let val = match decoder.peek_type() {
+          BytecodeType::Lazy => match decoder.decode_lazy() {
+            Ok(val) => val,
+            Err(err) => {
+              // Not cached, so the initialization is retried (and throws again) on next access
+              self.bytecode.decode_error.replace(Some(err));
+              return Val::Undefined;
+            }
+          },
+          _ => decoder.decode_val(registers),
+        };
+
         self.bytecode.cache.borrow_mut().insert(pos, val.clone());
 
         val
@@ -288,7 +400,37 @@ impl BytecodeDecoder {
     }
   }
 
-  pub fn decode_function(&mut self, is_generator: bool) -> Val {
+  /// Runs the initialization code of a lazy definition to get its value.
+  fn decode_lazy(&mut self) -> Result<Val, Val> {
+    let pos = self.pos;
+    self.decode_type(); // Lazy
+
+    if !self.bytecode.initializing.borrow_mut().insert(pos) {
+      return Err("Cannot access a value during its own initialization".to_type_error());
+    }
+
+    let register_count = self.decode_byte() as usize;
+
+    let init_fn = VsFunction {
+      bytecode: self.bytecode.clone(),
+      meta_pos: None,
+      is_generator: false,
+      is_async: false,
+      register_count,
+      parameter_count: 0,
+      has_rest_param: false,
+      start: self.pos,
+      binds: Vec::new(),
+    };
+
+    let result = VirtualMachine::default().run(None, &mut Val::Undefined, init_fn.to_val(), vec![]);
+
+    self.bytecode.initializing.borrow_mut().remove(&pos);
+
+    result
+  }
+
+  pub fn decode_function(&mut self, is_generator: bool, is_async: bool) -> Val {
     let meta_pos = if self.decode_byte() == 0 {
       None
     } else {
@@ -301,14 +443,20 @@ impl BytecodeDecoder {
 
     // TODO: Support >256
     let register_count = self.decode_byte() as usize;
-    let parameter_count = self.decode_byte() as usize;
+
+    // The high bit of the parameter count marks the last parameter as a rest parameter
+    let parameter_byte = self.decode_byte();
+    let parameter_count = (parameter_byte & 0x7f) as usize;
+    let has_rest_param = parameter_byte & 0x80 != 0;
 
     VsFunction {
       bytecode: self.bytecode.clone(),
       meta_pos,
       is_generator,
+      is_async,
       register_count,
       parameter_count,
+      has_rest_param,
       start: self.pos,
       binds: Vec::new(),
     }
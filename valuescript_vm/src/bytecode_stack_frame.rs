@@ -1,5 +1,6 @@
 use std::any::Any;
 use std::mem::take;
+use std::rc::Rc;
 
 use valuescript_common::InstructionByte;
 
@@ -8,14 +9,19 @@ use crate::builtins::type_error_builtin::ToTypeError;
 use crate::bytecode_decoder::BytecodeDecoder;
 use crate::bytecode_decoder::BytecodeType;
 use crate::cat_stack_frame::CatStackFrame;
+use crate::getter_call_frame::GetterCallFrame;
 use crate::jsx_element::JsxElement;
 use crate::native_function::ThisWrapper;
 use crate::operations;
 use crate::operations::op_delete;
+use crate::own_properties::has_own_getters;
+use crate::position_table::StackTraceEntry;
+use crate::read_properties_frame::{ReadProperties, ReadPropertiesFrame};
 use crate::stack_frame::FrameStepOk;
 use crate::stack_frame::FrameStepResult;
 use crate::stack_frame::{CallResult, StackFrame, StackFrameTrait};
 use crate::vs_object::VsObject;
+use crate::vs_promise::{await_val, AwaitResult};
 use crate::vs_value::ToDynamicVal;
 use crate::vs_value::ToVal;
 use crate::vs_value::{LoadFunctionResult, Val, ValTrait};
@@ -23,10 +29,16 @@ use crate::vs_value::{LoadFunctionResult, Val, ValTrait};
 #[derive(Clone)]
 pub struct BytecodeStackFrame {
   pub decoder: BytecodeDecoder,
+  pub meta_pos: Option<usize>,
   pub registers: Vec<Val>,
   pub const_this: bool,
   pub param_start: usize,
   pub param_end: usize,
+
+  /// The register which collects arguments beyond `param_end`, if the function has a rest
+  /// parameter.
+  pub rest_param: Option<usize>,
+
   pub this_target: Option<usize>,
   pub return_target: Option<usize>,
   pub catch_setting: Option<CatchSetting>,
@@ -91,6 +103,93 @@ impl BytecodeStackFrame {
     }
   }
 
+  /// Calls the getter of an accessor property, writing the result to the register that follows in
+  /// the bytecode. Getters can't mutate `this`.
+  fn call_getter(&mut self, getter: Val, mut this: Val) -> FrameStepResult {
+    let return_target = self.decoder.decode_register_index();
+
+    match getter.load_function() {
+      // Accessors without a getter read as undefined
+      LoadFunctionResult::NotAFunction => {
+        if let Some(return_target) = return_target {
+          self.registers[return_target] = Val::Undefined;
+        }
+      }
+      LoadFunctionResult::StackFrame(mut new_frame) => {
+        new_frame.write_this(true, this)?;
+
+        self.return_target = return_target;
+        self.this_target = None;
+
+        return Ok(FrameStepOk::Push(new_frame));
+      }
+      LoadFunctionResult::NativeFunction(native_fn) => {
+        let res = native_fn(ThisWrapper::new(true, &mut this), vec![])?;
+
+        if let Some(return_target) = return_target {
+          self.registers[return_target] = res;
+        }
+      }
+    }
+
+    Ok(FrameStepOk::Continue)
+  }
+
+  /// Reads properties in a `ReadPropertiesFrame`, so that getters can be called, writing the result
+  /// to `return_target`.
+  fn read_properties(
+    &mut self,
+    read: &'static ReadProperties,
+    params: Vec<Val>,
+    return_target: Option<usize>,
+  ) -> FrameStepOk {
+    self.return_target = return_target;
+    self.this_target = None;
+
+    FrameStepOk::Push(Box::new(ReadPropertiesFrame::with_params(read, params)))
+  }
+
+  /// Calls the setter of an accessor property. Like a method call, changes the setter makes to
+  /// `this` are written back to the target register.
+  fn call_setter(
+    &mut self,
+    setter: Val,
+    target_index: usize,
+    subscript: &Val,
+    value: Val,
+  ) -> FrameStepResult {
+    match setter.load_function() {
+      LoadFunctionResult::NotAFunction => {
+        return Err(
+          format!("Cannot set property {} which only has a getter", subscript).to_type_error(),
+        );
+      }
+      LoadFunctionResult::StackFrame(mut new_frame) => {
+        new_frame.write_this(false, take(&mut self.registers[target_index]))?;
+        new_frame.write_param(value);
+
+        self.return_target = None;
+        self.this_target = Some(target_index);
+
+        return Ok(FrameStepOk::Push(new_frame));
+      }
+      LoadFunctionResult::NativeFunction(native_fn) => {
+        native_fn(
+          ThisWrapper::new(false, self.registers.get_mut(target_index).unwrap()),
+          vec![value],
+        )?;
+      }
+    }
+
+    Ok(FrameStepOk::Continue)
+  }
+
+  /// Applying a function to this frame's own `this` passes on its constness, like `ThisSubCall`.
+  /// This is how `super.method()` calls work in methods that were called on a const `this`.
+  fn is_const_apply(&self, instruction_byte: &InstructionByte, this_target: usize) -> bool {
+    *instruction_byte == InstructionByte::ConstApply || (this_target == 1 && self.const_this)
+  }
+
   pub fn decode_parameters(&mut self) -> Vec<Val> {
     let mut res = Vec::<Val>::new();
 
@@ -111,27 +210,12 @@ impl BytecodeStackFrame {
     let params = self.decoder.decode_val(&mut self.registers);
 
     match params {
-      Val::Array(array_data) => array_data.elements.clone(),
+      Val::Array(array_data) => array_data.elements.to_vec(),
       _ => panic!("Unexpected non-array params"),
     }
   }
-}
 
-impl StackFrameTrait for BytecodeStackFrame {
-  fn write_this(&mut self, const_: bool, this: Val) -> Result<(), Val> {
-    self.registers[1] = this;
-    self.const_this = const_;
-    Ok(())
-  }
-
-  fn write_param(&mut self, param: Val) {
-    if self.param_start < self.param_end {
-      self.registers[self.param_start] = param;
-      self.param_start += 1;
-    }
-  }
-
-  fn step(&mut self) -> FrameStepResult {
+  fn step_instruction(&mut self) -> FrameStepResult {
     use InstructionByte::*;
 
     let instruction_byte = self.decoder.decode_instruction();
@@ -198,6 +282,10 @@ impl StackFrameTrait for BytecodeStackFrame {
         let mut left = self.decoder.decode_val(&mut self.registers);
         let right = self.decoder.decode_val(&mut self.registers);
 
+        if let Some((getter, _)) = operations::op_sub_accessor(&left, &right) {
+          return self.call_getter(getter, left);
+        }
+
         if let Some(register_index) = self.decoder.decode_register_index() {
           self.registers[register_index] = operations::op_optional_chain(&mut left, &right)?;
         }
@@ -213,9 +301,27 @@ impl StackFrameTrait for BytecodeStackFrame {
       OpRightShiftUnsigned => self.apply_binary_op(operations::op_right_shift_unsigned)?,
 
       TypeOf => self.apply_unary_op(operations::op_typeof)?,
+      ForInKeys => self.apply_unary_op(operations::op_for_in_keys)?,
 
       InstanceOf => self.apply_binary_op(operations::op_instance_of)?,
       In => self.apply_binary_op(operations::op_in)?,
+      ObjectRest => {
+        let input = self.decoder.decode_val(&mut self.registers);
+        let excluded_keys = self.decoder.decode_val(&mut self.registers);
+        let register_index = self.decoder.decode_register_index();
+
+        if has_own_getters(&input) {
+          return Ok(self.read_properties(
+            &operations::OBJECT_REST,
+            vec![input, excluded_keys],
+            register_index,
+          ));
+        }
+
+        if let Some(register_index) = register_index {
+          self.registers[register_index] = operations::op_object_rest(&input, &excluded_keys)?;
+        }
+      }
 
       Call => {
         let fn_ = self.decoder.decode_val(&mut self.registers);
@@ -246,7 +352,20 @@ impl StackFrameTrait for BytecodeStackFrame {
       }
 
       Apply | ConstApply => {
-        let fn_ = self.decoder.decode_val(&mut self.registers);
+        let mut fn_ = self.decoder.decode_val(&mut self.registers);
+
+        // Applying a class runs its constructor on `this`, which is how `super(...)` is compiled
+        if let Some(class) = fn_.as_class_data() {
+          if let Val::Void = class.constructor {
+            self.decoder.decode_register_index();
+            self.decode_parameters();
+            self.decoder.decode_register_index();
+
+            return Ok(FrameStepOk::Continue);
+          }
+
+          fn_ = class.constructor.clone();
+        }
 
         match fn_.load_function() {
           LoadFunctionResult::NotAFunction => {
@@ -256,10 +375,10 @@ impl StackFrameTrait for BytecodeStackFrame {
             let this_target = self.decoder.decode_register_index();
             self.this_target = this_target;
 
-            if this_target.is_some() {
+            if let Some(this_target) = this_target {
               new_frame.write_this(
-                instruction_byte == ConstApply,
-                self.registers[this_target.unwrap()].clone(),
+                self.is_const_apply(&instruction_byte, this_target),
+                self.registers[this_target].clone(),
               )?;
             }
 
@@ -269,8 +388,25 @@ impl StackFrameTrait for BytecodeStackFrame {
 
             return Ok(FrameStepOk::Push(new_frame));
           }
-          LoadFunctionResult::NativeFunction(_native_fn) => {
-            return Err("TODO: apply native functions".to_internal_error());
+          LoadFunctionResult::NativeFunction(native_fn) => {
+            let res = match self.decoder.decode_register_index() {
+              Some(this_target) => {
+                let const_ = self.is_const_apply(&instruction_byte, this_target);
+                let params = self.decode_parameters();
+                native_fn(
+                  ThisWrapper::new(const_, &mut self.registers[this_target]),
+                  params,
+                )?
+              }
+              None => native_fn(
+                ThisWrapper::new(true, &mut Val::Undefined),
+                self.decode_parameters(),
+              )?,
+            };
+
+            if let Some(return_target) = self.decoder.decode_register_index() {
+              self.registers[return_target] = res;
+            }
           }
         }
       }
@@ -288,7 +424,7 @@ impl StackFrameTrait for BytecodeStackFrame {
           None => return Err("bind params should always be array".to_internal_error()),
         };
 
-        let bound_fn = match fn_val.bind(params_array.elements.clone()) {
+        let bound_fn = match fn_val.bind(params_array.elements.to_vec()) {
           Some(bound_fn) => bound_fn,
 
           // Not sure this needs to be an exception in future since compiled
@@ -305,6 +441,10 @@ impl StackFrameTrait for BytecodeStackFrame {
         let mut left = self.decoder.decode_val(&mut self.registers);
         let right = self.decoder.decode_val(&mut self.registers);
 
+        if let Some((getter, _)) = operations::op_sub_accessor(&left, &right) {
+          return self.call_getter(getter, left);
+        }
+
         if let Some(register_index) = self.decoder.decode_register_index() {
           self.registers[register_index] = operations::op_sub(&mut left, &right)?;
         }
@@ -322,17 +462,39 @@ impl StackFrameTrait for BytecodeStackFrame {
 
         let target_index = self.decoder.decode_register_index().unwrap();
 
+        if let Some((_, setter)) =
+          operations::op_sub_accessor(&self.registers[target_index], &subscript)
+        {
+          return self.call_setter(setter, target_index, &subscript, value);
+        }
+
         operations::op_submov(&mut self.registers[target_index], &subscript, value)?;
       }
 
+      ObjectSpread => {
+        let source = self.decoder.decode_val(&mut self.registers);
+        let target_index = self.decoder.decode_register_index().unwrap();
+
+        if has_own_getters(&source) {
+          let target = take(&mut self.registers[target_index]);
+
+          return Ok(self.read_properties(
+            &operations::OBJECT_SPREAD,
+            vec![target, source],
+            Some(target_index),
+          ));
+        }
+
+        operations::op_object_spread(&mut self.registers[target_index], &source)?;
+      }
+
       ConstSubCall => {
         let const_call = true;
 
         let mut obj = self.decoder.decode_val(&mut self.registers);
         let subscript = self.decoder.decode_val(&mut self.registers);
-        let fn_ = obj.sub(&subscript)?;
 
-        match fn_.load_function() {
+        match load_method(&obj, &subscript)? {
           LoadFunctionResult::NotAFunction => {
             return Err("fn_ is not a function".to_type_error());
           }
@@ -364,9 +526,8 @@ impl StackFrameTrait for BytecodeStackFrame {
 
         let obj_i = self.decoder.decode_register_index().unwrap();
         let subscript = self.decoder.decode_val(&mut self.registers);
-        let fn_ = self.registers[obj_i].sub(&subscript)?;
 
-        match fn_.load_function() {
+        match load_method(&self.registers[obj_i], &subscript)? {
           LoadFunctionResult::NotAFunction => {
             return Err("fn_ is not a function".to_type_error());
           }
@@ -422,8 +583,6 @@ impl StackFrameTrait for BytecodeStackFrame {
       UnaryMinus => self.apply_unary_op(operations::op_unary_minus)?,
 
       New => {
-        // TODO: new Array
-
         let class = match self.decoder.decode_val(&mut self.registers).as_class_data() {
           Some(class) => class,
           None => {
@@ -434,6 +593,9 @@ impl StackFrameTrait for BytecodeStackFrame {
         let mut instance = VsObject {
           string_map: Default::default(),
           symbol_map: Default::default(),
+          getter_map: Default::default(),
+          setter_map: Default::default(),
+          private_map: Default::default(),
           prototype: class.prototype.clone(),
         }
         .to_val();
@@ -581,7 +743,7 @@ impl StackFrameTrait for BytecodeStackFrame {
             CatStackFrame::from_vec_val(self.decoder.decode_vec_val(&mut self.registers))
           }
           _ => match self.decoder.decode_val(&mut self.registers) {
-            Val::Array(array) => CatStackFrame::from_vec_val(array.elements.clone()),
+            Val::Array(array) => CatStackFrame::from_vec_val(array.elements.to_vec()),
             _ => {
               return Err(
                 "TODO: cat instruction on non-array (usually type error)".to_internal_error(),
@@ -598,14 +760,16 @@ impl StackFrameTrait for BytecodeStackFrame {
 
       Yield => {
         let val = self.decoder.decode_val(&mut self.registers);
-        self.decoder.decode_register_index(); // TODO: Use this
+        self.this_target = None;
+        self.return_target = self.decoder.decode_register_index();
 
         return Ok(FrameStepOk::Yield(val));
       }
 
       YieldStar => {
         let val = self.decoder.decode_val(&mut self.registers);
-        self.decoder.decode_register_index(); // TODO: Use this
+        self.this_target = None;
+        self.return_target = self.decoder.decode_register_index();
 
         return Ok(FrameStepOk::YieldStar(val));
       }
@@ -664,7 +828,7 @@ impl StackFrameTrait for BytecodeStackFrame {
         }
 
         let children = match children_val {
-          Val::Array(array) => array.elements.clone(),
+          Val::Array(array) => array.elements.to_vec(),
           _ => return Err("Unexpected non-array children".to_type_error()),
         };
 
@@ -685,10 +849,57 @@ impl StackFrameTrait for BytecodeStackFrame {
 
         return Ok(FrameStepOk::Continue);
       }
+
+      Await => {
+        let val = self.decoder.decode_val(&mut self.registers);
+        let dst = self.decoder.decode_register_index();
+
+        match await_val(val)? {
+          AwaitResult::Value(value) => {
+            if let Some(i) = dst {
+              self.registers[i] = value;
+            }
+          }
+          AwaitResult::Push(frame) => {
+            self.this_target = None;
+            self.return_target = dst;
+
+            return Ok(FrameStepOk::Push(frame));
+          }
+        }
+      }
     };
 
     Ok(FrameStepOk::Continue)
   }
+}
+
+impl StackFrameTrait for BytecodeStackFrame {
+  fn write_this(&mut self, const_: bool, this: Val) -> Result<(), Val> {
+    self.registers[1] = this;
+    self.const_this = const_;
+    Ok(())
+  }
+
+  fn write_param(&mut self, param: Val) {
+    if self.param_start < self.param_end {
+      self.registers[self.param_start] = param;
+      self.param_start += 1;
+    } else if let Some(rest_param) = self.rest_param {
+      if let Val::Array(rest) = &mut self.registers[rest_param] {
+        Rc::make_mut(rest).elements.push(param);
+      }
+    }
+  }
+
+  fn step(&mut self) -> FrameStepResult {
+    let result = self.step_instruction();
+
+    match self.decoder.bytecode.decode_error.take() {
+      Some(error) => Err(error),
+      None => result,
+    }
+  }
 
   fn apply_call_result(&mut self, call_result: CallResult) {
     match self.this_target {
@@ -710,6 +921,12 @@ impl StackFrameTrait for BytecodeStackFrame {
     panic!("Not appropriate for BytecodeStackFrame")
   }
 
+  fn write_yield_result(&mut self, value: Val) {
+    if let Some(rt) = self.return_target {
+      self.registers[rt] = value;
+    }
+  }
+
   fn can_catch_exception(&self, _exception: &Val) -> bool {
     self.catch_setting.is_some()
   }
@@ -741,4 +958,31 @@ impl StackFrameTrait for BytecodeStackFrame {
   fn as_any_mut(&mut self) -> &mut dyn Any {
     self
   }
+
+  fn trace_entry(&self) -> Option<StackTraceEntry> {
+    let name = match self.meta_pos {
+      Some(meta_pos) => self.decoder.clone_at(meta_pos).decode_meta().name,
+      None => "".to_string(),
+    };
+
+    // The decoder has moved past the start of the instruction that threw
+    let pos = self.decoder.pos.saturating_sub(1);
+
+    Some(StackTraceEntry {
+      name,
+      position: self.decoder.bytecode.positions.lookup(pos).cloned(),
+    })
+  }
+}
+
+/// Loads the method `obj[subscript]` to be called on `obj`. If it's an accessor, the frame calls
+/// its getter first (see `GetterCallFrame`).
+fn load_method(obj: &Val, subscript: &Val) -> Result<LoadFunctionResult, Val> {
+  if let Some((getter, _)) = operations::op_sub_accessor(obj, subscript) {
+    return Ok(LoadFunctionResult::StackFrame(Box::new(
+      GetterCallFrame::new(getter),
+    )));
+  }
+
+  Ok(obj.sub(subscript)?.load_function())
 }
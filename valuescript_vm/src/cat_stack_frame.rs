@@ -52,10 +52,12 @@ impl CatStackFrame {
 
     if let Val::Array(mut arg) = arg {
       match Rc::get_mut(&mut arg) {
-        Some(arg) => self.res.append(&mut arg.elements),
+        Some(arg) => self
+          .res
+          .extend(arg.elements.take_vec().into_iter().map(fill_hole)),
         None => {
           for item in &arg.elements {
-            self.res.push(item.clone());
+            self.res.push(fill_hole(item.clone()));
           }
         }
       }
@@ -96,6 +98,14 @@ impl CatStackFrame {
   }
 }
 
+/// Holes in spread arrays become undefined.
+fn fill_hole(item: Val) -> Val {
+  match item {
+    Val::Void => Val::Undefined,
+    _ => item,
+  }
+}
+
 impl StackFrameTrait for CatStackFrame {
   fn write_this(&mut self, _const: bool, _this: Val) -> Result<(), Val> {
     Ok(())
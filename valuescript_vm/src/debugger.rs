@@ -0,0 +1,210 @@
+use std::collections::BTreeSet;
+use std::rc::Rc;
+
+use crate::bytecode::{Bytecode, DecoderMaker};
+use crate::bytecode_stack_frame::BytecodeStackFrame;
+use crate::position_table::SourcePosition;
+use crate::stack_frame::StackFrameTrait;
+use crate::virtual_machine::VirtualMachine;
+use crate::vs_value::Val;
+
+/// Runs a program one step at a time, stopping at breakpoints and after step commands.
+///
+/// Steps are by statement when the bytecode has source positions, and by instruction otherwise.
+pub struct Debugger {
+  pub vm: VirtualMachine,
+  pub bytecode: Rc<Bytecode>,
+  breakpoints: BTreeSet<usize>,
+  result: Option<Result<Val, Val>>,
+}
+
+#[derive(Debug)]
+pub enum StopReason {
+  Step,
+  Breakpoint(usize),
+
+  /// The program finished, either returning a value or throwing an uncaught exception (in which
+  /// case the `VirtualMachine`'s stack trace describes where it was thrown).
+  Finished(Result<Val, Val>),
+}
+
+/// A snapshot of a bytecode frame, for inspection by the user.
+#[derive(Debug)]
+pub struct FrameInfo {
+  pub name: String,
+
+  /// The offset of the next instruction to run in this frame.
+  pub offset: usize,
+  pub position: Option<SourcePosition>,
+  pub this: Val,
+
+  /// The initialized registers (excluding `this`) along with their names. The optimizer often
+  /// stores variables directly in `return`, so it's included.
+  pub registers: Vec<(String, Val)>,
+}
+
+impl Debugger {
+  /// Prepares to call the default export of `bytecode` without running any of it.
+  pub fn new(bytecode: Rc<Bytecode>, args: Vec<Val>) -> Result<Debugger, Val> {
+    let fn_ = bytecode.decoder(0).decode_val(&mut vec![]);
+
+    let mut vm = VirtualMachine::default();
+    vm.start(Val::Undefined, fn_, args)?;
+
+    Ok(Debugger {
+      vm,
+      bytecode,
+      breakpoints: Default::default(),
+      result: None,
+    })
+  }
+
+  pub fn add_breakpoint(&mut self, offset: usize) {
+    self.breakpoints.insert(offset);
+  }
+
+  pub fn remove_breakpoint(&mut self, offset: usize) {
+    self.breakpoints.remove(&offset);
+  }
+
+  /// Sets a breakpoint at each place code from the given line starts, returning those offsets
+  /// (which is empty if there's no code there).
+  pub fn add_line_breakpoint(&mut self, path: &str, line: u32) -> Vec<usize> {
+    let offsets = self.bytecode.positions.line_offsets(path, line);
+    self.breakpoints.extend(&offsets);
+
+    offsets
+  }
+
+  pub fn clear_breakpoints(&mut self) {
+    self.breakpoints.clear();
+  }
+
+  /// Whether the next instruction to run has a breakpoint.
+  pub fn at_breakpoint(&self) -> bool {
+    match self.current_offset() {
+      Some(offset) => self.breakpoints.contains(&offset),
+      None => false,
+    }
+  }
+
+  pub fn result(&self) -> Option<&Result<Val, Val>> {
+    self.result.as_ref()
+  }
+
+  /// Executes a single instruction.
+  pub fn step_instruction(&mut self) -> StopReason {
+    self.run_until(|_, _| true)
+  }
+
+  /// Runs until the next statement, including statements in functions that are called.
+  pub fn step_into(&mut self) -> StopReason {
+    self.run_until(|dbg, _| dbg.at_step_boundary())
+  }
+
+  /// Runs until the next statement in the current function or its callers.
+  pub fn step_over(&mut self) -> StopReason {
+    let depth = self.vm.stack.len();
+    self.run_until(|dbg, stack_len| stack_len <= depth && dbg.at_step_boundary())
+  }
+
+  /// Runs until the current function returns to a bytecode frame.
+  pub fn step_out(&mut self) -> StopReason {
+    let depth = self.vm.stack.len();
+    self.run_until(|dbg, stack_len| stack_len < depth && dbg.current_offset().is_some())
+  }
+
+  /// Runs until a breakpoint is reached or the program finishes.
+  pub fn continue_(&mut self) -> StopReason {
+    self.run_until(|_, _| false)
+  }
+
+  /// The bytecode frames on the stack, innermost first.
+  pub fn frames(&self) -> Vec<FrameInfo> {
+    std::iter::once(&self.vm.frame)
+      .chain(self.vm.stack.iter().rev())
+      .enumerate()
+      .filter_map(|(i, frame)| Some((i, frame.as_any().downcast_ref::<BytecodeStackFrame>()?)))
+      .map(|(i, frame)| {
+        let positions = &frame.decoder.bytecode.positions;
+        let offset = frame.decoder.pos;
+        let names = positions.register_names(offset);
+
+        // Frames other than the current one have moved past the call they're waiting on
+        let position_offset = match i {
+          0 => offset,
+          _ => offset.saturating_sub(1),
+        };
+
+        let registers = frame
+          .registers
+          .iter()
+          .enumerate()
+          .filter(|(i, val)| *i != 1 && !matches!(val, Val::Void))
+          .map(|(i, val)| {
+            let name = match names.get(i) {
+              Some(name) if !name.is_empty() => name.clone(),
+              _ => format!("%r{}", i),
+            };
+
+            (name, val.clone())
+          })
+          .collect();
+
+        FrameInfo {
+          name: frame.trace_entry().map(|e| e.name).unwrap_or_default(),
+          offset,
+          position: positions.lookup(position_offset).cloned(),
+          this: frame.registers[1].clone(),
+          registers,
+        }
+      })
+      .collect()
+  }
+
+  fn run_until(&mut self, stop: impl Fn(&Debugger, usize) -> bool) -> StopReason {
+    loop {
+      if let Some(result) = self.advance() {
+        return StopReason::Finished(result);
+      }
+
+      if let Some(offset) = self.current_offset() {
+        if self.breakpoints.contains(&offset) {
+          return StopReason::Breakpoint(offset);
+        }
+      }
+
+      if stop(self, self.vm.stack.len()) {
+        return StopReason::Step;
+      }
+    }
+  }
+
+  fn current_offset(&self) -> Option<usize> {
+    self
+      .vm
+      .frame
+      .as_any()
+      .downcast_ref::<BytecodeStackFrame>()
+      .map(|frame| frame.decoder.pos)
+  }
+
+  fn at_step_boundary(&self) -> bool {
+    match self.vm.frame.as_any().downcast_ref::<BytecodeStackFrame>() {
+      Some(frame) => {
+        let positions = &frame.decoder.bytecode.positions;
+        positions.is_empty() || positions.starts_at(frame.decoder.pos).is_some()
+      }
+      None => false,
+    }
+  }
+
+  /// Executes one step, returning the result of the program if it finished.
+  fn advance(&mut self) -> Option<Result<Val, Val>> {
+    if self.result.is_none() {
+      self.result = self.vm.advance();
+    }
+
+    self.result.clone()
+  }
+}
@@ -1,7 +1,7 @@
 use std::{
   any::Any,
   fmt,
-  mem::{swap, take},
+  mem::{replace, swap, take},
   rc::Rc,
 };
 
@@ -15,22 +15,52 @@ use crate::{
   stack_frame::{CallResult, FrameStepOk, FrameStepResult, StackFrame, StackFrameTrait},
   vs_array::VsArray,
   vs_class::VsClass,
+  vs_promise::{await_val, AwaitResult, VsPromise},
   vs_symbol::VsSymbol,
   vs_value::{dynamic_make_mut, ToDynamicVal, ToVal, Val, VsType},
   LoadFunctionResult, ValTrait,
 };
 
+/// The object returned by calling a generator function.
+///
+/// Async generators run on the same machinery. Their methods return promises of the iteration
+/// results, and exceptions which escape them reject those promises.
 #[derive(Clone, Default)]
 pub struct Generator {
   frame: StackFrame,
   stack: Vec<StackFrame>,
+  is_async: bool,
+  started: bool,
+  done: bool,
+
+  /// The value passed to `return()`, kept until the return signal unwinds the whole generator.
+  /// Finally blocks can yield before that happens.
+  return_value: Val,
+
+  /// Whether the generator has yielded since `return()` was called. If so, the return signal no
+  /// longer propagates out of a `yield*` delegate. Instead, the delegate finishes with the return
+  /// value, which becomes the result of the `yield*` (since the outer generator was resumed by
+  /// `next()`, not `return()`).
+  return_yielded: bool,
 }
 
 impl Generator {
-  pub fn new(frame: StackFrame) -> Generator {
+  pub fn new(frame: StackFrame, is_async: bool) -> Generator {
     Generator {
       frame,
       stack: vec![],
+      is_async,
+      started: false,
+      done: false,
+      return_value: Val::Undefined,
+      return_yielded: false,
+    }
+  }
+
+  fn type_name(&self) -> &'static str {
+    match self.is_async {
+      false => "Generator",
+      true => "AsyncGenerator",
     }
   }
 }
@@ -81,28 +111,34 @@ impl ValTrait for Generator {
   }
 
   fn sub(&self, key: &Val) -> Result<Val, Val> {
-    // TODO: Add symbol for next for performance? (Still needs this fallback)
-    if key.to_string() == "next" {
-      return Ok(NEXT.to_val());
-    }
-
-    if let Val::Symbol(VsSymbol::ITERATOR) = key {
-      return Ok(RETURN_THIS.to_val());
+    if let Val::Symbol(symbol) = key {
+      return Ok(match (symbol, self.is_async) {
+        (VsSymbol::ITERATOR, false) | (VsSymbol::ASYNC_ITERATOR, true) => RETURN_THIS.to_val(),
+        _ => Val::Undefined,
+      });
     }
 
-    Ok(Val::Undefined)
+    // TODO: Add symbol for next for performance? (Still needs this fallback)
+    Ok(match key.to_string().as_str() {
+      "next" => NEXT.to_val(),
+      "return" => RETURN.to_val(),
+      "throw" => THROW.to_val(),
+      _ => Val::Undefined,
+    })
   }
 
   fn has(&self, key: &Val) -> Option<bool> {
-    if key.to_string() == "next" {
-      return Some(true);
+    if let Val::Symbol(symbol) = key {
+      return Some(matches!(
+        (symbol, self.is_async),
+        (VsSymbol::ITERATOR, false) | (VsSymbol::ASYNC_ITERATOR, true)
+      ));
     }
 
-    if let Val::Symbol(VsSymbol::ITERATOR) = key {
-      return Some(true);
-    }
-
-    Some(false)
+    Some(matches!(
+      key.to_string().as_str(),
+      "next" | "return" | "throw"
+    ))
   }
 
   fn submov(&mut self, _key: &Val, _value: Val) -> Result<(), Val> {
@@ -110,17 +146,17 @@ impl ValTrait for Generator {
   }
 
   fn pretty_fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    write!(f, "\x1b[36m[Generator]\x1b[39m")
+    write!(f, "\x1b[36m[{}]\x1b[39m", self.type_name())
   }
 
   fn codify(&self) -> String {
-    "Generator {{ [native data] }}".to_string()
+    format!("{} {{ [native data] }}", self.type_name())
   }
 }
 
 impl fmt::Display for Generator {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    write!(f, "[object Generator]")
+    write!(f, "[object {}]", self.type_name())
   }
 }
 
@@ -131,77 +167,213 @@ impl fmt::Display for Generator {
 // needing to copy.
 //
 static NEXT: NativeFrameFunction = NativeFrameFunction {
-  make_frame: || Box::<GeneratorFrame>::default(),
+  make_frame: || Box::new(GeneratorFrame::new(GeneratorMethod::Next)),
 };
 
+static RETURN: NativeFrameFunction = NativeFrameFunction {
+  make_frame: || Box::new(GeneratorFrame::new(GeneratorMethod::Return)),
+};
+
+static THROW: NativeFrameFunction = NativeFrameFunction {
+  make_frame: || Box::new(GeneratorFrame::new(GeneratorMethod::Throw)),
+};
+
+#[derive(Clone, Copy, Default)]
+enum GeneratorMethod {
+  #[default]
+  Next,
+  Return,
+  Throw,
+}
+
 #[derive(Clone, Default)]
 struct GeneratorFrame {
   generator: Generator,
+  method: GeneratorMethod,
+  arg: Option<Val>,
+  resumed: bool,
+
+  /// An exception which escaped an async generator, to be reported by rejecting the result.
+  rejection: Option<Val>,
 }
 
-impl GeneratorFrame {}
+impl GeneratorFrame {
+  fn new(method: GeneratorMethod) -> GeneratorFrame {
+    GeneratorFrame {
+      method,
+      ..Default::default()
+    }
+  }
 
-impl StackFrameTrait for GeneratorFrame {
-  fn write_this(&mut self, const_: bool, this: Val) -> Result<(), Val> {
-    let mut dynamic = match this {
-      Val::Dynamic(dynamic) => dynamic,
-      _ => return Err("TODO: indirection".to_internal_error()),
-    };
+  fn method_name(&self) -> &'static str {
+    match self.method {
+      GeneratorMethod::Next => "next",
+      GeneratorMethod::Return => "return",
+      GeneratorMethod::Throw => "throw",
+    }
+  }
 
-    if const_ {
-      return Err("Cannot call Generator.next on a const generator".to_type_error());
+  /// Applies the method call to the suspended generator before it continues stepping.
+  fn resume(&mut self) -> FrameStepResult {
+    let arg = self.arg.take().unwrap_or(Val::Undefined);
+
+    if self.generator.done {
+      return match self.method {
+        GeneratorMethod::Next => Ok(self.complete(Val::Undefined)),
+        GeneratorMethod::Return => Ok(self.complete(arg)),
+        GeneratorMethod::Throw => self.throw(arg),
+      };
     }
 
-    let generator = dynamic_make_mut(&mut dynamic)
-      .as_any_mut()
-      .downcast_mut::<Generator>()
-      .ok_or_else(|| "Generator.next called on different object".to_type_error())?;
+    let started = replace(&mut self.generator.started, true);
 
-    self.generator = take(generator);
+    match self.method {
+      GeneratorMethod::Next => {
+        if started {
+          self.generator.frame.write_yield_result(arg);
+        }
 
-    Ok(())
+        Ok(FrameStepOk::Continue)
+      }
+      GeneratorMethod::Return if !started => Ok(self.complete(arg)),
+      GeneratorMethod::Throw if !started => self.throw(arg),
+      GeneratorMethod::Return => {
+        // The return signal unwinds the generator like an exception so that its finally blocks
+        // run. Its catch blocks rethrow it.
+        self.generator.return_value = arg;
+        self.generator.return_yielded = false;
+        self.unwind(VsSymbol::GENERATOR_RETURN.to_val())
+      }
+      GeneratorMethod::Throw => self.unwind(arg),
+    }
   }
 
-  fn write_param(&mut self, _param: Val) {
-    panic!("TODO: results of yield expressions")
+  /// Unwinds the generator's internal stack until a frame catches the exception.
+  fn unwind(&mut self, mut exception: Val) -> FrameStepResult {
+    let is_return = matches!(exception, Val::Symbol(VsSymbol::GENERATOR_RETURN));
+
+    loop {
+      if self.generator.frame.can_catch_exception(&exception) {
+        if is_return {
+          // `yield*` forwards the return to its iterator's `return()`
+          if let Some(yield_star) = self
+            .generator
+            .frame
+            .as_any_mut()
+            .downcast_mut::<YieldStarFrame>()
+          {
+            yield_star.return_value = self.generator.return_value.clone();
+          }
+        }
+
+        self.generator.frame.catch_exception(&mut exception);
+        return Ok(FrameStepOk::Continue);
+      }
+
+      // The internal stack only holds frames which are waiting on a `yield*` delegate
+      match self.generator.stack.pop() {
+        Some(frame) => {
+          self.generator.frame = frame;
+
+          if is_return && self.generator.return_yielded {
+            self.generator.return_yielded = false;
+
+            self.generator.frame.apply_call_result(CallResult {
+              return_: take(&mut self.generator.return_value),
+              this: Val::Undefined,
+            });
+
+            return Ok(FrameStepOk::Continue);
+          }
+        }
+        None => break,
+      }
+    }
+
+    match exception {
+      Val::Symbol(VsSymbol::GENERATOR_RETURN) => {
+        let value = take(&mut self.generator.return_value);
+        Ok(self.complete(value))
+      }
+      _ => self.throw(exception),
+    }
   }
 
-  fn step(&mut self) -> FrameStepResult {
-    let fsr = self.generator.frame.step();
+  /// Throws an exception out of the generator. Async generators reject their result instead.
+  fn throw(&mut self, exception: Val) -> FrameStepResult {
+    if !self.generator.is_async {
+      return Err(exception);
+    }
+
+    self.finish();
+
+    Ok(FrameStepOk::Pop(CallResult {
+      return_: VsPromise::rejected(exception).to_dynamic_val(),
+      this: take(&mut self.generator).to_dynamic_val(),
+    }))
+  }
+
+  fn complete(&mut self, value: Val) -> FrameStepOk {
+    self.finish();
+    self.pop(value, true)
+  }
+
+  fn finish(&mut self) {
+    self.generator = Generator {
+      is_async: self.generator.is_async,
+      done: true,
+      ..Default::default()
+    };
+  }
+
+  fn pop(&mut self, value: Val, done: bool) -> FrameStepOk {
+    let mut return_ = IterationResult { value, done }.to_dynamic_val();
+
+    if self.generator.is_async {
+      return_ = VsPromise::fulfilled(return_).to_dynamic_val();
+    }
+
+    FrameStepOk::Pop(CallResult {
+      return_,
+      this: take(&mut self.generator).to_dynamic_val(),
+    })
+  }
+
+  fn step_generator(&mut self) -> FrameStepResult {
+    let fsr = self.generator.frame.step()?;
 
     match fsr {
-      Err(_) => fsr, // TODO: Stack unwind internal stack first
-      Ok(FrameStepOk::Continue) | Ok(FrameStepOk::Push(_)) => fsr,
-      Ok(FrameStepOk::Pop(call_result)) => match self.generator.stack.pop() {
+      FrameStepOk::Continue | FrameStepOk::Push(_) | FrameStepOk::Spawn(_) => Ok(fsr),
+      FrameStepOk::Pop(call_result) => match self.generator.stack.pop() {
         Some(mut frame) => {
           frame.apply_call_result(call_result);
           swap(&mut frame, &mut self.generator.frame);
 
           Ok(FrameStepOk::Continue)
         }
-        None => Ok(FrameStepOk::Pop(CallResult {
-          return_: IterationResult {
-            value: call_result.return_, // TODO: Assert call_result.this is undefined?
-            done: true,
-          }
-          .to_dynamic_val(),
-          this: take(&mut self.generator).to_dynamic_val(),
-        })),
+        // TODO: Assert call_result.this is undefined?
+        None => Ok(self.complete(call_result.return_)),
       },
-      Ok(FrameStepOk::Yield(val)) => Ok(FrameStepOk::Pop(CallResult {
-        return_: IterationResult {
-          value: val,
-          done: false,
+      FrameStepOk::Yield(val) => {
+        self.generator.return_yielded = true;
+        Ok(self.pop(val, false))
+      }
+      FrameStepOk::YieldStar(iterable) => {
+        let is_async = self.generator.is_async;
+
+        let mut make_iter = Val::Undefined;
+
+        if is_async {
+          make_iter = iterable.sub(&VsSymbol::ASYNC_ITERATOR.to_val())?;
+        }
+
+        if make_iter.is_nullish() {
+          make_iter = iterable.sub(&VsSymbol::ITERATOR.to_val())?;
         }
-        .to_dynamic_val(),
-        this: take(&mut self.generator).to_dynamic_val(),
-      })),
-      Ok(FrameStepOk::YieldStar(iterable)) => {
-        let make_iter = iterable.sub(&VsSymbol::ITERATOR.to_val())?;
 
         let mut frame = 'f: {
           if let Val::Function(make_iter) = &make_iter {
-            if make_iter.is_generator {
+            if make_iter.is_generator && make_iter.is_async == is_async {
               let mut frame: StackFrame = Box::new(make_iter.make_bytecode_frame());
               frame.write_this(true, iterable)?;
 
@@ -209,10 +381,7 @@ impl StackFrameTrait for GeneratorFrame {
             }
           }
 
-          Box::new(YieldStarFrame {
-            iter: YieldStarIter::MakeIterator(iterable, make_iter),
-            iter_result: None,
-          })
+          Box::new(YieldStarFrame::new(iterable, make_iter, is_async))
         };
 
         swap(&mut frame, &mut self.generator.frame);
@@ -223,6 +392,70 @@ impl StackFrameTrait for GeneratorFrame {
     }
   }
 
+  fn internal_can_catch(&self, exception: &Val) -> bool {
+    self.generator.frame.can_catch_exception(exception)
+      || self
+        .generator
+        .stack
+        .iter()
+        .any(|frame| frame.can_catch_exception(exception))
+  }
+}
+
+impl StackFrameTrait for GeneratorFrame {
+  fn write_this(&mut self, const_: bool, this: Val) -> Result<(), Val> {
+    let mut dynamic = match this {
+      Val::Dynamic(dynamic) => dynamic,
+      _ => return Err("TODO: indirection".to_internal_error()),
+    };
+
+    if const_ {
+      return Err(
+        format!(
+          "Cannot call Generator.{} on a const generator",
+          self.method_name()
+        )
+        .to_type_error(),
+      );
+    }
+
+    let generator = dynamic_make_mut(&mut dynamic)
+      .as_any_mut()
+      .downcast_mut::<Generator>()
+      .ok_or_else(|| {
+        format!(
+          "Generator.{} called on different object",
+          self.method_name()
+        )
+        .to_type_error()
+      })?;
+
+    self.generator = take(generator);
+
+    Ok(())
+  }
+
+  fn write_param(&mut self, param: Val) {
+    if self.arg.is_none() {
+      self.arg = Some(param);
+    }
+  }
+
+  fn step(&mut self) -> FrameStepResult {
+    if let Some(reason) = self.rejection.take() {
+      return self.throw(reason);
+    }
+
+    if !self.resumed {
+      self.resumed = true;
+      return self.resume();
+    }
+
+    self
+      .step_generator()
+      .or_else(|exception| self.unwind(exception))
+  }
+
   fn apply_call_result(&mut self, call_result: CallResult) {
     self.generator.frame.apply_call_result(call_result)
   }
@@ -232,10 +465,23 @@ impl StackFrameTrait for GeneratorFrame {
   }
 
   fn can_catch_exception(&self, exception: &Val) -> bool {
-    self.generator.frame.can_catch_exception(exception)
+    self.generator.is_async || self.internal_can_catch(exception)
   }
 
   fn catch_exception(&mut self, exception: &mut Val) {
+    if !self.internal_can_catch(exception) {
+      self.rejection = Some(take(exception));
+      return;
+    }
+
+    while !self.generator.frame.can_catch_exception(exception) {
+      self.generator.frame = self
+        .generator
+        .stack
+        .pop()
+        .expect("can_catch_exception should have been checked before calling this");
+    }
+
     self.generator.frame.catch_exception(exception)
   }
 
@@ -256,6 +502,26 @@ impl StackFrameTrait for GeneratorFrame {
 struct YieldStarFrame {
   iter: YieldStarIter,
   iter_result: Option<Val>,
+
+  /// The value passed to the outer generator's `next`, which is forwarded to the inner iterator.
+  sent: Val,
+
+  /// An exception thrown into the outer generator while this frame was suspended, which is
+  /// forwarded to the inner iterator's `throw` (or `return` for the return signal).
+  exception: Option<Val>,
+
+  suspended: bool,
+
+  /// Whether the inner iterator's `return()` was called and it hasn't yielded since. If it yields,
+  /// the outer generator is resumed by `next()`, so the `yield*` finishes normally instead.
+  returning: bool,
+
+  /// The value passed to the outer generator's `return()`, forwarded to the inner iterator.
+  return_value: Val,
+
+  /// In async generators, the inner iterator's results are promises which need to be awaited.
+  is_async: bool,
+  awaiting: bool,
 }
 
 #[derive(Clone)]
@@ -270,6 +536,52 @@ impl Default for YieldStarIter {
   }
 }
 
+impl YieldStarFrame {
+  fn new(iterable: Val, make_iter: Val, is_async: bool) -> YieldStarFrame {
+    YieldStarFrame {
+      iter: YieldStarIter::MakeIterator(iterable, make_iter),
+      iter_result: None,
+      sent: Val::Undefined,
+      exception: None,
+      suspended: false,
+      returning: false,
+      return_value: Val::Undefined,
+      is_async,
+      awaiting: false,
+    }
+  }
+
+  fn handle_iter_result(&mut self, mut iter_result: Val) -> FrameStepResult {
+    if self.is_async {
+      match await_val(iter_result)? {
+        AwaitResult::Value(value) => iter_result = value,
+        AwaitResult::Push(frame) => {
+          self.awaiting = true;
+          return Ok(FrameStepOk::Push(frame));
+        }
+      }
+    }
+
+    let value = iter_result.sub(&"value".to_val())?; // TODO: mutable subscript to avoid cloning
+
+    if !iter_result.sub(&"done".to_val())?.is_truthy() {
+      self.suspended = true;
+      self.returning = false;
+      return Ok(FrameStepOk::Yield(value));
+    }
+
+    if self.returning {
+      // The inner iterator has finished cleaning up, continue returning from the outer generator
+      return Err(VsSymbol::GENERATOR_RETURN.to_val());
+    }
+
+    Ok(FrameStepOk::Pop(CallResult {
+      return_: value,
+      this: Val::Undefined,
+    }))
+  }
+}
+
 impl StackFrameTrait for YieldStarFrame {
   fn write_this(&mut self, _const: bool, _this: Val) -> Result<(), Val> {
     panic!("Not appropriate for YieldStarFrame")
@@ -280,18 +592,10 @@ impl StackFrameTrait for YieldStarFrame {
   }
 
   fn step(&mut self) -> FrameStepResult {
-    let iter_result = take(&mut self.iter_result);
+    self.suspended = false;
 
-    if let Some(iter_result) = iter_result {
-      let value = iter_result.sub(&"value".to_val())?; // TODO: mutable subscript to avoid cloning
-
-      return match iter_result.sub(&"done".to_val())?.is_truthy() {
-        false => Ok(FrameStepOk::Yield(value)),
-        true => Ok(FrameStepOk::Pop(CallResult {
-          return_: value,
-          this: Val::Undefined,
-        })),
-      };
+    if let Some(iter_result) = take(&mut self.iter_result) {
+      return self.handle_iter_result(iter_result);
     }
 
     match &mut self.iter {
@@ -307,26 +611,28 @@ impl StackFrameTrait for YieldStarFrame {
         LoadFunctionResult::StackFrame(frame) => Ok(FrameStepOk::Push(frame)),
       },
       YieldStarIter::Iterator(iterator) => {
-        let next_fn = iterator.sub(&"next".to_val())?;
-
-        match next_fn.load_function() {
-          LoadFunctionResult::NotAFunction => {
-            Err("iterator.next is not a function".to_type_error())
+        let (method, arg) = match self.exception.take() {
+          None => ("next", replace(&mut self.sent, Val::Undefined)),
+          Some(Val::Symbol(VsSymbol::GENERATOR_RETURN)) => {
+            self.returning = true;
+            ("return", take(&mut self.return_value))
           }
+          Some(exception) => ("throw", exception),
+        };
+
+        match iterator.sub(&method.to_val())?.load_function() {
+          LoadFunctionResult::NotAFunction => match method {
+            "next" => Err("iterator.next is not a function".to_type_error()),
+            "return" => Err(VsSymbol::GENERATOR_RETURN.to_val()),
+            _ => Err(arg),
+          },
           LoadFunctionResult::NativeFunction(native_fn) => {
-            let iter_result = native_fn(ThisWrapper::new(false, iterator), vec![])?;
-            let value = iter_result.sub(&"value".to_val())?;
-
-            match iter_result.sub(&"done".to_val())?.is_truthy() {
-              false => Ok(FrameStepOk::Yield(value)),
-              true => Ok(FrameStepOk::Pop(CallResult {
-                return_: value,
-                this: Val::Undefined,
-              })),
-            }
+            let iter_result = native_fn(ThisWrapper::new(false, iterator), vec![arg])?;
+            self.handle_iter_result(iter_result)
           }
           LoadFunctionResult::StackFrame(mut frame) => {
             frame.write_this(false, take(iterator))?;
+            frame.write_param(arg);
 
             Ok(FrameStepOk::Push(frame))
           }
@@ -336,6 +642,12 @@ impl StackFrameTrait for YieldStarFrame {
   }
 
   fn apply_call_result(&mut self, call_result: CallResult) {
+    if self.awaiting {
+      self.awaiting = false;
+      self.iter_result = Some(call_result.return_);
+      return;
+    }
+
     let iter = &mut self.iter;
     let mut iter_result = None;
 
@@ -356,11 +668,17 @@ impl StackFrameTrait for YieldStarFrame {
     panic!("Not appropriate for YieldStarFrame")
   }
 
+  fn write_yield_result(&mut self, value: Val) {
+    self.sent = value;
+  }
+
   fn can_catch_exception(&self, _exception: &Val) -> bool {
-    false
+    self.suspended
   }
 
-  fn catch_exception(&mut self, _exception: &mut Val) {}
+  fn catch_exception(&mut self, exception: &mut Val) {
+    self.exception = Some(take(exception));
+  }
 
   fn clone_to_stack_frame(&self) -> StackFrame {
     Box::new(self.clone())
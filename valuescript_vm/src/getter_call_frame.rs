@@ -0,0 +1,127 @@
+use std::any::Any;
+use std::mem::take;
+
+use crate::builtins::internal_error_builtin::ToInternalError;
+use crate::builtins::type_error_builtin::ToTypeError;
+use crate::native_frame_function::{call, Call};
+use crate::native_function::ThisWrapper;
+use crate::stack_frame::{CallResult, FrameStepOk, FrameStepResult, StackFrame, StackFrameTrait};
+use crate::vs_value::{LoadFunctionResult, Val, ValTrait};
+
+/// Calls a method which is read through a getter, as in `obj.f()` where `f` is an accessor. The
+/// getter needs its own frame, so this frame calls it first, and then calls the function it returned
+/// on the same `this`.
+#[derive(Clone)]
+pub struct GetterCallFrame {
+  const_: bool,
+  this: Val,
+  args: Vec<Val>,
+  state: GetterCallState,
+}
+
+#[derive(Clone, Default)]
+enum GetterCallState {
+  Getting(Val),
+  Got(Val),
+  #[default]
+  Calling,
+  Returned(CallResult),
+}
+
+impl GetterCallFrame {
+  pub fn new(getter: Val) -> GetterCallFrame {
+    GetterCallFrame {
+      const_: false,
+      this: Val::Undefined,
+      args: vec![],
+      state: GetterCallState::Getting(getter),
+    }
+  }
+}
+
+impl StackFrameTrait for GetterCallFrame {
+  fn write_this(&mut self, const_: bool, this: Val) -> Result<(), Val> {
+    self.const_ = const_;
+    self.this = this;
+    Ok(())
+  }
+
+  fn write_param(&mut self, param: Val) {
+    self.args.push(param);
+  }
+
+  fn step(&mut self) -> FrameStepResult {
+    let fn_ = match take(&mut self.state) {
+      // Accessors without a getter read as undefined
+      GetterCallState::Getting(Val::Void) => Val::Undefined,
+      GetterCallState::Getting(getter) => match call(&getter, self.this.clone(), vec![])? {
+        Call::Native(fn_) => fn_,
+        Call::Frame(frame) => {
+          // The getter's result arrives in `apply_call_result`
+          self.state = GetterCallState::Getting(getter);
+          return Ok(FrameStepOk::Push(frame));
+        }
+      },
+      GetterCallState::Got(fn_) => fn_,
+      GetterCallState::Calling => {
+        return Err("Unexpected step during GetterCallFrame call".to_internal_error())
+      }
+      GetterCallState::Returned(call_result) => return Ok(FrameStepOk::Pop(call_result)),
+    };
+
+    match fn_.load_function() {
+      LoadFunctionResult::NotAFunction => Err("fn_ is not a function".to_type_error()),
+      LoadFunctionResult::NativeFunction(native_fn) => {
+        let return_ = native_fn(
+          ThisWrapper::new(self.const_, &mut self.this),
+          take(&mut self.args),
+        )?;
+
+        Ok(FrameStepOk::Pop(CallResult {
+          return_,
+          this: take(&mut self.this),
+        }))
+      }
+      LoadFunctionResult::StackFrame(mut new_frame) => {
+        new_frame.write_this(self.const_, take(&mut self.this))?;
+
+        for arg in take(&mut self.args) {
+          new_frame.write_param(arg);
+        }
+
+        self.state = GetterCallState::Calling;
+
+        Ok(FrameStepOk::Push(new_frame))
+      }
+    }
+  }
+
+  fn apply_call_result(&mut self, call_result: CallResult) {
+    self.state = match self.state {
+      GetterCallState::Calling => GetterCallState::Returned(call_result),
+      _ => GetterCallState::Got(call_result.return_),
+    };
+  }
+
+  fn get_call_result(&mut self) -> CallResult {
+    panic!("Not appropriate for GetterCallFrame")
+  }
+
+  fn can_catch_exception(&self, _exception: &Val) -> bool {
+    false
+  }
+
+  fn catch_exception(&mut self, _exception: &mut Val) {}
+
+  fn clone_to_stack_frame(&self) -> StackFrame {
+    Box::new(self.clone())
+  }
+
+  fn as_any(&self) -> &dyn Any {
+    self
+  }
+
+  fn as_any_mut(&mut self) -> &mut dyn Any {
+    self
+  }
+}
@@ -0,0 +1,187 @@
+use std::borrow::Borrow;
+use std::collections::{btree_map, BTreeMap};
+use std::fmt;
+use std::hash::Hash;
+
+use hamt::Hamt;
+
+/// A map which is a `BTreeMap` while it's small, and a persistent hash trie (`Hamt`) once it grows
+/// past `SMALL_MAX_LEN` entries. Since maps are copied when a shared one is modified (values are
+/// values), this means large maps only copy the path to the modified entry instead of all of
+/// their entries.
+///
+/// Either way, iteration is in key order. For the hash trie this means sorting, which is fine
+/// because iterating is already O(n).
+#[derive(Clone)]
+pub enum HybridMap<K, V> {
+  Small(BTreeMap<K, V>),
+  Large(Hamt<K, V>),
+}
+
+impl<K: Ord + Hash + Clone, V: Clone> HybridMap<K, V> {
+  /// Maps only go back to being small when they shrink to half of this, so that inserting and
+  /// removing around the boundary doesn't convert back and forth.
+  pub const SMALL_MAX_LEN: usize = 64;
+
+  pub fn new() -> Self {
+    HybridMap::Small(BTreeMap::new())
+  }
+
+  pub fn len(&self) -> usize {
+    match self {
+      HybridMap::Small(map) => map.len(),
+      HybridMap::Large(map) => map.len(),
+    }
+  }
+
+  pub fn is_empty(&self) -> bool {
+    match self {
+      HybridMap::Small(map) => map.is_empty(),
+      HybridMap::Large(map) => map.is_empty(),
+    }
+  }
+
+  pub fn get<Q>(&self, key: &Q) -> Option<&V>
+  where
+    K: Borrow<Q>,
+    Q: Ord + Hash + ?Sized,
+  {
+    match self {
+      HybridMap::Small(map) => map.get(key),
+      HybridMap::Large(map) => map.get(key),
+    }
+  }
+
+  pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+  where
+    K: Borrow<Q>,
+    Q: Ord + Hash + ?Sized,
+  {
+    match self {
+      HybridMap::Small(map) => map.get_mut(key),
+      HybridMap::Large(map) => map.get_mut(key),
+    }
+  }
+
+  pub fn contains_key<Q>(&self, key: &Q) -> bool
+  where
+    K: Borrow<Q>,
+    Q: Ord + Hash + ?Sized,
+  {
+    self.get(key).is_some()
+  }
+
+  pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+    let res = match self {
+      HybridMap::Small(map) => map.insert(key, value),
+      HybridMap::Large(map) => map.insert(key, value),
+    };
+
+    self.fit();
+
+    res
+  }
+
+  pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+  where
+    K: Borrow<Q>,
+    Q: Ord + Hash + ?Sized,
+  {
+    let res = match self {
+      HybridMap::Small(map) => map.remove(key),
+      HybridMap::Large(map) => map.remove(key),
+    };
+
+    self.fit();
+
+    res
+  }
+
+  pub fn iter(&self) -> HybridMapIter<'_, K, V> {
+    match self {
+      HybridMap::Small(map) => HybridMapIter::Small(map.iter()),
+      HybridMap::Large(map) => {
+        let mut entries = map.iter().collect::<Vec<_>>();
+        entries.sort_unstable_by_key(|(key, _)| *key);
+
+        HybridMapIter::Large(entries.into_iter())
+      }
+    }
+  }
+
+  /// Switches representation if the length calls for it.
+  fn fit(&mut self) {
+    match self {
+      HybridMap::Small(map) if map.len() > Self::SMALL_MAX_LEN => {
+        *self = HybridMap::Large(std::mem::take(map).into_iter().collect());
+      }
+      HybridMap::Large(map) if map.len() <= Self::SMALL_MAX_LEN / 2 => {
+        *self = HybridMap::Small(
+          map
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect(),
+        );
+      }
+      _ => {}
+    }
+  }
+}
+
+impl<K: Ord + Hash + Clone, V: Clone> Default for HybridMap<K, V> {
+  fn default() -> Self {
+    HybridMap::new()
+  }
+}
+
+impl<K: Ord + Hash + Clone, V: Clone> From<BTreeMap<K, V>> for HybridMap<K, V> {
+  fn from(map: BTreeMap<K, V>) -> Self {
+    let mut res = HybridMap::Small(map);
+    res.fit();
+
+    res
+  }
+}
+
+impl<K: Ord + Hash + Clone, V: Clone, const N: usize> From<[(K, V); N]> for HybridMap<K, V> {
+  fn from(entries: [(K, V); N]) -> Self {
+    BTreeMap::from(entries).into()
+  }
+}
+
+impl<K: Ord + Hash + Clone, V: Clone> FromIterator<(K, V)> for HybridMap<K, V> {
+  fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+    iter.into_iter().collect::<BTreeMap<K, V>>().into()
+  }
+}
+
+impl<K: Ord + Hash + Clone + fmt::Debug, V: Clone + fmt::Debug> fmt::Debug for HybridMap<K, V> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_map().entries(self.iter()).finish()
+  }
+}
+
+impl<'a, K: Ord + Hash + Clone, V: Clone> IntoIterator for &'a HybridMap<K, V> {
+  type Item = (&'a K, &'a V);
+  type IntoIter = HybridMapIter<'a, K, V>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+pub enum HybridMapIter<'a, K, V> {
+  Small(btree_map::Iter<'a, K, V>),
+  Large(std::vec::IntoIter<(&'a K, &'a V)>),
+}
+
+impl<'a, K, V> Iterator for HybridMapIter<'a, K, V> {
+  type Item = (&'a K, &'a V);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    match self {
+      HybridMapIter::Small(iter) => iter.next(),
+      HybridMapIter::Large(iter) => iter.next(),
+    }
+  }
+}
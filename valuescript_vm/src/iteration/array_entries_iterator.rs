@@ -83,6 +83,7 @@ impl ValTrait for ArrayEntriesIterator {
         VsSymbol::ITERATOR => {
           return Ok(RETURN_THIS.to_val());
         }
+        VsSymbol::GENERATOR_RETURN | VsSymbol::ASYNC_ITERATOR => {}
       }
     }
 
@@ -83,6 +83,7 @@ impl ValTrait for ArrayIterator {
         VsSymbol::ITERATOR => {
           return Ok(RETURN_THIS.to_val());
         }
+        VsSymbol::GENERATOR_RETURN | VsSymbol::ASYNC_ITERATOR => {}
       }
     }
 
@@ -10,6 +10,7 @@ pub fn iterator_has(key: &Val) -> Option<bool> {
       VsSymbol::ITERATOR => {
         return Some(true);
       }
+      VsSymbol::GENERATOR_RETURN | VsSymbol::ASYNC_ITERATOR => {}
     }
   }
 
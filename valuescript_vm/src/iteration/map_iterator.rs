@@ -0,0 +1,192 @@
+use std::{fmt, rc::Rc};
+
+use num_bigint::BigInt;
+
+use crate::{
+  builtins::{internal_error_builtin::ToInternalError, type_error_builtin::ToTypeError},
+  native_function::{native_fn, NativeFunction},
+  vs_array::VsArray,
+  vs_class::VsClass,
+  vs_symbol::VsSymbol,
+  vs_value::{dynamic_make_mut, ToDynamicVal, ToVal, Val, VsType},
+  LoadFunctionResult, ValTrait,
+};
+
+use super::{
+  iteration_result::IterationResult, iterator_has::iterator_has, return_this::RETURN_THIS,
+};
+
+#[derive(Clone, Copy)]
+pub enum MapIteratorKind {
+  Keys,
+  Values,
+  Entries,
+}
+
+/// Iterates a `Map` or a `Set`. Sets iterate like maps whose keys and values are both the set's
+/// values.
+///
+/// The iterator holds its own copy of the collection, so changes made during iteration aren't
+/// seen, just like any other use of a value.
+#[derive(Clone)]
+pub struct MapIterator {
+  pub collection: Val,
+  pub pos: usize,
+  pub kind: MapIteratorKind,
+}
+
+impl MapIterator {
+  pub fn new(collection: Val, kind: MapIteratorKind) -> MapIterator {
+    MapIterator {
+      collection,
+      pos: 0,
+      kind,
+    }
+  }
+
+  /// Skips the slots left behind by deleted entries.
+  fn next_entry(&mut self) -> Option<(Val, Val)> {
+    loop {
+      let entry = match &self.collection {
+        Val::Map(map) if self.pos < map.slot_count() => {
+          map.slot(self.pos).map(|(k, v)| (k.clone(), v.clone()))
+        }
+        Val::Set(set) if self.pos < set.slot_count() => {
+          set.slot(self.pos).map(|v| (v.clone(), v.clone()))
+        }
+        _ => return None,
+      };
+
+      self.pos += 1;
+
+      if entry.is_some() {
+        return entry;
+      }
+    }
+  }
+}
+
+impl ValTrait for MapIterator {
+  fn typeof_(&self) -> VsType {
+    VsType::Object
+  }
+
+  fn to_number(&self) -> f64 {
+    f64::NAN
+  }
+
+  fn to_index(&self) -> Option<usize> {
+    None
+  }
+
+  fn is_primitive(&self) -> bool {
+    false
+  }
+
+  fn is_truthy(&self) -> bool {
+    true
+  }
+
+  fn is_nullish(&self) -> bool {
+    false
+  }
+
+  fn bind(&self, _params: Vec<Val>) -> Option<Val> {
+    None
+  }
+
+  fn as_bigint_data(&self) -> Option<BigInt> {
+    None
+  }
+
+  fn as_array_data(&self) -> Option<Rc<VsArray>> {
+    None
+  }
+
+  fn as_class_data(&self) -> Option<Rc<VsClass>> {
+    None
+  }
+
+  fn load_function(&self) -> LoadFunctionResult {
+    LoadFunctionResult::NotAFunction
+  }
+
+  fn sub(&self, key: &Val) -> Result<Val, Val> {
+    if key.to_string() == "next" {
+      return Ok(NEXT.to_val());
+    }
+
+    if let Val::Symbol(key) = key {
+      match key {
+        VsSymbol::ITERATOR => {
+          return Ok(RETURN_THIS.to_val());
+        }
+        VsSymbol::GENERATOR_RETURN | VsSymbol::ASYNC_ITERATOR => {}
+      }
+    }
+
+    Ok(Val::Undefined)
+  }
+
+  fn has(&self, key: &Val) -> Option<bool> {
+    iterator_has(key)
+  }
+
+  fn submov(&mut self, _key: &Val, _value: Val) -> Result<(), Val> {
+    Err("Cannot assign to subscript of map iterator".to_type_error())
+  }
+
+  fn pretty_fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match &self.collection {
+      Val::Set(_) => write!(f, "\x1b[36m[SetIterator]\x1b[39m"),
+      _ => write!(f, "\x1b[36m[MapIterator]\x1b[39m"),
+    }
+  }
+
+  fn codify(&self) -> String {
+    format!(
+      "MapIterator({{ collection: {}, pos: {} }})",
+      self.collection.codify(),
+      self.pos
+    )
+  }
+}
+
+impl fmt::Display for MapIterator {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match &self.collection {
+      Val::Set(_) => write!(f, "[object Set Iterator]"),
+      _ => write!(f, "[object Map Iterator]"),
+    }
+  }
+}
+
+static NEXT: NativeFunction = native_fn(|mut this, _| {
+  let dynamic = match this.get_mut()? {
+    Val::Dynamic(dynamic) => dynamic,
+    _ => return Err("TODO: indirection".to_internal_error()),
+  };
+
+  let iter = dynamic_make_mut(dynamic)
+    .as_any_mut()
+    .downcast_mut::<MapIterator>()
+    .ok_or_else(|| "MapIterator.next called on different object".to_type_error())?;
+
+  Ok(
+    match iter.next_entry() {
+      Some((key, value)) => IterationResult {
+        value: match iter.kind {
+          MapIteratorKind::Keys => key,
+          MapIteratorKind::Values => value,
+          MapIteratorKind::Entries => vec![key, value].to_val(),
+        },
+        done: false,
+      },
+      None => IterationResult {
+        value: Val::Undefined,
+        done: true,
+      },
+    }
+    .to_dynamic_val(),
+  )
+});
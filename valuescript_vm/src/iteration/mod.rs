@@ -2,5 +2,6 @@ pub mod array_entries_iterator;
 pub mod array_iterator;
 pub mod iteration_result;
 mod iterator_has;
+pub mod map_iterator;
 pub mod return_this;
 pub mod string_iterator;
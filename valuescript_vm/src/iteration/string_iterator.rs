@@ -119,6 +119,7 @@ impl ValTrait for StringIterator {
         VsSymbol::ITERATOR => {
           return Ok(RETURN_THIS.to_val());
         }
+        VsSymbol::GENERATOR_RETURN | VsSymbol::ASYNC_ITERATOR => {}
       }
     }
 
@@ -1,25 +1,37 @@
+pub mod array_elements;
 mod array_higher_functions;
 mod array_methods;
 mod bigint_methods;
 pub mod binary_op;
 mod builtins;
 mod bytecode;
-mod bytecode_decoder;
+pub mod bytecode_decoder;
 mod bytecode_stack_frame;
 pub mod cat_stack_frame;
 mod copy_counter;
+pub mod debugger;
 mod first_stack_frame;
 mod generator;
+mod getter_call_frame;
 mod helpers;
+pub mod hybrid_map;
 mod iteration;
 pub mod jsx_element;
 mod make_generator_frame;
+mod make_promise_frame;
+mod map_methods;
 pub mod native_frame_function;
 pub mod native_function;
 mod number_methods;
 pub mod operations;
+pub mod own_properties;
+pub mod position_table;
+pub mod read_properties_frame;
+pub mod sendable_val;
+mod set_methods;
 mod stack_frame;
 mod string_methods;
+mod string_replace_frame;
 mod todo_fn;
 pub mod unary_op;
 mod val_storage;
@@ -27,7 +39,12 @@ mod virtual_machine;
 pub mod vs_array;
 pub mod vs_class;
 mod vs_function;
+pub mod vs_map;
 pub mod vs_object;
+pub mod vs_private_name;
+pub mod vs_promise;
+pub mod vs_regexp;
+pub mod vs_set;
 mod vs_storage_ptr;
 mod vs_symbol;
 pub mod vs_value;
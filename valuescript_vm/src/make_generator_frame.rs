@@ -10,11 +10,15 @@ use crate::{
 #[derive(Clone)]
 pub struct MakeGeneratorFrame {
   pub frame: Option<BytecodeStackFrame>,
+  pub is_async: bool,
 }
 
 impl MakeGeneratorFrame {
-  pub fn new(frame: BytecodeStackFrame) -> MakeGeneratorFrame {
-    MakeGeneratorFrame { frame: Some(frame) }
+  pub fn new(frame: BytecodeStackFrame, is_async: bool) -> MakeGeneratorFrame {
+    MakeGeneratorFrame {
+      frame: Some(frame),
+      is_async,
+    }
   }
 
   fn frame_mut(&mut self) -> &mut BytecodeStackFrame {
@@ -37,7 +41,7 @@ impl StackFrameTrait for MakeGeneratorFrame {
 
   fn step(&mut self) -> FrameStepResult {
     Ok(FrameStepOk::Pop(CallResult {
-      return_: Generator::new(Box::new(self.take_frame())).to_dynamic_val(),
+      return_: Generator::new(Box::new(self.take_frame()), self.is_async).to_dynamic_val(),
       this: Val::Undefined,
     }))
   }
@@ -0,0 +1,84 @@
+use std::{any::Any, mem::take};
+
+use crate::{
+  stack_frame::{CallResult, FrameStepOk, FrameStepResult, StackFrame, StackFrameTrait},
+  vs_promise::VsPromise,
+  vs_value::{ToDynamicVal, Val},
+};
+
+/// Wraps a job (such as the body of an `async` function) in a new promise. The promise is spawned
+/// so that the `VirtualMachine` runs the job even if nothing awaits it.
+#[derive(Clone)]
+pub struct MakePromiseFrame {
+  job: Option<StackFrame>,
+  this: Val,
+  promise: Option<VsPromise>,
+}
+
+impl MakePromiseFrame {
+  pub fn new(job: StackFrame) -> MakePromiseFrame {
+    MakePromiseFrame {
+      job: Some(job),
+      this: Val::Undefined,
+      promise: None,
+    }
+  }
+
+  fn job_mut(&mut self) -> &mut StackFrame {
+    self.job.as_mut().unwrap()
+  }
+}
+
+impl StackFrameTrait for MakePromiseFrame {
+  fn write_this(&mut self, _const: bool, this: Val) -> Result<(), Val> {
+    self.this = this.clone();
+
+    // The job runs after the call has returned, so it can't mutate this
+    self.job_mut().write_this(true, this)
+  }
+
+  fn write_param(&mut self, param: Val) {
+    self.job_mut().write_param(param);
+  }
+
+  fn step(&mut self) -> FrameStepResult {
+    Ok(match take(&mut self.job) {
+      Some(job) => {
+        let promise = VsPromise::new(job);
+        self.promise = Some(promise.clone());
+
+        FrameStepOk::Spawn(promise)
+      }
+      None => FrameStepOk::Pop(CallResult {
+        return_: take(&mut self.promise).unwrap().to_dynamic_val(),
+        this: take(&mut self.this),
+      }),
+    })
+  }
+
+  fn apply_call_result(&mut self, _call_result: CallResult) {
+    panic!("Not appropriate for MakePromiseFrame");
+  }
+
+  fn get_call_result(&mut self) -> CallResult {
+    panic!("Not appropriate for MakePromiseFrame")
+  }
+
+  fn can_catch_exception(&self, _exception: &Val) -> bool {
+    false
+  }
+
+  fn catch_exception(&mut self, _exception: &mut Val) {}
+
+  fn clone_to_stack_frame(&self) -> StackFrame {
+    Box::new(self.clone())
+  }
+
+  fn as_any(&self) -> &dyn Any {
+    self
+  }
+
+  fn as_any_mut(&mut self) -> &mut dyn Any {
+    self
+  }
+}
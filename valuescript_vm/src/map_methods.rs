@@ -0,0 +1,101 @@
+use std::rc::Rc;
+
+use crate::builtins::internal_error_builtin::ToInternalError;
+use crate::iteration::map_iterator::{MapIterator, MapIteratorKind};
+use crate::native_function::{native_fn, NativeFunction};
+use crate::vs_map::VsMap;
+use crate::vs_symbol::VsSymbol;
+use crate::vs_value::{ToDynamicVal, ToVal, Val};
+
+pub fn op_sub_map(map: &Rc<VsMap>, key: &Val) -> Val {
+  if let Val::Symbol(symbol) = key {
+    return match symbol {
+      VsSymbol::ITERATOR => ENTRIES.to_val(),
+      VsSymbol::GENERATOR_RETURN | VsSymbol::ASYNC_ITERATOR => Val::Undefined,
+    };
+  }
+
+  Val::Static(match key.to_string().as_str() {
+    "clear" => &CLEAR,
+    "delete" => &DELETE,
+    "entries" => &ENTRIES,
+    // forEach: Not included because it cannot work as expected in ValueScript
+    // (Use a for..of loop)
+    "get" => &GET,
+    "has" => &HAS,
+    "keys" => &KEYS,
+    "set" => &SET,
+    "size" => return (map.len() as f64).to_val(),
+    "values" => &VALUES,
+    _ => return Val::Undefined,
+  })
+}
+
+static CLEAR: NativeFunction = native_fn(|mut this, _params| {
+  match this.get_mut()? {
+    Val::Map(map) => Rc::make_mut(map).clear(),
+    _ => return Err("map indirection".to_internal_error()),
+  };
+
+  Ok(Val::Undefined)
+});
+
+static DELETE: NativeFunction = native_fn(|mut this, params| {
+  let key = params.first().cloned().unwrap_or(Val::Undefined);
+
+  Ok(match this.get_mut()? {
+    Val::Map(map) => match map.has(&key) {
+      true => Rc::make_mut(map).remove(&key).to_val(),
+      false => false.to_val(),
+    },
+    _ => return Err("map indirection".to_internal_error()),
+  })
+});
+
+static ENTRIES: NativeFunction = native_fn(|this, _params| match this.get() {
+  map @ Val::Map(_) => Ok(MapIterator::new(map, MapIteratorKind::Entries).to_dynamic_val()),
+  _ => Err("map indirection".to_internal_error()),
+});
+
+static GET: NativeFunction = native_fn(|this, params| {
+  let key = params.first().cloned().unwrap_or(Val::Undefined);
+
+  Ok(match this.get() {
+    Val::Map(map) => map.get(&key).cloned().unwrap_or(Val::Undefined),
+    _ => return Err("map indirection".to_internal_error()),
+  })
+});
+
+static HAS: NativeFunction = native_fn(|this, params| {
+  let key = params.first().cloned().unwrap_or(Val::Undefined);
+
+  Ok(match this.get() {
+    Val::Map(map) => map.has(&key).to_val(),
+    _ => return Err("map indirection".to_internal_error()),
+  })
+});
+
+static KEYS: NativeFunction = native_fn(|this, _params| match this.get() {
+  map @ Val::Map(_) => Ok(MapIterator::new(map, MapIteratorKind::Keys).to_dynamic_val()),
+  _ => Err("map indirection".to_internal_error()),
+});
+
+static SET: NativeFunction = native_fn(|mut this, params| {
+  let mut params = params.into_iter();
+  let key = params.next().unwrap_or(Val::Undefined);
+  let value = params.next().unwrap_or(Val::Undefined);
+
+  let this = this.get_mut()?;
+
+  match this {
+    Val::Map(map) => Rc::make_mut(map).insert(key, value),
+    _ => return Err("map indirection".to_internal_error()),
+  };
+
+  Ok(this.clone())
+});
+
+static VALUES: NativeFunction = native_fn(|this, _params| match this.get() {
+  map @ Val::Map(_) => Ok(MapIterator::new(map, MapIteratorKind::Values).to_dynamic_val()),
+  _ => Err("map indirection".to_internal_error()),
+});
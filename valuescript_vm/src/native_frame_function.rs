@@ -5,6 +5,7 @@ use num_bigint::BigInt;
 
 use crate::builtins::internal_error_builtin::ToInternalError;
 use crate::builtins::type_error_builtin::ToTypeError;
+use crate::native_function::ThisWrapper;
 use crate::stack_frame::StackFrame;
 use crate::vs_array::VsArray;
 use crate::vs_class::VsClass;
@@ -85,3 +86,29 @@ impl fmt::Display for NativeFrameFunction {
     write!(f, "function() {{ [native code] }}")
   }
 }
+
+pub enum Call {
+  Native(Val),
+  Frame(StackFrame),
+}
+
+/// Calls `function` from a native frame. Native functions are run immediately, other functions give
+/// a frame to push, which reports back via `apply_call_result`.
+pub fn call(function: &Val, this: Val, args: Vec<Val>) -> Result<Call, Val> {
+  match function.load_function() {
+    LoadFunctionResult::NotAFunction => Err("Not a function".to_type_error()),
+    LoadFunctionResult::NativeFunction(native_fn) => Ok(Call::Native(native_fn(
+      ThisWrapper::new(true, &mut this.clone()),
+      args,
+    )?)),
+    LoadFunctionResult::StackFrame(mut frame) => {
+      frame.write_this(true, this)?;
+
+      for arg in args {
+        frame.write_param(arg);
+      }
+
+      Ok(Call::Frame(frame))
+    }
+  }
+}
@@ -128,3 +128,54 @@ fn format_exponential(number: f64, precision: Option<usize>) -> Val {
 
   string.to_val()
 }
+
+/// Formats a number the way JS's `Number.prototype.toString` does, i.e. the shortest digits that
+/// round trip, switching to exponential notation outside `1e-7 < |x| < 1e21`.
+pub fn format_number(number: f64) -> String {
+  if number.is_nan() {
+    return "NaN".to_string();
+  }
+
+  if number == 0.0 {
+    return "0".to_string();
+  }
+
+  if number.is_infinite() {
+    return match number.is_sign_positive() {
+      true => "Infinity",
+      false => "-Infinity",
+    }
+    .to_string();
+  }
+
+  if number < 0.0 {
+    return format!("-{}", format_number(-number));
+  }
+
+  let exp_format = format!("{:e}", number);
+  let (mantissa, exponent) = exp_format.split_once('e').unwrap();
+  let digits = mantissa.replace('.', "");
+  let k = digits.len() as i32;
+  let n = exponent.parse::<i32>().unwrap() + 1;
+
+  if k <= n && n <= 21 {
+    format!("{}{}", digits, "0".repeat((n - k) as usize))
+  } else if 0 < n && n <= 21 {
+    format!("{}.{}", &digits[..n as usize], &digits[n as usize..])
+  } else if -6 < n && n <= 0 {
+    format!("0.{}{}", "0".repeat(-n as usize), digits)
+  } else {
+    let sign = if n - 1 < 0 { '-' } else { '+' };
+
+    match k {
+      1 => format!("{}e{}{}", digits, sign, (n - 1).abs()),
+      _ => format!(
+        "{}.{}e{}{}",
+        &digits[..1],
+        &digits[1..],
+        sign,
+        (n - 1).abs()
+      ),
+    }
+  }
+}
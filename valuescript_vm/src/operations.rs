@@ -1,4 +1,5 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeSet, HashSet};
+use std::mem::take;
 use std::rc::Rc;
 use std::str::FromStr;
 
@@ -15,11 +16,20 @@ use crate::binary_op::BinaryOp;
 use crate::builtins::internal_error_builtin::ToInternalError;
 use crate::builtins::range_error_builtin::ToRangeError;
 use crate::builtins::type_error_builtin::ToTypeError;
+use crate::map_methods::op_sub_map;
 use crate::native_function::native_fn;
 use crate::native_function::NativeFunction;
 use crate::number_methods::op_sub_number;
+use crate::own_properties::{own_properties, sort_property_keys};
+use crate::read_properties_frame::{read_properties_now, ReadProperties};
+use crate::set_methods::op_sub_set;
 use crate::string_methods::op_sub_string;
 use crate::unary_op::UnaryOp;
+use crate::vs_array::VsArray;
+use crate::vs_map::same_value_zero;
+use crate::vs_object::{PropertyKey, VsObject};
+use crate::vs_private_name::VsPrivateName;
+use crate::vs_symbol::VsSymbol;
 use crate::vs_value::ToVal;
 use crate::vs_value::Val;
 use crate::vs_value::ValTrait;
@@ -163,8 +173,11 @@ pub fn op_exp(left: &Val, right: &Val) -> Result<Val, Val> {
 
 pub fn op_eq_impl(left: &Val, right: &Val) -> Result<bool, Val> {
   Ok(match (left, right) {
-    (Val::Void, Val::Void) => true,
-    (left, Val::Undefined | Val::Null) => matches!(left, Val::Undefined | Val::Null),
+    // Array holes (`Val::Void`) read as undefined
+    (left, Val::Void | Val::Undefined | Val::Null) => {
+      matches!(left, Val::Void | Val::Undefined | Val::Null)
+    }
+    (Val::Void, _) => false,
     (Val::Bool(left_bool), Val::Bool(right_bool)) => left_bool == right_bool,
     (Val::Number(left_number), Val::Number(right_number)) => left_number == right_number,
     (Val::String(left_string), Val::String(right_string)) => left_string == right_string,
@@ -175,6 +188,7 @@ pub fn op_eq_impl(left: &Val, right: &Val) -> Result<bool, Val> {
       **left_string == right_number.to_string()
     }
     (Val::BigInt(left_bigint), Val::BigInt(right_bigint)) => left_bigint == right_bigint,
+    (Val::Symbol(left_symbol), Val::Symbol(right_symbol)) => left_symbol == right_symbol,
     (Val::Array(left_array), Val::Array(right_array)) => 'b: {
       if std::ptr::eq(&**left_array, &**right_array) {
         break 'b true;
@@ -186,6 +200,19 @@ pub fn op_eq_impl(left: &Val, right: &Val) -> Result<bool, Val> {
         break 'b false;
       }
 
+      let raw_eq = match (&left_array.raw, &right_array.raw) {
+        (None, None) => true,
+        (Some(left_raw), Some(right_raw)) => op_eq_impl(
+          &Val::Array(left_raw.clone()),
+          &Val::Array(right_raw.clone()),
+        )?,
+        _ => false,
+      };
+
+      if !raw_eq {
+        break 'b false;
+      }
+
       for (left_item, right_item) in left_array.elements.iter().zip(right_array.elements.iter()) {
         if !op_eq_impl(left_item, right_item)? {
           break 'b false;
@@ -209,7 +236,7 @@ pub fn op_eq_impl(left: &Val, right: &Val) -> Result<bool, Val> {
         break 'b true;
       }
 
-      if !compare_btrees(
+      if !compare_maps(
         &left_object.string_map,
         &right_object.string_map,
         op_eq_impl,
@@ -217,7 +244,7 @@ pub fn op_eq_impl(left: &Val, right: &Val) -> Result<bool, Val> {
         break 'b false;
       }
 
-      if !compare_btrees(
+      if !compare_maps(
         &left_object.symbol_map,
         &right_object.symbol_map,
         op_eq_impl,
@@ -225,6 +252,30 @@ pub fn op_eq_impl(left: &Val, right: &Val) -> Result<bool, Val> {
         break 'b false;
       }
 
+      if !compare_maps(
+        &left_object.getter_map,
+        &right_object.getter_map,
+        op_eq_impl,
+      )? {
+        break 'b false;
+      }
+
+      if !compare_maps(
+        &left_object.setter_map,
+        &right_object.setter_map,
+        op_eq_impl,
+      )? {
+        break 'b false;
+      }
+
+      if !compare_maps(
+        &left_object.private_map,
+        &right_object.private_map,
+        op_eq_impl,
+      )? {
+        break 'b false;
+      }
+
       true
     }
     (Val::Function(left), Val::Function(right)) => {
@@ -271,27 +322,28 @@ pub fn op_eq_impl(left: &Val, right: &Val) -> Result<bool, Val> {
   })
 }
 
-fn compare_btrees<K, Cmp>(
-  left: &BTreeMap<K, Val>,
-  right: &BTreeMap<K, Val>,
-  cmp: Cmp,
-) -> Result<bool, Val>
+/// Compares the entries of two maps (which iterate in key order).
+fn compare_maps<'a, K, L, R, Cmp>(left: L, right: R, cmp: Cmp) -> Result<bool, Val>
 where
+  K: PartialEq + 'a,
+  L: IntoIterator<Item = (&'a K, &'a Val)>,
+  R: IntoIterator<Item = (&'a K, &'a Val)>,
   Cmp: Fn(&Val, &Val) -> Result<bool, Val>,
 {
-  let symbol_len = left.len();
-
-  if right.len() != symbol_len {
-    return Ok(false);
-  }
-
-  for (left_value, right_value) in left.values().zip(right.values()) {
-    if !cmp(left_value, right_value)? {
-      return Ok(false);
+  let mut left = left.into_iter();
+  let mut right = right.into_iter();
+
+  loop {
+    match (left.next(), right.next()) {
+      (None, None) => return Ok(true),
+      (Some((left_key, left_value)), Some((right_key, right_value))) => {
+        if left_key != right_key || !cmp(left_value, right_value)? {
+          return Ok(false);
+        }
+      }
+      _ => return Ok(false),
     }
   }
-
-  Ok(true)
 }
 
 pub fn op_eq(left: &Val, right: &Val) -> Result<Val, Val> {
@@ -312,13 +364,14 @@ pub fn op_ne(left: &Val, right: &Val) -> Result<Val, Val> {
 
 pub fn op_triple_eq_impl(left: &Val, right: &Val) -> Result<bool, Val> {
   Ok(match (left, right) {
-    (Val::Void, Val::Void) => true,
-    (Val::Undefined, Val::Undefined) => true,
+    // Array holes (`Val::Void`) read as undefined
+    (Val::Void | Val::Undefined, Val::Void | Val::Undefined) => true,
     (Val::Null, Val::Null) => true,
     (Val::Bool(left_bool), Val::Bool(right_bool)) => left_bool == right_bool,
     (Val::Number(left_number), Val::Number(right_number)) => left_number == right_number,
     (Val::String(left_string), Val::String(right_string)) => left_string == right_string,
     (Val::BigInt(left_bigint), Val::BigInt(right_bigint)) => left_bigint == right_bigint,
+    (Val::Symbol(left_symbol), Val::Symbol(right_symbol)) => left_symbol == right_symbol,
     (Val::Array(left_array), Val::Array(right_array)) => 'b: {
       if std::ptr::eq(&**left_array, &**right_array) {
         break 'b true;
@@ -330,6 +383,19 @@ pub fn op_triple_eq_impl(left: &Val, right: &Val) -> Result<bool, Val> {
         break 'b false;
       }
 
+      let raw_eq = match (&left_array.raw, &right_array.raw) {
+        (None, None) => true,
+        (Some(left_raw), Some(right_raw)) => op_triple_eq_impl(
+          &Val::Array(left_raw.clone()),
+          &Val::Array(right_raw.clone()),
+        )?,
+        _ => false,
+      };
+
+      if !raw_eq {
+        break 'b false;
+      }
+
       for (left_item, right_item) in left_array.elements.iter().zip(right_array.elements.iter()) {
         if !op_triple_eq_impl(left_item, right_item)? {
           break 'b false;
@@ -347,7 +413,7 @@ pub fn op_triple_eq_impl(left: &Val, right: &Val) -> Result<bool, Val> {
         break 'b true;
       }
 
-      if !compare_btrees(
+      if !compare_maps(
         &left_object.string_map,
         &right_object.string_map,
         op_triple_eq_impl,
@@ -355,7 +421,7 @@ pub fn op_triple_eq_impl(left: &Val, right: &Val) -> Result<bool, Val> {
         break 'b false;
       }
 
-      if !compare_btrees(
+      if !compare_maps(
         &left_object.symbol_map,
         &right_object.symbol_map,
         op_triple_eq_impl,
@@ -363,6 +429,30 @@ pub fn op_triple_eq_impl(left: &Val, right: &Val) -> Result<bool, Val> {
         break 'b false;
       }
 
+      if !compare_maps(
+        &left_object.getter_map,
+        &right_object.getter_map,
+        op_triple_eq_impl,
+      )? {
+        break 'b false;
+      }
+
+      if !compare_maps(
+        &left_object.setter_map,
+        &right_object.setter_map,
+        op_triple_eq_impl,
+      )? {
+        break 'b false;
+      }
+
+      if !compare_maps(
+        &left_object.private_map,
+        &right_object.private_map,
+        op_triple_eq_impl,
+      )? {
+        break 'b false;
+      }
+
       true
     }
     (Val::Function(left), Val::Function(right)) => {
@@ -387,6 +477,40 @@ pub fn op_triple_eq_impl(left: &Val, right: &Val) -> Result<bool, Val> {
 
       true
     }
+    (Val::Map(left), Val::Map(right)) => 'b: {
+      if std::ptr::eq(&**left, &**right) {
+        break 'b true;
+      }
+
+      if left.len() != right.len() {
+        break 'b false;
+      }
+
+      for ((left_key, left_value), (right_key, right_value)) in left.iter().zip(right.iter()) {
+        if !same_value_zero(left_key, right_key) || !op_triple_eq_impl(left_value, right_value)? {
+          break 'b false;
+        }
+      }
+
+      true
+    }
+    (Val::Set(left), Val::Set(right)) => 'b: {
+      if std::ptr::eq(&**left, &**right) {
+        break 'b true;
+      }
+
+      if left.len() != right.len() {
+        break 'b false;
+      }
+
+      for (left_value, right_value) in left.iter().zip(right.iter()) {
+        if !same_value_zero(left_value, right_value) {
+          break 'b false;
+        }
+      }
+
+      true
+    }
     (Val::Static(left), Val::Static(right)) => std::ptr::eq(&**left, &**right),
     (Val::Dynamic(left), Val::Dynamic(right)) => std::ptr::eq(&**left, &**right),
     (Val::Static(..) | Val::Dynamic(..) | Val::CopyCounter(..), _)
@@ -404,8 +528,13 @@ pub fn op_triple_eq_impl(left: &Val, right: &Val) -> Result<bool, Val> {
       (None, Some(_)) | (Some(_), None) => return Ok(false),
       (Some(left_hash), Some(right_hash)) => left_hash == right_hash,
     },
+    // Maps and sets have the same typeof as objects, but are never equal to them
+    (Val::Map(..) | Val::Set(..), _) | (_, Val::Map(..) | Val::Set(..)) => false,
     _ => {
-      assert!(left.typeof_() != right.typeof_());
+      if left.typeof_() == right.typeof_() {
+        return Err(format!("TODO: op=== with ({}, {})", left, right).to_internal_error());
+      }
+
       false
     }
   })
@@ -679,11 +808,14 @@ pub fn op_instance_of(left: &Val, right: &Val) -> Result<Val, Val> {
     None => return Err("Right-hand side of `instanceof` is not a class".to_type_error()),
   };
 
-  let left_prototype = match left {
-    Val::Object(obj) => match &obj.prototype {
-      Val::Void => return Ok(false.to_val()),
-      proto => proto,
-    },
+  // Builtin classes have no content hash, so they're identified by name
+  let builtin_class = |name: &str| class_data.content_hash.is_none() && class_data.name == name;
+
+  let mut left_prototype = match left {
+    Val::Object(obj) => &obj.prototype,
+    Val::Array(_) => return Ok(builtin_class("Array").to_val()),
+    Val::Map(_) => return Ok(builtin_class("Map").to_val()),
+    Val::Set(_) => return Ok(builtin_class("Set").to_val()),
     Val::Null => return Ok(false.to_val()),
     _ => match left.typeof_() {
       VsType::Object => return Err("TODO: instanceof indirection".to_internal_error()),
@@ -691,7 +823,20 @@ pub fn op_instance_of(left: &Val, right: &Val) -> Result<Val, Val> {
     },
   };
 
-  Ok(op_triple_eq_impl(left_prototype, &class_data.prototype)?.to_val())
+  // Walk the prototype chain so that instances of derived classes are included
+  loop {
+    match left_prototype {
+      Val::Object(proto) => {
+        if op_triple_eq_impl(left_prototype, &class_data.prototype)? {
+          return Ok(true.to_val());
+        }
+
+        left_prototype = &proto.prototype;
+      }
+      Val::Void => return Ok(false.to_val()),
+      _ => return Ok(op_triple_eq_impl(left_prototype, &class_data.prototype)?.to_val()),
+    }
+  }
 }
 
 pub fn op_in(left: &Val, right: &Val) -> Result<Val, Val> {
@@ -701,7 +846,162 @@ pub fn op_in(left: &Val, right: &Val) -> Result<Val, Val> {
   }
 }
 
+/// The keys visited by `for...in`: the enumerable string keys of `input` followed by the ones it
+/// inherits. Each object's keys are ordered by `sort_property_keys`, so integer keys come first like
+/// in JS, but the other keys are in sorted order rather than insertion order.
+pub fn op_for_in_keys(input: &Val) -> Result<Val, Val> {
+  let mut keys = Vec::<Val>::new();
+
+  match input {
+    Val::Array(array) => {
+      for (i, item) in array.elements.iter().enumerate() {
+        if !matches!(item, Val::Void) {
+          keys.push(i.to_string().to_val());
+        }
+      }
+    }
+    Val::String(string) => {
+      for i in 0..string.len() {
+        keys.push(i.to_string().to_val());
+      }
+    }
+    Val::Object(object) => push_for_in_keys(&mut keys, &mut HashSet::new(), object, true),
+    Val::Class(class) => {
+      if let Val::Object(static_) = &class.static_ {
+        push_for_in_keys(&mut keys, &mut HashSet::new(), static_, false);
+      }
+    }
+    Val::StoragePtr(ptr) => return op_for_in_keys(&ptr.get()),
+    _ => {}
+  }
+
+  Ok(VsArray::from(keys).to_val())
+}
+
+/// Methods are skipped in prototypes and class statics, since class methods aren't enumerable.
+fn push_for_in_keys(
+  keys: &mut Vec<Val>,
+  seen: &mut HashSet<String>,
+  object: &VsObject,
+  is_own: bool,
+) {
+  let mut object_keys = Vec::<&String>::new();
+
+  for (key, value) in &object.string_map {
+    if !is_own && matches!(value.typeof_(), VsType::Function | VsType::Class) {
+      seen.insert(key.clone());
+      continue;
+    }
+
+    object_keys.push(key);
+  }
+
+  if is_own {
+    for key in object.getter_map.keys().chain(object.setter_map.keys()) {
+      if let PropertyKey::String(key) = key {
+        object_keys.push(key);
+      }
+    }
+  }
+
+  sort_property_keys(&mut object_keys, |key| key);
+
+  for key in object_keys {
+    if seen.insert(key.clone()) {
+      keys.push(key.clone().to_val());
+    }
+  }
+
+  if let Val::Object(prototype) = &object.prototype {
+    push_for_in_keys(keys, seen, prototype, false);
+  }
+}
+
+/// The object bound by a rest pattern like `const { a, ...rest } = input`: a new plain object with
+/// the own properties of `input`, except for `excluded_keys` (the keys picked out before it).
+///
+/// Reading the properties can call getters, so the bytecode frame uses a `ReadPropertiesFrame` with
+/// `OBJECT_REST` when there are any.
+pub fn op_object_rest(input: &Val, excluded_keys: &Val) -> Result<Val, Val> {
+  read_properties_now(&OBJECT_REST, vec![input.clone(), excluded_keys.clone()])
+}
+
+/// Copies the own properties of `source` into `target`, like `{ ...source }` does in an object
+/// literal. Spreading `undefined` or `null` copies nothing.
+///
+/// Like `op_object_rest`, the bytecode frame uses `OBJECT_SPREAD` instead when there are getters.
+pub fn op_object_spread(target: &mut Val, source: &Val) -> Result<(), Val> {
+  *target = read_properties_now(&OBJECT_SPREAD, vec![take(target), source.clone()])?;
+
+  Ok(())
+}
+
+/// Reads the properties for `op_object_rest`. The parameters are the input and the excluded keys.
+pub static OBJECT_REST: ReadProperties = ReadProperties {
+  list: |params| {
+    let input = &params[0];
+
+    if input.is_nullish() {
+      return Err(format!("Cannot destructure {}", input).to_type_error());
+    }
+
+    let mut excluded_strings = HashSet::<String>::new();
+    let mut excluded_symbols = BTreeSet::<VsSymbol>::new();
+
+    if let Val::Array(excluded_keys) = &params[1] {
+      for key in &excluded_keys.elements {
+        match key {
+          Val::Symbol(symbol) => excluded_symbols.insert(symbol.clone()),
+          _ => excluded_strings.insert(key.to_string()),
+        };
+      }
+    }
+
+    let mut properties = own_properties(input, true)?;
+
+    properties.retain(|(key, _)| match key {
+      Val::Symbol(symbol) => !excluded_symbols.contains(symbol),
+      _ => !excluded_strings.contains(&key.to_string()),
+    });
+
+    Ok(properties)
+  },
+  finish: |_params, properties| {
+    let mut rest = VsObject::default();
+
+    for (key, value) in properties {
+      match key {
+        Val::Symbol(symbol) => rest.symbol_map.insert(symbol, value),
+        _ => rest.string_map.insert(key.to_string(), value),
+      };
+    }
+
+    Ok(rest.to_val())
+  },
+};
+
+/// Reads the properties for `op_object_spread`. The parameters are the target and the source.
+pub static OBJECT_SPREAD: ReadProperties = ReadProperties {
+  list: |params| match &params[1] {
+    source if source.is_nullish() => Ok(vec![]),
+    source => own_properties(source, true),
+  },
+  finish: |mut params, properties| {
+    let mut target = take(&mut params[0]);
+
+    for (key, value) in properties {
+      op_submov(&mut target, &key, value)?;
+    }
+
+    Ok(target)
+  },
+};
+
 pub fn op_sub(left: &mut Val, right: &Val) -> Result<Val, Val> {
+  if let Some(name) = VsPrivateName::from_val(right) {
+    return op_sub_private(left, name);
+  }
+
   match left {
     Val::Void => Err("Internal: Shouldn't happen".to_internal_error()), // TODO: Internal errors
     Val::Undefined => Err("Cannot subscript undefined".to_type_error()),
@@ -717,8 +1017,13 @@ pub fn op_sub(left: &mut Val, right: &Val) -> Result<Val, Val> {
     Val::String(string_data) => Ok(op_sub_string(string_data, right)),
     Val::Array(array_data) => op_sub_array(array_data, right),
     Val::Object(object_data) => Ok(object_data.sub(right)), // TODO: move on single ref
+    Val::Map(map) => Ok(op_sub_map(map, right)),
+    Val::Set(set) => Ok(op_sub_set(set, right)),
     Val::Function(_) => Ok(Val::Undefined),
-    Val::Class(class) => op_sub(&mut class.static_.clone(), right),
+    Val::Class(class) => match right.to_string().as_str() {
+      "prototype" => Ok(class.prototype.clone()),
+      _ => op_sub(&mut class.static_.clone(), right),
+    },
     Val::Static(s) => s.sub(right),
     Val::Dynamic(dynamic_data) => dynamic_data.sub(right),
     Val::CopyCounter(cc) => Ok(match right.to_string().as_str() {
@@ -730,7 +1035,39 @@ pub fn op_sub(left: &mut Val, right: &Val) -> Result<Val, Val> {
   }
 }
 
+fn op_sub_private(target: &Val, name: &VsPrivateName) -> Result<Val, Val> {
+  let found = match target {
+    Val::Object(object_data) => object_data.private_sub(name),
+    Val::Class(class) => return op_sub_private(&class.static_, name),
+    Val::StoragePtr(ptr) => return op_sub_private(&ptr.get(), name),
+    _ => None,
+  };
+
+  found.ok_or_else(|| {
+    format!(
+      "Cannot read private member {} from an object whose class did not declare it",
+      name
+    )
+    .to_type_error()
+  })
+}
+
+/// If `subscript` is an accessor property of `target`, returns its getter and setter (either of
+/// which can be `Val::Void`). These need their own stack frames, so they're called by the
+/// bytecode frame rather than by `op_sub` and `op_submov`.
+pub fn op_sub_accessor(target: &Val, subscript: &Val) -> Option<(Val, Val)> {
+  match target {
+    Val::Object(object_data) => object_data.accessor(subscript),
+    Val::Class(class) => op_sub_accessor(&class.static_, subscript),
+    _ => None,
+  }
+}
+
 pub fn op_submov(target: &mut Val, subscript: &Val, value: Val) -> Result<(), Val> {
+  if let Some(name) = VsPrivateName::from_val(subscript) {
+    return op_submov_private(target, name, value);
+  }
+
   match target {
     Val::Void => Err("Internal: Shouldn't happen".to_internal_error()), // TODO: Internal errors
     Val::Undefined => Err("Cannot assign to subscript of undefined".to_type_error()),
@@ -742,27 +1079,18 @@ pub fn op_submov(target: &mut Val, subscript: &Val, value: Val) -> Result<(), Va
     Val::String(_) => Err("Cannot assign to subscript of string".to_type_error()),
     Val::Array(array_data) => {
       let subscript_index = match subscript.to_index() {
-        // TODO: Internal errors
-        None => return Err("TODO: non-uint array subscript assignment".to_type_error()),
         Some(i) => i,
+        None => return op_submov_array_key(array_data, subscript, value),
       };
 
       let array_data_mut = Rc::make_mut(array_data);
 
-      if subscript_index < array_data_mut.elements.len() {
-        array_data_mut.elements[subscript_index] = value;
-      } else {
-        if subscript_index - array_data_mut.elements.len() > 100 {
-          return Err("TODO: Sparse arrays".to_type_error());
-        }
-
-        while subscript_index > array_data_mut.elements.len() {
-          array_data_mut.elements.push(Val::Void);
-        }
-
-        array_data_mut.elements.push(value);
+      if subscript_index >= array_data_mut.elements.len() {
+        array_data_mut.set_length(VsArray::to_length(subscript_index as f64 + 1.0)?);
       }
 
+      array_data_mut.elements[subscript_index] = value;
+
       Ok(())
     }
     Val::Object(object_data) => {
@@ -778,8 +1106,22 @@ pub fn op_submov(target: &mut Val, subscript: &Val, value: Val) -> Result<(), Va
 
       Ok(())
     }
+    Val::Map(_) => Err(
+      format!(
+        "Cannot assign to {} of Map (use .set() to add entries)",
+        subscript.codify()
+      )
+      .to_type_error(),
+    ),
+    Val::Set(_) => Err(
+      format!(
+        "Cannot assign to {} of Set (use .add() to add values)",
+        subscript.codify()
+      )
+      .to_type_error(),
+    ),
     Val::Function(_) => Err("TODO: function subscript assignment".to_type_error()),
-    Val::Class(_) => Err("Cannot assign to subscript of class".to_type_error()),
+    Val::Class(class) => op_submov(&mut Rc::make_mut(class).static_, subscript, value),
     Val::Static(_) => Err("Cannot assign to subscript of static value".to_type_error()),
     Val::Dynamic(_) => Err("TODO: Assign to subscript of dynamic value".to_type_error()),
     Val::CopyCounter(_) => Err("Cannot assign to subscript of CopyCounter".to_type_error()),
@@ -793,6 +1135,56 @@ pub fn op_submov(target: &mut Val, subscript: &Val, value: Val) -> Result<(), Va
   }
 }
 
+/// Assigns a key that isn't an index (see `VsArray` for which keys arrays have).
+fn op_submov_array_key(array: &mut Rc<VsArray>, key: &Val, value: Val) -> Result<(), Val> {
+  match key.to_string().as_str() {
+    "length" => {
+      let len = VsArray::to_length(value.to_number())?;
+      Rc::make_mut(array).set_length(len);
+    }
+    _ => {
+      return Err(
+        format!(
+          "Cannot assign to {} of array (arrays only have elements)",
+          key.codify()
+        )
+        .to_type_error(),
+      )
+    }
+  };
+
+  Ok(())
+}
+
+/// Private fields can only be written to objects that already have them, which is the case for
+/// instances because the class prototype declares them.
+fn op_submov_private(target: &mut Val, name: &VsPrivateName, value: Val) -> Result<(), Val> {
+  match target {
+    Val::Object(object_data) if object_data.private_sub(name).is_some() => {
+      Rc::make_mut(object_data)
+        .private_map
+        .insert(name.clone(), value);
+
+      Ok(())
+    }
+    Val::Class(class) => op_submov_private(&mut Rc::make_mut(class).static_, name, value),
+    Val::StoragePtr(ptr) => {
+      let mut val = ptr.get();
+      op_submov_private(&mut val, name, value)?;
+      *target = val;
+
+      Ok(())
+    }
+    _ => Err(
+      format!(
+        "Cannot write private member {} to an object whose class did not declare it",
+        name
+      )
+      .to_type_error(),
+    ),
+  }
+}
+
 pub fn op_delete(target: &mut Val, subscript: &Val) -> Result<(), Val> {
   match target {
     Val::Void => Err("Internal: Shouldn't happen".to_internal_error()), // TODO: Internal errors
@@ -805,9 +1197,14 @@ pub fn op_delete(target: &mut Val, subscript: &Val) -> Result<(), Val> {
     Val::String(_) => Err("Cannot delete from string".to_type_error()),
     Val::Array(array_data) => {
       let subscript_index = match subscript.to_index() {
-        // TODO: Internal errors
-        None => return Err("TODO: non-uint array subscript deletion".to_type_error()),
         Some(i) => i,
+        None => {
+          return match subscript.to_string().as_str() {
+            "length" => Err("Cannot delete length of array".to_type_error()),
+            // Other keys aren't stored on arrays, so there's nothing to delete
+            _ => Ok(()),
+          };
+        }
       };
 
       let array_data_mut = Rc::make_mut(array_data);
@@ -827,8 +1224,14 @@ pub fn op_delete(target: &mut Val, subscript: &Val) -> Result<(), Val> {
         _ => object_data_mut.string_map.remove(&subscript.to_string()),
       };
 
+      let key = PropertyKey::from_val(subscript);
+      object_data_mut.getter_map.remove(&key);
+      object_data_mut.setter_map.remove(&key);
+
       Ok(())
     }
+    // Maps and sets only have entries, which are removed using .delete()
+    Val::Map(_) | Val::Set(_) => Ok(()),
     Val::Function(_) => Err("TODO: function subscript assignment".to_type_error()),
     Val::Class(_) => Err("Cannot delete from class".to_type_error()),
     Val::Static(_) => Err("Cannot delete from static value".to_type_error()),
@@ -0,0 +1,159 @@
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+
+use crate::builtins::type_error_builtin::ToTypeError;
+use crate::jsx_element::JsxElement;
+use crate::vs_object::{PropertyKey, VsObject};
+use crate::vs_value::{ToVal, Val, ValTrait, VsType};
+
+/// An own enumerable property, as read by spreads, `Object.entries`, `JSON.stringify` etc.
+#[derive(Clone)]
+pub enum OwnProperty {
+  Value(Val),
+
+  /// An accessor property. Its value comes from calling `getter` (`Val::Void` if the accessor only
+  /// has a setter, which reads as undefined) with `this`, which needs a stack frame (see
+  /// `ReadPropertiesFrame`).
+  Getter {
+    getter: Val,
+    this: Val,
+  },
+}
+
+/// The own enumerable properties of `val`. String keys come first, ordered by
+/// `sort_property_keys`, followed by symbol keys if `include_symbols` is set. JSX elements give
+/// their attributes.
+pub fn own_properties(val: &Val, include_symbols: bool) -> Result<Vec<(Val, OwnProperty)>, Val> {
+  let val = val.not_ptr();
+
+  Ok(match &val {
+    Val::Undefined | Val::Null => {
+      return Err(format!("Cannot convert {} to object", val.codify()).to_type_error())
+    }
+    Val::Array(array) => array
+      .elements
+      .iter()
+      .enumerate()
+      .filter(|(_, element)| !matches!(element, Val::Void))
+      .map(|(i, element)| (i.to_string().to_val(), OwnProperty::Value(element.clone())))
+      .collect(),
+    Val::String(string) => string
+      .chars()
+      .enumerate()
+      .map(|(i, c)| {
+        let value = OwnProperty::Value(c.to_string().to_val());
+        (i.to_string().to_val(), value)
+      })
+      .collect(),
+    Val::Object(object) => object_properties(object, &val, include_symbols, false),
+    Val::Class(class) => match &class.static_ {
+      Val::Object(static_) => object_properties(static_, &class.static_, include_symbols, true),
+      _ => vec![],
+    },
+    Val::Dynamic(dynamic) => match dynamic.as_any().downcast_ref::<JsxElement>() {
+      Some(jsx) => jsx
+        .attrs
+        .iter()
+        .map(|(key, value)| (key.clone().to_val(), OwnProperty::Value(value.clone())))
+        .collect(),
+      None => vec![],
+    },
+    _ => vec![],
+  })
+}
+
+/// Whether reading the own properties of `val` calls any getters.
+pub fn has_own_getters(val: &Val) -> bool {
+  match val {
+    Val::Object(object) => !object.getter_map.is_empty(),
+    Val::StoragePtr(ptr) => has_own_getters(&ptr.get()),
+    _ => false,
+  }
+}
+
+/// Class methods and accessors aren't enumerable, so `is_class_static` leaves them out.
+fn object_properties(
+  object: &VsObject,
+  this: &Val,
+  include_symbols: bool,
+  is_class_static: bool,
+) -> Vec<(Val, OwnProperty)> {
+  let mut string_properties = Vec::<(String, OwnProperty)>::new();
+  let mut symbol_properties = Vec::<(Val, OwnProperty)>::new();
+
+  for (key, value) in &object.string_map {
+    if is_class_static && matches!(value.typeof_(), VsType::Function | VsType::Class) {
+      continue;
+    }
+
+    string_properties.push((key.clone(), OwnProperty::Value(value.clone())));
+  }
+
+  if include_symbols {
+    for (symbol, value) in &object.symbol_map {
+      symbol_properties.push((symbol.clone().to_val(), OwnProperty::Value(value.clone())));
+    }
+  }
+
+  // Keys with both a getter and a setter only count once
+  let accessor_keys = match is_class_static {
+    true => BTreeSet::new(),
+    false => object
+      .getter_map
+      .keys()
+      .chain(object.setter_map.keys())
+      .collect::<BTreeSet<_>>(),
+  };
+
+  for key in accessor_keys {
+    let property = OwnProperty::Getter {
+      getter: object.getter_map.get(key).cloned().unwrap_or_default(),
+      this: this.clone(),
+    };
+
+    // Plain values take precedence over accessors (see `VsObject::accessor`)
+    match key {
+      PropertyKey::String(key) => {
+        if !object.string_map.contains_key(key) {
+          string_properties.push((key.clone(), property));
+        }
+      }
+      PropertyKey::Symbol(symbol) => {
+        if include_symbols && !object.symbol_map.contains_key(symbol) {
+          symbol_properties.push((symbol.clone().to_val(), property));
+        }
+      }
+    }
+  }
+
+  sort_property_keys(&mut string_properties, |(key, _)| key);
+
+  string_properties
+    .into_iter()
+    .map(|(key, property)| (key.to_val(), property))
+    .chain(symbol_properties)
+    .collect()
+}
+
+/// Sorts property keys the way JS orders them when listing an object's properties: integer keys
+/// first, in ascending order, followed by the other keys. JS puts the other keys in insertion
+/// order, but objects don't keep track of that, so they're in sorted order instead.
+pub fn sort_property_keys<T>(items: &mut [T], key: impl Fn(&T) -> &str) {
+  items.sort_by(|left, right| {
+    let (left, right) = (key(left), key(right));
+
+    match (integer_key(left), integer_key(right)) {
+      (Some(left), Some(right)) => left.cmp(&right),
+      (Some(_), None) => Ordering::Less,
+      (None, Some(_)) => Ordering::Greater,
+      (None, None) => left.cmp(right),
+    }
+  });
+}
+
+fn integer_key(key: &str) -> Option<u32> {
+  match key.parse::<u32>() {
+    Ok(i) if i != u32::MAX && i.to_string() == key => Some(i),
+    _ => None,
+  }
+}
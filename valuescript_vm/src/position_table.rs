@@ -0,0 +1,173 @@
+use std::fmt;
+
+/// A line and column (both starting at 1) in a source file.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SourcePosition {
+  pub path: String,
+  pub line: u32,
+  pub col: u32,
+}
+
+impl fmt::Display for SourcePosition {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if !self.path.is_empty() {
+      write!(f, "{}:", self.path)?;
+    }
+
+    write!(f, "{}:{}", self.line, self.col)
+  }
+}
+
+/// Maps bytecode offsets to the source positions they were compiled from, and functions to the
+/// names of their registers.
+///
+/// Each entry applies from its offset until the next entry. Entries without a position mark code
+/// (such as the start of a function) which doesn't have one.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PositionTable {
+  entries: Vec<(usize, Option<SourcePosition>)>,
+  register_names: Vec<(usize, Vec<String>)>,
+}
+
+impl PositionTable {
+  /// Entries must be pushed in order of offset.
+  pub fn push(&mut self, offset: usize, position: Option<SourcePosition>) {
+    if let Some((last_offset, last_position)) = self.entries.last_mut() {
+      if *last_offset == offset {
+        *last_position = position;
+        return;
+      }
+
+      if *last_position == position {
+        return;
+      }
+    }
+
+    self.entries.push((offset, position));
+  }
+
+  pub fn lookup(&self, offset: usize) -> Option<&SourcePosition> {
+    let i = self.entries.partition_point(|(o, _)| *o <= offset);
+
+    match i {
+      0 => None,
+      _ => self.entries[i - 1].1.as_ref(),
+    }
+  }
+
+  /// The position of the code starting exactly at `offset`, if a new position starts there.
+  pub fn starts_at(&self, offset: usize) -> Option<&SourcePosition> {
+    match self.entries.binary_search_by_key(&offset, |(o, _)| *o) {
+      Ok(i) => self.entries[i].1.as_ref(),
+      Err(_) => None,
+    }
+  }
+
+  /// The offsets where code from the given line starts.
+  pub fn line_offsets(&self, path: &str, line: u32) -> Vec<usize> {
+    self
+      .entries
+      .iter()
+      .filter_map(|(offset, position)| match position {
+        Some(position) if position.path == path && position.line == line => Some(*offset),
+        _ => None,
+      })
+      .collect()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.entries.is_empty()
+  }
+
+  /// Functions must be pushed in order of offset.
+  pub fn push_register_names(&mut self, fn_start: usize, names: Vec<String>) {
+    self.register_names.push((fn_start, names));
+  }
+
+  /// The register names of the function containing `offset`, indexed by register.
+  pub fn register_names(&self, offset: usize) -> &[String] {
+    let i = self.register_names.partition_point(|(o, _)| *o <= offset);
+
+    match i {
+      0 => &[],
+      _ => &self.register_names[i - 1].1,
+    }
+  }
+}
+
+/// A bytecode function which was running when an uncaught exception was thrown.
+#[derive(Clone, Debug)]
+pub struct StackTraceEntry {
+  pub name: String,
+  pub position: Option<SourcePosition>,
+}
+
+impl fmt::Display for StackTraceEntry {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let name = match self.name.as_str() {
+      "" => "<anonymous>",
+      name => name,
+    };
+
+    match &self.position {
+      Some(position) => write!(f, "at {} ({})", name, position),
+      None => write!(f, "at {}", name),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{PositionTable, SourcePosition};
+
+  fn at(line: u32, col: u32) -> Option<SourcePosition> {
+    Some(SourcePosition {
+      path: "main.ts".to_string(),
+      line,
+      col,
+    })
+  }
+
+  #[test]
+  fn entries() {
+    let mut table = PositionTable::default();
+
+    table.push(0, None);
+    table.push(3, at(1, 1));
+    table.push(5, at(1, 1)); // Same position, so no new entry
+    table.push(8, at(2, 3));
+    table.push(8, at(2, 5)); // Same offset, so replaces the previous entry
+    table.push(12, None);
+
+    assert_eq!(
+      table.entries,
+      [(0, None), (3, at(1, 1)), (8, at(2, 5)), (12, None)]
+    );
+
+    assert_eq!(table.lookup(2), None);
+    assert_eq!(table.lookup(3), at(1, 1).as_ref());
+    assert_eq!(table.lookup(7), at(1, 1).as_ref());
+    assert_eq!(table.lookup(8), at(2, 5).as_ref());
+    assert_eq!(table.lookup(100), None);
+
+    assert_eq!(table.starts_at(3), at(1, 1).as_ref());
+    assert_eq!(table.starts_at(5), None);
+    assert_eq!(table.line_offsets("main.ts", 2), [8]);
+    assert_eq!(table.line_offsets("other.ts", 2), [] as [usize; 0]);
+  }
+
+  #[test]
+  fn register_names() {
+    let mut table = PositionTable::default();
+
+    table.push_register_names(4, vec!["return".to_string(), "this".to_string()]);
+    table.push_register_names(
+      20,
+      vec!["return".to_string(), "this".to_string(), "x".to_string()],
+    );
+
+    assert!(table.register_names(0).is_empty());
+    assert_eq!(table.register_names(10), ["return", "this"]);
+    assert_eq!(table.register_names(25), ["return", "this", "x"]);
+  }
+}
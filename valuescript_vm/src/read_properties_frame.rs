@@ -0,0 +1,139 @@
+use std::any::Any;
+use std::mem::take;
+
+use crate::builtins::internal_error_builtin::ToInternalError;
+use crate::native_frame_function::{call, Call};
+use crate::own_properties::OwnProperty;
+use crate::stack_frame::{CallResult, FrameStepOk, FrameStepResult, StackFrame, StackFrameTrait};
+use crate::vs_value::Val;
+
+/// Describes reading properties in bulk, like a spread or `Object.entries` does. Reading them can
+/// mean calling getters, which need their own stack frames, so this is done by a
+/// `ReadPropertiesFrame`.
+pub struct ReadProperties {
+  /// Lists the properties to read, given the parameters.
+  pub list: ListProperties,
+
+  /// Produces the result from the parameters and the values that were read.
+  pub finish: FinishReading,
+}
+
+pub type ListProperties = fn(params: &[Val]) -> Result<Vec<(Val, OwnProperty)>, Val>;
+pub type FinishReading = fn(params: Vec<Val>, properties: Vec<(Val, Val)>) -> Result<Val, Val>;
+
+#[derive(Clone)]
+pub struct ReadPropertiesFrame {
+  read: &'static ReadProperties,
+  params: Vec<Val>,
+
+  /// `None` until the properties have been listed.
+  pending: Option<Vec<(Val, OwnProperty)>>,
+  pending_i: usize,
+
+  properties: Vec<(Val, Val)>,
+}
+
+impl ReadPropertiesFrame {
+  pub fn new(read: &'static ReadProperties) -> ReadPropertiesFrame {
+    ReadPropertiesFrame::with_params(read, vec![])
+  }
+
+  pub fn with_params(read: &'static ReadProperties, params: Vec<Val>) -> ReadPropertiesFrame {
+    ReadPropertiesFrame {
+      read,
+      params,
+      pending: None,
+      pending_i: 0,
+      properties: vec![],
+    }
+  }
+}
+
+/// Reads the properties without a frame, for callers that can't push one (e.g. when evaluating at
+/// compile time). Fails if a getter needs to be called.
+pub fn read_properties_now(read: &ReadProperties, params: Vec<Val>) -> Result<Val, Val> {
+  let properties = (read.list)(&params)?
+    .into_iter()
+    .map(|(key, property)| match property {
+      OwnProperty::Value(value) => Ok((key, value)),
+      OwnProperty::Getter {
+        getter: Val::Void, ..
+      } => Ok((key, Val::Undefined)),
+      OwnProperty::Getter { .. } => {
+        Err(format!("Reading {} requires calling its getter", key).to_internal_error())
+      }
+    })
+    .collect::<Result<Vec<_>, Val>>()?;
+
+  (read.finish)(params, properties)
+}
+
+impl StackFrameTrait for ReadPropertiesFrame {
+  fn write_this(&mut self, _const: bool, _this: Val) -> Result<(), Val> {
+    Ok(())
+  }
+
+  fn write_param(&mut self, param: Val) {
+    self.params.push(param);
+  }
+
+  fn step(&mut self) -> FrameStepResult {
+    let pending = match &self.pending {
+      Some(pending) => pending,
+      None => self.pending.insert((self.read.list)(&self.params)?),
+    };
+
+    while let Some((key, property)) = pending.get(self.pending_i) {
+      self.pending_i += 1;
+
+      let value = match property {
+        OwnProperty::Value(value) => value.clone(),
+        OwnProperty::Getter {
+          getter: Val::Void, ..
+        } => Val::Undefined,
+        OwnProperty::Getter { getter, this } => match call(getter, this.clone(), vec![])? {
+          Call::Native(value) => value,
+          Call::Frame(frame) => return Ok(FrameStepOk::Push(frame)),
+        },
+      };
+
+      self.properties.push((key.clone(), value));
+    }
+
+    Ok(FrameStepOk::Pop(CallResult {
+      return_: (self.read.finish)(take(&mut self.params), take(&mut self.properties))?,
+      this: Val::Undefined,
+    }))
+  }
+
+  fn apply_call_result(&mut self, call_result: CallResult) {
+    let (key, _) = &self
+      .pending
+      .as_ref()
+      .expect("Call result before listing properties")[self.pending_i - 1];
+
+    self.properties.push((key.clone(), call_result.return_));
+  }
+
+  fn get_call_result(&mut self) -> CallResult {
+    panic!("Not appropriate for ReadPropertiesFrame")
+  }
+
+  fn can_catch_exception(&self, _exception: &Val) -> bool {
+    false
+  }
+
+  fn catch_exception(&mut self, _exception: &mut Val) {}
+
+  fn clone_to_stack_frame(&self) -> StackFrame {
+    Box::new(self.clone())
+  }
+
+  fn as_any(&self) -> &dyn Any {
+    self
+  }
+
+  fn as_any_mut(&mut self) -> &mut dyn Any {
+    self
+  }
+}
@@ -0,0 +1,438 @@
+use std::{collections::HashMap, rc::Rc, sync::Arc};
+
+use num_bigint::BigInt;
+
+use crate::{
+  builtins::{internal_error_builtin::ToInternalError, type_error_builtin::ToTypeError},
+  bytecode::Bytecode,
+  position_table::PositionTable,
+  vs_array::VsArray,
+  vs_class::VsClass,
+  vs_function::VsFunction,
+  vs_map::VsMap,
+  vs_object::{PropertyKey, VsObject},
+  vs_private_name::VsPrivateName,
+  vs_promise::{as_promise, AwaitResult, VsPromise},
+  vs_regexp::{as_regexp, VsRegExp},
+  vs_set::VsSet,
+  vs_symbol::VsSymbol,
+  vs_value::{ToDynamicVal, ToVal, Val, ValTrait},
+};
+
+/// A deep copy of a `Val` which can be sent to another thread.
+///
+/// `Val` shares its data using `Rc`, so it can't cross threads directly. Converting to
+/// `SendableVal` copies everything reachable from the value (including the code of any functions),
+/// and converting back rebuilds it on the receiving thread. Value semantics mean the copy is
+/// indistinguishable from the original.
+pub enum SendableVal {
+  Void,
+  Undefined,
+  Null,
+  Bool(bool),
+  Number(f64),
+  BigInt(BigInt),
+  Symbol(VsSymbol),
+  String(String),
+  Array {
+    elements: Vec<SendableVal>,
+    raw: Option<Vec<SendableVal>>,
+  },
+  Object(Box<SendableObject>),
+  Map(Vec<(SendableVal, SendableVal)>),
+  Set(Vec<SendableVal>),
+  Function {
+    bytecode: Arc<SendableBytecode>,
+    meta_pos: Option<usize>,
+    is_generator: bool,
+    is_async: bool,
+    register_count: usize,
+    parameter_count: usize,
+    has_rest_param: bool,
+    start: usize,
+    binds: Vec<SendableVal>,
+  },
+  Class {
+    name: String,
+    content_hash: Option<[u8; 32]>,
+    constructor: Box<SendableVal>,
+    prototype: Box<SendableVal>,
+    static_: Box<SendableVal>,
+  },
+  Static(&'static (dyn ValTrait + Sync)),
+  RegExp {
+    source: String,
+    flags: String,
+  },
+  Promise(Result<Box<SendableVal>, Box<SendableVal>>),
+}
+
+/// The code of a function, with the positions used for its stack traces.
+pub struct SendableBytecode {
+  code: Vec<u8>,
+  positions: PositionTable,
+}
+
+/// The fields of `SendableVal::Object`, boxed to keep `SendableVal` small.
+pub struct SendableObject {
+  string_map: Vec<(String, SendableVal)>,
+  symbol_map: Vec<(VsSymbol, SendableVal)>,
+  getter_map: Vec<(PropertyKey, SendableVal)>,
+  setter_map: Vec<(PropertyKey, SendableVal)>,
+  private_map: Vec<(VsPrivateName, SendableVal)>,
+  prototype: SendableVal,
+}
+
+impl SendableVal {
+  pub fn from_val(val: &Val) -> Result<SendableVal, Val> {
+    SendContext::default().convert(val)
+  }
+
+  /// Fails if the value can't be rebuilt, e.g. a regex which doesn't compile on this thread.
+  pub fn into_val(self) -> Result<Val, Val> {
+    ReceiveContext::default().convert(self)
+  }
+}
+
+/// Tracks the bytecode that has already been copied, so that functions sharing bytecode (usually
+/// all of them) only copy it once.
+#[derive(Default)]
+struct SendContext {
+  bytecodes: HashMap<*const Bytecode, Arc<SendableBytecode>>,
+}
+
+impl SendContext {
+  fn convert(&mut self, val: &Val) -> Result<SendableVal, Val> {
+    Ok(match val {
+      Val::Void => SendableVal::Void,
+      Val::Undefined => SendableVal::Undefined,
+      Val::Null => SendableVal::Null,
+      Val::Bool(b) => SendableVal::Bool(*b),
+      Val::Number(n) => SendableVal::Number(*n),
+      Val::BigInt(b) => SendableVal::BigInt(b.clone()),
+      Val::Symbol(s) => SendableVal::Symbol(s.clone()),
+      Val::String(s) => SendableVal::String(s.to_string()),
+      Val::Array(a) => SendableVal::Array {
+        elements: self.convert_vec(&a.elements.to_vec())?,
+        raw: match &a.raw {
+          Some(raw) => Some(self.convert_vec(&raw.elements.to_vec())?),
+          None => None,
+        },
+      },
+      Val::Object(obj) => {
+        let mut string_map = Vec::new();
+
+        for (key, value) in &obj.string_map {
+          string_map.push((key.clone(), self.convert(value)?));
+        }
+
+        let mut symbol_map = Vec::new();
+
+        for (key, value) in &obj.symbol_map {
+          symbol_map.push((key.clone(), self.convert(value)?));
+        }
+
+        let mut getter_map = Vec::new();
+
+        for (key, value) in &obj.getter_map {
+          getter_map.push((key.clone(), self.convert(value)?));
+        }
+
+        let mut setter_map = Vec::new();
+
+        for (key, value) in &obj.setter_map {
+          setter_map.push((key.clone(), self.convert(value)?));
+        }
+
+        let mut private_map = Vec::new();
+
+        for (key, value) in &obj.private_map {
+          private_map.push((key.clone(), self.convert(value)?));
+        }
+
+        SendableVal::Object(Box::new(SendableObject {
+          string_map,
+          symbol_map,
+          getter_map,
+          setter_map,
+          private_map,
+          prototype: self.convert(&obj.prototype)?,
+        }))
+      }
+      Val::Map(map) => {
+        let mut entries = Vec::new();
+
+        for (key, value) in map.iter() {
+          entries.push((self.convert(key)?, self.convert(value)?));
+        }
+
+        SendableVal::Map(entries)
+      }
+      Val::Set(set) => {
+        let mut values = Vec::new();
+
+        for value in set.iter() {
+          values.push(self.convert(value)?);
+        }
+
+        SendableVal::Set(values)
+      }
+      Val::Function(f) => SendableVal::Function {
+        bytecode: self
+          .bytecodes
+          .entry(Rc::as_ptr(&f.bytecode))
+          .or_insert_with(|| {
+            Arc::new(SendableBytecode {
+              code: f.bytecode.code.clone(),
+              positions: f.bytecode.positions.clone(),
+            })
+          })
+          .clone(),
+        meta_pos: f.meta_pos,
+        is_generator: f.is_generator,
+        is_async: f.is_async,
+        register_count: f.register_count,
+        parameter_count: f.parameter_count,
+        has_rest_param: f.has_rest_param,
+        start: f.start,
+        binds: self.convert_vec(&f.binds)?,
+      },
+      Val::Class(c) => SendableVal::Class {
+        name: c.name.clone(),
+        content_hash: c.content_hash,
+        constructor: Box::new(self.convert(&c.constructor)?),
+        prototype: Box::new(self.convert(&c.prototype)?),
+        static_: Box::new(self.convert(&c.static_)?),
+      },
+      Val::Static(s) => SendableVal::Static(*s),
+      Val::Dynamic(_) => {
+        if let Some(regexp) = as_regexp(val) {
+          return Ok(SendableVal::RegExp {
+            source: regexp.source.to_string(),
+            flags: regexp.flags.to_string(),
+          });
+        }
+
+        if let Some(promise) = as_promise(val) {
+          if promise.is_settled() {
+            return Ok(SendableVal::Promise(match promise.await_() {
+              Ok(AwaitResult::Value(value)) => Ok(Box::new(self.convert(&value)?)),
+              Ok(AwaitResult::Push(_)) => unreachable!(),
+              Err(reason) => Err(Box::new(self.convert(&reason)?)),
+            }));
+          }
+
+          return Err("Cannot send a pending promise to another thread".to_type_error());
+        }
+
+        return Err(format!("Cannot send {} to another thread", val).to_type_error());
+      }
+      Val::CopyCounter(_) => {
+        return Err("Cannot send a CopyCounter to another thread".to_type_error());
+      }
+      Val::StoragePtr(ptr) => self.convert(&ptr.get())?,
+    })
+  }
+
+  fn convert_vec(&mut self, vals: &[Val]) -> Result<Vec<SendableVal>, Val> {
+    vals.iter().map(|val| self.convert(val)).collect()
+  }
+}
+
+#[derive(Default)]
+struct ReceiveContext {
+  bytecodes: HashMap<*const SendableBytecode, Rc<Bytecode>>,
+}
+
+impl ReceiveContext {
+  fn convert(&mut self, sendable: SendableVal) -> Result<Val, Val> {
+    Ok(match sendable {
+      SendableVal::Void => Val::Void,
+      SendableVal::Undefined => Val::Undefined,
+      SendableVal::Null => Val::Null,
+      SendableVal::Bool(b) => Val::Bool(b),
+      SendableVal::Number(n) => Val::Number(n),
+      SendableVal::BigInt(b) => Val::BigInt(b),
+      SendableVal::Symbol(s) => Val::Symbol(s),
+      SendableVal::String(s) => s.to_val(),
+      SendableVal::Array { elements, raw } => match raw {
+        Some(raw) => VsArray::template_strings(self.convert_vec(elements)?, self.convert_vec(raw)?),
+        None => VsArray::from(self.convert_vec(elements)?),
+      }
+      .to_val(),
+      SendableVal::Object(obj) => {
+        let SendableObject {
+          string_map,
+          symbol_map,
+          getter_map,
+          setter_map,
+          private_map,
+          prototype,
+        } = *obj;
+
+        VsObject {
+          string_map: string_map
+            .into_iter()
+            .map(|(key, value)| Ok((key, self.convert(value)?)))
+            .collect::<Result<_, Val>>()?,
+          symbol_map: symbol_map
+            .into_iter()
+            .map(|(key, value)| Ok((key, self.convert(value)?)))
+            .collect::<Result<_, Val>>()?,
+          getter_map: getter_map
+            .into_iter()
+            .map(|(key, value)| Ok((key, self.convert(value)?)))
+            .collect::<Result<_, Val>>()?,
+          setter_map: setter_map
+            .into_iter()
+            .map(|(key, value)| Ok((key, self.convert(value)?)))
+            .collect::<Result<_, Val>>()?,
+          private_map: private_map
+            .into_iter()
+            .map(|(key, value)| Ok((key, self.convert(value)?)))
+            .collect::<Result<_, Val>>()?,
+          prototype: self.convert(prototype)?,
+        }
+        .to_val()
+      }
+      SendableVal::Map(entries) => {
+        let mut map = VsMap::default();
+
+        for (key, value) in entries {
+          map.insert(self.convert(key)?, self.convert(value)?);
+        }
+
+        map.to_val()
+      }
+      SendableVal::Set(values) => {
+        let mut set = VsSet::default();
+
+        for value in values {
+          set.insert(self.convert(value)?);
+        }
+
+        set.to_val()
+      }
+      SendableVal::Function {
+        bytecode,
+        meta_pos,
+        is_generator,
+        is_async,
+        register_count,
+        parameter_count,
+        has_rest_param,
+        start,
+        binds,
+      } => VsFunction {
+        bytecode: self
+          .bytecodes
+          .entry(Arc::as_ptr(&bytecode))
+          .or_insert_with(|| {
+            Rc::new(Bytecode::with_positions(
+              bytecode.code.clone(),
+              bytecode.positions.clone(),
+            ))
+          })
+          .clone(),
+        meta_pos,
+        is_generator,
+        is_async,
+        register_count,
+        parameter_count,
+        has_rest_param,
+        start,
+        binds: self.convert_vec(binds)?,
+      }
+      .to_val(),
+      SendableVal::Class {
+        name,
+        content_hash,
+        constructor,
+        prototype,
+        static_,
+      } => VsClass {
+        name,
+        content_hash,
+        constructor: self.convert(*constructor)?,
+        prototype: self.convert(*prototype)?,
+        static_: self.convert(*static_)?,
+      }
+      .to_val(),
+      SendableVal::Static(s) => Val::Static(s),
+      SendableVal::RegExp { source, flags } => match VsRegExp::new(&source, &flags) {
+        Ok(regexp) => regexp.to_dynamic_val(),
+        Err(_) => {
+          return Err(format!("Received invalid regex /{}/{}", source, flags).to_internal_error())
+        }
+      },
+      SendableVal::Promise(result) => match result {
+        Ok(value) => VsPromise::fulfilled(self.convert(*value)?),
+        Err(reason) => VsPromise::rejected(self.convert(*reason)?),
+      }
+      .to_dynamic_val(),
+    })
+  }
+
+  fn convert_vec(&mut self, sendables: Vec<SendableVal>) -> Result<Vec<Val>, Val> {
+    sendables
+      .into_iter()
+      .map(|sendable| self.convert(sendable))
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::rc::Rc;
+
+  use crate::{
+    bytecode::Bytecode,
+    position_table::{PositionTable, SourcePosition},
+    vs_function::VsFunction,
+    vs_value::{ToVal, Val},
+  };
+
+  use super::SendableVal;
+
+  #[test]
+  fn invalid_regex_is_an_error() {
+    let sendable = SendableVal::RegExp {
+      source: "(".to_string(),
+      flags: "".to_string(),
+    };
+
+    assert!(sendable.into_val().is_err());
+  }
+
+  #[test]
+  fn function_keeps_positions() {
+    let position = SourcePosition {
+      path: "main.ts".to_string(),
+      line: 3,
+      col: 5,
+    };
+
+    let mut positions = PositionTable::default();
+    positions.push(0, Some(position.clone()));
+
+    let fn_ = VsFunction {
+      bytecode: Rc::new(Bytecode::with_positions(vec![0], positions)),
+      meta_pos: None,
+      is_generator: false,
+      is_async: false,
+      register_count: 3,
+      parameter_count: 0,
+      has_rest_param: false,
+      start: 0,
+      binds: vec![],
+    }
+    .to_val();
+
+    let received = SendableVal::from_val(&fn_).unwrap().into_val().unwrap();
+
+    match received {
+      Val::Function(fn_) => assert_eq!(fn_.bytecode.positions.lookup(0), Some(&position)),
+      _ => panic!("Expected a function"),
+    }
+  }
+}
@@ -0,0 +1,83 @@
+use std::rc::Rc;
+
+use crate::builtins::internal_error_builtin::ToInternalError;
+use crate::iteration::map_iterator::{MapIterator, MapIteratorKind};
+use crate::native_function::{native_fn, NativeFunction};
+use crate::vs_set::VsSet;
+use crate::vs_symbol::VsSymbol;
+use crate::vs_value::{ToDynamicVal, ToVal, Val};
+
+pub fn op_sub_set(set: &Rc<VsSet>, key: &Val) -> Val {
+  if let Val::Symbol(symbol) = key {
+    return match symbol {
+      VsSymbol::ITERATOR => VALUES.to_val(),
+      VsSymbol::GENERATOR_RETURN | VsSymbol::ASYNC_ITERATOR => Val::Undefined,
+    };
+  }
+
+  Val::Static(match key.to_string().as_str() {
+    "add" => &ADD,
+    "clear" => &CLEAR,
+    "delete" => &DELETE,
+    "entries" => &ENTRIES,
+    // forEach: Not included because it cannot work as expected in ValueScript
+    // (Use a for..of loop)
+    "has" => &HAS,
+    "keys" => &VALUES,
+    "size" => return (set.len() as f64).to_val(),
+    "values" => &VALUES,
+    _ => return Val::Undefined,
+  })
+}
+
+static ADD: NativeFunction = native_fn(|mut this, params| {
+  let value = params.into_iter().next().unwrap_or(Val::Undefined);
+  let this = this.get_mut()?;
+
+  match this {
+    Val::Set(set) => Rc::make_mut(set).insert(value),
+    _ => return Err("set indirection".to_internal_error()),
+  };
+
+  Ok(this.clone())
+});
+
+static CLEAR: NativeFunction = native_fn(|mut this, _params| {
+  match this.get_mut()? {
+    Val::Set(set) => Rc::make_mut(set).clear(),
+    _ => return Err("set indirection".to_internal_error()),
+  };
+
+  Ok(Val::Undefined)
+});
+
+static DELETE: NativeFunction = native_fn(|mut this, params| {
+  let value = params.first().cloned().unwrap_or(Val::Undefined);
+
+  Ok(match this.get_mut()? {
+    Val::Set(set) => match set.has(&value) {
+      true => Rc::make_mut(set).remove(&value).to_val(),
+      false => false.to_val(),
+    },
+    _ => return Err("set indirection".to_internal_error()),
+  })
+});
+
+static ENTRIES: NativeFunction = native_fn(|this, _params| match this.get() {
+  set @ Val::Set(_) => Ok(MapIterator::new(set, MapIteratorKind::Entries).to_dynamic_val()),
+  _ => Err("set indirection".to_internal_error()),
+});
+
+static HAS: NativeFunction = native_fn(|this, params| {
+  let value = params.first().cloned().unwrap_or(Val::Undefined);
+
+  Ok(match this.get() {
+    Val::Set(set) => set.has(&value).to_val(),
+    _ => return Err("set indirection".to_internal_error()),
+  })
+});
+
+static VALUES: NativeFunction = native_fn(|this, _params| match this.get() {
+  set @ Val::Set(_) => Ok(MapIterator::new(set, MapIteratorKind::Values).to_dynamic_val()),
+  _ => Err("set indirection".to_internal_error()),
+});
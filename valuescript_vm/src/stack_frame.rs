@@ -1,5 +1,7 @@
 use std::any::Any;
 
+use super::position_table::StackTraceEntry;
+use super::vs_promise::VsPromise;
 use super::vs_value::Val;
 
 pub type StackFrame = Box<dyn StackFrameTrait>;
@@ -16,6 +18,10 @@ pub enum FrameStepOk {
   Push(StackFrame),
   Yield(Val),
   YieldStar(Val),
+
+  /// Queue a promise's job to be run by the `VirtualMachine`. The frame continues stepping
+  /// afterwards.
+  Spawn(VsPromise),
 }
 
 pub type FrameStepResult = Result<FrameStepOk, Val>;
@@ -31,6 +37,16 @@ pub trait StackFrameTrait {
   fn clone_to_stack_frame(&self) -> StackFrame;
   fn as_any(&self) -> &dyn Any;
   fn as_any_mut(&mut self) -> &mut dyn Any;
+
+  /// Describes this frame in the stack trace of an uncaught exception. Only frames which run
+  /// bytecode functions appear in the trace.
+  fn trace_entry(&self) -> Option<StackTraceEntry> {
+    None
+  }
+
+  /// Resumes a suspended generator frame with the value passed to `generator.next(value)`, which
+  /// becomes the result of the `yield` expression it stopped at.
+  fn write_yield_result(&mut self, _value: Val) {}
 }
 
 impl Clone for StackFrame {
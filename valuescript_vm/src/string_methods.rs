@@ -3,10 +3,13 @@ use std::{rc::Rc, str::Chars};
 use valuescript_common::{code_point_at, unicode_at};
 
 use crate::{
-  builtins::internal_error_builtin::ToInternalError,
+  builtins::{internal_error_builtin::ToInternalError, type_error_builtin::ToTypeError},
   helpers::{to_wrapping_index, to_wrapping_index_clamped},
-  iteration::string_iterator::StringIterator,
+  iteration::{array_iterator::ArrayIterator, string_iterator::StringIterator},
   native_function::{native_fn, NativeFunction},
+  string_replace_frame::{REPLACE, REPLACE_ALL},
+  vs_array::VsArray,
+  vs_regexp::{advance_char, as_regexp, to_regexp, VsRegExp},
   vs_symbol::VsSymbol,
   vs_value::{ToDynamicVal, ToVal, Val},
   ValTrait,
@@ -29,6 +32,7 @@ pub fn op_sub_string(string_data: &Rc<str>, subscript: &Val) -> Val {
   if let Val::Symbol(subscript) = subscript {
     match subscript {
       VsSymbol::ITERATOR => return VALUES.to_val(),
+      VsSymbol::GENERATOR_RETURN | VsSymbol::ASYNC_ITERATOR => return Val::Undefined,
     }
   }
 
@@ -64,15 +68,15 @@ pub fn get_string_method(method: &str) -> Val {
     "indexOf" => &INDEX_OF,
     "lastIndexOf" => &LAST_INDEX_OF,
     "localeCompare" => &TODO_LOCALE, // (TODO)
-    "match" => &TODO_REGEXES,        // (TODO: regex)
-    "matchAll" => &TODO_REGEXES,     // (TODO: regex)
-    "normalize" => &NORMALIZE,       // (TODO)
+    "match" => &MATCH,
+    "matchAll" => &MATCH_ALL,
+    "normalize" => &NORMALIZE, // (TODO)
     "padEnd" => &PAD_END,
     "padStart" => &PAD_START,
     "repeat" => &REPEAT,
-    "replace" => &TODO_REGEXES,    // (TODO: regex)
-    "replaceAll" => &TODO_REGEXES, // (TODO: regex)
-    "search" => &TODO_REGEXES,     // (TODO: regex)
+    "replace" => return REPLACE.to_val(),
+    "replaceAll" => return REPLACE_ALL.to_val(),
+    "search" => &SEARCH,
     "slice" => &SLICE,
     "split" => &SPLIT,
     "startsWith" => &STARTS_WITH,
@@ -289,12 +293,67 @@ static TODO_LOCALE: NativeFunction = native_fn(|this, _params| {
   }
 });
 
-static TODO_REGEXES: NativeFunction = native_fn(|this, _params| {
-  // TODO: Ok(...)
-  match this.get() {
-    Val::String(_string_data) => Err("TODO: regexes".to_internal_error()),
-    _ => Err("string indirection".to_internal_error()),
-  }
+static MATCH: NativeFunction = native_fn(|this, params| {
+  Ok(match this.get() {
+    Val::String(string_data) => {
+      let regexp = to_regexp(params.first(), "")?;
+
+      if !regexp.is_global() {
+        return Ok(match regexp.exec_at(&string_data, 0)? {
+          Some(m) => m.to_array_val(),
+          None => Val::Null,
+        });
+      }
+
+      let matches = regexp.exec_all(&string_data)?;
+
+      if matches.is_empty() {
+        return Ok(Val::Null);
+      }
+
+      matches
+        .iter()
+        .map(|m| string_data[m.start..m.end].to_val())
+        .collect::<Vec<Val>>()
+        .to_val()
+    }
+    _ => return Err("string indirection".to_internal_error()),
+  })
+});
+
+static MATCH_ALL: NativeFunction = native_fn(|this, params| {
+  Ok(match this.get() {
+    Val::String(string_data) => {
+      let regexp = to_regexp(params.first(), "g")?;
+
+      if !regexp.is_global() {
+        return Err("matchAll must be called with a global RegExp".to_type_error());
+      }
+
+      let matches = regexp
+        .exec_all(&string_data)?
+        .iter()
+        .map(|m| m.to_array_val())
+        .collect::<Vec<Val>>();
+
+      ArrayIterator::new(Rc::new(VsArray::from(matches))).to_dynamic_val()
+    }
+    _ => return Err("string indirection".to_internal_error()),
+  })
+});
+
+static SEARCH: NativeFunction = native_fn(|this, params| {
+  Ok(match this.get() {
+    Val::String(string_data) => {
+      let regexp = to_regexp(params.first(), "")?;
+
+      match regexp.exec_at(&string_data, 0)? {
+        Some(m) => Val::Number(m.start as f64),
+        None => Val::Number(-1.0),
+      }
+    }
+    _ => return Err("string indirection".to_internal_error()),
+  })
 });
 
 static NORMALIZE: NativeFunction = native_fn(|this, _params| {
@@ -472,7 +531,7 @@ static SPLIT: NativeFunction = native_fn(|this, params| {
   Ok(match this.get() {
     Val::String(string_data) => {
       let separator = match params.first() {
-        Some(s) => s.to_string(), // TODO: Regexes
+        Some(s) => s.to_string(),
         None => return Ok(Val::String(string_data.clone())),
       };
 
@@ -491,6 +550,10 @@ static SPLIT: NativeFunction = native_fn(|this, params| {
         return Ok(result.to_val());
       }
 
+      if let Some(regexp) = params.first().and_then(as_regexp) {
+        return Ok(split_by_regexp(&string_data, regexp, limit)?.to_val());
+      }
+
       if separator.is_empty() {
         for c in string_data.chars() {
           result.push(c.to_val());
@@ -665,6 +728,64 @@ static VALUES: NativeFunction = native_fn(|this, _params| match this.get() {
   _ => Err("string indirection".to_internal_error()),
 });
 
+/// Splits like `split` with a RegExp separator, which includes the captures of each separator in
+/// the result.
+fn split_by_regexp(string_data: &str, regexp: &VsRegExp, limit: usize) -> Result<Vec<Val>, Val> {
+  let mut result = Vec::<Val>::new();
+
+  if string_data.is_empty() {
+    if regexp.exec_at(string_data, 0)?.is_none() {
+      result.push("".to_val());
+    }
+
+    return Ok(result);
+  }
+
+  // Start of the current part
+  let mut p = 0;
+
+  // Where to search for the next separator
+  let mut q = 0;
+
+  while q < string_data.len() {
+    let m = match regexp.exec_at(string_data, q)? {
+      Some(m) => m,
+      None => break,
+    };
+
+    if m.start >= string_data.len() {
+      break;
+    }
+
+    if m.end == p {
+      // Empty separators can't produce empty parts
+      q = advance_char(string_data, m.start);
+      continue;
+    }
+
+    result.push(string_data[p..m.start].to_val());
+
+    if result.len() == limit {
+      return Ok(result);
+    }
+
+    for capture in m.captures.iter().skip(1) {
+      result.push(capture.clone());
+
+      if result.len() == limit {
+        return Ok(result);
+      }
+    }
+
+    p = m.end;
+    q = p;
+  }
+
+  result.push(string_data[p..].to_val());
+
+  Ok(result)
+}
+
 /**
  * Tries to match str_chars_param against matcher.
  * - Successful match: Advances str_chars_param and returns true.
@@ -694,7 +815,7 @@ fn match_chars(str_chars_param: &mut Chars, matcher: &str) -> bool {
   }
 }
 
-fn index_of(string_bytes: &[u8], search_bytes: &[u8], start_pos: usize) -> Option<usize> {
+pub fn index_of(string_bytes: &[u8], search_bytes: &[u8], start_pos: usize) -> Option<usize> {
   let search_length = search_bytes.len();
 
   if start_pos + search_length > string_bytes.len() {
@@ -704,7 +825,7 @@ fn index_of(string_bytes: &[u8], search_bytes: &[u8], start_pos: usize) -> Optio
   }
 
   if search_length == 0 {
-    return Some(0);
+    return Some(start_pos);
   }
 
   'outer: for i in start_pos..=(string_bytes.len() - search_length) {
@@ -0,0 +1,306 @@
+use std::any::Any;
+use std::rc::Rc;
+
+use crate::builtins::type_error_builtin::ToTypeError;
+use crate::native_frame_function::NativeFrameFunction;
+use crate::native_function::ThisWrapper;
+use crate::stack_frame::{CallResult, FrameStepOk, FrameStepResult, StackFrame, StackFrameTrait};
+use crate::string_methods::index_of;
+use crate::vs_regexp::{advance_char, as_regexp, RegExpMatch};
+use crate::vs_value::{LoadFunctionResult, ToVal, Val, ValTrait};
+
+pub static REPLACE: NativeFrameFunction = NativeFrameFunction {
+  make_frame: || Box::new(StringReplaceFrame::new(false)),
+};
+
+pub static REPLACE_ALL: NativeFrameFunction = NativeFrameFunction {
+  make_frame: || Box::new(StringReplaceFrame::new(true)),
+};
+
+/// Implements `replace` and `replaceAll`. This needs to be a frame because the replacement can be
+/// a function, which is called once for each match.
+#[derive(Clone)]
+struct StringReplaceFrame {
+  all: bool,
+  this: Option<Rc<str>>,
+  pattern: Val,
+  replacement: Val,
+  param_i: usize,
+
+  matches: Option<Vec<RegExpMatch>>,
+  match_i: usize,
+  last_end: usize,
+  result: String,
+}
+
+impl StringReplaceFrame {
+  fn new(all: bool) -> StringReplaceFrame {
+    StringReplaceFrame {
+      all,
+      this: None,
+      pattern: Val::Undefined,
+      replacement: Val::Undefined,
+      param_i: 0,
+      matches: None,
+      match_i: 0,
+      last_end: 0,
+      result: String::new(),
+    }
+  }
+
+  fn find_matches(&self, string_data: &str) -> Result<Vec<RegExpMatch>, Val> {
+    if let Some(regexp) = as_regexp(&self.pattern) {
+      if self.all && !regexp.is_global() {
+        return Err("replaceAll must be called with a global RegExp".to_type_error());
+      }
+
+      return regexp.exec_all(string_data);
+    }
+
+    let pattern = self.pattern.to_string();
+    let mut matches = Vec::<RegExpMatch>::new();
+    let mut pos = 0;
+
+    while let Some(start) = index_of(string_data.as_bytes(), pattern.as_bytes(), pos) {
+      matches.push(RegExpMatch {
+        start,
+        end: start + pattern.len(),
+        captures: vec![pattern.clone().to_val()],
+        groups: None,
+      });
+
+      if !self.all {
+        break;
+      }
+
+      pos = match pattern.is_empty() {
+        true => advance_char(string_data, start),
+        false => start + pattern.len(),
+      };
+
+      if pos > string_data.len() {
+        break;
+      }
+    }
+
+    Ok(matches)
+  }
+
+  fn pop(&mut self, string_data: Rc<str>) -> FrameStepResult {
+    self.result.push_str(&string_data[self.last_end..]);
+
+    Ok(FrameStepOk::Pop(CallResult {
+      return_: std::mem::take(&mut self.result).to_val(),
+      this: Val::String(string_data),
+    }))
+  }
+}
+
+impl StackFrameTrait for StringReplaceFrame {
+  fn write_this(&mut self, _const: bool, this: Val) -> Result<(), Val> {
+    if let Val::String(string_data) = this {
+      self.this = Some(string_data);
+    }
+
+    Ok(())
+  }
+
+  fn write_param(&mut self, param: Val) {
+    match self.param_i {
+      0 => self.pattern = param,
+      1 => self.replacement = param,
+      _ => {}
+    };
+
+    self.param_i += 1;
+  }
+
+  fn step(&mut self) -> FrameStepResult {
+    let string_data = match &self.this {
+      None => return Err("string indirection".to_type_error()),
+      Some(string_data) => string_data.clone(),
+    };
+
+    let matches = match &self.matches {
+      Some(matches) => matches,
+      None => {
+        let matches = self.find_matches(&string_data)?;
+
+        if let LoadFunctionResult::NotAFunction = self.replacement.load_function() {
+          let template = self.replacement.to_string();
+
+          for m in &matches {
+            self.result.push_str(&string_data[self.last_end..m.start]);
+            expand_replacement(&mut self.result, &template, &string_data, m);
+            self.last_end = m.end;
+          }
+
+          return self.pop(string_data);
+        }
+
+        self.matches.insert(matches)
+      }
+    };
+
+    let m = match matches.get(self.match_i) {
+      Some(m) => m,
+      None => return self.pop(string_data),
+    };
+
+    self.result.push_str(&string_data[self.last_end..m.start]);
+
+    let mut args = m.captures.clone();
+    args.push(Val::Number(m.start as f64));
+    args.push(Val::String(string_data.clone()));
+
+    if m.groups.is_some() {
+      args.push(m.groups_val());
+    }
+
+    match self.replacement.load_function() {
+      LoadFunctionResult::NotAFunction => Err("replacement is not a function".to_type_error()),
+      LoadFunctionResult::NativeFunction(native_fn) => {
+        let replacement = native_fn(ThisWrapper::new(true, &mut Val::Undefined), args)?;
+
+        self.apply_call_result(CallResult {
+          return_: replacement,
+          this: Val::Undefined,
+        });
+
+        Ok(FrameStepOk::Continue)
+      }
+      LoadFunctionResult::StackFrame(mut new_frame) => {
+        new_frame.write_this(true, Val::Undefined)?;
+
+        for arg in args {
+          new_frame.write_param(arg);
+        }
+
+        Ok(FrameStepOk::Push(new_frame))
+      }
+    }
+  }
+
+  fn apply_call_result(&mut self, call_result: CallResult) {
+    let m = match &self.matches {
+      Some(matches) => &matches[self.match_i],
+      None => panic!("Call result without matches"),
+    };
+
+    self.result.push_str(&call_result.return_.to_string());
+    self.last_end = m.end;
+    self.match_i += 1;
+  }
+
+  fn get_call_result(&mut self) -> CallResult {
+    panic!("Not appropriate for StringReplaceFrame")
+  }
+
+  fn can_catch_exception(&self, _exception: &Val) -> bool {
+    false
+  }
+
+  fn catch_exception(&mut self, _exception: &mut Val) {}
+
+  fn clone_to_stack_frame(&self) -> StackFrame {
+    Box::new(self.clone())
+  }
+
+  fn as_any(&self) -> &dyn Any {
+    self
+  }
+
+  fn as_any_mut(&mut self) -> &mut dyn Any {
+    self
+  }
+}
+
+/// Expands `$` patterns in a replacement string (`$$`, `$&`, `` $` ``, `$'`, `$n`, `$nn` and
+/// `$<name>`).
+fn expand_replacement(result: &mut String, template: &str, input: &str, m: &RegExpMatch) {
+  let bytes = template.as_bytes();
+  let group_count = m.captures.len() - 1;
+  let mut i = 0;
+
+  while i < bytes.len() {
+    if bytes[i] != b'$' || i + 1 == bytes.len() {
+      let len = template[i..].chars().next().map_or(1, |c| c.len_utf8());
+      result.push_str(&template[i..i + len]);
+      i += len;
+      continue;
+    }
+
+    match bytes[i + 1] {
+      b'$' => {
+        result.push('$');
+        i += 2;
+      }
+      b'&' => {
+        result.push_str(&input[m.start..m.end]);
+        i += 2;
+      }
+      b'`' => {
+        result.push_str(&input[..m.start]);
+        i += 2;
+      }
+      b'\'' => {
+        result.push_str(&input[m.end..]);
+        i += 2;
+      }
+      b'0'..=b'9' => {
+        let one_digit = (bytes[i + 1] - b'0') as usize;
+
+        let two_digits = match bytes.get(i + 2) {
+          Some(d @ b'0'..=b'9') => Some(one_digit * 10 + (d - b'0') as usize),
+          _ => None,
+        };
+
+        let (group, len) = match two_digits {
+          Some(n) if n >= 1 && n <= group_count => (n, 3),
+          _ => (one_digit, 2),
+        };
+
+        if group >= 1 && group <= group_count {
+          if let Val::String(s) = &m.captures[group] {
+            result.push_str(s);
+          }
+        } else {
+          result.push_str(&template[i..i + len]);
+        }
+
+        i += len;
+      }
+      b'<' => {
+        let groups = match &m.groups {
+          Some(groups) => groups,
+          None => {
+            result.push_str("$<");
+            i += 2;
+            continue;
+          }
+        };
+
+        let close = match template[i + 2..].find('>') {
+          Some(close) => i + 2 + close,
+          None => {
+            result.push_str("$<");
+            i += 2;
+            continue;
+          }
+        };
+
+        let name = &template[i + 2..close];
+
+        if let Some((_, Val::String(s))) = groups.iter().find(|(n, _)| n == name) {
+          result.push_str(s);
+        }
+
+        i = close + 1;
+      }
+      _ => {
+        result.push('$');
+        i += 1;
+      }
+    }
+  }
+}
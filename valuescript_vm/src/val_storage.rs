@@ -3,15 +3,22 @@ use std::{collections::BTreeMap, error::Error, rc::Rc};
 use num_bigint::BigInt;
 use num_derive::{FromPrimitive, ToPrimitive};
 use num_traits::{FromPrimitive, ToPrimitive};
+use radix_tree::StrictRadixTreeData;
 use storage::{
   GenericError, RcKey, StorageBackend, StorageEntity, StorageEntry, StorageEntryReader,
   StorageEntryWriter, StorageReader, StorageTxMut,
 };
 
 use crate::{
+  array_elements::{ArrayElements, ArrayTree},
+  hybrid_map::HybridMap,
+  vs_array::VsArray,
   vs_class::VsClass,
   vs_function::VsFunction,
-  vs_object::VsObject,
+  vs_map::VsMap,
+  vs_object::{PropertyKey, VsObject},
+  vs_private_name::VsPrivateName,
+  vs_set::VsSet,
   vs_storage_ptr::VsStoragePtr,
   vs_value::{ToVal, Val},
   Bytecode, VsSymbol,
@@ -35,6 +42,8 @@ enum Tag {
   Dynamic,
   CopyCounter,
   StoragePtr,
+  Map,
+  Set,
 }
 
 impl Tag {
@@ -74,10 +83,28 @@ impl<SB: StorageBackend + 'static> StorageEntity<SB> for Val {
     match self {
       Val::Array(a) => {
         writer.write_u8(Tag::Array.to_byte());
-        writer.write_vlq(a.elements.len());
 
-        for item in a.elements.iter() {
-          write_to_entry(item, tx, writer)?;
+        match &a.elements {
+          ArrayElements::Small(items) => {
+            writer.write_u8(0);
+            writer.write_vlq(items.len());
+
+            for item in items {
+              write_to_entry(item, tx, writer)?;
+            }
+          }
+          ArrayElements::Tree(tree) => {
+            writer.write_u8(1);
+            write_array_tree_to_entry(tree, tx, writer)?;
+          }
+        }
+
+        match &a.raw {
+          None => writer.write_u8(0),
+          Some(raw) => {
+            writer.write_u8(1);
+            write_to_entry(&Val::Array(raw.clone()), tx, writer)?;
+          }
         }
       }
       Val::Object(obj) => {
@@ -97,15 +124,61 @@ impl<SB: StorageBackend + 'static> StorageEntity<SB> for Val {
           write_to_entry(value, tx, writer)?;
         }
 
+        for accessor_map in [&obj.getter_map, &obj.setter_map] {
+          writer.write_vlq(accessor_map.len());
+
+          for (key, value) in accessor_map.iter() {
+            match key {
+              PropertyKey::String(string) => {
+                writer.write_u8(0);
+                writer.write_vlq_buf(string.as_bytes());
+              }
+              PropertyKey::Symbol(symbol) => {
+                writer.write_u8(1);
+                writer.write_vlq(symbol.to_usize().unwrap());
+              }
+            }
+
+            write_to_entry(value, tx, writer)?;
+          }
+        }
+
+        writer.write_vlq(obj.private_map.len());
+
+        for (key, value) in obj.private_map.iter() {
+          writer.write_bytes(&key.brand);
+          writer.write_vlq_buf(key.name.as_bytes());
+          write_to_entry(value, tx, writer)?;
+        }
+
         write_to_entry(&obj.prototype, tx, writer)?;
       }
+      Val::Map(map) => {
+        writer.write_u8(Tag::Map.to_byte());
+        writer.write_vlq(map.len());
+
+        for (key, value) in map.iter() {
+          write_to_entry(key, tx, writer)?;
+          write_to_entry(value, tx, writer)?;
+        }
+      }
+      Val::Set(set) => {
+        writer.write_u8(Tag::Set.to_byte());
+        writer.write_vlq(set.len());
+
+        for value in set.iter() {
+          write_to_entry(value, tx, writer)?;
+        }
+      }
       Val::Function(f) => {
         let VsFunction {
           bytecode,
           meta_pos,
           is_generator,
+          is_async,
           register_count,
           parameter_count,
+          has_rest_param,
           start,
           binds,
         } = f.as_ref();
@@ -123,8 +196,10 @@ impl<SB: StorageBackend + 'static> StorageEntity<SB> for Val {
         };
 
         writer.write_u8(if *is_generator { 1 } else { 0 });
+        writer.write_u8(if *is_async { 1 } else { 0 });
         writer.write_vlq(*register_count);
         writer.write_vlq(*parameter_count);
+        writer.write_u8(if *has_rest_param { 1 } else { 0 });
         writer.write_vlq(*start);
         writer.write_vlq(binds.len());
 
@@ -195,6 +270,8 @@ fn write_to_entry<SB: StorageBackend + 'static, Tx: StorageTxMut<SB>>(
     }
     Val::Array(a) => write_ptr_to_entry(tx, writer, RcKey::from(a.clone()), val)?,
     Val::Object(obj) => write_ptr_to_entry(tx, writer, RcKey::from(obj.clone()), val)?,
+    Val::Map(map) => write_ptr_to_entry(tx, writer, RcKey::from(map.clone()), val)?,
+    Val::Set(set) => write_ptr_to_entry(tx, writer, RcKey::from(set.clone()), val)?,
     Val::Function(f) => write_ptr_to_entry(tx, writer, RcKey::from(f.clone()), val)?,
     Val::Class(c) => {
       writer.write_u8(Tag::Class.to_byte());
@@ -282,18 +359,35 @@ fn read_from_entry<SB: StorageBackend + 'static, Tx: StorageReader<SB>>(
     Tag::Symbol => Val::Symbol(FromPrimitive::from_usize(reader.read_vlq()?).unwrap()),
     Tag::String => String::from_utf8(reader.read_vlq_buf()?)?.to_val(),
     Tag::Array => {
-      let len = reader.read_vlq()?;
-      let mut items = Vec::new();
+      let elements = match reader.read_u8()? {
+        0 => {
+          let len = reader.read_vlq()?;
+          let mut items = Vec::new();
 
-      for _ in 0..len {
-        items.push(read_from_entry(tx, reader)?);
-      }
+          for _ in 0..len {
+            items.push(read_from_entry(tx, reader)?);
+          }
 
-      items.to_val()
+          items.into()
+        }
+        1 => ArrayElements::Tree(read_array_tree_from_entry(tx, reader)?),
+        _ => panic!("Invalid array kind byte"),
+      };
+
+      let raw = match reader.read_u8()? {
+        0 => None,
+        1 => match read_from_entry(tx, reader)? {
+          Val::Array(raw) => Some(raw),
+          _ => panic!("Invalid raw strings"),
+        },
+        _ => panic!("Invalid raw strings byte"),
+      };
+
+      VsArray { elements, raw }.to_val()
     }
     Tag::Object => {
       let len = reader.read_vlq()?;
-      let mut string_map = BTreeMap::<String, Val>::new();
+      let mut string_map = HybridMap::<String, Val>::new();
 
       for _ in 0..len {
         let key = read_string_from_entry(reader)?;
@@ -312,11 +406,48 @@ fn read_from_entry<SB: StorageBackend + 'static, Tx: StorageReader<SB>>(
         symbol_map.insert(key, value);
       }
 
+      let mut accessor_maps = [
+        BTreeMap::<PropertyKey, Val>::new(),
+        BTreeMap::<PropertyKey, Val>::new(),
+      ];
+
+      for accessor_map in &mut accessor_maps {
+        let len = reader.read_vlq()?;
+
+        for _ in 0..len {
+          let key = match reader.read_u8()? {
+            0 => PropertyKey::String(read_string_from_entry(reader)?),
+            1 => PropertyKey::Symbol(read_symbol_from_entry(reader)?),
+            _ => panic!("Invalid property key kind byte"),
+          };
+
+          let value = read_from_entry(tx, reader)?;
+
+          accessor_map.insert(key, value);
+        }
+      }
+
+      let [getter_map, setter_map] = accessor_maps;
+
+      let len = reader.read_vlq()?;
+      let mut private_map = BTreeMap::<VsPrivateName, Val>::new();
+
+      for _ in 0..len {
+        let brand = reader.read_u8_array()?;
+        let name = read_string_from_entry(reader)?;
+        let value = read_from_entry(tx, reader)?;
+
+        private_map.insert(VsPrivateName { brand, name }, value);
+      }
+
       let prototype = read_from_entry(tx, reader)?;
 
       VsObject {
         string_map,
         symbol_map,
+        getter_map,
+        setter_map,
+        private_map,
         prototype,
       }
       .to_val()
@@ -336,8 +467,21 @@ fn read_from_entry<SB: StorageBackend + 'static, Tx: StorageReader<SB>>(
         _ => panic!("Invalid is_generator byte"),
       };
 
+      let is_async = match reader.read_u8()? {
+        0 => false,
+        1 => true,
+        _ => panic!("Invalid is_async byte"),
+      };
+
       let register_count = reader.read_vlq()?;
       let parameter_count = reader.read_vlq()?;
+
+      let has_rest_param = match reader.read_u8()? {
+        0 => false,
+        1 => true,
+        _ => panic!("Invalid has_rest_param byte"),
+      };
+
       let start = reader.read_vlq()?;
 
       let len = reader.read_vlq()?;
@@ -351,8 +495,10 @@ fn read_from_entry<SB: StorageBackend + 'static, Tx: StorageReader<SB>>(
         bytecode,
         meta_pos,
         is_generator,
+        is_async,
         register_count,
         parameter_count,
+        has_rest_param,
         start,
         binds,
       }
@@ -384,6 +530,29 @@ fn read_from_entry<SB: StorageBackend + 'static, Tx: StorageReader<SB>>(
     Tag::Dynamic => todo!(),
     Tag::CopyCounter => todo!(),
     Tag::StoragePtr => VsStoragePtr::new(tx.get_auto_ptr(reader.read_ref()?)).to_val(),
+    Tag::Map => {
+      let len = reader.read_vlq()?;
+      let mut map = VsMap::default();
+
+      for _ in 0..len {
+        let key = read_from_entry(tx, reader)?;
+        let value = read_from_entry(tx, reader)?;
+
+        map.insert(key, value);
+      }
+
+      map.to_val()
+    }
+    Tag::Set => {
+      let len = reader.read_vlq()?;
+      let mut set = VsSet::default();
+
+      for _ in 0..len {
+        set.insert(read_from_entry(tx, reader)?);
+      }
+
+      set.to_val()
+    }
   })
 }
 
@@ -440,3 +609,302 @@ fn write_ref_bytecode_to_entry<SB: StorageBackend, Tx: StorageTxMut<SB>>(
 
   Ok(())
 }
+
+/// A node of an array's radix tree. The nodes below the root are stored as their own entries, so
+/// arrays which share nodes in memory also share them in storage.
+struct ArrayTreeNode(ArrayTree);
+
+impl<SB: StorageBackend + 'static> StorageEntity<SB> for ArrayTreeNode {
+  fn from_storage_entry<Tx: StorageReader<SB>>(
+    tx: &Tx,
+    entry: StorageEntry,
+  ) -> Result<Self, GenericError> {
+    let mut reader = StorageEntryReader::new(&entry);
+    let res = read_array_tree_from_entry(tx, &mut reader);
+    assert!(reader.done());
+
+    Ok(ArrayTreeNode(res?))
+  }
+
+  fn to_storage_entry<'a, TxMut: StorageTxMut<SB>>(
+    &self,
+    tx: &mut TxMut,
+  ) -> Result<StorageEntry, GenericError> {
+    let mut entry = StorageEntry {
+      ref_count: 1,
+      refs: vec![],
+      data: vec![],
+    };
+
+    let writer = &mut StorageEntryWriter::new(&mut entry);
+    write_array_tree_to_entry(&self.0, tx, writer)?;
+
+    Ok(entry)
+  }
+}
+
+fn write_array_tree_to_entry<SB: StorageBackend + 'static, Tx: StorageTxMut<SB>>(
+  tree: &ArrayTree,
+  tx: &mut Tx,
+  writer: &mut StorageEntryWriter,
+) -> Result<(), GenericError> {
+  match tree.data() {
+    StrictRadixTreeData::Leaves(leaves) => {
+      writer.write_u8(0);
+      writer.write_vlq(leaves.len());
+
+      for item in leaves {
+        write_to_entry(item, tx, writer)?;
+      }
+    }
+    StrictRadixTreeData::Meta(meta) => {
+      writer.write_u8(1);
+      writer.write_vlq(meta.len());
+
+      for child in meta {
+        let key = RcKey::from(child.rc().clone());
+
+        if let Some(ptr) = tx.cache_get(key.clone()) {
+          writer.entry.refs.push(ptr);
+        } else {
+          let ptr = tx.store_and_cache(&ArrayTreeNode(child.clone()), key)?;
+          writer.entry.refs.push(ptr);
+        }
+      }
+    }
+  }
+
+  Ok(())
+}
+
+fn read_array_tree_from_entry<SB: StorageBackend + 'static, Tx: StorageReader<SB>>(
+  tx: &Tx,
+  reader: &mut StorageEntryReader,
+) -> Result<ArrayTree, GenericError> {
+  Ok(match reader.read_u8()? {
+    0 => {
+      let len = reader.read_vlq()?;
+      let mut leaves = Vec::new();
+
+      for _ in 0..len {
+        leaves.push(read_from_entry(tx, reader)?);
+      }
+
+      ArrayTree::from_leaves(leaves).ok_or("Invalid array tree node")?
+    }
+    1 => {
+      let len = reader.read_vlq()?;
+      let mut meta = Vec::new();
+
+      for _ in 0..len {
+        meta.push(read_ref_array_tree_from_entry(tx, reader)?);
+      }
+
+      ArrayTree::from_meta(meta).ok_or("Invalid array tree node")?
+    }
+    _ => panic!("Invalid array tree node byte"),
+  })
+}
+
+/// Like `read_ref_bytecode_from_entry`, this uses the read cache so that nodes which are shared in
+/// storage are also shared in memory.
+fn read_ref_array_tree_from_entry<SB: StorageBackend + 'static, Tx: StorageReader<SB>>(
+  tx: &Tx,
+  reader: &mut StorageEntryReader,
+) -> Result<ArrayTree, GenericError> {
+  let ref_cell = tx
+    .get_backend()
+    .upgrade()
+    .ok_or(Into::<Box<dyn Error>>::into("Storage backend missing"))?;
+
+  let ptr = reader.read_ref()?;
+
+  if let Some(cache_box) = ref_cell.borrow().get_read_cache().get(&ptr.data) {
+    if let Some(cache_val) = cache_box.downcast_ref::<ArrayTree>() {
+      return Ok(cache_val.clone());
+    }
+  }
+
+  // The read cache can't stay borrowed here since reading the node reads its children
+  let ArrayTreeNode(res) = tx.get(ptr)?;
+
+  ref_cell
+    .borrow()
+    .get_read_cache()
+    .insert(ptr.data, Box::new(res.clone()));
+
+  Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+  use storage::{storage_head_ptr, MemoryBackend, Storage};
+
+  use super::*;
+  use crate::vs_value::ValTrait;
+
+  fn round_trip(storage: &mut Storage<MemoryBackend>, val: &Val) -> Val {
+    storage.set_head(storage_head_ptr(b"test"), val).unwrap();
+    storage.clear_read_cache();
+
+    storage
+      .get_head(storage_head_ptr(b"test"))
+      .unwrap()
+      .unwrap()
+  }
+
+  fn array_elements(val: &Val) -> ArrayElements {
+    match val.not_ptr() {
+      Val::Array(array) => array.elements.clone(),
+      _ => panic!("Expected array"),
+    }
+  }
+
+  fn numbers(len: usize) -> Vec<Val> {
+    (0..len).map(|i| Val::Number(i as f64)).collect()
+  }
+
+  #[test]
+  fn small_array() {
+    let mut storage = Storage::new(MemoryBackend::new());
+    let val = VsArray::from(numbers(10)).to_val();
+
+    let elements = array_elements(&round_trip(&mut storage, &val));
+
+    assert!(matches!(elements, ArrayElements::Small(_)));
+    assert_eq!(
+      val.codify(),
+      VsArray::from(elements.to_vec()).to_val().codify()
+    );
+  }
+
+  #[test]
+  fn tree_array() {
+    let mut storage = Storage::new(MemoryBackend::new());
+    let val = VsArray::from(numbers(200)).to_val();
+
+    let elements = array_elements(&round_trip(&mut storage, &val));
+
+    assert!(matches!(elements, ArrayElements::Tree(_)));
+    assert_eq!(
+      val.codify(),
+      VsArray::from(elements.to_vec()).to_val().codify()
+    );
+  }
+
+  #[test]
+  fn tree_arrays_share_subtrees() {
+    let mut storage = Storage::new(MemoryBackend::new());
+
+    let a = VsArray::from(numbers(100));
+    let mut b = a.clone();
+    b.elements[0] = "changed".to_val();
+
+    let val = VsArray::from(vec![a.to_val(), b.to_val()]).to_val();
+    let reloaded = array_elements(&round_trip(&mut storage, &val));
+
+    let [a, b] = [0, 1].map(|i| match array_elements(&reloaded[i]) {
+      ArrayElements::Tree(tree) => tree,
+      _ => panic!("Expected tree"),
+    });
+
+    let (StrictRadixTreeData::Meta(a_meta), StrictRadixTreeData::Meta(b_meta)) =
+      (a.data(), b.data())
+    else {
+      panic!("Expected meta nodes");
+    };
+
+    assert_eq!(a_meta.len(), 4);
+    assert!(!Rc::ptr_eq(a_meta[0].rc(), b_meta[0].rc()));
+
+    for i in 1..4 {
+      assert!(Rc::ptr_eq(a_meta[i].rc(), b_meta[i].rc()));
+    }
+
+    assert_eq!(array_elements(&reloaded[1])[0].codify(), "\"changed\"");
+    assert_eq!(array_elements(&reloaded[1])[99].codify(), "99");
+  }
+
+  #[test]
+  fn map() {
+    let mut storage = Storage::new(MemoryBackend::new());
+
+    let mut map = VsMap::default();
+    map.insert("b".to_val(), Val::Number(1.0));
+    map.insert(Val::Number(2.0), "two".to_val());
+    map.insert("a".to_val(), Val::Bool(true));
+
+    let Val::Map(reloaded) = round_trip(&mut storage, &map.to_val()) else {
+      panic!("Expected map");
+    };
+
+    let entries = reloaded
+      .iter()
+      .map(|(key, value)| (key.codify(), value.codify()))
+      .collect::<Vec<_>>();
+
+    assert_eq!(
+      entries,
+      vec![
+        ("\"b\"".to_string(), "1".to_string()),
+        ("2".to_string(), "\"two\"".to_string()),
+        ("\"a\"".to_string(), "true".to_string()),
+      ]
+    );
+
+    assert_eq!(reloaded.get(&"a".to_val()).unwrap().codify(), "true");
+  }
+
+  #[test]
+  fn set() {
+    let mut storage = Storage::new(MemoryBackend::new());
+
+    let mut set = VsSet::default();
+    set.insert(Val::Number(3.0));
+    set.insert("x".to_val());
+    set.insert(Val::Number(1.0));
+
+    let Val::Set(reloaded) = round_trip(&mut storage, &set.to_val()) else {
+      panic!("Expected set");
+    };
+
+    let values = reloaded
+      .iter()
+      .map(|value| value.codify())
+      .collect::<Vec<_>>();
+
+    assert_eq!(values, vec!["3", "\"x\"", "1"]);
+    assert!(reloaded.has(&"x".to_val()));
+  }
+
+  #[test]
+  fn large_object() {
+    let mut storage = Storage::new(MemoryBackend::new());
+
+    let mut obj = VsObject::default();
+
+    for i in (0..100).rev() {
+      obj
+        .string_map
+        .insert(format!("k{:03}", i), Val::Number(i as f64));
+    }
+
+    let Val::Object(reloaded) = round_trip(&mut storage, &obj.to_val()) else {
+      panic!("Expected object");
+    };
+
+    assert!(matches!(reloaded.string_map, HybridMap::Large(_)));
+
+    let entries = reloaded
+      .string_map
+      .iter()
+      .map(|(key, value)| (key.clone(), value.codify()))
+      .collect::<Vec<_>>();
+
+    let expected = (0..100)
+      .map(|i| (format!("k{:03}", i), i.to_string()))
+      .collect::<Vec<_>>();
+
+    assert_eq!(entries, expected);
+  }
+}
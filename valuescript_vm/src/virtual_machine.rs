@@ -1,3 +1,4 @@
+use std::collections::VecDeque;
 use std::mem::take;
 use std::rc::Rc;
 
@@ -5,14 +6,36 @@ use crate::builtins::internal_error_builtin::ToInternalError;
 use crate::bytecode::Bytecode;
 use crate::bytecode::DecoderMaker;
 use crate::first_stack_frame::FirstStackFrame;
+use crate::position_table::StackTraceEntry;
 use crate::stack_frame::CallResult;
 use crate::stack_frame::FrameStepOk;
 use crate::stack_frame::StackFrame;
+use crate::vs_promise::{await_val, AwaitResult, VsPromise};
 use crate::vs_value::{LoadFunctionResult, Val, ValTrait};
 
 pub struct VirtualMachine {
   pub frame: StackFrame,
   pub stack: Vec<StackFrame>,
+
+  /// Promises spawned during the run, in the order they were created. Jobs which haven't already
+  /// been run by an await are run in this order after the main function returns.
+  pub jobs: VecDeque<VsPromise>,
+
+  /// The bytecode frames (innermost first) that were unwound by the most recent uncaught exception.
+  pub stack_trace: Vec<StackTraceEntry>,
+
+  phase: Phase,
+
+  /// The result of the main function, once it has returned.
+  main_result: Option<CallResult>,
+}
+
+/// The parts of a run (see `VirtualMachine::advance`).
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Phase {
+  Main,
+  Jobs,
+  Await,
 }
 
 impl Default for VirtualMachine {
@@ -20,6 +43,10 @@ impl Default for VirtualMachine {
     VirtualMachine {
       frame: Box::new(FirstStackFrame::new()),
       stack: Default::default(),
+      jobs: Default::default(),
+      stack_trace: Default::default(),
+      phase: Phase::Main,
+      main_result: None,
     }
   }
 }
@@ -32,12 +59,43 @@ impl VirtualMachine {
     fn_: Val,
     args: Vec<Val>,
   ) -> Result<Val, Val> {
+    self.start(take(this), fn_, args)?;
+
+    let mut step_count = 0;
+
+    let result = loop {
+      if let Some(step_limit) = step_limit {
+        if step_count >= step_limit {
+          break Err("step limit reached".to_internal_error());
+        }
+      }
+
+      step_count += 1;
+
+      if let Some(result) = self.advance() {
+        break result;
+      }
+    };
+
+    if let Some(main_result) = take(&mut self.main_result) {
+      *this = main_result.this;
+    }
+
+    result
+  }
+
+  /// Prepares to call `fn_`, which is then run by calling `advance` until it gives a result.
+  pub fn start(&mut self, this: Val, fn_: Val, args: Vec<Val>) -> Result<(), Val> {
     let mut frame = match fn_.load_function() {
       LoadFunctionResult::StackFrame(f) => f,
       _ => return Err("fn_ is not a function".to_internal_error()),
     };
 
-    frame.write_this(false, take(this))?;
+    self.stack_trace.clear();
+    self.phase = Phase::Main;
+    self.main_result = None;
+
+    frame.write_this(false, this)?;
 
     for a in args {
       frame.write_param(a);
@@ -45,38 +103,62 @@ impl VirtualMachine {
 
     self.push(frame);
 
-    let res = match step_limit {
-      Some(step_limit) => 'b: {
-        let mut step_count = 0;
+    Ok(())
+  }
 
-        while step_count < step_limit {
-          self.step()?;
-          step_count += 1;
+  /// Executes one step of the run, returning its result if it finished.
+  ///
+  /// A run calls the main function, then runs any pending jobs, then awaits the main function's
+  /// result (since it may be a promise, which is settled by now). Rejections of jobs are stored in
+  /// their promises, so they only matter to code which awaits them.
+  pub fn advance(&mut self) -> Option<Result<Val, Val>> {
+    if let Err(err) = self.step() {
+      match self.phase {
+        Phase::Jobs => self.stack_trace.clear(),
+        Phase::Main | Phase::Await => return Some(Err(err)),
+      }
+    } else if self.stack.is_empty() {
+      let call_result = self.frame.get_call_result();
 
-          if self.stack.is_empty() {
-            break 'b self.frame.get_call_result();
-          }
+      match self.phase {
+        Phase::Main => {
+          self.main_result = Some(call_result);
+          self.phase = Phase::Jobs;
         }
-
-        return Err("step limit reached".to_internal_error());
+        Phase::Jobs => {}
+        Phase::Await => return Some(Ok(call_result.return_)),
       }
-      None => {
-        while !self.stack.is_empty() {
-          self.step()?;
+    }
+
+    while self.stack.is_empty() {
+      if let Some(promise) = self.jobs.pop_front() {
+        if !promise.is_pending() {
+          continue;
+        }
+
+        if let Ok(AwaitResult::Push(frame)) = promise.await_() {
+          self.push(frame);
         }
 
-        self.frame.get_call_result()
+        continue;
       }
-    };
 
-    let CallResult {
-      return_,
-      this: updated_this,
-    } = res;
+      let return_ = match &mut self.main_result {
+        Some(main_result) => take(&mut main_result.return_),
+        None => return Some(Err("Advanced without a run".to_internal_error())),
+      };
 
-    *this = updated_this;
+      match await_val(return_) {
+        Ok(AwaitResult::Value(value)) => return Some(Ok(value)),
+        Ok(AwaitResult::Push(frame)) => {
+          self.phase = Phase::Await;
+          self.push(frame);
+        }
+        Err(err) => return Some(Err(err)),
+      }
+    }
 
-    Ok(return_)
+    None
   }
 
   pub fn step(&mut self) -> Result<(), Val> {
@@ -101,6 +183,9 @@ impl VirtualMachine {
       FrameStepOk::YieldStar(_) => {
         return self.handle_exception("Unexpected yield*".to_internal_error())
       }
+      FrameStepOk::Spawn(promise) => {
+        self.jobs.push_back(promise);
+      }
     }
 
     Ok(())
@@ -118,19 +203,21 @@ impl VirtualMachine {
   }
 
   pub fn handle_exception(&mut self, mut exception: Val) -> Result<(), Val> {
+    let mut stack_trace = Vec::new();
+
     while !self.stack.is_empty() {
       if self.frame.can_catch_exception(&exception) {
         self.frame.catch_exception(&mut exception);
         return Ok(());
       }
 
-      if self.stack.is_empty() {
-        return Err(exception);
-      }
+      stack_trace.extend(self.frame.trace_entry());
 
       self.pop();
     }
 
+    self.stack_trace = stack_trace;
+
     Err(exception)
   }
 
@@ -1,15 +1,68 @@
 use std::rc::Rc;
 
+use crate::array_elements::ArrayElements;
+use crate::builtins::range_error_builtin::ToRangeError;
 use crate::vs_value::{ToVal, Val};
 
 #[derive(Clone, Debug, Default)]
 pub struct VsArray {
-  pub elements: Vec<Val>, // TODO: VsArray(Vec<Val>)?
+  pub elements: ArrayElements,
+
+  /// The `raw` strings of a tagged template's strings array. Only set when the array is created (see
+  /// `VsArray::template_strings`).
+  pub raw: Option<Rc<VsArray>>,
 }
 
+/// Arrays are stored densely. When an array grows past its elements (via `new Array(n)`, assigning
+/// to `length`, or assigning to an index beyond the end), the gap is filled with holes. As in
+/// JavaScript, holes read as `undefined` (including when spreading or iterating), while methods
+/// like `map` and `indexOf` skip them. Since holes take up memory, lengths are limited to
+/// `MAX_LENGTH`.
+///
+/// Arrays only have elements. Their other keys (`length` and the methods) are computed, and
+/// `length` is the only one that can be assigned. (The exception is the `raw` strings of a tagged
+/// template's strings array, which can be read but not assigned.)
 impl VsArray {
+  pub const MAX_LENGTH: usize = 1 << 24;
+
   pub fn from(vals: Vec<Val>) -> VsArray {
-    VsArray { elements: vals }
+    VsArray {
+      elements: vals.into(),
+      raw: None,
+    }
+  }
+
+  /// The strings array passed to a template tag, which also has the `raw` strings.
+  pub fn template_strings(cooked: Vec<Val>, raw: Vec<Val>) -> VsArray {
+    VsArray {
+      elements: cooked.into(),
+      raw: Some(Rc::new(VsArray::from(raw))),
+    }
+  }
+
+  /// Converts `len` to an array length, or gives a `RangeError` if arrays can't have that length.
+  pub fn to_length(len: f64) -> Result<usize, Val> {
+    if len.is_nan() || len < 0.0 || len != len.floor() || len > u32::MAX as f64 {
+      return Err("Invalid array length".to_range_error());
+    }
+
+    if len > VsArray::MAX_LENGTH as f64 {
+      return Err(
+        format!(
+          "Array length {} exceeds the maximum of {}",
+          len,
+          VsArray::MAX_LENGTH
+        )
+        .to_range_error(),
+      );
+    }
+
+    Ok(len as usize)
+  }
+
+  /// Truncates the array, or extends it with holes.
+  pub fn set_length(&mut self, len: usize) {
+    self.elements.resize(len, Val::Void);
   }
 }
 
@@ -4,7 +4,7 @@ use crate::vs_value::ToVal;
 
 use super::vs_value::Val;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct VsClass {
   pub name: String,
   pub content_hash: Option<[u8; 32]>,
@@ -13,7 +13,23 @@ pub struct VsClass {
   pub static_: Val,
 }
 
-impl VsClass {}
+impl VsClass {
+  /// Makes this class inherit from `parent`: its prototype and static objects fall back to the
+  /// parent's, and the parent's constructor is used if this class doesn't have one.
+  pub fn extend(&mut self, parent: &VsClass) {
+    if let Val::Void = self.constructor {
+      self.constructor = parent.constructor.clone();
+    }
+
+    if let Val::Object(prototype) = &mut self.prototype {
+      Rc::make_mut(prototype).prototype = parent.prototype.clone();
+    }
+
+    if let Val::Object(static_) = &mut self.static_ {
+      Rc::make_mut(static_).prototype = parent.static_.clone();
+    }
+  }
+}
 
 impl ToVal for VsClass {
   fn to_val(self) -> Val {
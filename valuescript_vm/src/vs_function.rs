@@ -3,6 +3,8 @@ use std::rc::Rc;
 use crate::builtins::internal_error_builtin::ToInternalError;
 use crate::bytecode::{Bytecode, DecoderMaker};
 use crate::make_generator_frame::MakeGeneratorFrame;
+use crate::make_promise_frame::MakePromiseFrame;
+use crate::vs_array::VsArray;
 use crate::vs_value::ToVal;
 
 use super::bytecode_decoder::BytecodeDecoder;
@@ -15,8 +17,13 @@ pub struct VsFunction {
   pub bytecode: Rc<Bytecode>,
   pub meta_pos: Option<usize>,
   pub is_generator: bool,
+  pub is_async: bool,
   pub register_count: usize,
   pub parameter_count: usize,
+
+  /// Whether the last parameter collects any remaining arguments into an array.
+  pub has_rest_param: bool,
+
   pub start: usize,
   pub binds: Vec<Val>,
 }
@@ -33,8 +40,10 @@ impl VsFunction {
       bytecode: self.bytecode.clone(),
       meta_pos: self.meta_pos,
       is_generator: self.is_generator,
+      is_async: self.is_async,
       register_count: self.register_count,
       parameter_count: self.parameter_count,
+      has_rest_param: self.has_rest_param,
       start: self.start,
       binds: new_binds,
     }
@@ -64,15 +73,26 @@ impl VsFunction {
       registers.push(Val::Void);
     }
 
+    let mut param_end = self.parameter_count + 2;
+    let mut rest_param = None;
+
+    if self.has_rest_param {
+      param_end -= 1;
+      rest_param = Some(param_end);
+      registers[param_end] = VsArray::from(vec![]).to_val();
+    }
+
     BytecodeStackFrame {
       decoder: BytecodeDecoder {
         bytecode: self.bytecode.clone(),
         pos: self.start,
       },
+      meta_pos: self.meta_pos,
       registers,
       const_this: true,
       param_start: self.binds.len() + 2,
-      param_end: self.parameter_count + 2,
+      param_end,
+      rest_param,
       this_target: None,
       return_target: None,
       catch_setting: None,
@@ -82,9 +102,10 @@ impl VsFunction {
   pub fn make_frame(&self) -> StackFrame {
     let frame = self.make_bytecode_frame();
 
-    match self.is_generator {
-      false => Box::new(frame),
-      true => Box::new(MakeGeneratorFrame::new(frame)),
+    match (self.is_generator, self.is_async) {
+      (false, false) => Box::new(frame),
+      (true, is_async) => Box::new(MakeGeneratorFrame::new(frame, is_async)),
+      (false, true) => Box::new(MakePromiseFrame::new(Box::new(frame))),
     }
   }
 }
@@ -0,0 +1,223 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+use crate::array_elements::ArrayElements;
+use crate::hybrid_map::HybridMap;
+use crate::operations::op_triple_eq_impl;
+use crate::vs_value::{ToVal, Val, ValTrait};
+
+/// The data of a `Map`. Entries are kept in insertion order, and keys are compared structurally,
+/// like `===` except that `NaN` matches itself (SameValueZero).
+///
+/// Both `slots` and `index` become persistent trees when they're large, so modifying a copy of a
+/// large map doesn't copy all of its entries.
+#[derive(Clone, Debug, Default)]
+pub struct VsMap {
+  /// Deleting an entry leaves a `None` behind so that the positions in `index` (and those used by
+  /// iterators) stay valid. These are compacted away once they outnumber the entries.
+  slots: ArrayElements<Option<(Val, Val)>>,
+
+  /// The positions in `slots` of the entries with each key hash.
+  index: HybridMap<u64, Vec<usize>>,
+
+  len: usize,
+}
+
+impl VsMap {
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  pub fn get(&self, key: &Val) -> Option<&Val> {
+    let key = normalize_key(key.clone());
+
+    self
+      .find(&key, key_hash(&key))
+      .and_then(|pos| self.slots[pos].as_ref())
+      .map(|(_, value)| value)
+  }
+
+  pub fn has(&self, key: &Val) -> bool {
+    let key = normalize_key(key.clone());
+    self.find(&key, key_hash(&key)).is_some()
+  }
+
+  /// Sets the value for `key`. Existing keys keep their position in the iteration order.
+  pub fn insert(&mut self, key: Val, value: Val) {
+    let key = normalize_key(key);
+    let hash = key_hash(&key);
+
+    if let Some(pos) = self.find(&key, hash) {
+      self.slots[pos] = Some((key, value));
+      return;
+    }
+
+    match self.index.get_mut(&hash) {
+      Some(positions) => positions.push(self.slots.len()),
+      None => {
+        self.index.insert(hash, vec![self.slots.len()]);
+      }
+    }
+
+    self.slots.push(Some((key, value)));
+    self.len += 1;
+  }
+
+  /// Removes `key`, returning whether it was present.
+  pub fn remove(&mut self, key: &Val) -> bool {
+    let key = normalize_key(key.clone());
+    let hash = key_hash(&key);
+
+    let pos = match self.find(&key, hash) {
+      Some(pos) => pos,
+      None => return false,
+    };
+
+    self.slots[pos] = None;
+    self.len -= 1;
+
+    if let Some(positions) = self.index.get_mut(&hash) {
+      positions.retain(|p| *p != pos);
+
+      if positions.is_empty() {
+        self.index.remove(&hash);
+      }
+    }
+
+    if self.slots.len() > 2 * self.len + 8 {
+      self.compact();
+    }
+
+    true
+  }
+
+  pub fn clear(&mut self) {
+    *self = VsMap::default();
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = (&Val, &Val)> {
+    self.slots.iter().flatten().map(|(key, value)| (key, value))
+  }
+
+  /// Iterators walk the slots of their own copy of the map, so they track the position in
+  /// `slots` rather than the number of entries visited.
+  pub fn slot_count(&self) -> usize {
+    self.slots.len()
+  }
+
+  pub fn slot(&self, pos: usize) -> Option<(&Val, &Val)> {
+    self.slots[pos].as_ref().map(|(key, value)| (key, value))
+  }
+
+  fn find(&self, key: &Val, hash: u64) -> Option<usize> {
+    self.index.get(&hash)?.iter().copied().find(|pos| {
+      self.slots[*pos]
+        .as_ref()
+        .is_some_and(|(slot_key, _)| same_value_zero(slot_key, key))
+    })
+  }
+
+  fn compact(&mut self) {
+    let slots = self.slots.take_vec();
+    self.clear();
+
+    for (key, value) in slots.into_iter().flatten() {
+      self.insert(key, value);
+    }
+  }
+}
+
+impl ToVal for VsMap {
+  fn to_val(self) -> Val {
+    Val::Map(Rc::new(self))
+  }
+}
+
+/// Keys are stored by value, and `-0` is stored as `0` like in JS.
+fn normalize_key(key: Val) -> Val {
+  match key {
+    Val::StoragePtr(ptr) => normalize_key(ptr.get()),
+    // This pattern also matches -0
+    Val::Number(0.0) => Val::Number(0.0),
+    _ => key,
+  }
+}
+
+pub fn same_value_zero(left: &Val, right: &Val) -> bool {
+  match (left, right) {
+    (Val::Number(left), Val::Number(right)) if left.is_nan() && right.is_nan() => true,
+    _ => op_triple_eq_impl(left, right).unwrap_or(false),
+  }
+}
+
+/// A hash which is consistent with `same_value_zero`, i.e. keys which match always have the same
+/// hash. Values which are only compared by identity just hash their type.
+pub fn key_hash(key: &Val) -> u64 {
+  let mut hasher = DefaultHasher::new();
+  hash_val(key, &mut hasher);
+  hasher.finish()
+}
+
+fn hash_val<H: Hasher>(val: &Val, state: &mut H) {
+  match val {
+    Val::Void | Val::Undefined => 0u8.hash(state),
+    Val::Null => 1u8.hash(state),
+    Val::Bool(b) => (2u8, b).hash(state),
+    Val::Number(n) => {
+      3u8.hash(state);
+
+      match *n {
+        0.0 => 0.0f64.to_bits().hash(state),
+        n if n.is_nan() => f64::NAN.to_bits().hash(state),
+        n => n.to_bits().hash(state),
+      }
+    }
+    Val::BigInt(b) => (4u8, b).hash(state),
+    Val::Symbol(s) => (5u8, s.clone() as u8).hash(state),
+    Val::String(s) => (6u8, s).hash(state),
+    Val::Array(array) => {
+      (7u8, array.elements.len()).hash(state);
+
+      for element in &array.elements {
+        hash_val(element, state);
+      }
+    }
+    Val::Object(object) => {
+      (8u8, object.string_map.len()).hash(state);
+
+      for (key, value) in &object.string_map {
+        key.hash(state);
+        hash_val(value, state);
+      }
+
+      for (key, value) in &object.symbol_map {
+        (key.clone() as u8).hash(state);
+        hash_val(value, state);
+      }
+    }
+    Val::Map(map) => {
+      (9u8, map.len()).hash(state);
+
+      for (key, value) in map.iter() {
+        hash_val(key, state);
+        hash_val(value, state);
+      }
+    }
+    Val::Set(set) => {
+      (10u8, set.len()).hash(state);
+
+      for value in set.iter() {
+        hash_val(value, state);
+      }
+    }
+    Val::StoragePtr(ptr) => hash_val(&ptr.get(), state),
+    Val::Function(_) | Val::Class(_) | Val::Static(_) | Val::Dynamic(_) | Val::CopyCounter(_) => {
+      (11u8, val.typeof_() as u8).hash(state)
+    }
+  }
+}
@@ -1,7 +1,10 @@
 use std::collections::BTreeMap;
+use std::fmt;
 use std::rc::Rc;
 
-use crate::vs_symbol::VsSymbol;
+use crate::hybrid_map::HybridMap;
+use crate::vs_private_name::VsPrivateName;
+use crate::vs_symbol::{symbol_to_name, VsSymbol};
 use crate::vs_value::ToVal;
 use crate::ValTrait;
 
@@ -9,8 +12,11 @@ use super::vs_value::Val;
 
 #[derive(Clone, Default, Debug)]
 pub struct VsObject {
-  pub string_map: BTreeMap<String, Val>,
+  pub string_map: HybridMap<String, Val>,
   pub symbol_map: BTreeMap<VsSymbol, Val>,
+  pub getter_map: BTreeMap<PropertyKey, Val>,
+  pub setter_map: BTreeMap<PropertyKey, Val>,
+  pub private_map: BTreeMap<VsPrivateName, Val>,
   pub prototype: Val,
 }
 
@@ -26,11 +32,118 @@ impl VsObject {
       return val.clone();
     }
 
+    // Getters need their own stack frame, so they're run by the caller (see `accessor`)
+    if self.has_own_accessor(key) {
+      return Val::Undefined;
+    }
+
     match &self.prototype {
       Val::Void => Val::Undefined,
       prototype => prototype.sub(key).map_err(|e| e.to_string()).unwrap(), // TODO: Exception
     }
   }
+
+  /// Looks up a private member. Private methods and the declarations of private fields live on the
+  /// prototype, so the lookup continues there. `None` means the object doesn't have the private
+  /// name's brand.
+  pub fn private_sub(&self, name: &VsPrivateName) -> Option<Val> {
+    if let Some(val) = self.private_map.get(name) {
+      return Some(val.clone());
+    }
+
+    match &self.prototype {
+      Val::Object(prototype) => prototype.private_sub(name),
+      _ => None,
+    }
+  }
+
+  /// Whether `key` is an own property, including accessor properties.
+  pub fn has_own(&self, key: &Val) -> bool {
+    let has_own_value = match key {
+      Val::String(string) => self.string_map.contains_key(&**string),
+      Val::Symbol(symbol) => self.symbol_map.contains_key(symbol),
+      _ => self.string_map.contains_key(&key.to_string()),
+    };
+
+    has_own_value || self.has_own_accessor(key)
+  }
+
+  /// If `key` is an accessor property of this object (or its prototype), returns its getter and
+  /// setter. Either can be `Val::Void` if it isn't defined.
+  pub fn accessor(&self, key: &Val) -> Option<(Val, Val)> {
+    if VsPrivateName::from_val(key).is_some() {
+      return None;
+    }
+
+    let has_own_value = match &key {
+      Val::String(string) => self.string_map.contains_key(&**string),
+      Val::Symbol(symbol) => self.symbol_map.contains_key(symbol),
+      _ => self.string_map.contains_key(&key.to_string()),
+    };
+
+    if has_own_value {
+      return None;
+    }
+
+    if self.has_own_accessor(key) {
+      let key = PropertyKey::from_val(key);
+
+      return Some((
+        self.getter_map.get(&key).cloned().unwrap_or_default(),
+        self.setter_map.get(&key).cloned().unwrap_or_default(),
+      ));
+    }
+
+    match &self.prototype {
+      Val::Object(prototype) => prototype.accessor(key),
+      _ => None,
+    }
+  }
+
+  fn has_own_accessor(&self, key: &Val) -> bool {
+    if self.getter_map.is_empty() && self.setter_map.is_empty() {
+      return false;
+    }
+
+    let key = PropertyKey::from_val(key);
+
+    self.getter_map.contains_key(&key) || self.setter_map.contains_key(&key)
+  }
+}
+
+/// The key of an accessor property. Unlike other properties, which are split between `string_map`
+/// and `symbol_map`, accessors keep both kinds of key in the same map.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PropertyKey {
+  String(String),
+  Symbol(VsSymbol),
+}
+
+impl PropertyKey {
+  pub fn from_val(key: &Val) -> PropertyKey {
+    match key {
+      Val::Symbol(symbol) => PropertyKey::Symbol(symbol.clone()),
+      _ => PropertyKey::String(key.to_string()),
+    }
+  }
+}
+
+impl ToVal for PropertyKey {
+  fn to_val(self) -> Val {
+    match self {
+      PropertyKey::String(string) => string.to_val(),
+      PropertyKey::Symbol(symbol) => symbol.to_val(),
+    }
+  }
+}
+
+impl fmt::Display for PropertyKey {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      PropertyKey::String(string) => write!(f, "{}", string),
+      PropertyKey::Symbol(symbol) => write!(f, "[Symbol.{}]", symbol_to_name(symbol.clone())),
+    }
+  }
 }
 
 impl ToVal for VsObject {
@@ -0,0 +1,102 @@
+use std::{fmt, rc::Rc};
+
+use num_bigint::BigInt;
+
+use crate::{
+  builtins::type_error_builtin::ToTypeError,
+  vs_array::VsArray,
+  vs_class::VsClass,
+  vs_value::{Val, VsType},
+  LoadFunctionResult, ValTrait,
+};
+
+/// The key of a `#private` class member.
+///
+/// The brand identifies the class that declared the name. It comes from the class's source, so
+/// classes with the same content hash also share their private names.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct VsPrivateName {
+  pub brand: [u8; 32],
+  pub name: String,
+}
+
+impl VsPrivateName {
+  pub fn from_val(val: &Val) -> Option<&VsPrivateName> {
+    match val {
+      Val::Dynamic(dynamic) => dynamic.as_any().downcast_ref::<VsPrivateName>(),
+      _ => None,
+    }
+  }
+}
+
+impl ValTrait for VsPrivateName {
+  fn typeof_(&self) -> VsType {
+    VsType::Symbol
+  }
+
+  fn to_number(&self) -> f64 {
+    f64::NAN
+  }
+
+  fn to_index(&self) -> Option<usize> {
+    None
+  }
+
+  fn is_primitive(&self) -> bool {
+    true
+  }
+
+  fn is_truthy(&self) -> bool {
+    true
+  }
+
+  fn is_nullish(&self) -> bool {
+    false
+  }
+
+  fn bind(&self, _params: Vec<Val>) -> Option<Val> {
+    None
+  }
+
+  fn as_bigint_data(&self) -> Option<BigInt> {
+    None
+  }
+
+  fn as_array_data(&self) -> Option<Rc<VsArray>> {
+    None
+  }
+
+  fn as_class_data(&self) -> Option<Rc<VsClass>> {
+    None
+  }
+
+  fn load_function(&self) -> LoadFunctionResult {
+    LoadFunctionResult::NotAFunction
+  }
+
+  fn sub(&self, _key: &Val) -> Result<Val, Val> {
+    Ok(Val::Undefined)
+  }
+
+  fn has(&self, _key: &Val) -> Option<bool> {
+    None
+  }
+
+  fn submov(&mut self, _key: &Val, _value: Val) -> Result<(), Val> {
+    Err("Cannot assign to subscript of private name".to_type_error())
+  }
+
+  fn pretty_fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "\x1b[32m{}\x1b[39m", self)
+  }
+
+  fn codify(&self) -> String {
+    self.to_string()
+  }
+}
+
+impl fmt::Display for VsPrivateName {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "#{}", self.name)
+  }
+}
@@ -0,0 +1,546 @@
+use std::{any::Any, cell::RefCell, fmt, mem::take, rc::Rc};
+
+use num_bigint::BigInt;
+
+use crate::{
+  builtins::{internal_error_builtin::ToInternalError, type_error_builtin::ToTypeError},
+  make_promise_frame::MakePromiseFrame,
+  native_frame_function::NativeFrameFunction,
+  native_function::ThisWrapper,
+  stack_frame::{CallResult, FrameStepOk, FrameStepResult, StackFrame, StackFrameTrait},
+  vs_array::VsArray,
+  vs_class::VsClass,
+  vs_value::{ToVal, Val, VsType},
+  LoadFunctionResult, ValTrait,
+};
+
+/// A promise is a handle to the eventual result of a job (usually the body of an `async`
+/// function).
+///
+/// Jobs don't run when they are created. Instead, they run when the promise is first awaited (or
+/// `.wait()`ed), or when the `VirtualMachine` drains its job queue after the main function returns.
+/// Because ValueScript functions are pure, running jobs at these points produces the same results
+/// as any other order would, and it keeps evaluation deterministic.
+///
+/// Copies of a promise share the same state, so each job runs at most once.
+#[derive(Clone)]
+pub struct VsPromise {
+  state: Rc<RefCell<PromiseState>>,
+}
+
+pub enum PromiseState {
+  Pending(StackFrame),
+  Running,
+  Fulfilled(Val),
+  Rejected(Val),
+}
+
+pub enum AwaitResult {
+  Value(Val),
+  Push(StackFrame),
+}
+
+impl VsPromise {
+  pub fn new(job: StackFrame) -> VsPromise {
+    VsPromise {
+      state: Rc::new(RefCell::new(PromiseState::Pending(job))),
+    }
+  }
+
+  pub fn fulfilled(value: Val) -> VsPromise {
+    VsPromise {
+      state: Rc::new(RefCell::new(PromiseState::Fulfilled(value))),
+    }
+  }
+
+  pub fn rejected(reason: Val) -> VsPromise {
+    VsPromise {
+      state: Rc::new(RefCell::new(PromiseState::Rejected(reason))),
+    }
+  }
+
+  pub fn is_settled(&self) -> bool {
+    matches!(
+      &*self.state.borrow(),
+      PromiseState::Fulfilled(_) | PromiseState::Rejected(_)
+    )
+  }
+
+  pub fn is_pending(&self) -> bool {
+    matches!(&*self.state.borrow(), PromiseState::Pending(_))
+  }
+
+  fn settle(&self, result: Result<Val, Val>) {
+    *self.state.borrow_mut() = match result {
+      Ok(value) => PromiseState::Fulfilled(value),
+      Err(reason) => PromiseState::Rejected(reason),
+    };
+  }
+
+  /// Get the result of the promise if it is settled. Otherwise, start its job and return a frame
+  /// which settles the promise and returns its value (or throws its rejection).
+  pub fn await_(&self) -> Result<AwaitResult, Val> {
+    let mut state = self.state.borrow_mut();
+
+    match &*state {
+      PromiseState::Fulfilled(value) => return Ok(AwaitResult::Value(value.clone())),
+      PromiseState::Rejected(reason) => return Err(reason.clone()),
+      PromiseState::Running => {
+        return Err("Promise awaited itself (circular await)".to_type_error());
+      }
+      PromiseState::Pending(_) => {}
+    };
+
+    let job = match std::mem::replace(&mut *state, PromiseState::Running) {
+      PromiseState::Pending(job) => job,
+      _ => unreachable!(),
+    };
+
+    Ok(AwaitResult::Push(Box::new(PromiseJobFrame {
+      promise: self.clone(),
+      frame: job,
+      adopting: false,
+      adopted: None,
+      settled: false,
+      rethrow: None,
+    })))
+  }
+}
+
+/// Get the promise from a `Promise` value, if it is one.
+pub fn as_promise(val: &Val) -> Option<&VsPromise> {
+  match val {
+    Val::Dynamic(dynamic) => dynamic.as_any().downcast_ref::<VsPromise>(),
+    _ => None,
+  }
+}
+
+/// Implements `await`. Values which aren't promises are passed through unchanged.
+pub fn await_val(val: Val) -> Result<AwaitResult, Val> {
+  match as_promise(&val) {
+    Some(promise) => promise.await_(),
+    None => Ok(AwaitResult::Value(val)),
+  }
+}
+
+impl ValTrait for VsPromise {
+  fn typeof_(&self) -> VsType {
+    VsType::Object
+  }
+
+  fn to_number(&self) -> f64 {
+    f64::NAN
+  }
+
+  fn to_index(&self) -> Option<usize> {
+    None
+  }
+
+  fn is_primitive(&self) -> bool {
+    false
+  }
+
+  fn is_truthy(&self) -> bool {
+    true
+  }
+
+  fn is_nullish(&self) -> bool {
+    false
+  }
+
+  fn bind(&self, _params: Vec<Val>) -> Option<Val> {
+    None
+  }
+
+  fn as_bigint_data(&self) -> Option<BigInt> {
+    None
+  }
+
+  fn as_array_data(&self) -> Option<Rc<VsArray>> {
+    None
+  }
+
+  fn as_class_data(&self) -> Option<Rc<VsClass>> {
+    None
+  }
+
+  fn load_function(&self) -> LoadFunctionResult {
+    LoadFunctionResult::NotAFunction
+  }
+
+  fn sub(&self, key: &Val) -> Result<Val, Val> {
+    Ok(match key.to_string().as_str() {
+      "then" => THEN.to_val(),
+      "catch" => CATCH.to_val(),
+      "wait" => WAIT.to_val(),
+      _ => Val::Undefined,
+    })
+  }
+
+  fn has(&self, key: &Val) -> Option<bool> {
+    Some(matches!(
+      key.to_string().as_str(),
+      "then" | "catch" | "wait"
+    ))
+  }
+
+  fn submov(&mut self, _key: &Val, _value: Val) -> Result<(), Val> {
+    Err("Cannot assign to subscript of Promise".to_type_error())
+  }
+
+  fn pretty_fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match &*self.state.borrow() {
+      PromiseState::Pending(_) | PromiseState::Running => {
+        write!(f, "Promise {{ \x1b[36m<pending>\x1b[39m }}")
+      }
+      PromiseState::Fulfilled(value) => write!(f, "Promise {{ {} }}", value.pretty()),
+      PromiseState::Rejected(reason) => {
+        write!(
+          f,
+          "Promise {{ \x1b[31m<rejected>\x1b[39m {} }}",
+          reason.pretty()
+        )
+      }
+    }
+  }
+
+  fn codify(&self) -> String {
+    match &*self.state.borrow() {
+      PromiseState::Pending(_) | PromiseState::Running => "Promise { <pending> }".to_string(),
+      PromiseState::Fulfilled(value) => format!("Promise {{ {} }}", value.codify()),
+      PromiseState::Rejected(reason) => format!("Promise {{ <rejected> {} }}", reason.codify()),
+    }
+  }
+}
+
+impl fmt::Display for VsPromise {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "[object Promise]")
+  }
+}
+
+/// Runs a promise's job and settles the promise with the outcome.
+///
+/// Frames pushed by the job are pushed onto the enclosing stack, so call results and exceptions
+/// pass through this frame on their way to the job. If the job returns another promise, that
+/// promise is awaited and its outcome is adopted.
+#[derive(Clone)]
+struct PromiseJobFrame {
+  promise: VsPromise,
+  frame: StackFrame,
+  adopting: bool,
+  adopted: Option<Val>,
+  settled: bool,
+  rethrow: Option<Val>,
+}
+
+impl PromiseJobFrame {
+  fn fulfill(&mut self, value: Val) -> FrameStepResult {
+    self.promise.settle(Ok(value.clone()));
+    self.settled = true;
+
+    Ok(FrameStepOk::Pop(CallResult {
+      return_: value,
+      this: Val::Undefined,
+    }))
+  }
+
+  fn reject(&mut self, reason: Val) -> FrameStepResult {
+    self.promise.settle(Err(reason.clone()));
+    self.settled = true;
+
+    Err(reason)
+  }
+}
+
+impl StackFrameTrait for PromiseJobFrame {
+  fn write_this(&mut self, _const: bool, _this: Val) -> Result<(), Val> {
+    panic!("Not appropriate for PromiseJobFrame")
+  }
+
+  fn write_param(&mut self, _param: Val) {
+    panic!("Not appropriate for PromiseJobFrame")
+  }
+
+  fn step(&mut self) -> FrameStepResult {
+    if let Some(reason) = take(&mut self.rethrow) {
+      return self.reject(reason);
+    }
+
+    if let Some(value) = take(&mut self.adopted) {
+      return self.fulfill(value);
+    }
+
+    match self.frame.step()? {
+      FrameStepOk::Continue => Ok(FrameStepOk::Continue),
+      FrameStepOk::Push(frame) => Ok(FrameStepOk::Push(frame)),
+      FrameStepOk::Spawn(promise) => Ok(FrameStepOk::Spawn(promise)),
+      FrameStepOk::Pop(call_result) => match await_val(call_result.return_) {
+        Ok(AwaitResult::Value(value)) => self.fulfill(value),
+        Ok(AwaitResult::Push(frame)) => {
+          self.adopting = true;
+          Ok(FrameStepOk::Push(frame))
+        }
+        Err(reason) => self.reject(reason),
+      },
+      FrameStepOk::Yield(_) | FrameStepOk::YieldStar(_) => {
+        self.reject("Unexpected yield in promise job".to_internal_error())
+      }
+    }
+  }
+
+  fn apply_call_result(&mut self, call_result: CallResult) {
+    match self.adopting {
+      false => self.frame.apply_call_result(call_result),
+      true => self.adopted = Some(call_result.return_),
+    }
+  }
+
+  fn get_call_result(&mut self) -> CallResult {
+    panic!("Not appropriate for PromiseJobFrame")
+  }
+
+  fn can_catch_exception(&self, _exception: &Val) -> bool {
+    // Exceptions which the job doesn't catch still need to pass through here so that the promise
+    // is rejected
+    !self.settled
+  }
+
+  fn catch_exception(&mut self, exception: &mut Val) {
+    if !self.adopting && self.frame.can_catch_exception(exception) {
+      self.frame.catch_exception(exception);
+    } else {
+      self.rethrow = Some(take(exception));
+    }
+  }
+
+  fn clone_to_stack_frame(&self) -> StackFrame {
+    Box::new(self.clone())
+  }
+
+  fn as_any(&self) -> &dyn Any {
+    self
+  }
+
+  fn as_any_mut(&mut self) -> &mut dyn Any {
+    self
+  }
+}
+
+static WAIT: NativeFrameFunction = NativeFrameFunction {
+  make_frame: || Box::<WaitFrame>::default(),
+};
+
+/// Implements `promise.wait()`, which is `await` for use outside of `async` functions.
+#[derive(Clone, Default)]
+struct WaitFrame {
+  this: Val,
+  result: Option<Val>,
+}
+
+impl StackFrameTrait for WaitFrame {
+  fn write_this(&mut self, _const: bool, this: Val) -> Result<(), Val> {
+    self.this = this;
+    Ok(())
+  }
+
+  fn write_param(&mut self, _param: Val) {}
+
+  fn step(&mut self) -> FrameStepResult {
+    let value = match take(&mut self.result) {
+      Some(value) => value,
+      None => {
+        if as_promise(&self.this).is_none() {
+          return Err("Promise.prototype.wait called on incompatible receiver".to_type_error());
+        }
+
+        match await_val(self.this.clone())? {
+          AwaitResult::Value(value) => value,
+          AwaitResult::Push(frame) => return Ok(FrameStepOk::Push(frame)),
+        }
+      }
+    };
+
+    Ok(FrameStepOk::Pop(CallResult {
+      return_: value,
+      this: take(&mut self.this),
+    }))
+  }
+
+  fn apply_call_result(&mut self, call_result: CallResult) {
+    self.result = Some(call_result.return_);
+  }
+
+  fn get_call_result(&mut self) -> CallResult {
+    panic!("Not appropriate for WaitFrame")
+  }
+
+  fn can_catch_exception(&self, _exception: &Val) -> bool {
+    false
+  }
+
+  fn catch_exception(&mut self, _exception: &mut Val) {}
+
+  fn clone_to_stack_frame(&self) -> StackFrame {
+    Box::new(self.clone())
+  }
+
+  fn as_any(&self) -> &dyn Any {
+    self
+  }
+
+  fn as_any_mut(&mut self) -> &mut dyn Any {
+    self
+  }
+}
+
+static THEN: NativeFrameFunction = NativeFrameFunction {
+  make_frame: || Box::new(MakePromiseFrame::new(Box::new(ThenFrame::new(false)))),
+};
+
+static CATCH: NativeFrameFunction = NativeFrameFunction {
+  make_frame: || Box::new(MakePromiseFrame::new(Box::new(ThenFrame::new(true)))),
+};
+
+/// The job for `then` and `catch`: await the source promise, then pass its outcome to the relevant
+/// handler.
+#[derive(Clone)]
+struct ThenFrame {
+  is_catch: bool,
+  source: Val,
+  on_fulfilled: Val,
+  on_rejected: Val,
+  param_i: usize,
+  stage: ThenStage,
+}
+
+#[derive(Clone)]
+enum ThenStage {
+  Start,
+  AwaitingSource,
+  SourceSettled(Result<Val, Val>),
+  AwaitingHandler,
+  Done(Val),
+}
+
+impl ThenFrame {
+  fn new(is_catch: bool) -> ThenFrame {
+    ThenFrame {
+      is_catch,
+      source: Val::Undefined,
+      on_fulfilled: Val::Undefined,
+      on_rejected: Val::Undefined,
+      param_i: 0,
+      stage: ThenStage::Start,
+    }
+  }
+
+  fn call_handler(&mut self, outcome: Result<Val, Val>) -> FrameStepResult {
+    let (handler, arg, is_fulfilled) = match outcome {
+      Ok(value) => (&self.on_fulfilled, value, true),
+      Err(reason) => (&self.on_rejected, reason, false),
+    };
+
+    match handler.load_function() {
+      // Without a handler, the outcome passes through to the new promise
+      LoadFunctionResult::NotAFunction => match is_fulfilled {
+        true => Ok(pop(arg)),
+        false => Err(arg),
+      },
+      LoadFunctionResult::NativeFunction(native_fn) => Ok(pop(native_fn(
+        ThisWrapper::new(true, &mut Val::Undefined),
+        vec![arg],
+      )?)),
+      LoadFunctionResult::StackFrame(mut frame) => {
+        frame.write_this(true, Val::Undefined)?;
+        frame.write_param(arg);
+        self.stage = ThenStage::AwaitingHandler;
+
+        Ok(FrameStepOk::Push(frame))
+      }
+    }
+  }
+}
+
+impl StackFrameTrait for ThenFrame {
+  fn write_this(&mut self, _const: bool, this: Val) -> Result<(), Val> {
+    self.source = this;
+    Ok(())
+  }
+
+  fn write_param(&mut self, param: Val) {
+    match (self.is_catch, self.param_i) {
+      (false, 0) => self.on_fulfilled = param,
+      (false, 1) | (true, 0) => self.on_rejected = param,
+      _ => {}
+    };
+
+    self.param_i += 1;
+  }
+
+  fn step(&mut self) -> FrameStepResult {
+    match take_stage(&mut self.stage) {
+      ThenStage::Start => {
+        if as_promise(&self.source).is_none() {
+          return Err("Promise.prototype.then called on incompatible receiver".to_type_error());
+        }
+
+        match await_val(self.source.clone()) {
+          Ok(AwaitResult::Value(value)) => self.call_handler(Ok(value)),
+          Ok(AwaitResult::Push(frame)) => {
+            self.stage = ThenStage::AwaitingSource;
+            Ok(FrameStepOk::Push(frame))
+          }
+          Err(reason) => self.call_handler(Err(reason)),
+        }
+      }
+      ThenStage::SourceSettled(outcome) => self.call_handler(outcome),
+      ThenStage::Done(value) => Ok(pop(value)),
+      ThenStage::AwaitingSource | ThenStage::AwaitingHandler => {
+        Err("ThenFrame stepped while awaiting".to_internal_error())
+      }
+    }
+  }
+
+  fn apply_call_result(&mut self, call_result: CallResult) {
+    self.stage = match self.stage {
+      ThenStage::AwaitingSource => ThenStage::SourceSettled(Ok(call_result.return_)),
+      _ => ThenStage::Done(call_result.return_),
+    };
+  }
+
+  fn get_call_result(&mut self) -> CallResult {
+    panic!("Not appropriate for ThenFrame")
+  }
+
+  fn can_catch_exception(&self, _exception: &Val) -> bool {
+    matches!(self.stage, ThenStage::AwaitingSource)
+  }
+
+  fn catch_exception(&mut self, exception: &mut Val) {
+    self.stage = ThenStage::SourceSettled(Err(take(exception)));
+  }
+
+  fn clone_to_stack_frame(&self) -> StackFrame {
+    Box::new(self.clone())
+  }
+
+  fn as_any(&self) -> &dyn Any {
+    self
+  }
+
+  fn as_any_mut(&mut self) -> &mut dyn Any {
+    self
+  }
+}
+
+fn take_stage(stage: &mut ThenStage) -> ThenStage {
+  std::mem::replace(stage, ThenStage::Start)
+}
+
+fn pop(value: Val) -> FrameStepOk {
+  FrameStepOk::Pop(CallResult {
+    return_: value,
+    this: Val::Undefined,
+  })
+}
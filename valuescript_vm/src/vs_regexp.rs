@@ -0,0 +1,421 @@
+use std::{fmt, rc::Rc};
+
+use num_bigint::BigInt;
+
+use crate::{
+  builtins::{internal_error_builtin::ToInternalError, type_error_builtin::ToTypeError},
+  native_function::{native_fn, NativeFunction},
+  vs_array::VsArray,
+  vs_class::VsClass,
+  vs_object::VsObject,
+  vs_value::{ToVal, Val, VsType},
+  LoadFunctionResult, ValTrait,
+};
+
+/// A compiled regular expression.
+///
+/// Unlike JavaScript, there is no `lastIndex` state. Every search starts from the beginning of the
+/// input (or from an explicit position for the string methods that need one), so a regex value
+/// behaves the same way no matter how many times it has been used.
+#[derive(Clone)]
+pub struct VsRegExp {
+  pub source: Rc<str>,
+  pub flags: Rc<str>,
+  regex: Rc<fancy_regex::Regex>,
+}
+
+/// A single successful match, with positions as byte offsets (like the rest of ValueScript's
+/// string indexing).
+#[derive(Clone)]
+pub struct RegExpMatch {
+  pub start: usize,
+  pub end: usize,
+
+  /// The full match followed by each capture group (`Val::Undefined` for groups that didn't
+  /// participate).
+  pub captures: Vec<Val>,
+
+  /// Named capture groups, if the regex has any.
+  pub groups: Option<Vec<(String, Val)>>,
+}
+
+impl VsRegExp {
+  pub fn new(source: &str, flags: &str) -> Result<VsRegExp, Val> {
+    let regex = build_regex(source, flags).map_err(|msg| msg.to_type_error())?;
+
+    Ok(VsRegExp {
+      source: source.into(),
+      flags: flags.into(),
+      regex: Rc::new(regex),
+    })
+  }
+
+  /// Checks whether a regex can be constructed, without constructing it. This allows the compiler
+  /// to report invalid regex literals.
+  pub fn validate(source: &str, flags: &str) -> Result<(), String> {
+    build_regex(source, flags).map(|_| ())
+  }
+
+  pub fn has_flag(&self, flag: char) -> bool {
+    self.flags.contains(flag)
+  }
+
+  pub fn is_global(&self) -> bool {
+    self.has_flag('g')
+  }
+
+  /// Find the first match starting at or after `pos` (exactly at `pos` for sticky regexes).
+  pub fn exec_at(&self, input: &str, pos: usize) -> Result<Option<RegExpMatch>, Val> {
+    if pos > input.len() {
+      return Ok(None);
+    }
+
+    let captures = match self.regex.captures_from_pos(input, pos) {
+      Ok(Some(captures)) => captures,
+      Ok(None) => return Ok(None),
+      Err(err) => return Err(format!("Regex execution failed: {}", err).to_internal_error()),
+    };
+
+    let full = match captures.get(0) {
+      Some(full) => full,
+      None => return Err("Regex match without group 0".to_internal_error()),
+    };
+
+    if self.has_flag('y') && full.start() != pos {
+      return Ok(None);
+    }
+
+    let mut capture_vals = Vec::<Val>::new();
+
+    for i in 0..captures.len() {
+      capture_vals.push(match captures.get(i) {
+        Some(m) => m.as_str().to_val(),
+        None => Val::Undefined,
+      });
+    }
+
+    let mut groups = Vec::<(String, Val)>::new();
+
+    for name in self.regex.capture_names().flatten() {
+      groups.push((
+        name.to_string(),
+        match captures.name(name) {
+          Some(m) => m.as_str().to_val(),
+          None => Val::Undefined,
+        },
+      ));
+    }
+
+    Ok(Some(RegExpMatch {
+      start: full.start(),
+      end: full.end(),
+      captures: capture_vals,
+      groups: match groups.is_empty() {
+        true => None,
+        false => Some(groups),
+      },
+    }))
+  }
+
+  /// Find successive non-overlapping matches. Only the first match is returned unless the regex is
+  /// global.
+  pub fn exec_all(&self, input: &str) -> Result<Vec<RegExpMatch>, Val> {
+    let mut matches = Vec::<RegExpMatch>::new();
+    let mut pos = 0;
+
+    while let Some(m) = self.exec_at(input, pos)? {
+      pos = match m.start == m.end {
+        true => advance_char(input, m.end),
+        false => m.end,
+      };
+
+      matches.push(m);
+
+      if !self.is_global() || pos > input.len() {
+        break;
+      }
+    }
+
+    Ok(matches)
+  }
+}
+
+impl RegExpMatch {
+  pub fn groups_val(&self) -> Val {
+    match &self.groups {
+      None => Val::Undefined,
+      Some(groups) => VsObject {
+        string_map: groups.iter().cloned().collect(),
+        symbol_map: Default::default(),
+        getter_map: Default::default(),
+        setter_map: Default::default(),
+        private_map: Default::default(),
+        prototype: Val::Void,
+      }
+      .to_val(),
+    }
+  }
+
+  /// The result of `exec` and non-global `match`. This is a plain array of the full match and the
+  /// capture groups - `index`, `input` and `groups` are not attached.
+  pub fn to_array_val(&self) -> Val {
+    self.captures.clone().to_val()
+  }
+}
+
+fn build_regex(source: &str, flags: &str) -> Result<fancy_regex::Regex, String> {
+  let mut inline_flags = String::new();
+
+  for (i, flag) in flags.char_indices() {
+    if !"dgimsuy".contains(flag) || flags[..i].contains(flag) {
+      return Err(format!("Invalid regular expression flags '{}'", flags));
+    }
+
+    if "ims".contains(flag) {
+      inline_flags.push(flag);
+    }
+  }
+
+  let mut pattern = String::new();
+
+  if !inline_flags.is_empty() {
+    pattern.push_str(&format!("(?{})", inline_flags));
+  }
+
+  pattern.push_str(&translate_pattern(source));
+
+  fancy_regex::Regex::new(&pattern)
+    .map_err(|err| format!("Invalid regular expression: /{}/: {}", source, err))
+}
+
+/// The byte position of the character after the one starting at `pos`.
+pub fn advance_char(input: &str, pos: usize) -> usize {
+  match input[pos..].chars().next() {
+    Some(c) => pos + c.len_utf8(),
+    None => pos + 1,
+  }
+}
+
+/// Converts JavaScript regex syntax into the syntax accepted by `fancy_regex`. The two are very
+/// close, so this only needs to handle a few differences.
+fn translate_pattern(source: &str) -> String {
+  let mut result = String::new();
+  let mut chars = source.chars().peekable();
+  let mut in_class = false;
+
+  while let Some(c) = chars.next() {
+    match c {
+      '\\' => match chars.next() {
+        // JS's \0 is a null character, not a backreference
+        Some('0') if !chars.peek().is_some_and(|c| c.is_ascii_digit()) => {
+          result.push_str("\\x00");
+        }
+        // JS's \d and \w are ascii-only
+        Some('d') => result.push_str(if in_class { "0-9" } else { "[0-9]" }),
+        Some('w') => result.push_str(if in_class {
+          "0-9A-Za-z_"
+        } else {
+          "[0-9A-Za-z_]"
+        }),
+        Some('D') if !in_class => result.push_str("[^0-9]"),
+        Some('W') if !in_class => result.push_str("[^0-9A-Za-z_]"),
+        Some(escaped) => {
+          result.push('\\');
+          result.push(escaped);
+        }
+        None => result.push('\\'),
+      },
+      '[' if !in_class => {
+        let negated = chars.peek() == Some(&'^');
+
+        if negated {
+          chars.next();
+        }
+
+        // JS allows empty classes: [] never matches and [^] matches any character
+        if chars.peek() == Some(&']') {
+          chars.next();
+          result.push_str(if negated { "[\\s\\S]" } else { "(?!)" });
+          continue;
+        }
+
+        in_class = true;
+        result.push_str(if negated { "[^" } else { "[" });
+      }
+      ']' if in_class => {
+        in_class = false;
+        result.push(']');
+      }
+      '[' if in_class => result.push_str("\\["),
+      _ => result.push(c),
+    }
+  }
+
+  result
+}
+
+impl ValTrait for VsRegExp {
+  fn typeof_(&self) -> VsType {
+    VsType::Object
+  }
+
+  fn to_number(&self) -> f64 {
+    f64::NAN
+  }
+
+  fn to_index(&self) -> Option<usize> {
+    None
+  }
+
+  fn is_primitive(&self) -> bool {
+    false
+  }
+
+  fn is_truthy(&self) -> bool {
+    true
+  }
+
+  fn is_nullish(&self) -> bool {
+    false
+  }
+
+  fn bind(&self, _params: Vec<Val>) -> Option<Val> {
+    None
+  }
+
+  fn as_bigint_data(&self) -> Option<BigInt> {
+    None
+  }
+
+  fn as_array_data(&self) -> Option<Rc<VsArray>> {
+    None
+  }
+
+  fn as_class_data(&self) -> Option<Rc<VsClass>> {
+    None
+  }
+
+  fn load_function(&self) -> LoadFunctionResult {
+    LoadFunctionResult::NotAFunction
+  }
+
+  fn sub(&self, key: &Val) -> Result<Val, Val> {
+    Ok(match key.to_string().as_str() {
+      "source" => Val::String(self.source.clone()),
+      "flags" => Val::String(self.flags.clone()),
+      "global" => self.has_flag('g').to_val(),
+      "ignoreCase" => self.has_flag('i').to_val(),
+      "multiline" => self.has_flag('m').to_val(),
+      "dotAll" => self.has_flag('s').to_val(),
+      "unicode" => self.has_flag('u').to_val(),
+      "sticky" => self.has_flag('y').to_val(),
+      "hasIndices" => self.has_flag('d').to_val(),
+      "lastIndex" => 0.0.to_val(),
+      "exec" => EXEC.to_val(),
+      "test" => TEST.to_val(),
+      "toString" => TO_STRING.to_val(),
+      _ => Val::Undefined,
+    })
+  }
+
+  fn has(&self, key: &Val) -> Option<bool> {
+    Some(matches!(
+      key.to_string().as_str(),
+      "source"
+        | "flags"
+        | "global"
+        | "ignoreCase"
+        | "multiline"
+        | "dotAll"
+        | "unicode"
+        | "sticky"
+        | "hasIndices"
+        | "lastIndex"
+        | "exec"
+        | "test"
+        | "toString"
+    ))
+  }
+
+  fn submov(&mut self, _key: &Val, _value: Val) -> Result<(), Val> {
+    Err("Cannot assign to subscript of RegExp".to_type_error())
+  }
+
+  fn pretty_fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "\x1b[31m{}\x1b[39m", self)
+  }
+
+  fn codify(&self) -> String {
+    self.to_string()
+  }
+}
+
+impl fmt::Display for VsRegExp {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "/{}/{}", self.source, self.flags)
+  }
+}
+
+/// Get the regex from a `RegExp` value, if it is one.
+pub fn as_regexp(val: &Val) -> Option<&VsRegExp> {
+  match val {
+    Val::Dynamic(dynamic) => dynamic.as_any().downcast_ref::<VsRegExp>(),
+    _ => None,
+  }
+}
+
+/// Interpret a value as a regex the way String.prototype.match and friends do: regexes are used
+/// as-is, anything else is converted to a string and compiled with the given flags.
+pub fn to_regexp(val: Option<&Val>, flags: &str) -> Result<VsRegExp, Val> {
+  match val {
+    None | Some(Val::Undefined) => VsRegExp::new("(?:)", flags),
+    Some(val) => match as_regexp(val) {
+      Some(regexp) => Ok(regexp.clone()),
+      None => VsRegExp::new(&val.to_string(), flags),
+    },
+  }
+}
+
+static EXEC: NativeFunction = native_fn(|this, params| {
+  let this = this.get();
+
+  let regexp = match as_regexp(&this) {
+    Some(regexp) => regexp,
+    None => return Err("RegExp.prototype.exec called on incompatible receiver".to_type_error()),
+  };
+
+  let input = match params.first() {
+    Some(input) => input.to_string(),
+    None => "undefined".to_string(),
+  };
+
+  Ok(match regexp.exec_at(&input, 0)? {
+    Some(m) => m.to_array_val(),
+    None => Val::Null,
+  })
+});
+
+static TEST: NativeFunction = native_fn(|this, params| {
+  let this = this.get();
+
+  let regexp = match as_regexp(&this) {
+    Some(regexp) => regexp,
+    None => return Err("RegExp.prototype.test called on incompatible receiver".to_type_error()),
+  };
+
+  let input = match params.first() {
+    Some(input) => input.to_string(),
+    None => "undefined".to_string(),
+  };
+
+  Ok(regexp.exec_at(&input, 0)?.is_some().to_val())
+});
+
+static TO_STRING: NativeFunction = native_fn(|this, _params| {
+  let this = this.get();
+
+  match as_regexp(&this) {
+    Some(regexp) => Ok(regexp.to_string().to_val()),
+    None => Err("RegExp.prototype.toString called on incompatible receiver".to_type_error()),
+  }
+});
@@ -0,0 +1,56 @@
+use std::rc::Rc;
+
+use crate::vs_map::VsMap;
+use crate::vs_value::{ToVal, Val};
+
+/// The data of a `Set`. This is a `VsMap` without values, so it has the same insertion ordering
+/// and SameValueZero comparisons.
+#[derive(Clone, Debug, Default)]
+pub struct VsSet {
+  map: VsMap,
+}
+
+impl VsSet {
+  pub fn len(&self) -> usize {
+    self.map.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.map.is_empty()
+  }
+
+  pub fn has(&self, value: &Val) -> bool {
+    self.map.has(value)
+  }
+
+  pub fn insert(&mut self, value: Val) {
+    self.map.insert(value, Val::Void);
+  }
+
+  /// Removes `value`, returning whether it was present.
+  pub fn remove(&mut self, value: &Val) -> bool {
+    self.map.remove(value)
+  }
+
+  pub fn clear(&mut self) {
+    self.map.clear();
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = &Val> {
+    self.map.iter().map(|(value, _)| value)
+  }
+
+  pub fn slot_count(&self) -> usize {
+    self.map.slot_count()
+  }
+
+  pub fn slot(&self, pos: usize) -> Option<&Val> {
+    self.map.slot(pos).map(|(value, _)| value)
+  }
+}
+
+impl ToVal for VsSet {
+  fn to_val(self) -> Val {
+    Val::Set(Rc::new(self))
+  }
+}
@@ -5,11 +5,21 @@ use crate::vs_value::{ToVal, Val};
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, FromPrimitive, ToPrimitive)]
 pub enum VsSymbol {
   ITERATOR,
+
+  /// Thrown into a generator by `generator.return(value)` so that its finally blocks run. Catch
+  /// blocks in generators rethrow it.
+  #[allow(non_camel_case_types)]
+  GENERATOR_RETURN,
+
+  #[allow(non_camel_case_types)]
+  ASYNC_ITERATOR,
 }
 
 pub fn symbol_to_name(symbol: VsSymbol) -> &'static str {
   match symbol {
     VsSymbol::ITERATOR => "iterator",
+    VsSymbol::GENERATOR_RETURN => "generatorReturn",
+    VsSymbol::ASYNC_ITERATOR => "asyncIterator",
   }
 }
 
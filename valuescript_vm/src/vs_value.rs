@@ -11,14 +11,19 @@ use num_traits::Zero;
 
 use crate::binary_op::BinaryOp;
 use crate::copy_counter::CopyCounter;
+use crate::hybrid_map::HybridMap;
 use crate::native_function::ThisWrapper;
+use crate::number_methods::format_number;
 use crate::operations::{op_sub, op_submov};
 use crate::stack_frame::StackFrame;
 use crate::unary_op::UnaryOp;
 use crate::vs_array::VsArray;
 use crate::vs_class::VsClass;
 use crate::vs_function::VsFunction;
+use crate::vs_map::VsMap;
 use crate::vs_object::VsObject;
+use crate::vs_private_name::VsPrivateName;
+use crate::vs_set::VsSet;
 use crate::vs_storage_ptr::VsStoragePtr;
 use crate::vs_symbol::{symbol_to_name, VsSymbol};
 
@@ -35,9 +40,11 @@ pub enum Val {
   String(Rc<str>),
   Array(Rc<VsArray>),
   Object(Rc<VsObject>),
+  Map(Rc<VsMap>),
+  Set(Rc<VsSet>),
   Function(Rc<VsFunction>),
   Class(Rc<VsClass>),
-  Static(&'static dyn ValTrait),
+  Static(&'static (dyn ValTrait + Sync)),
   Dynamic(Rc<dyn DynValTrait>),
   CopyCounter(Box<CopyCounter>),
   StoragePtr(Rc<VsStoragePtr>),
@@ -150,7 +157,7 @@ pub fn dynamic_make_mut(rc: &mut Rc<dyn DynValTrait>) -> &mut dyn DynValTrait {
   Rc::get_mut(rc).unwrap()
 }
 
-impl fmt::Debug for dyn ValTrait {
+impl fmt::Debug for dyn ValTrait + Sync {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     write!(f, "(dyn ValTrait)(")?;
     self.pretty_fmt(f)?;
@@ -205,7 +212,7 @@ impl Val {
         elements.to_val()
       }
       serde_json::Value::Object(obj) => {
-        let mut string_map = BTreeMap::new();
+        let mut string_map = HybridMap::new();
 
         for (k, v) in obj {
           string_map.insert(k.clone(), Val::from_json(v));
@@ -214,6 +221,9 @@ impl Val {
         VsObject {
           string_map,
           symbol_map: BTreeMap::new(),
+          getter_map: Default::default(),
+          setter_map: Default::default(),
+          private_map: Default::default(),
           prototype: Val::Void,
         }
         .to_val()
@@ -253,6 +263,8 @@ impl Val {
 
         Some(serde_json::Value::Object(string_map))
       }
+      Val::Map(_) => None,
+      Val::Set(_) => None,
       Val::Function(_) => None,
       Val::Class(_) => None,
       Val::Static(_) => None,
@@ -285,6 +297,8 @@ impl ValTrait for Val {
       String(_) => VsType::String,
       Array(_) => VsType::Array,
       Object(_) => VsType::Object,
+      Map(_) => VsType::Object,
+      Set(_) => VsType::Object,
       Function(_) => VsType::Function,
       Class(_) => VsType::Class,
       Static(val) => val.typeof_(),
@@ -312,6 +326,8 @@ impl ValTrait for Val {
         _ => f64::NAN,
       },
       Object(_) => f64::NAN,
+      Map(_) => f64::NAN,
+      Set(_) => f64::NAN,
       Function(_) => f64::NAN,
       Class(_) => f64::NAN,
       Static(val) => val.to_number(),
@@ -338,6 +354,8 @@ impl ValTrait for Val {
       },
       Array(_) => None,
       Object(_) => None,
+      Map(_) => None,
+      Set(_) => None,
       Function(_) => None,
       Class(_) => None,
       Static(val) => val.to_index(),
@@ -361,6 +379,8 @@ impl ValTrait for Val {
       String(_) => true,
       Array(_) => false,
       Object(_) => false,
+      Map(_) => false,
+      Set(_) => false,
       Function(_) => false,
       Class(_) => false,
       Static(val) => val.is_primitive(), // TODO: false?
@@ -384,6 +404,8 @@ impl ValTrait for Val {
       String(s) => s.len() > 0,
       Array(_) => true,
       Object(_) => true,
+      Map(_) => true,
+      Set(_) => true,
       Function(_) => true,
       Class(_) => true,
       Static(val) => val.is_truthy(), // TODO: true?
@@ -407,6 +429,8 @@ impl ValTrait for Val {
       String(_) => false,
       Array(_) => false,
       Object(_) => false,
+      Map(_) => false,
+      Set(_) => false,
       Function(_) => false,
       Class(_) => false,
       Static(_) => false,
@@ -537,30 +561,26 @@ impl ValTrait for Val {
           Some(i) => i,
         };
 
-        Some(index < array.elements.len())
+        Some(matches!(
+          array.elements.get(index),
+          Some(element) if !matches!(element, Val::Void)
+        ))
       }
-      Val::Object(object) => match key {
-        Val::Symbol(symbol) => {
-          if object.symbol_map.contains_key(symbol) {
-            Some(true)
-          } else {
-            match &object.prototype {
-              Val::Void => Some(false),
-              prototype => prototype.has(key),
-            }
-          }
+      Val::Object(object) => {
+        if let Some(name) = VsPrivateName::from_val(key) {
+          return Some(object.private_sub(name).is_some());
         }
-        _ => {
-          if object.string_map.contains_key(&key.to_string()) {
-            Some(true)
-          } else {
-            match &object.prototype {
-              Val::Void => Some(false),
-              prototype => prototype.has(key),
-            }
-          }
+
+        if object.has_own(key) {
+          return Some(true);
         }
-      },
+
+        match &object.prototype {
+          Val::Void => Some(false),
+          prototype => prototype.has(key),
+        }
+      }
+      Val::Map(_) | Val::Set(_) => Some(!matches!(self.sub(key), Ok(Val::Undefined))),
       Val::Function(_) => Some(false),
       Val::Class(class) => class.static_.has(key),
       Val::Static(static_) => static_.has(key),
@@ -586,6 +606,8 @@ impl ValTrait for Val {
       | Val::String(_)
       | Val::Array(_)
       | Val::Object(_)
+      | Val::Map(_)
+      | Val::Set(_)
       | Val::Function(_)
       | Val::Class(_)
       | Val::CopyCounter(_)
@@ -607,6 +629,8 @@ impl ValTrait for Val {
       | Val::String(_)
       | Val::Array(_)
       | Val::Object(_)
+      | Val::Map(_)
+      | Val::Set(_)
       | Val::Function(_)
       | Val::Class(_)
       | Val::CopyCounter(_)
@@ -684,6 +708,18 @@ impl ValTrait for Val {
 
         res
       }
+      Val::Map(map) => {
+        let entries = map
+          .iter()
+          .map(|(key, value)| format!("[{},{}]", key.codify(), value.codify()))
+          .collect::<Vec<_>>();
+
+        format!("new Map([{}])", entries.join(","))
+      }
+      Val::Set(set) => {
+        let values = set.iter().map(|value| value.codify()).collect::<Vec<_>>();
+        format!("new Set([{}])", values.join(","))
+      }
       Val::Function(_) => "() => { [unavailable] }".to_string(),
       Val::Class(_) => "class { [unavailable] }".to_string(),
       Val::Static(val) => val.codify(),
@@ -707,17 +743,7 @@ impl fmt::Display for Val {
       Undefined => write!(f, "undefined"),
       Null => write!(f, "null"),
       Bool(b) => b.fmt(f),
-      Number(x) => {
-        if x.is_infinite() {
-          if x.is_sign_positive() {
-            write!(f, "Infinity")
-          } else {
-            write!(f, "-Infinity")
-          }
-        } else {
-          x.fmt(f)
-        }
-      } // TODO: Match js's number string format
+      Number(x) => write!(f, "{}", format_number(*x)),
       BigInt(x) => x.fmt(f),
       Symbol(s) => write!(f, "Symbol(Symbol.{})", symbol_to_name(s.clone())),
       String(s) => s.fmt(f),
@@ -745,6 +771,8 @@ impl fmt::Display for Val {
         }
       }
       Object(_) => write!(f, "[object Object]"),
+      Map(_) => write!(f, "[object Map]"),
+      Set(_) => write!(f, "[object Set]"),
       Function(_) => write!(f, "[function]"),
       Class(_) => write!(f, "[class]"),
       Static(val) => val.fmt(f),
@@ -817,7 +845,7 @@ impl ToVal for Vec<Val> {
 
 impl<T> ToVal for &'static T
 where
-  T: ValTrait,
+  T: ValTrait + Sync,
 {
   fn to_val(self) -> Val {
     Val::Static(self)
@@ -886,7 +914,10 @@ impl<'a> std::fmt::Display for PrettyVal<'a> {
           }
         }
 
-        if object.string_map.is_empty() {
+        if object.string_map.is_empty()
+          && object.getter_map.is_empty()
+          && object.setter_map.is_empty()
+        {
           return f.write_str("{}");
         }
 
@@ -909,6 +940,78 @@ impl<'a> std::fmt::Display for PrettyVal<'a> {
           write!(f, "{}: {}", k, v.pretty())?;
         }
 
+        let accessor_keys = object.getter_map.keys().chain(
+          object
+            .setter_map
+            .keys()
+            .filter(|k| !object.getter_map.contains_key(*k)),
+        );
+
+        for k in accessor_keys {
+          if first {
+            first = false;
+          } else {
+            write!(f, ", ")?;
+          }
+
+          let kind = match (
+            object.getter_map.contains_key(k),
+            object.setter_map.contains_key(k),
+          ) {
+            (true, true) => "Getter/Setter",
+            (true, false) => "Getter",
+            _ => "Setter",
+          };
+
+          write!(f, "{}: \x1b[36m[{}]\x1b[39m", k, kind)?;
+        }
+
+        f.write_str(" }")
+      }
+      Val::Map(map) => {
+        write!(f, "Map({}) ", map.len())?;
+
+        if map.is_empty() {
+          return f.write_str("{}");
+        }
+
+        f.write_str("{ ")?;
+
+        let mut first = true;
+
+        for (key, value) in map.iter() {
+          if first {
+            first = false;
+          } else {
+            write!(f, ", ")?;
+          }
+
+          write!(f, "{} => {}", key.pretty(), value.pretty())?;
+        }
+
+        f.write_str(" }")
+      }
+      Val::Set(set) => {
+        write!(f, "Set({}) ", set.len())?;
+
+        if set.is_empty() {
+          return f.write_str("{}");
+        }
+
+        f.write_str("{ ")?;
+
+        let mut first = true;
+
+        for value in set.iter() {
+          if first {
+            first = false;
+          } else {
+            write!(f, ", ")?;
+          }
+
+          write!(f, "{}", value.pretty())?;
+        }
+
         f.write_str(" }")
       }
       Val::Function(_) => write!(f, "\x1b[36m[Function]\x1b[39m"),
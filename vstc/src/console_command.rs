@@ -106,6 +106,9 @@ fn console_start(args: &[String]) {
       .cloned()
       .collect(),
     symbol_map: Default::default(),
+    getter_map: Default::default(),
+    setter_map: Default::default(),
+    private_map: Default::default(),
     prototype: Val::Void,
   }
   .to_val();
@@ -192,6 +195,9 @@ impl ConsoleApp {
       .cloned()
       .collect(),
       symbol_map: Default::default(),
+      getter_map: Default::default(),
+      setter_map: Default::default(),
+      private_map: Default::default(),
       prototype: Val::Void,
     }
     .to_val();
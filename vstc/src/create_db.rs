@@ -1,7 +1,7 @@
 use std::{error::Error, process::exit, rc::Rc};
 
 use storage::{storage_head_ptr, SledBackend, Storage};
-use valuescript_compiler::asm;
+use valuescript_compiler::inline_valuescript;
 use valuescript_vm::{
   vs_value::{ToVal, Val},
   DecoderMaker, VirtualMachine,
@@ -18,12 +18,9 @@ pub fn create_db(
     .decoder(0)
     .decode_val(&mut vec![]);
 
-  // TODO: Use compile_str instead. Need to implement rest params: `new Class(...args)`.
-  let create = asm::inline(
-    "export @create {}
-
-    @create = function (%class, %args) {
-      new %class %args %return
+  let create = inline_valuescript(
+    "export default function create(Class, args) {
+      return new Class(...args);
     }",
   );
 
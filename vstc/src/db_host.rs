@@ -175,6 +175,9 @@ impl Handler<DbRequest> for DbActor {
       .into_iter()
       .collect(),
       symbol_map: vec![].into_iter().collect(),
+      getter_map: Default::default(),
+      setter_map: Default::default(),
+      private_map: Default::default(),
       prototype: Val::Void,
     }
     .to_val();
@@ -0,0 +1,456 @@
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::rc::Rc;
+
+use serde_json::{json, Value};
+use valuescript_compiler::{assemble_with_positions, compile, parse_module, DiagnosticLevel};
+use valuescript_vm::debugger::{Debugger, StopReason};
+use valuescript_vm::vs_value::{Val, ValTrait};
+use valuescript_vm::Bytecode;
+
+use crate::exit_command_failed::exit_command_failed;
+use crate::handle_diagnostics_cli::pos_to_line_col;
+use crate::resolve_entry_path::resolve_entry_path;
+use crate::to_bytecode::{format_from_path, RunFormat};
+
+const DEFAULT_PORT: u16 = 4711;
+
+pub fn debug_command(args: &[String]) {
+  let port = match args.get(2).map(String::as_str) {
+    Some("-h") | Some("--help") => {
+      show_help();
+      return;
+    }
+    Some("--port") if args.len() == 4 => match args[3].parse::<u16>() {
+      Ok(port) => port,
+      Err(_) => exit_command_failed(args, Some("Invalid port"), "vstc debug --help"),
+    },
+    None => DEFAULT_PORT,
+    Some(_) => exit_command_failed(args, None, "vstc debug --help"),
+  };
+
+  let listener = match TcpListener::bind(("127.0.0.1", port)) {
+    Ok(listener) => listener,
+    Err(err) => {
+      println!("Failed to listen on port {}: {}", port, err);
+      std::process::exit(1);
+    }
+  };
+
+  println!("Debug adapter listening on 127.0.0.1:{}", port);
+
+  for stream in listener.incoming() {
+    match stream {
+      Ok(stream) => {
+        if let Err(err) = DapSession::new(stream).and_then(|mut session| session.run()) {
+          println!("Debug session ended: {}", err);
+        }
+      }
+      Err(err) => println!("Failed to accept connection: {}", err),
+    }
+  }
+}
+
+fn show_help() {
+  println!("vstc debug");
+  println!();
+  println!("Start a Debug Adapter Protocol server");
+  println!();
+  println!("USAGE:");
+  println!("  vstc debug [--port <port>]");
+  println!();
+  println!("NOTE:");
+  println!(
+    "  Listens on 127.0.0.1:{} by default. Connect to it with `debugServer` in a",
+    DEFAULT_PORT
+  );
+  println!("  VS Code launch configuration, passing the file to run as `program`.");
+  println!("  Output of the program (eg console.log) is printed here.");
+}
+
+/// A debugging session for one connection. There is a single thread (id 1), frames are identified
+/// by their index in the stack trace, and each frame's registers are variable reference index + 1.
+struct DapSession {
+  reader: BufReader<TcpStream>,
+  writer: TcpStream,
+  seq: u64,
+  debugger: Option<Debugger>,
+  stop_on_entry: bool,
+  line_breakpoints: BTreeMap<String, Vec<u32>>,
+}
+
+impl DapSession {
+  fn new(stream: TcpStream) -> std::io::Result<DapSession> {
+    Ok(DapSession {
+      reader: BufReader::new(stream.try_clone()?),
+      writer: stream,
+      seq: 0,
+      debugger: None,
+      stop_on_entry: false,
+      line_breakpoints: BTreeMap::new(),
+    })
+  }
+
+  fn run(&mut self) -> std::io::Result<()> {
+    while let Some(request) = self.read_message()? {
+      let command = request["command"].as_str().unwrap_or("").to_string();
+      let args = &request["arguments"];
+
+      let result = match command.as_str() {
+        "initialize" => Ok(json!({ "supportsConfigurationDoneRequest": true })),
+        "launch" => self.launch(args),
+        "setBreakpoints" => Ok(self.set_breakpoints(args)),
+        "setExceptionBreakpoints" => Ok(json!({})),
+        "configurationDone" | "threads" | "continue" | "next" | "stepIn" | "stepOut"
+          if self.debugger.is_none() =>
+        {
+          Err("Program is not running".to_string())
+        }
+        "configurationDone" => Ok(json!({})),
+        "threads" => Ok(json!({ "threads": [{ "id": 1, "name": "main" }] })),
+        "stackTrace" => Ok(self.stack_trace()),
+        "scopes" => Ok(self.scopes(args)),
+        "variables" => Ok(self.variables(args)),
+        "continue" => Ok(json!({ "allThreadsContinued": true })),
+        "next" | "stepIn" | "stepOut" => Ok(json!({})),
+        "disconnect" | "terminate" => Ok(json!({})),
+        _ => Err(format!("Unsupported request: {}", command)),
+      };
+
+      let success = result.is_ok();
+      self.respond(&request, result)?;
+
+      if !success {
+        continue;
+      }
+
+      match command.as_str() {
+        "launch" => self.send_event("initialized", json!({}))?,
+        "configurationDone" => {
+          let debugger = self.debugger.as_mut().unwrap();
+
+          let stop = if self.stop_on_entry {
+            Some("entry")
+          } else if debugger.at_breakpoint() {
+            Some("breakpoint")
+          } else {
+            None
+          };
+
+          match stop {
+            Some(reason) => self.send_stopped(reason)?,
+            None => self.resume(Debugger::continue_)?,
+          }
+        }
+        "continue" => self.resume(Debugger::continue_)?,
+        "next" => self.resume(Debugger::step_over)?,
+        "stepIn" => self.resume(Debugger::step_into)?,
+        "stepOut" => self.resume(Debugger::step_out)?,
+        "disconnect" | "terminate" => break,
+        _ => {}
+      }
+    }
+
+    Ok(())
+  }
+
+  fn launch(&mut self, args: &Value) -> Result<Value, String> {
+    let program = match args["program"].as_str() {
+      Some(program) => program,
+      None => return Err("Missing program".to_string()),
+    };
+
+    let bytecode = self.load(program)?;
+
+    let program_args = match args["args"].as_array() {
+      Some(args) => args
+        .iter()
+        .filter_map(|a| a.as_str())
+        .map(|a| Val::String(Rc::from(a)))
+        .collect(),
+      None => vec![],
+    };
+
+    self.stop_on_entry = args["stopOnEntry"].as_bool().unwrap_or(false);
+
+    self.debugger = Some(
+      Debugger::new(Rc::new(bytecode), program_args)
+        .map_err(|err| format!("Failed to start: {}", err.codify()))?,
+    );
+
+    Ok(json!({}))
+  }
+
+  fn load(&mut self, program: &str) -> Result<Bytecode, String> {
+    let (code, positions) = match format_from_path(program) {
+      RunFormat::TypeScript => {
+        let compile_result = compile(resolve_entry_path(program), |path| {
+          std::fs::read_to_string(path).map_err(|err| err.to_string())
+        });
+
+        let mut has_errors = false;
+
+        for (path, diagnostics) in &compile_result.diagnostics {
+          let text = std::fs::read_to_string(&path.path).unwrap_or_default();
+
+          for diagnostic in diagnostics {
+            let (line, col) = pos_to_line_col(&text, diagnostic.span.lo.0);
+
+            self
+              .output(
+                "stderr",
+                &format!(
+                  "{}:{}:{}: {}: {}\n",
+                  path.path, line, col, diagnostic.level, diagnostic.message
+                ),
+              )
+              .map_err(|err| err.to_string())?;
+
+            has_errors |= matches!(
+              diagnostic.level,
+              DiagnosticLevel::Error | DiagnosticLevel::InternalError
+            );
+          }
+        }
+
+        match compile_result.module {
+          Some(module) if !has_errors => assemble_with_positions(&module),
+          _ => return Err(format!("Failed to compile {}", program)),
+        }
+      }
+      RunFormat::Assembly => {
+        let content = std::fs::read_to_string(program).map_err(|err| err.to_string())?;
+        assemble_with_positions(&parse_module(&content))
+      }
+      RunFormat::Bytecode => (
+        std::fs::read(program).map_err(|err| err.to_string())?,
+        Default::default(),
+      ),
+    };
+
+    Ok(Bytecode::with_positions(code, positions))
+  }
+
+  fn set_breakpoints(&mut self, args: &Value) -> Value {
+    let path = args["source"]["path"].as_str().unwrap_or("").to_string();
+
+    let lines = args["breakpoints"]
+      .as_array()
+      .map(|bps| {
+        bps
+          .iter()
+          .filter_map(|bp| bp["line"].as_u64())
+          .map(|line| line as u32)
+          .collect::<Vec<_>>()
+      })
+      .unwrap_or_default();
+
+    self.line_breakpoints.insert(path.clone(), lines.clone());
+
+    let debugger = match &mut self.debugger {
+      Some(debugger) => debugger,
+      None => {
+        let breakpoints = lines
+          .iter()
+          .map(|line| json!({ "verified": false, "line": line }))
+          .collect::<Vec<_>>();
+
+        return json!({ "breakpoints": breakpoints });
+      }
+    };
+
+    debugger.clear_breakpoints();
+
+    for (other_path, other_lines) in &self.line_breakpoints {
+      if other_path != &path {
+        for line in other_lines {
+          debugger.add_line_breakpoint(other_path, *line);
+        }
+      }
+    }
+
+    let breakpoints = lines
+      .iter()
+      .map(|line| {
+        let verified = !debugger.add_line_breakpoint(&path, *line).is_empty();
+        json!({ "verified": verified, "line": line })
+      })
+      .collect::<Vec<_>>();
+
+    json!({ "breakpoints": breakpoints })
+  }
+
+  fn stack_trace(&self) -> Value {
+    let frames = match &self.debugger {
+      Some(debugger) => debugger.frames(),
+      None => vec![],
+    };
+
+    let stack_frames = frames
+      .iter()
+      .enumerate()
+      .map(|(i, frame)| {
+        let name = match frame.name.as_str() {
+          "" => "<anonymous>",
+          name => name,
+        };
+
+        match &frame.position {
+          Some(position) => json!({
+            "id": i,
+            "name": name,
+            "source": { "path": position.path },
+            "line": position.line,
+            "column": position.col,
+          }),
+          None => json!({
+            "id": i,
+            "name": format!("{} @{}", name, frame.offset),
+            "line": 0,
+            "column": 0,
+          }),
+        }
+      })
+      .collect::<Vec<_>>();
+
+    json!({ "stackFrames": stack_frames, "totalFrames": frames.len() })
+  }
+
+  fn scopes(&self, args: &Value) -> Value {
+    let frame_id = args["frameId"].as_u64().unwrap_or(0);
+
+    json!({
+      "scopes": [{
+        "name": "Registers",
+        "variablesReference": frame_id + 1,
+        "expensive": false,
+      }],
+    })
+  }
+
+  fn variables(&self, args: &Value) -> Value {
+    let frame_index = args["variablesReference"].as_u64().unwrap_or(0) as usize;
+
+    let frame = match &self.debugger {
+      Some(debugger) if frame_index > 0 => debugger.frames().into_iter().nth(frame_index - 1),
+      _ => None,
+    };
+
+    let variables = match frame {
+      Some(frame) => std::iter::once(("this".to_string(), frame.this))
+        .filter(|(_, this)| !matches!(this, Val::Undefined))
+        .chain(frame.registers)
+        .map(|(name, val)| json!({ "name": name, "value": val.codify(), "variablesReference": 0 }))
+        .collect(),
+      None => vec![],
+    };
+
+    json!({ "variables": variables })
+  }
+
+  fn resume(&mut self, command: fn(&mut Debugger) -> StopReason) -> std::io::Result<()> {
+    let debugger = self.debugger.as_mut().unwrap();
+
+    match command(debugger) {
+      StopReason::Step => self.send_stopped("step"),
+      StopReason::Breakpoint(_) => self.send_stopped("breakpoint"),
+      StopReason::Finished(result) => {
+        let exit_code = match result {
+          Ok(Val::Undefined) => 0,
+          Ok(value) => {
+            self.output("stdout", &format!("{}\n", value.codify()))?;
+            0
+          }
+          Err(err) => {
+            let mut message = format!("Uncaught exception: {}\n", err.codify());
+
+            for entry in &self.debugger.as_ref().unwrap().vm.stack_trace {
+              message.push_str(&format!("    {}\n", entry));
+            }
+
+            self.output("stderr", &message)?;
+            1
+          }
+        };
+
+        self.send_event("exited", json!({ "exitCode": exit_code }))?;
+        self.send_event("terminated", json!({}))
+      }
+    }
+  }
+
+  fn send_stopped(&mut self, reason: &str) -> std::io::Result<()> {
+    self.send_event(
+      "stopped",
+      json!({ "reason": reason, "threadId": 1, "allThreadsStopped": true }),
+    )
+  }
+
+  fn output(&mut self, category: &str, output: &str) -> std::io::Result<()> {
+    self.send_event("output", json!({ "category": category, "output": output }))
+  }
+
+  fn respond(&mut self, request: &Value, result: Result<Value, String>) -> std::io::Result<()> {
+    let mut response = json!({
+      "type": "response",
+      "request_seq": request["seq"],
+      "command": request["command"],
+      "success": result.is_ok(),
+    });
+
+    match result {
+      Ok(body) => response["body"] = body,
+      Err(message) => response["message"] = json!(message),
+    }
+
+    self.send(response)
+  }
+
+  fn send_event(&mut self, event: &str, body: Value) -> std::io::Result<()> {
+    self.send(json!({ "type": "event", "event": event, "body": body }))
+  }
+
+  fn send(&mut self, mut message: Value) -> std::io::Result<()> {
+    self.seq += 1;
+    message["seq"] = json!(self.seq);
+
+    let content = message.to_string();
+    write!(
+      self.writer,
+      "Content-Length: {}\r\n\r\n{}",
+      content.len(),
+      content
+    )?;
+    self.writer.flush()
+  }
+
+  fn read_message(&mut self) -> std::io::Result<Option<Value>> {
+    let mut content_length = None;
+
+    loop {
+      let mut line = String::new();
+
+      if self.reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+      }
+
+      let line = line.trim();
+
+      if line.is_empty() {
+        break;
+      }
+
+      if let Some(len) = line.strip_prefix("Content-Length:") {
+        content_length = len.trim().parse::<usize>().ok();
+      }
+    }
+
+    let mut content = vec![0u8; content_length.unwrap_or(0)];
+    self.reader.read_exact(&mut content)?;
+
+    serde_json::from_slice(&content)
+      .map(Some)
+      .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+  }
+}
@@ -0,0 +1,152 @@
+#[cfg(test)]
+mod tests {
+  use std::rc::Rc;
+
+  use valuescript_compiler::{assemble_with_positions, compile_str};
+  use valuescript_vm::debugger::{Debugger, StopReason};
+  use valuescript_vm::vs_value::{ToVal, Val, ValTrait};
+  use valuescript_vm::Bytecode;
+
+  const PROGRAM: &str = "export default function main(n: number) {
+  let x = n;
+  x = double(x);
+  x += 1;
+  return x;
+}
+
+function double(n: number) {
+  const result = n * 2;
+  return result;
+}
+";
+
+  fn debugger(source: &str, args: Vec<Val>) -> Debugger {
+    let module = compile_str(source).module.expect("Failed to compile");
+    let (code, positions) = assemble_with_positions(&module);
+
+    Debugger::new(Rc::new(Bytecode::with_positions(code, positions)), args).unwrap()
+  }
+
+  fn line(debugger: &Debugger) -> u32 {
+    debugger.frames()[0].position.as_ref().unwrap().line
+  }
+
+  #[test]
+  fn step_into() {
+    let mut dbg = debugger(PROGRAM, vec![1.0.to_val()]);
+    assert_eq!(line(&dbg), 3);
+
+    let mut lines = vec![];
+
+    while let StopReason::Step = dbg.step_into() {
+      lines.push((dbg.frames()[0].name.clone(), line(&dbg)));
+    }
+
+    assert_eq!(
+      lines,
+      vec![
+        ("double".to_string(), 9),
+        ("double".to_string(), 10),
+        ("main".to_string(), 4),
+        ("main".to_string(), 5),
+      ]
+    );
+
+    assert_eq!(dbg.result().unwrap().clone().unwrap().to_number(), 3.0);
+  }
+
+  #[test]
+  fn step_over() {
+    let mut dbg = debugger(PROGRAM, vec![1.0.to_val()]);
+
+    assert!(matches!(dbg.step_over(), StopReason::Step));
+    assert_eq!(dbg.frames().len(), 1);
+    assert_eq!(line(&dbg), 4);
+  }
+
+  #[test]
+  fn step_out() {
+    let mut dbg = debugger(PROGRAM, vec![1.0.to_val()]);
+
+    assert!(matches!(dbg.step_into(), StopReason::Step));
+    assert_eq!(dbg.frames().len(), 2);
+
+    assert!(matches!(dbg.step_out(), StopReason::Step));
+    assert_eq!(dbg.frames().len(), 1);
+    assert_eq!(dbg.frames()[0].name, "main");
+  }
+
+  #[test]
+  fn breakpoint() {
+    let mut dbg = debugger(PROGRAM, vec![1.0.to_val()]);
+    assert!(!dbg.add_line_breakpoint("(str)", 9).is_empty());
+
+    assert!(matches!(dbg.continue_(), StopReason::Breakpoint(_)));
+    assert!(dbg.at_breakpoint());
+
+    let frames = dbg.frames();
+    assert_eq!(frames[0].name, "double");
+    assert_eq!(frames[1].name, "main");
+    assert_eq!(line(&dbg), 9);
+
+    match dbg.continue_() {
+      StopReason::Finished(Ok(result)) => assert_eq!(result.to_number(), 3.0),
+      other => panic!("Unexpected stop: {:?}", other),
+    }
+  }
+
+  #[test]
+  fn uncaught_error_in_job() {
+    let mut dbg = debugger(
+      "export default async function main() {
+  fail();
+  return \"done\";
+}
+
+async function fail() {
+  throw new Error(\"job failed\");
+}
+",
+      vec![],
+    );
+
+    match dbg.continue_() {
+      StopReason::Finished(Ok(result)) => assert_eq!(result.codify(), "\"done\""),
+      other => panic!("Unexpected stop: {:?}", other),
+    }
+
+    assert!(dbg.vm.stack_trace.is_empty());
+  }
+
+  #[test]
+  fn awaited_error_in_job() {
+    let mut dbg = debugger(
+      "export default async function main() {
+  await fail();
+  return \"done\";
+}
+
+async function fail() {
+  throw new Error(\"job failed\");
+}
+",
+      vec![],
+    );
+
+    assert!(matches!(dbg.continue_(), StopReason::Finished(Err(_))));
+  }
+
+  #[test]
+  fn uncaught_error_in_main() {
+    let mut dbg = debugger(
+      "export default function main() {
+  throw new Error(\"boom\");
+}
+",
+      vec![],
+    );
+
+    assert!(matches!(dbg.continue_(), StopReason::Finished(Err(_))));
+    assert!(!dbg.vm.stack_trace.is_empty());
+  }
+}
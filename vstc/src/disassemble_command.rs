@@ -0,0 +1,49 @@
+use std::fs::File;
+use std::io::Write;
+
+use valuescript_compiler::asm::Structured;
+use valuescript_compiler::disassemble;
+
+use crate::exit_command_failed::exit_command_failed;
+
+pub fn disassemble_command(args: &[String]) {
+  if args.len() != 3 {
+    exit_command_failed(args, None, "vstc disassemble --help");
+  }
+
+  if args[2] == "-h" || args[2] == "--help" {
+    show_help();
+    return;
+  }
+
+  let bytecode = match std::fs::read(&args[2]) {
+    Ok(bytecode) => bytecode,
+    Err(_) => {
+      println!("Failed to read file {}", args[2]);
+      std::process::exit(1);
+    }
+  };
+
+  let module = match disassemble(&bytecode) {
+    Ok(module) => module,
+    Err(err) => exit_command_failed(args, Some(&err), "vstc disassemble --help"),
+  };
+  let output_filename = "out.vsm";
+
+  let mut file = File::create(output_filename).expect("Couldn't create out.vsm");
+
+  file
+    .write_all(Structured(&module).to_string().as_bytes())
+    .expect("Failed to write out.vsm");
+
+  file.write_all(b"\n").expect("Failed to write out.vsm");
+}
+
+fn show_help() {
+  println!("vstc disassemble");
+  println!();
+  println!("Convert ValueScript bytecode to assembly");
+  println!();
+  println!("USAGE:");
+  println!("  vstc disassemble <file>");
+}
@@ -4,9 +4,13 @@ mod console_command;
 mod create_db;
 mod db_command;
 mod db_host;
+mod debug_command;
+mod debugger_tests;
+mod disassemble_command;
 mod exit_command_failed;
 mod handle_diagnostics_cli;
 mod parse_command_line;
+mod repl_command;
 mod resolve_entry_path;
 mod run_command;
 mod test_inputs;
@@ -18,6 +22,9 @@ use assemble_command::assemble_command;
 use compile_command::compile_command;
 use console_command::console_command;
 use db_command::db_command;
+use debug_command::debug_command;
+use disassemble_command::disassemble_command;
+use repl_command::repl_command;
 use run_command::run_command;
 
 use crate::exit_command_failed::exit_command_failed;
@@ -28,10 +35,13 @@ fn main() {
   match args.get(1).map(|s| s.as_str()) {
     Some("help") | Some("-h") | Some("--help") | None => show_help(),
     Some("assemble") => assemble_command(&args),
+    Some("disassemble") => disassemble_command(&args),
     Some("run") => run_command(&args),
     Some("compile") => compile_command(&args),
     Some("db") => db_command(&args),
     Some("console") => console_command(&args),
+    Some("repl") => repl_command(&args),
+    Some("debug") => debug_command(&args),
     _ => exit_command_failed(&args, None, "vstc help"),
   }
 }
@@ -62,12 +72,15 @@ fn show_help() {
   println!("  assemble");
   println!("    Convert assembly to bytecode");
   println!();
-  println!("  disassemble (TODO)");
+  println!("  disassemble");
   println!("    Convert bytecode to assembly");
   println!();
-  println!("  repl (TODO)");
+  println!("  repl");
   println!("    Read Eval Print Loop");
   println!();
+  println!("  debug");
+  println!("    Start a debug adapter (for VS Code)");
+  println!();
   println!("  host (TODO)");
   println!("    Start database server");
 }
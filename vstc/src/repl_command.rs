@@ -0,0 +1,492 @@
+use std::{
+  collections::{BTreeMap, BTreeSet},
+  io::{stdin, stdout, Write},
+  rc::Rc,
+};
+
+use swc_common::{sync::Lrc, FileName, SourceMap, Spanned};
+use swc_ecma_ast::{
+  Decl, EsVersion, ModuleDecl, ModuleItem, ObjectPatProp, Pat, Stmt, VarDeclKind,
+};
+use swc_ecma_parser::{error::SyntaxError, parse_file_as_module, Syntax, TsConfig};
+use valuescript_compiler::{assemble, compile_str, Diagnostic, DiagnosticLevel};
+use valuescript_vm::{
+  vs_object::VsObject,
+  vs_value::{ToVal, Val},
+  Bytecode, DecoderMaker, ValTrait, VirtualMachine,
+};
+
+use crate::{exit_command_failed::exit_command_failed, handle_diagnostics_cli::pos_to_line_col};
+
+pub fn repl_command(args: &[String]) {
+  match args.get(2).map(String::as_str) {
+    Some("-h") | Some("--help") => {
+      show_help();
+      return;
+    }
+    Some(_) => exit_command_failed(args, None, "vstc repl --help"),
+    None => {}
+  }
+
+  let mut repl = Repl::default();
+  let mut input = String::new();
+
+  loop {
+    print!("{}", if input.is_empty() { "> " } else { "... " });
+    stdout().flush().unwrap();
+
+    let mut line = String::new();
+
+    if stdin().read_line(&mut line).unwrap() == 0 {
+      println!();
+      break;
+    }
+
+    if input.is_empty() {
+      let command = line.trim();
+
+      match command {
+        "" => continue,
+        ".exit" => break,
+        ".help" => {
+          show_commands();
+          continue;
+        }
+        _ => {}
+      }
+
+      if let Some(path) = command.strip_prefix(".load ") {
+        repl.load(path.trim());
+        continue;
+      }
+    } else if line.trim() == ".break" {
+      input.clear();
+      continue;
+    }
+
+    input.push_str(&line);
+
+    if let EvalResult::Incomplete = repl.eval(&input, "") {
+      continue;
+    }
+
+    input.clear();
+  }
+}
+
+fn show_help() {
+  println!("vstc repl");
+  println!();
+  println!("Read Eval Print Loop");
+  println!();
+  println!("USAGE:");
+  println!("  vstc repl");
+  println!();
+  show_commands();
+}
+
+fn show_commands() {
+  println!("Commands:");
+  println!("  .help         Show this message");
+  println!("  .load <file>  Evaluate a file in the current session");
+  println!("  .break        Discard incomplete multi-line input");
+  println!("  .exit         Exit the repl (or use Ctrl+D)");
+}
+
+enum EvalResult {
+  Done,
+  Incomplete,
+}
+
+/// Declarations persist across inputs by compiling each input as a function which receives the
+/// existing bindings and returns them (along with any new ones) after running the input.
+#[derive(Default)]
+struct Repl {
+  /// Maps each binding to whether it is const.
+  declarations: BTreeMap<String, bool>,
+  bindings: VsObject,
+}
+
+impl Repl {
+  fn load(&mut self, path: &str) {
+    let source = match std::fs::read_to_string(path) {
+      Ok(source) => source,
+      Err(err) => {
+        println!("Failed to read {}: {}", path, err);
+        return;
+      }
+    };
+
+    if let EvalResult::Incomplete = self.eval(&source, path) {
+      println!("{}: Error: Unexpected end of file", path);
+    }
+  }
+
+  fn eval(&mut self, input: &str, path: &str) -> EvalResult {
+    let analysis = match analyze(input) {
+      Ok(analysis) => analysis,
+      Err(ParseFailure::Incomplete) => return EvalResult::Incomplete,
+      Err(ParseFailure::Error(pos, message)) => {
+        print_diagnostic(input, path, pos, DiagnosticLevel::Error, &message);
+        return EvalResult::Done;
+      }
+    };
+
+    let generated = generate(&self.declarations, input, &analysis);
+
+    let compile_result = compile_str(&generated.source);
+    let mut has_errors = false;
+
+    for diagnostics in compile_result.diagnostics.values() {
+      for Diagnostic {
+        level,
+        message,
+        span,
+      } in diagnostics
+      {
+        let is_error = matches!(
+          level,
+          DiagnosticLevel::Error | DiagnosticLevel::InternalError
+        );
+
+        has_errors |= is_error;
+
+        // Diagnostics about the generated code around the input (such as lints on the bindings
+        // from previous inputs) are dropped, except for errors, which would otherwise go unexplained
+        match generated.input_pos(span.lo.0 as usize) {
+          Some(pos) => print_diagnostic(input, path, pos, *level, message),
+          None if is_error => println!("{}: {}", level, message),
+          None => {}
+        }
+      }
+    }
+
+    let module = match compile_result.module {
+      Some(module) if !has_errors => module,
+      _ => return EvalResult::Done,
+    };
+
+    let bytecode = Rc::new(Bytecode::new(assemble(&module)));
+    let fn_ = bytecode.decoder(0).decode_val(&mut vec![]);
+
+    let mut vm = VirtualMachine::default();
+
+    let result = vm.run(
+      None,
+      &mut Val::Undefined,
+      fn_,
+      vec![self.bindings.clone().to_val()],
+    );
+
+    match result {
+      Ok(result) => {
+        let elements = result
+          .as_array_data()
+          .map(|array| array.elements.to_vec())
+          .unwrap_or_default();
+
+        if let [value, Val::Object(bindings)] = elements.as_slice() {
+          self.bindings = (**bindings).clone();
+          self.declarations.extend(analysis.declarations);
+
+          if analysis.result.is_some() {
+            println!("{}", value.pretty());
+          }
+        }
+      }
+      Err(err) => println!("Uncaught exception: {}", err.pretty()),
+    }
+
+    EvalResult::Done
+  }
+}
+
+/// Generates the function compiled for an input. `declarations` are the bindings from previous
+/// inputs, which the input can redeclare.
+fn generate(
+  declarations: &BTreeMap<String, bool>,
+  input: &str,
+  analysis: &Analysis,
+) -> GeneratedSource {
+  let mut source = "export default function (__repl) {\n".to_string();
+
+  for (name, is_const) in declarations {
+    if !analysis.declarations.contains_key(name) {
+      let kind = if *is_const { "const" } else { "let" };
+      source.push_str(&format!("{} {} = __repl.{};\n", kind, name, name));
+    }
+  }
+
+  let mut segments = Vec::<Segment>::new();
+
+  let mut push_input = |source: &mut String, start: usize, end: usize| {
+    segments.push(Segment {
+      generated_start: source.len(),
+      input_start: start,
+      len: end - start,
+    });
+
+    source.push_str(&input[start..end]);
+  };
+
+  match analysis.result {
+    Some((start, end)) => {
+      push_input(&mut source, 0, start);
+      source.push_str("const __repl_result = (");
+      push_input(&mut source, start, end);
+      source.push_str(");");
+      push_input(&mut source, end, input.len());
+    }
+    None => push_input(&mut source, 0, input.len()),
+  }
+
+  let names = declarations
+    .keys()
+    .chain(analysis.declarations.keys())
+    .map(String::as_str)
+    .collect::<BTreeSet<_>>();
+
+  source.push_str(&format!(
+    "\nreturn [{}, {{ {} }}];\n}}\n",
+    match analysis.result {
+      Some(_) => "__repl_result",
+      None => "undefined",
+    },
+    names.into_iter().collect::<Vec<_>>().join(", "),
+  ));
+
+  GeneratedSource { source, segments }
+}
+
+/// The source compiled for an input, along with what's needed to map positions back to the input.
+struct GeneratedSource {
+  source: String,
+
+  /// Where the input was copied into the source. The rest of the source is generated.
+  segments: Vec<Segment>,
+}
+
+struct Segment {
+  generated_start: usize,
+  input_start: usize,
+  len: usize,
+}
+
+impl GeneratedSource {
+  /// Maps a position in the source to the input. Text inserted around the result maps to the input
+  /// position which follows it. The code before and after the input has no input position.
+  fn input_pos(&self, pos: usize) -> Option<usize> {
+    let first = self.segments.first()?;
+    let last = self.segments.last()?;
+
+    if pos < first.generated_start || pos > last.generated_start + last.len {
+      return None;
+    }
+
+    for segment in &self.segments {
+      if pos <= segment.generated_start + segment.len {
+        return Some(segment.input_start + pos.saturating_sub(segment.generated_start));
+      }
+    }
+
+    None
+  }
+}
+
+struct Analysis {
+  /// The names declared by the input, mapped to whether they are const.
+  declarations: BTreeMap<String, bool>,
+
+  /// The range of the trailing expression statement, if any, which becomes the printed result.
+  result: Option<(usize, usize)>,
+}
+
+enum ParseFailure {
+  Incomplete,
+  Error(usize, String),
+}
+
+fn analyze(input: &str) -> Result<Analysis, ParseFailure> {
+  let source_map = Lrc::<SourceMap>::default();
+  let file = source_map.new_source_file(FileName::Anon, input.into());
+  let offset = |pos: swc_common::BytePos| (pos.0 - file.start_pos.0) as usize;
+
+  let mut recovered_errors = Vec::new();
+
+  let parse_result = parse_file_as_module(
+    &file,
+    Syntax::Typescript(TsConfig {
+      tsx: true,
+      ..Default::default()
+    }),
+    EsVersion::Es2022,
+    None,
+    &mut recovered_errors,
+  );
+
+  let module = match (parse_result, recovered_errors.into_iter().next()) {
+    (Ok(module), None) => module,
+    (Err(err), _) | (Ok(_), Some(err)) => {
+      let pos = offset(err.span().lo);
+
+      return Err(match err.kind() {
+        SyntaxError::Eof | SyntaxError::UnterminatedBlockComment | SyntaxError::UnterminatedTpl => {
+          ParseFailure::Incomplete
+        }
+        SyntaxError::Expected(_, got) if got == "<eof>" => ParseFailure::Incomplete,
+        _ if pos >= input.trim_end().len() => ParseFailure::Incomplete,
+        kind => ParseFailure::Error(pos, kind.msg().to_string()),
+      });
+    }
+  };
+
+  let mut analysis = Analysis {
+    declarations: BTreeMap::new(),
+    result: None,
+  };
+
+  for (i, item) in module.body.iter().enumerate() {
+    let stmt = match item {
+      ModuleItem::Stmt(stmt) => stmt,
+      ModuleItem::ModuleDecl(decl) => {
+        return Err(ParseFailure::Error(
+          offset(decl.span().lo),
+          match decl {
+            ModuleDecl::Import(_) => "Imports are not supported in the repl",
+            _ => "Exports are not supported in the repl",
+          }
+          .to_string(),
+        ))
+      }
+    };
+
+    match stmt {
+      Stmt::Decl(Decl::Var(var_decl)) => {
+        let is_const = var_decl.kind == VarDeclKind::Const;
+
+        for declarator in &var_decl.decls {
+          let mut names = Vec::<String>::new();
+          pat_names(&declarator.name, &mut names);
+
+          for name in names {
+            analysis.declarations.insert(name, is_const);
+          }
+        }
+      }
+      Stmt::Decl(Decl::Fn(fn_decl)) => {
+        analysis
+          .declarations
+          .insert(fn_decl.ident.sym.to_string(), false);
+      }
+      Stmt::Decl(Decl::Class(class_decl)) => {
+        analysis
+          .declarations
+          .insert(class_decl.ident.sym.to_string(), false);
+      }
+      Stmt::Expr(expr_stmt) if i == module.body.len() - 1 => {
+        let span = expr_stmt.expr.span();
+        analysis.result = Some((offset(span.lo), offset(span.hi)));
+      }
+      _ => {}
+    }
+  }
+
+  Ok(analysis)
+}
+
+fn pat_names(pat: &Pat, names: &mut Vec<String>) {
+  match pat {
+    Pat::Ident(ident) => names.push(ident.id.sym.to_string()),
+    Pat::Array(array) => {
+      for elem in array.elems.iter().flatten() {
+        pat_names(elem, names);
+      }
+    }
+    Pat::Rest(rest) => pat_names(&rest.arg, names),
+    Pat::Object(object) => {
+      for prop in &object.props {
+        match prop {
+          ObjectPatProp::KeyValue(kv) => pat_names(&kv.value, names),
+          ObjectPatProp::Assign(assign) => names.push(assign.key.sym.to_string()),
+          ObjectPatProp::Rest(rest) => pat_names(&rest.arg, names),
+        }
+      }
+    }
+    Pat::Assign(assign) => pat_names(&assign.left, names),
+    Pat::Invalid(_) | Pat::Expr(_) => {}
+  }
+}
+
+fn print_diagnostic(input: &str, path: &str, pos: usize, level: DiagnosticLevel, message: &str) {
+  let (line, col) = pos_to_line_col(input, pos as u32);
+
+  match path {
+    "" => println!("{}:{}: {}: {}", line, col, level, message),
+    _ => println!("{}:{}:{}: {}: {}", path, line, col, level, message),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::BTreeMap;
+
+  use super::{analyze, generate, ParseFailure};
+
+  #[test]
+  fn incomplete_input() {
+    for input in ["let x =", "function f() {", "`abc", "/* comment", "f(1,\n"] {
+      assert!(
+        matches!(analyze(input), Err(ParseFailure::Incomplete)),
+        "{:?} should be incomplete",
+        input
+      );
+    }
+
+    assert!(matches!(
+      analyze("let 1 = 2;"),
+      Err(ParseFailure::Error(..))
+    ));
+
+    assert!(analyze("1 + 1").is_ok());
+  }
+
+  #[test]
+  fn redeclaration() {
+    let declarations = BTreeMap::from([("x".to_string(), true), ("y".to_string(), false)]);
+    let analysis = analyze("let x = 2;").ok().unwrap();
+
+    assert_eq!(analysis.declarations.get("x"), Some(&false));
+
+    let generated = generate(&declarations, "let x = 2;", &analysis);
+
+    assert!(!generated.source.contains("__repl.x"));
+    assert!(generated.source.contains("let y = __repl.y;"));
+    assert!(generated.source.contains("return [undefined, { x, y }];"));
+  }
+
+  #[test]
+  fn result_splice() {
+    let declarations = BTreeMap::from([("a".to_string(), true)]);
+    let input = "let b = a;\nb + 1";
+    let analysis = analyze(input).ok().unwrap();
+
+    assert_eq!(analysis.result, Some((11, 16)));
+
+    let generated = generate(&declarations, input, &analysis);
+    let source = &generated.source;
+
+    assert!(source
+      .contains("let b = a;\nconst __repl_result = (b + 1);\nreturn [__repl_result, { a, b }];"));
+
+    let pos_of = |text: &str| source.find(text).unwrap();
+
+    assert_eq!(generated.input_pos(pos_of("let b")), Some(0));
+    assert_eq!(generated.input_pos(pos_of("const __repl_result")), Some(11));
+    assert_eq!(generated.input_pos(pos_of("b + 1")), Some(11));
+    assert_eq!(generated.input_pos(pos_of("1)")), Some(15));
+
+    // The prelude and the return list aren't part of the input
+    assert_eq!(generated.input_pos(pos_of("const a")), None);
+    assert_eq!(generated.input_pos(pos_of("return")), None);
+  }
+}
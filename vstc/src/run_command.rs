@@ -1,6 +1,8 @@
+use std::path::Path;
 use std::process::exit;
 use std::rc::Rc;
 
+use valuescript_vm::position_table::StackTraceEntry;
 use valuescript_vm::VirtualMachine;
 use valuescript_vm::{vs_value::Val, DecoderMaker};
 
@@ -52,11 +54,28 @@ pub fn run_command(args: &[String]) {
     }
     Err(err) => {
       println!("Uncaught exception: {}", err.pretty());
+
+      for entry in &vm.stack_trace {
+        println!("    {}", relative_trace_entry(entry));
+      }
+
       exit(1);
     }
   }
 }
 
+fn relative_trace_entry(entry: &StackTraceEntry) -> StackTraceEntry {
+  let mut entry = entry.clone();
+
+  if let (Some(position), Ok(current_dir)) = (&mut entry.position, std::env::current_dir()) {
+    if let Ok(path) = Path::new(&position.path).strip_prefix(current_dir) {
+      position.path = path.to_string_lossy().to_string();
+    }
+  }
+
+  entry
+}
+
 fn format_from_option(option: &String) -> RunFormat {
   return match option.as_str() {
     "--typescript" => RunFormat::TypeScript,
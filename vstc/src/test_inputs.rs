@@ -3,12 +3,12 @@ mod tests {
   use std::collections::HashSet;
   use std::fs;
   use std::io::Error;
-  use std::path::PathBuf;
+  use std::path::{Path, PathBuf};
   use std::rc::Rc;
 
   use valuescript_compiler::asm::Structured;
   use valuescript_compiler::compile;
-  use valuescript_compiler::{assemble, parse_module};
+  use valuescript_compiler::{assemble, assemble_with_positions, disassemble, parse_module};
   use valuescript_vm::vs_value::Val;
   use valuescript_vm::{Bytecode, ValTrait};
   use valuescript_vm::{DecoderMaker, VirtualMachine};
@@ -81,7 +81,8 @@ mod tests {
             .module
             .expect("Should have exited if module is None");
 
-          let bytecode = Rc::new(Bytecode::new(assemble(&module)));
+          let (code, positions) = assemble_with_positions(&module);
+          let bytecode = Rc::new(Bytecode::with_positions(code, positions));
 
           let assembly = Structured(&module).to_string();
 
@@ -103,13 +104,34 @@ mod tests {
             failed_paths.insert(rel_file_path.clone());
           }
 
-          let bytecode_via_assembly = assemble(&parsed_assembly);
+          let (bytecode_via_assembly, positions_via_assembly) =
+            assemble_with_positions(&parsed_assembly);
 
           if bytecode.code != bytecode_via_assembly {
             println!("  Bytecode mismatch between original and parsed assembly");
             failed_paths.insert(rel_file_path.clone());
           }
 
+          if bytecode.positions != positions_via_assembly {
+            println!("  Position mismatch between original and parsed assembly");
+            failed_paths.insert(rel_file_path.clone());
+          }
+
+          match disassemble(&bytecode.code) {
+            Ok(module) => {
+              let disassembly = Structured(&module).to_string();
+
+              if assemble(&parse_module(&disassembly)) != bytecode.code {
+                println!("  Bytecode mismatch between original and disassembly");
+                failed_paths.insert(rel_file_path.clone());
+              }
+            }
+            Err(err) => {
+              println!("  Failed to disassemble: {}", err);
+              failed_paths.insert(rel_file_path.clone());
+            }
+          }
+
           let mut vm = VirtualMachine::default();
 
           let result = vm.run(
@@ -132,6 +154,41 @@ mod tests {
 
             failed_paths.insert(rel_file_path.clone());
           }
+
+          // Optional `//! test_trace(...)` lines give the expected stack trace, one entry each
+          let expected_trace = file_contents
+            .lines()
+            .skip(1)
+            .map_while(|line| line.strip_prefix("//! test_trace("))
+            .map(|entry| entry.strip_suffix(')').unwrap_or(entry).to_string())
+            .collect::<Vec<_>>();
+
+          if !expected_trace.is_empty() {
+            let trace = vm
+              .stack_trace
+              .iter()
+              .map(|entry| {
+                let mut entry = entry.clone();
+
+                if let Some(position) = &mut entry.position {
+                  if let Ok(path) = Path::new(&position.path).strip_prefix(project_dir) {
+                    position.path = path.to_string_lossy().to_string();
+                  }
+                }
+
+                entry.to_string()
+              })
+              .collect::<Vec<_>>();
+
+            if trace != expected_trace {
+              println!(
+                "  Expected trace: {:?}\n  Actual trace:   {:?}\n",
+                expected_trace, trace,
+              );
+
+              failed_paths.insert(rel_file_path.clone());
+            }
+          }
         }
       }
     }
@@ -1,6 +1,6 @@
 use std::{ffi::OsStr, fs, path::Path};
 
-use valuescript_compiler::{assemble, compile, parse_module};
+use valuescript_compiler::{assemble_with_positions, compile, parse_module};
 use valuescript_vm::Bytecode;
 
 use crate::{
@@ -8,7 +8,7 @@ use crate::{
 };
 
 pub fn to_bytecode(format: RunFormat, file_path: &str) -> Bytecode {
-  Bytecode::new(match format {
+  let (code, positions) = match format {
     RunFormat::TypeScript => {
       let resolved_entry_path = resolve_entry_path(file_path);
 
@@ -20,7 +20,7 @@ pub fn to_bytecode(format: RunFormat, file_path: &str) -> Bytecode {
         handle_diagnostics_cli(&path.path, diagnostics);
       }
 
-      assemble(
+      assemble_with_positions(
         &compile_result
           .module
           .expect("Should have exited if module is None"),
@@ -32,13 +32,18 @@ pub fn to_bytecode(format: RunFormat, file_path: &str) -> Bytecode {
         .unwrap_or_else(|_| panic!("Failed to read file {}", file_path));
 
       let module = parse_module(&file_content);
-      assemble(&module)
+      assemble_with_positions(&module)
     }
 
     RunFormat::Bytecode => {
-      fs::read(file_path).unwrap_or_else(|_| panic!("Failed to read file {}", file_path))
+      let code =
+        fs::read(file_path).unwrap_or_else(|_| panic!("Failed to read file {}", file_path));
+
+      (code, Default::default())
     }
-  })
+  };
+
+  Bytecode::with_positions(code, positions)
 }
 
 pub enum RunFormat {